@@ -0,0 +1,594 @@
+//! The `spqr` command line tool for computing, converting and printing SPQR decompositions.
+//!
+//! ```text
+//! spqr compute <graph> [-o <output>] [--binary]
+//! spqr convert <graph> <input> <output>
+//! spqr cat <graph> <decomposition>
+//! spqr validate <graph> <decomposition>
+//! spqr stats <decomposition> [--graph <graph>] [--json]
+//! spqr extract <graph> <decomposition> (--block <name> | --spqr-node <name>) [--format gfa|dot] [-o <output>]
+//! spqr diff <first> <second> --graph <graph>
+//! ```
+//!
+//! Graphs are read as GFA if their file name ends with `.gfa`, and as edge lists otherwise.
+//! An edge list contains one edge per line, given by the names of its two endpoints separated by whitespace,
+//! and a line with a single name declares a node without edges. Empty lines and lines starting with `#` are ignored.
+//!
+//! Decompositions are read in the plain SPQR file format if they start with its header, and in the binary format otherwise.
+//! `compute` writes the plain format unless `--binary` is given, `convert` writes the format that the input is not in,
+//! and `cat` prints the plain format.
+//! The plain format only keeps the extra data of nodes and edges, so converting to it drops the extra data of other entities.
+//! Output goes to standard output where no output file is given.
+//!
+//! `compute` only supports graphs whose blocks are single edges, possibly parallel, or cycles,
+//! since the crate does not implement the computation of triconnected components yet.
+//!
+//! `stats` prints the [statistics](SPQRDecomposition::statistics) of a decomposition and the size of its largest R-node, as text or as a JSON object.
+//! Without a graph, the statistics of a file in the plain format are computed in a single pass over the file,
+//! while decompositions in the binary format can only be read together with their graph.
+//! `extract` writes the subgraph of a block or the skeleton of an SPQR node as GFA or as DOT,
+//! where blocks and SPQR nodes are named like in the plain format written by `cat`, such as `B12` or `R3`.
+//! `validate` runs the structural checks of [`SPQRDecomposition::validate`] and prints each violated invariant on its own line.
+//! `diff` compares two decompositions of the same graph by their [canonical form](SPQRDecomposition::canonical_form),
+//! and prints the blocks and SPQR nodes that occur only in the first decomposition with `<`, and those only in the second with `>`.
+//!
+//! The tool exits with status 2 if the arguments are invalid or a file cannot be read, parsed or written,
+//! and with status 1 if `validate` finds problems or `diff` finds differences, such that it can be used as a quality control step in pipelines.
+
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use bidirected_adjacency_array::io::gfa1::{GfaReadError, PlainGfaEdgeData, PlainGfaNodeData};
+use rustc_hash::{FxHashMap, FxHashSet};
+use spqr_tree::{
+    decomposition::{
+        SPQRDecomposition,
+        builder::{BuildProblem, SPQRDecompositionBuilder},
+        statistics::SPQRDecompositionStatistics,
+    },
+    graph::{
+        StaticGraph, implementations::bidirected_adjacency_array::DefaultBidirectedAdjacencyArray,
+    },
+    io::plain_spqr_file::{error::ReadError, scan_statistics},
+};
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+type Graph = DefaultBidirectedAdjacencyArray<PlainGfaNodeData, PlainGfaEdgeData>;
+
+/// The exit status of `validate` if the decomposition is invalid.
+const EXIT_INVALID: u8 = 1;
+
+/// The exit status of `diff` if the decompositions differ.
+const EXIT_DIFFERENT: u8 = 1;
+
+/// The exit status if the arguments are invalid or a file cannot be read, parsed or written.
+const EXIT_ERROR: u8 = 2;
+
+/// The first bytes of a file in the plain SPQR file format.
+const PLAIN_HEADER: &[u8] = b"H ";
+
+const USAGE: &str = "usage:
+  spqr compute <graph> [-o <output>] [--binary]
+  spqr convert <graph> <input> <output>
+  spqr cat <graph> <decomposition>
+  spqr validate <graph> <decomposition>
+  spqr stats <decomposition> [--graph <graph>] [--json]
+  spqr extract <graph> <decomposition> (--block <name> | --spqr-node <name>) [--format gfa|dot] [-o <output>]
+  spqr diff <first> <second> --graph <graph>";
+
+#[derive(Error, Debug)]
+enum CliError {
+    #[error("{0}\n{USAGE}")]
+    Usage(String),
+
+    #[error("{}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+
+    #[error("{}: {source}", path.display())]
+    Gfa { path: PathBuf, source: GfaReadError },
+
+    #[error("{}: {source}", path.display())]
+    Plain { path: PathBuf, source: ReadError },
+
+    #[error("the decomposition has no block or SPQR node named {0:?}")]
+    UnknownName(String),
+
+    #[error(
+        "the graph has {0} blocks that are neither single edges nor cycles, which cannot be decomposed until the computation of triconnected components is implemented"
+    )]
+    Unsupported(usize),
+
+    #[error("{0}")]
+    Build(String),
+
+    #[error("cannot write output: {0}")]
+    Output(#[from] io::Error),
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match run(&args, &mut io::stdout().lock()) {
+        Ok(exit_code) => exit_code,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+/// Runs the subcommand given by `args`, writing to `stdout` where no output file is given.
+fn run(args: &[String], stdout: &mut impl Write) -> Result<ExitCode, CliError> {
+    let Some((subcommand, args)) = args.split_first() else {
+        return Err(CliError::Usage("missing subcommand".to_string()));
+    };
+    match subcommand.as_str() {
+        "compute" => {
+            let options = Options::parse(args, &["-o", "--output"], &["--binary"])?;
+            let [graph_path] = options.positional[..] else {
+                return Err(CliError::Usage(
+                    "compute expects exactly one graph".to_string(),
+                ));
+            };
+            let output = options.value("-o").or(options.value("--output"));
+            let binary = options.flag("--binary");
+
+            let graph = read_graph(Path::new(graph_path))?;
+            let spqr_decomposition = compute(&graph)?;
+            write_output(output.map(Path::new), stdout, |writer| {
+                if binary {
+                    spqr_decomposition.write_binary(writer)
+                } else {
+                    spqr_decomposition.write_plain_spqr(writer)
+                }
+            })?;
+            Ok(ExitCode::SUCCESS)
+        }
+        "convert" => {
+            let [graph_path, input, output] = args else {
+                return Err(CliError::Usage(
+                    "convert expects a graph, an input and an output".to_string(),
+                ));
+            };
+            let graph = read_graph(Path::new(graph_path))?;
+            let (spqr_decomposition, is_plain) = read_decomposition(&graph, Path::new(input))?;
+            write_output(Some(Path::new(output)), stdout, |writer| {
+                if is_plain {
+                    spqr_decomposition.write_binary(writer)
+                } else {
+                    spqr_decomposition.write_plain_spqr(writer)
+                }
+            })?;
+            Ok(ExitCode::SUCCESS)
+        }
+        "cat" => {
+            let [graph_path, input] = args else {
+                return Err(CliError::Usage(
+                    "cat expects a graph and a decomposition".to_string(),
+                ));
+            };
+            let graph = read_graph(Path::new(graph_path))?;
+            let (spqr_decomposition, _) = read_decomposition(&graph, Path::new(input))?;
+            write_output(None, stdout, |writer| {
+                spqr_decomposition.write_plain_spqr(writer)
+            })?;
+            Ok(ExitCode::SUCCESS)
+        }
+        "validate" => {
+            let [graph_path, input] = args else {
+                return Err(CliError::Usage(
+                    "validate expects a graph and a decomposition".to_string(),
+                ));
+            };
+            let graph = read_graph(Path::new(graph_path))?;
+            let (spqr_decomposition, _) = read_decomposition(&graph, Path::new(input))?;
+            match spqr_decomposition.validate() {
+                Ok(()) => {
+                    writeln!(stdout, "{input}: valid")?;
+                    Ok(ExitCode::SUCCESS)
+                }
+                Err(error) => {
+                    writeln!(stdout, "{input}: {} problems", error.problems.len())?;
+                    for problem in &error.problems {
+                        writeln!(stdout, "  {problem}")?;
+                    }
+                    Ok(ExitCode::from(EXIT_INVALID))
+                }
+            }
+        }
+        "stats" => {
+            let options = Options::parse(args, &["--graph"], &["--json"])?;
+            let [input] = options.positional[..] else {
+                return Err(CliError::Usage(
+                    "stats expects exactly one decomposition".to_string(),
+                ));
+            };
+            let input = Path::new(input);
+            let statistics = match options.value("--graph") {
+                Some(graph_path) => {
+                    let graph = read_graph(Path::new(graph_path))?;
+                    read_decomposition(&graph, input)?.0.statistics()
+                }
+                None => {
+                    let file = File::open(input).map_err(|source| CliError::Io {
+                        path: input.to_owned(),
+                        source,
+                    })?;
+                    let mut reader = BufReader::new(file);
+                    if !reader.fill_buf()?.starts_with(PLAIN_HEADER) {
+                        return Err(CliError::Usage(
+                            "stats needs --graph for decompositions in the binary format"
+                                .to_string(),
+                        ));
+                    }
+                    scan_statistics(reader).map_err(|source| CliError::Plain {
+                        path: input.to_owned(),
+                        source,
+                    })?
+                }
+            };
+            if options.flag("--json") {
+                writeln!(stdout, "{}", statistics_json(&statistics))?;
+            } else {
+                writeln!(stdout, "{statistics}")?;
+                match statistics.r_node_size_histogram.last_key_value() {
+                    Some((size, _)) => writeln!(stdout, "Largest R-node has {size} nodes")?,
+                    None => writeln!(stdout, "Largest R-node: none")?,
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        "extract" => {
+            let options = Options::parse(
+                args,
+                &["--block", "--spqr-node", "--format", "-o", "--output"],
+                &[],
+            )?;
+            let [graph_path, input] = options.positional[..] else {
+                return Err(CliError::Usage(
+                    "extract expects a graph and a decomposition".to_string(),
+                ));
+            };
+            let is_dot = match options.value("--format").unwrap_or("gfa") {
+                "gfa" => false,
+                "dot" => true,
+                format => {
+                    return Err(CliError::Usage(format!(
+                        "unknown format {format:?}, expected gfa or dot"
+                    )));
+                }
+            };
+            let output = options.value("-o").or(options.value("--output"));
+
+            let graph = read_graph(Path::new(graph_path))?;
+            let (spqr_decomposition, _) = read_decomposition(&graph, Path::new(input))?;
+            match (options.value("--block"), options.value("--spqr-node")) {
+                (Some(name), None) => {
+                    let (block_index, _) = spqr_decomposition
+                        .iter_blocks()
+                        .find(|(block_index, _)| format!("B{block_index}") == name)
+                        .ok_or_else(|| CliError::UnknownName(name.to_string()))?;
+                    write_output(output.map(Path::new), stdout, |writer| {
+                        if is_dot {
+                            spqr_decomposition.write_block_dot(block_index, writer)
+                        } else {
+                            spqr_decomposition.write_block_gfa(block_index, writer)
+                        }
+                    })?;
+                }
+                (None, Some(name)) => {
+                    let (spqr_node_index, _) = spqr_decomposition
+                        .iter_spqr_nodes()
+                        .find(|&(spqr_node_index, _)| {
+                            spqr_decomposition.spqr_node_name(spqr_node_index) == name
+                        })
+                        .ok_or_else(|| CliError::UnknownName(name.to_string()))?;
+                    write_output(output.map(Path::new), stdout, |writer| {
+                        if is_dot {
+                            spqr_decomposition.write_spqr_node_skeleton_dot(spqr_node_index, writer)
+                        } else {
+                            spqr_decomposition.write_spqr_node_skeleton_gfa(spqr_node_index, writer)
+                        }
+                    })?;
+                }
+                _ => {
+                    return Err(CliError::Usage(
+                        "extract expects exactly one of --block and --spqr-node".to_string(),
+                    ));
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        "diff" => {
+            let options = Options::parse(args, &["--graph"], &[])?;
+            let (&[first, second], Some(graph_path)) =
+                (&options.positional[..], options.value("--graph"))
+            else {
+                return Err(CliError::Usage(
+                    "diff expects two decompositions and --graph".to_string(),
+                ));
+            };
+            let graph = read_graph(Path::new(graph_path))?;
+            let (first, _) = read_decomposition(&graph, Path::new(first))?;
+            let (second, _) = read_decomposition(&graph, Path::new(second))?;
+
+            let node_names = |nodes: &mut dyn Iterator<Item = _>| {
+                nodes
+                    .map(|node_index| graph.node_name(node_index).into_owned())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            let diff = first.diff(&second);
+            for (marker, spqr_decomposition, block_indices) in [
+                ("<", &first, &diff.blocks_only_in_self),
+                (">", &second, &diff.blocks_only_in_other),
+            ] {
+                for &block_index in block_indices {
+                    writeln!(
+                        stdout,
+                        "{marker} B{block_index}: {}",
+                        node_names(&mut spqr_decomposition.block(block_index).iter_nodes()),
+                    )?;
+                }
+            }
+            for (marker, spqr_decomposition, spqr_node_indices) in [
+                ("<", &first, &diff.spqr_nodes_only_in_self),
+                (">", &second, &diff.spqr_nodes_only_in_other),
+            ] {
+                for &spqr_node_index in spqr_node_indices {
+                    let spqr_node = spqr_decomposition.spqr_node(spqr_node_index);
+                    writeln!(
+                        stdout,
+                        "{marker} {} in B{}: {}",
+                        spqr_decomposition.spqr_node_name(spqr_node_index),
+                        spqr_node.block(),
+                        node_names(&mut spqr_node.iter_nodes()),
+                    )?;
+                }
+            }
+
+            if first.is_equivalent_to(&second) {
+                Ok(ExitCode::SUCCESS)
+            } else {
+                if diff.is_empty() {
+                    writeln!(
+                        stdout,
+                        "the decompositions have the same blocks and SPQR nodes, but differ in their components, cut nodes or SPQR edges",
+                    )?;
+                }
+                Ok(ExitCode::from(EXIT_DIFFERENT))
+            }
+        }
+        _ => Err(CliError::Usage(format!(
+            "unknown subcommand {subcommand:?}"
+        ))),
+    }
+}
+
+/// The arguments of a subcommand, split into positional arguments, options with a value, and flags.
+struct Options<'args> {
+    positional: Vec<&'args str>,
+    values: FxHashMap<&'args str, &'args str>,
+    flags: FxHashSet<&'args str>,
+}
+
+impl<'args> Options<'args> {
+    /// Splits `args`, where the options in `value_options` take the following argument as value.
+    /// Arguments starting with `-` that are neither in `value_options` nor in `flag_options` are rejected.
+    fn parse(
+        args: &'args [String],
+        value_options: &[&str],
+        flag_options: &[&str],
+    ) -> Result<Self, CliError> {
+        let mut options = Self {
+            positional: Vec::new(),
+            values: FxHashMap::default(),
+            flags: FxHashSet::default(),
+        };
+        let mut args = args.iter().map(String::as_str);
+        while let Some(arg) = args.next() {
+            if value_options.contains(&arg) {
+                let value = args
+                    .next()
+                    .ok_or_else(|| CliError::Usage(format!("missing value after {arg}")))?;
+                options.values.insert(arg, value);
+            } else if flag_options.contains(&arg) {
+                options.flags.insert(arg);
+            } else if arg.starts_with('-') && arg != "-" {
+                return Err(CliError::Usage(format!("unknown option {arg}")));
+            } else {
+                options.positional.push(arg);
+            }
+        }
+        Ok(options)
+    }
+
+    fn value(&self, option: &str) -> Option<&'args str> {
+        self.values.get(option).copied()
+    }
+
+    fn flag(&self, option: &str) -> bool {
+        self.flags.contains(option)
+    }
+}
+
+/// Formats the given statistics as a JSON object, with the histograms as objects mapping sizes to counts.
+fn statistics_json(statistics: &SPQRDecompositionStatistics) -> String {
+    let histogram = |histogram: &BTreeMap<usize, usize>| {
+        let entries = histogram
+            .iter()
+            .map(|(size, count)| format!("\"{size}\":{count}"))
+            .collect::<Vec<_>>();
+        format!("{{{}}}", entries.join(","))
+    };
+    let largest_r_node_node_count = statistics
+        .r_node_size_histogram
+        .last_key_value()
+        .map_or("null".to_string(), |(size, _)| size.to_string());
+    let fields = [
+        ("node_count", statistics.node_count.to_string()),
+        ("edge_count", statistics.edge_count.to_string()),
+        ("component_count", statistics.component_count.to_string()),
+        ("block_count", statistics.block_count.to_string()),
+        ("cut_node_count", statistics.cut_node_count.to_string()),
+        ("spqr_node_count", statistics.spqr_node_count.to_string()),
+        ("spqr_edge_count", statistics.spqr_edge_count.to_string()),
+        ("s_node_count", statistics.s_node_count.to_string()),
+        ("p_node_count", statistics.p_node_count.to_string()),
+        ("r_node_count", statistics.r_node_count.to_string()),
+        ("bridge_count", statistics.bridge_count.to_string()),
+        (
+            "largest_component_node_count",
+            statistics.largest_component_node_count.to_string(),
+        ),
+        ("largest_r_node_node_count", largest_r_node_node_count),
+        (
+            "blocks_per_component_histogram",
+            histogram(&statistics.blocks_per_component_histogram),
+        ),
+        (
+            "block_size_histogram",
+            histogram(&statistics.block_size_histogram),
+        ),
+        (
+            "s_node_size_histogram",
+            histogram(&statistics.s_node_size_histogram),
+        ),
+        (
+            "r_node_size_histogram",
+            histogram(&statistics.r_node_size_histogram),
+        ),
+    ];
+    let fields = fields
+        .iter()
+        .map(|(name, value)| format!("\"{name}\":{value}"))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Reads a graph in GFA or edge list format, depending on the extension of `path`.
+fn read_graph(path: &Path) -> Result<Graph, CliError> {
+    let contents = fs::read_to_string(path).map_err(|source| CliError::Io {
+        path: path.to_owned(),
+        source,
+    })?;
+    let gfa = if path.extension() == Some(OsStr::new("gfa")) {
+        contents
+    } else {
+        edge_list_to_gfa(&contents)
+    };
+    Graph::read_gfa1(gfa.as_bytes()).map_err(|source| CliError::Gfa {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Translates an edge list into GFA, with empty sequences and overlaps.
+fn edge_list_to_gfa(edge_list: &str) -> String {
+    let mut nodes = Vec::new();
+    let mut links = String::new();
+    let mut declared = FxHashSet::default();
+    for line in edge_list.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let names = line.split_whitespace().collect::<Vec<_>>();
+        for &name in &names {
+            if declared.insert(name) {
+                nodes.push(name);
+            }
+        }
+        if let [u, v, ..] = names[..] {
+            writeln!(links, "L\t{u}\t+\t{v}\t+\t0M").unwrap();
+        }
+    }
+
+    let mut gfa = "H\tVN:Z:1.0\n".to_string();
+    for name in nodes {
+        writeln!(gfa, "S\t{name}\t*").unwrap();
+    }
+    gfa + &links
+}
+
+/// Computes the SPQR decomposition of the given graph, see the [module documentation](self) for its limitations.
+fn compute(graph: &Graph) -> Result<SPQRDecomposition<'_, Graph>, CliError> {
+    let mut builder = SPQRDecompositionBuilder::new(graph);
+    for component_index in builder.add_components_from_graph() {
+        for block_index in builder.add_blocks_from_graph(component_index) {
+            builder.add_spqr_node_if_cycle(block_index);
+        }
+        builder.add_self_loops_from_graph(component_index);
+    }
+    builder.try_build().map_err(|error| {
+        let unsupported_blocks = error
+            .problems
+            .iter()
+            .filter(|problem| matches!(problem, BuildProblem::BlockWithoutSPQRNodes(_)))
+            .count();
+        if unsupported_blocks > 0 {
+            CliError::Unsupported(unsupported_blocks)
+        } else {
+            CliError::Build(error.to_string())
+        }
+    })
+}
+
+/// Reads a decomposition in the plain or binary format, returning whether it was in the plain format.
+fn read_decomposition<'graph>(
+    graph: &'graph Graph,
+    path: &Path,
+) -> Result<(SPQRDecomposition<'graph, Graph>, bool), CliError> {
+    let bytes = fs::read(path).map_err(|source| CliError::Io {
+        path: path.to_owned(),
+        source,
+    })?;
+    if bytes.starts_with(PLAIN_HEADER) {
+        SPQRDecomposition::read_plain_spqr(graph, bytes.as_slice())
+            .map(|spqr_decomposition| (spqr_decomposition, true))
+            .map_err(|source| CliError::Plain {
+                path: path.to_owned(),
+                source,
+            })
+    } else {
+        SPQRDecomposition::read_binary_from_slice(graph, &bytes)
+            .map(|spqr_decomposition| (spqr_decomposition, false))
+            .map_err(|source| CliError::Io {
+                path: path.to_owned(),
+                source,
+            })
+    }
+}
+
+/// Writes to the file at `path`, or to `stdout` if no path is given.
+fn write_output(
+    path: Option<&Path>,
+    stdout: &mut impl Write,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> Result<(), CliError> {
+    match path {
+        Some(path) => {
+            let file = File::create(path).map_err(|source| CliError::Io {
+                path: path.to_owned(),
+                source,
+            })?;
+            let mut writer = BufWriter::new(file);
+            write(&mut writer)?;
+            writer.flush()?;
+        }
+        None => {
+            write(stdout)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use spqr_tree::decomposition::SPQRDecomposition;
+
+use crate::{CliError, EXIT_DIFFERENT, EXIT_INVALID, read_graph, run};
+
+/// Returns a path in a fresh temporary directory for the given test.
+fn temporary_path(test_name: &str, file_name: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("spqr-cli-{}-{test_name}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    directory.join(file_name)
+}
+
+fn run_to_string(args: &[&str]) -> Result<String, CliError> {
+    let (exit_code, stdout) = run_with_exit_code(args)?;
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    Ok(stdout)
+}
+
+fn run_with_exit_code(args: &[&str]) -> Result<(ExitCode, String), CliError> {
+    let args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+    let mut stdout = Vec::new();
+    let exit_code = run(&args, &mut stdout)?;
+    Ok((exit_code, String::from_utf8(stdout).unwrap()))
+}
+
+#[test]
+fn test_compute() {
+    let output = run_to_string(&["compute", "test_files/tiny1.gfa"]).unwrap();
+    let graph = read_graph("test_files/tiny1.gfa".as_ref()).unwrap();
+    let computed = SPQRDecomposition::read_plain_spqr(&graph, output.as_bytes()).unwrap();
+    let expected = SPQRDecomposition::read_plain_spqr(
+        &graph,
+        fs::read_to_string("test_files/tiny1.spqr")
+            .unwrap()
+            .as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(computed.statistics(), expected.statistics());
+
+    let edge_list = temporary_path("compute", "graph.txt");
+    fs::write(
+        &edge_list,
+        "# a triangle with a pendant edge\na b\nb c\nc a\nc d\nd d\ne\n",
+    )
+    .unwrap();
+    let output = run_to_string(&["compute", edge_list.to_str().unwrap()]).unwrap();
+    let graph = read_graph(&edge_list).unwrap();
+    assert_eq!(graph.node_count(), 5);
+    assert_eq!(graph.edge_count(), 5);
+    let computed = SPQRDecomposition::read_plain_spqr(&graph, output.as_bytes()).unwrap();
+    assert_eq!(computed.iter_components().count(), 2);
+    assert_eq!(computed.iter_blocks().count(), 2);
+    assert_eq!(computed.iter_spqr_nodes().count(), 1);
+
+    let k4 = temporary_path("compute", "k4.txt");
+    fs::write(&k4, "a b\na c\na d\nb c\nb d\nc d\n").unwrap();
+    assert!(matches!(
+        run_to_string(&["compute", k4.to_str().unwrap()]),
+        Err(CliError::Unsupported(1))
+    ));
+}
+
+#[test]
+fn test_convert_and_cat() {
+    let binary = temporary_path("convert", "tiny1.spqr.bin");
+    let plain = temporary_path("convert", "tiny1.spqr");
+    let (binary, plain) = (binary.to_str().unwrap(), plain.to_str().unwrap());
+    run_to_string(&[
+        "convert",
+        "test_files/tiny1.gfa",
+        "test_files/tiny1.spqr",
+        binary,
+    ])
+    .unwrap();
+    assert!(!fs::read(binary).unwrap().starts_with(b"H "));
+    run_to_string(&["convert", "test_files/tiny1.gfa", binary, plain]).unwrap();
+
+    let original =
+        run_to_string(&["cat", "test_files/tiny1.gfa", "test_files/tiny1.spqr"]).unwrap();
+    assert_eq!(
+        run_to_string(&["cat", "test_files/tiny1.gfa", binary]).unwrap(),
+        original
+    );
+    assert_eq!(fs::read_to_string(plain).unwrap(), original);
+}
+
+#[test]
+fn test_validate() {
+    let (exit_code, stdout) =
+        run_with_exit_code(&["validate", "test_files/tiny1.gfa", "test_files/tiny1.spqr"]).unwrap();
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    assert_eq!(stdout, "test_files/tiny1.spqr: valid\n");
+
+    // Declaring the S-node of a triangle as an R-node breaks the shape of its skeleton.
+    let invalid = temporary_path("validate", "invalid.spqr");
+    fs::write(
+        &invalid,
+        fs::read_to_string("test_files/tiny1.spqr")
+            .unwrap()
+            .replace("\nS S0 ", "\nR S0 "),
+    )
+    .unwrap();
+    let invalid = invalid.to_str().unwrap();
+    let (exit_code, stdout) =
+        run_with_exit_code(&["validate", "test_files/tiny1.gfa", invalid]).unwrap();
+    assert_eq!(exit_code, ExitCode::from(EXIT_INVALID));
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next().unwrap(), format!("{invalid}: 1 problems"));
+    assert!(
+        lines
+            .next()
+            .unwrap()
+            .contains("does not have the shape of an R-node")
+    );
+    assert_eq!(lines.next(), None);
+}
+
+#[test]
+fn test_stats() {
+    let text = run_to_string(&["stats", "test_files/tiny1.spqr"]).unwrap();
+    let graph = read_graph("test_files/tiny1.gfa".as_ref()).unwrap();
+    let statistics = SPQRDecomposition::read_plain_spqr(
+        &graph,
+        fs::read_to_string("test_files/tiny1.spqr")
+            .unwrap()
+            .as_bytes(),
+    )
+    .unwrap()
+    .statistics();
+    assert_eq!(text, format!("{statistics}\nLargest R-node: none\n"));
+
+    let json = run_to_string(&["stats", "test_files/tiny1.spqr", "--json"]).unwrap();
+    assert!(json.starts_with("{\"node_count\":11,\"edge_count\":"));
+    assert!(json.contains("\"largest_r_node_node_count\":null"));
+    assert!(json.contains("\"s_node_size_histogram\":{\"3\":5}"));
+
+    let binary = temporary_path("stats", "tiny1.spqr.bin");
+    let binary = binary.to_str().unwrap();
+    run_to_string(&[
+        "convert",
+        "test_files/tiny1.gfa",
+        "test_files/tiny1.spqr",
+        binary,
+    ])
+    .unwrap();
+    assert!(matches!(
+        run_to_string(&["stats", binary]),
+        Err(CliError::Usage(_))
+    ));
+    assert_eq!(
+        run_to_string(&["stats", binary, "--graph", "test_files/tiny1.gfa", "--json"]).unwrap(),
+        json
+    );
+}
+
+#[test]
+fn test_usage_errors() {
+    for args in [
+        &[][..],
+        &["decompose"],
+        &["compute"],
+        &["compute", "a.gfa", "b.gfa"],
+        &["compute", "a.gfa", "-o"],
+        &["convert", "test_files/tiny1.gfa", "test_files/tiny1.spqr"],
+        &["cat", "test_files/tiny1.gfa"],
+        &["validate", "test_files/tiny1.gfa"],
+        &["stats", "test_files/tiny1.spqr", "--verbose"],
+    ] {
+        assert!(
+            matches!(run_to_string(args), Err(CliError::Usage(_))),
+            "{args:?}"
+        );
+    }
+    assert!(matches!(
+        run_to_string(&["cat", "test_files/missing.gfa", "test_files/tiny1.spqr"]),
+        Err(CliError::Io { .. })
+    ));
+}
+
+#[test]
+fn test_extract() {
+    let gfa = run_to_string(&[
+        "extract",
+        "test_files/tiny1.gfa",
+        "test_files/tiny1.spqr",
+        "--block",
+        "B0",
+    ])
+    .unwrap();
+    assert!(gfa.starts_with("H\tVN:Z:1.0\n"));
+    assert_eq!(gfa.lines().filter(|line| line.starts_with('S')).count(), 3);
+    assert_eq!(gfa.lines().filter(|line| line.starts_with('L')).count(), 3);
+
+    let output = temporary_path("extract", "S0.dot");
+    run_to_string(&[
+        "extract",
+        "test_files/tiny1.gfa",
+        "test_files/tiny1.spqr",
+        "--spqr-node",
+        "S0",
+        "--format",
+        "dot",
+        "-o",
+        output.to_str().unwrap(),
+    ])
+    .unwrap();
+    assert!(
+        fs::read_to_string(&output)
+            .unwrap()
+            .starts_with("graph \"S0\" {\n")
+    );
+
+    assert!(matches!(
+        run_to_string(&[
+            "extract",
+            "test_files/tiny1.gfa",
+            "test_files/tiny1.spqr",
+            "--spqr-node",
+            "R0",
+        ]),
+        Err(CliError::UnknownName(name)) if name == "R0"
+    ));
+    for options in [
+        &["--block", "B0", "--spqr-node", "S0"][..],
+        &[],
+        &["--block", "B0", "--format", "svg"],
+    ] {
+        let mut args = vec!["extract", "test_files/tiny1.gfa", "test_files/tiny1.spqr"];
+        args.extend(options);
+        assert!(
+            matches!(run_to_string(&args), Err(CliError::Usage(_))),
+            "{args:?}"
+        );
+    }
+}
+
+#[test]
+fn test_diff() {
+    let binary = temporary_path("diff", "tiny1.spqr.bin");
+    let binary = binary.to_str().unwrap();
+    run_to_string(&[
+        "convert",
+        "test_files/tiny1.gfa",
+        "test_files/tiny1.spqr",
+        binary,
+    ])
+    .unwrap();
+    let (exit_code, stdout) = run_with_exit_code(&[
+        "diff",
+        "test_files/tiny1.spqr",
+        binary,
+        "--graph",
+        "test_files/tiny1.gfa",
+    ])
+    .unwrap();
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    assert_eq!(stdout, "");
+
+    let retyped = temporary_path("diff", "retyped.spqr");
+    fs::write(
+        &retyped,
+        fs::read_to_string("test_files/tiny1.spqr")
+            .unwrap()
+            .replace("\nS S0 ", "\nR S0 "),
+    )
+    .unwrap();
+    let (exit_code, stdout) = run_with_exit_code(&[
+        "diff",
+        "test_files/tiny1.spqr",
+        retyped.to_str().unwrap(),
+        "--graph",
+        "test_files/tiny1.gfa",
+    ])
+    .unwrap();
+    assert_eq!(exit_code, ExitCode::from(EXIT_DIFFERENT));
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("< S0 in B0: "));
+    assert!(lines[1].starts_with("> R0 in B0: "));
+
+    assert!(matches!(
+        run_to_string(&["diff", "test_files/tiny1.spqr", binary]),
+        Err(CliError::Usage(_))
+    ));
+}
@@ -1,19 +1,78 @@
 #![expect(clippy::type_complexity)]
 
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ptr,
+    str::FromStr,
+    sync::Arc,
+};
+
 use smallvec::SmallVec;
 use tagged_vec::TaggedVec;
+use thiserror::Error;
 
 use crate::{
-    decomposition::indices::{
-        BlockIndex, ComponentIndex, CutNodeIndex, GraphIndex, GraphIndexInteger,
-        OptionalBlockIndex, OptionalCutNodeIndex, OptionalSPQRNodeIndex, SPQREdgeIndex,
-        SPQRNodeIndex,
+    decomposition::{
+        extra_data::ExtraData,
+        indices::{
+            BlockIndex, ComponentIndex, CutNodeIndex, GraphIndex, GraphIndexInteger,
+            OptionalBlockIndex, OptionalCutNodeIndex, OptionalSPQRNodeIndex, SPQREdgeIndex,
+            SPQRNodeIndex,
+        },
     },
     graph::StaticGraph,
 };
+use cache::DerivedDataCache;
 
+pub mod adjacency;
+pub mod augmentation;
+pub mod block_solver;
+pub mod block_spqr_tree;
+pub mod bubbles;
 pub mod builder;
+mod cache;
+pub mod canonical;
+pub mod chordless_cycles;
+pub mod condensation;
+mod editing;
+pub mod embedding;
+pub mod extra_data;
+pub mod flat;
+pub mod girth;
 pub mod indices;
+pub mod isomorphism;
+pub mod layout;
+pub mod memory;
+pub mod node_set;
+pub mod orientation;
+#[cfg(feature = "parallel")]
+mod parallel;
+pub mod path;
+pub mod path_avoidance;
+pub mod path_counting;
+pub mod q_nodes;
+pub mod r_node_classification;
+pub mod reliability;
+mod render;
+mod reorder;
+mod rooted;
+pub mod sampling;
+pub mod separators;
+pub mod series_parallel;
+pub mod spanning_trees;
+pub mod st_planarity;
+pub mod statistics;
+pub mod symmetry;
+#[cfg(test)]
+pub(crate) mod tests;
+mod transform;
+pub mod treewidth;
+pub mod triconnected;
+pub mod validation;
 
 /// Represents the SPQR decomposition as an augmentation over a graph.
 ///
@@ -23,9 +82,28 @@ pub mod indices;
 ///
 /// The decomposition of a connected component into its biconnected components is called the [block cut tree](https://en.wikipedia.org/wiki/Biconnected_component#Block-cut_tree).
 /// The decomposition of a biconnected component into its triconnected components is called the [SPQR tree](https://en.wikipedia.org/wiki/SPQR_tree).
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct SPQRDecomposition<'graph, Graph: StaticGraph> {
-    pub(crate) graph: &'graph Graph,
+///
+/// By default, the decomposition borrows its graph.
+/// Using a different `GraphStorage`, the decomposition can also own its graph, see [`OwnedSPQRDecomposition`].
+///
+/// Two decompositions are equal if they have the same structure and their graphs have the same amounts of nodes and edges.
+/// The graphs themselves are not compared, so decompositions of equal graphs stored in different places are equal as well.
+/// Decompositions are ordered lexicographically by the same data, starting with the amounts of nodes and edges of their graphs.
+///
+/// # Thread safety
+///
+/// The decomposition is `Send + Sync` whenever its graph storage and the index types of the graph are.
+/// Derived data that is computed lazily by queries, such as node sets or rooted SPQR trees, is cached in [`OnceLock`](std::sync::OnceLock)s,
+/// so a single decomposition can be queried from many threads at once, e.g. by sharing a [`SharedSPQRDecomposition`] between request handlers.
+/// If multiple threads trigger the computation of the same cached data, one of them computes it while the others wait.
+#[derive(Debug, Clone)]
+pub struct SPQRDecomposition<
+    'graph,
+    Graph: StaticGraph,
+    GraphStorage: Borrow<Graph> = &'graph Graph,
+> {
+    pub(crate) graph: GraphStorage,
+    pub(crate) graph_lifetime: PhantomData<&'graph Graph>,
     pub(crate) components: TaggedVec<
         ComponentIndex<Graph::IndexType>,
         Component<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
@@ -44,8 +122,34 @@ pub struct SPQRDecomposition<'graph, Graph: StaticGraph> {
         TaggedVec<SPQREdgeIndex<Graph::IndexType>, SPQREdge<Graph::NodeIndex, Graph::IndexType>>,
     pub(crate) node_data: TaggedVec<Graph::NodeIndex, SPQRDecompositionNodeData<Graph::IndexType>>,
     pub(crate) edge_data: TaggedVec<Graph::EdgeIndex, SPQRDecompositionEdgeData<Graph::IndexType>>,
+    pub(crate) cache: DerivedDataCache<Graph::NodeIndex, Graph::IndexType>,
 }
 
+// Fails to compile if interior mutability that is not thread-safe is added to the decomposition.
+const _: () = {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[expect(dead_code)]
+    fn assert_decomposition_send_sync<Graph: StaticGraph + Send + Sync + 'static>()
+    where
+        Graph::IndexType: Send + Sync,
+        Graph::NodeIndex: Send + Sync,
+        Graph::EdgeIndex: Send + Sync,
+    {
+        assert_send_sync::<SPQRDecomposition<'_, Graph>>();
+        assert_send_sync::<OwnedSPQRDecomposition<Graph>>();
+        assert_send_sync::<SharedSPQRDecomposition<Graph>>();
+    }
+};
+
+/// An SPQR decomposition that owns its graph.
+///
+/// This allows to store the graph and its decomposition together, e.g. when returning both from a function.
+pub type OwnedSPQRDecomposition<Graph> = SPQRDecomposition<'static, Graph, Graph>;
+
+/// An SPQR decomposition that shares ownership of its graph, see [`SPQRDecomposition::into_owned`].
+pub type SharedSPQRDecomposition<Graph> = SPQRDecomposition<'static, Graph, Arc<Graph>>;
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Component<NodeIndex, EdgeIndex, IndexType> {
     pub(crate) nodes: Vec<NodeIndex>,
@@ -55,6 +159,7 @@ pub struct Component<NodeIndex, EdgeIndex, IndexType> {
     pub(crate) blocks: Vec<BlockIndex<IndexType>>,
     /// Only populated if the component has at least two nodes.
     pub(crate) cut_nodes: Vec<CutNodeIndex<IndexType>>,
+    pub(crate) extra_data: ExtraData,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -68,6 +173,7 @@ pub struct Block<NodeIndex, EdgeIndex, IndexType> {
     pub(crate) spqr_nodes: Vec<SPQRNodeIndex<IndexType>>,
     /// Only populated if the block has at least three nodes.
     pub(crate) spqr_edges: Vec<SPQREdgeIndex<IndexType>>,
+    pub(crate) extra_data: ExtraData,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -75,6 +181,7 @@ pub struct CutNode<NodeIndex, IndexType> {
     pub(crate) component: ComponentIndex<IndexType>,
     pub(crate) node: NodeIndex,
     pub(crate) adjacent_blocks: SmallVec<[BlockIndex<IndexType>; 2]>,
+    pub(crate) extra_data: ExtraData,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -84,6 +191,7 @@ pub struct SPQRNode<NodeIndex, EdgeIndex, IndexType> {
     pub(crate) edges: Vec<EdgeIndex>,
     pub(crate) spqr_node_type: SPQRNodeType,
     pub(crate) spqr_edges: SmallVec<[SPQREdgeIndex<IndexType>; 2]>,
+    pub(crate) extra_data: ExtraData,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -93,6 +201,11 @@ pub enum SPQRNodeType {
     RNode,
 }
 
+/// The error returned when parsing an [`SPQRNodeType`] from a string other than `S`, `P` or `R`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid SPQR node type: {0:?}. Expected one of S, P, R")]
+pub struct ParseSPQRNodeTypeError(pub String);
+
 /// An edge in the SPQR tree connecting two SPQR nodes.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SPQREdge<NodeIndex, IndexType> {
@@ -100,13 +213,39 @@ pub struct SPQREdge<NodeIndex, IndexType> {
     pub(crate) virtual_edge: (NodeIndex, NodeIndex),
 }
 
+/// An edge in the skeleton graph of an SPQR node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SkeletonEdge<EdgeIndex, IndexType> {
+    /// An edge of the graph, also known as Q-node.
+    Real(EdgeIndex),
+    /// A virtual edge, represented by the SPQR edge that connects the two SPQR nodes containing it.
+    Virtual(SPQREdgeIndex<IndexType>),
+}
+
+/// The amount of blocks and SPQR nodes that are stored inline for each node.
+///
+/// Nodes that are in more blocks or SPQR nodes than this store the respective indices on the heap.
+/// The default is one, which can be raised to four with the `wide-node-index-lists` feature.
+/// This is useful for graphs where many nodes are in three or more blocks, such as pangenome graphs.
+#[cfg(not(feature = "wide-node-index-lists"))]
+pub const NODE_INDEX_LIST_INLINE_CAPACITY: usize = 1;
+/// The amount of blocks and SPQR nodes that are stored inline for each node.
+///
+/// Nodes that are in more blocks or SPQR nodes than this store the respective indices on the heap.
+/// The default is one, which was raised to four by the `wide-node-index-lists` feature.
+#[cfg(feature = "wide-node-index-lists")]
+pub const NODE_INDEX_LIST_INLINE_CAPACITY: usize = 4;
+
+/// A list of blocks or SPQR nodes belonging to a single node.
+pub(crate) type NodeIndexList<Index> = SmallVec<[Index; NODE_INDEX_LIST_INLINE_CAPACITY]>;
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) struct SPQRDecompositionNodeData<IndexType: GraphIndexInteger> {
     pub(crate) component_index: ComponentIndex<IndexType>,
-    pub(crate) block_indices: SmallVec<[BlockIndex<IndexType>; 1]>,
+    pub(crate) block_indices: NodeIndexList<BlockIndex<IndexType>>,
     pub(crate) cut_node_index: OptionalCutNodeIndex<IndexType>,
-    pub(crate) spqr_node_indices: SmallVec<[SPQRNodeIndex<IndexType>; 1]>,
-    pub(crate) extra_data: String,
+    pub(crate) spqr_node_indices: NodeIndexList<SPQRNodeIndex<IndexType>>,
+    pub(crate) extra_data: ExtraData,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -114,7 +253,7 @@ pub(crate) struct SPQRDecompositionEdgeData<IndexType: GraphIndexInteger> {
     pub(crate) component_index: ComponentIndex<IndexType>,
     pub(crate) block_index: OptionalBlockIndex<IndexType>,
     pub(crate) spqr_node_index: OptionalSPQRNodeIndex<IndexType>,
-    pub(crate) extra_data: String,
+    pub(crate) extra_data: ExtraData,
 }
 
 impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
@@ -122,6 +261,109 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
         self.graph
     }
 
+    /// Converts this decomposition into one that shares ownership of its graph.
+    ///
+    /// The resulting decomposition has a `'static` lifetime, so it can be cached, sent across threads or stored in long-lived services.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `graph` is not the graph of this decomposition.
+    /// If `graph` is not the same object, then it is compared structurally to the graph of this decomposition.
+    pub fn into_owned(self, graph: Arc<Graph>) -> SharedSPQRDecomposition<Graph> {
+        if !ptr::eq(self.graph, graph.as_ref()) {
+            assert_eq!(self.graph.node_count(), graph.node_count());
+            assert_eq!(self.graph.edge_count(), graph.edge_count());
+            assert!(
+                self.graph
+                    .node_indices()
+                    .zip(graph.node_indices())
+                    .all(|(a, b)| a == b && self.graph.node_name(a) == graph.node_name(b)),
+                "the nodes of the given graph differ from the nodes of the decomposed graph",
+            );
+            assert!(
+                self.graph
+                    .edge_indices()
+                    .zip(graph.edge_indices())
+                    .all(|(a, b)| a == b
+                        && self.graph.edge_endpoints(a) == graph.edge_endpoints(b)),
+                "the edges of the given graph differ from the edges of the decomposed graph",
+            );
+        }
+
+        let Self {
+            graph: _,
+            graph_lifetime: _,
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+            cache,
+        } = self;
+
+        SPQRDecomposition {
+            graph,
+            graph_lifetime: PhantomData,
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+            cache,
+        }
+    }
+}
+
+impl<Graph: StaticGraph> OwnedSPQRDecomposition<Graph> {
+    /// Creates an SPQR decomposition that owns the given graph.
+    ///
+    /// The decomposition is created by `decompose`, which receives a reference to the graph, e.g. by reading it from a file.
+    pub fn new_owned<Error>(
+        graph: Graph,
+        decompose: impl FnOnce(&Graph) -> Result<SPQRDecomposition<'_, Graph>, Error>,
+    ) -> Result<Self, Error> {
+        let SPQRDecomposition {
+            graph: _,
+            graph_lifetime: _,
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+            cache,
+        } = decompose(&graph)?;
+
+        Ok(Self {
+            graph,
+            graph_lifetime: PhantomData,
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+            cache,
+        })
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the graph of this decomposition, independently of how it is stored.
+    ///
+    /// For a decomposition that borrows its graph, [`graph`](SPQRDecomposition::graph) returns the graph with the lifetime of the borrow instead.
+    pub fn graph_ref(&self) -> &Graph {
+        self.graph.borrow()
+    }
+
     pub fn component_count(&self) -> usize {
         self.components.len()
     }
@@ -130,10 +372,18 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
         self.blocks.len()
     }
 
+    pub fn cut_node_count(&self) -> usize {
+        self.cut_nodes.len()
+    }
+
     pub fn spqr_node_count(&self) -> usize {
         self.spqr_nodes.len()
     }
 
+    pub fn spqr_edge_count(&self) -> usize {
+        self.spqr_edges.len()
+    }
+
     pub fn iter_component_indices(&self) -> impl Iterator<Item = ComponentIndex<Graph::IndexType>> {
         self.components.iter_indices(..)
     }
@@ -160,6 +410,17 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
         self.blocks.iter(..)
     }
 
+    pub fn iter_cut_nodes(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            CutNodeIndex<Graph::IndexType>,
+            &CutNode<Graph::NodeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.cut_nodes.iter(..)
+    }
+
     pub fn iter_spqr_nodes(
         &self,
     ) -> impl Iterator<
@@ -171,6 +432,17 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
         self.spqr_nodes.iter(..)
     }
 
+    pub fn iter_spqr_edges(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            SPQREdgeIndex<Graph::IndexType>,
+            &SPQREdge<Graph::NodeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.spqr_edges.iter(..)
+    }
+
     pub fn iter_blocks_in_component(
         &self,
         component_index: ComponentIndex<Graph::IndexType>,
@@ -203,6 +475,21 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
             .map(move |spqr_node_index| (spqr_node_index, &self.spqr_nodes[spqr_node_index]))
     }
 
+    /// Returns an iterator over the SPQR nodes of the given type in the given block.
+    pub fn iter_spqr_nodes_of_type(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        spqr_node_type: SPQRNodeType,
+    ) -> impl Iterator<
+        Item = (
+            SPQRNodeIndex<Graph::IndexType>,
+            &SPQRNode<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.iter_spqr_nodes_in_block(block_index)
+            .filter(move |(_, spqr_node)| spqr_node.spqr_node_type() == spqr_node_type)
+    }
+
     pub fn iter_spqr_edges_in_block(
         &self,
         block_index: BlockIndex<Graph::IndexType>,
@@ -219,8 +506,40 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
             .map(move |spqr_edge_index| (spqr_edge_index, &self.spqr_edges[spqr_edge_index]))
     }
 
+    /// Returns an iterator over all edges of the graph that belong to the given block.
+    ///
+    /// Each edge is returned exactly once, regardless of whether it is stored in the block itself or in one of its SPQR nodes.
+    pub fn iter_edges_in_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> impl Iterator<Item = Graph::EdgeIndex> {
+        let block = &self.blocks[block_index];
+        block.iter_edges().chain(
+            block
+                .spqr_nodes
+                .iter()
+                .flat_map(|spqr_node_index| self.spqr_nodes[*spqr_node_index].iter_edges()),
+        )
+    }
+
+    /// Returns an iterator over all edges of the graph that belong to the given component.
+    ///
+    /// Each edge is returned exactly once, regardless of whether it is stored in the component itself, in one of its blocks or in one of its SPQR nodes.
+    pub fn iter_edges_in_component(
+        &self,
+        component_index: ComponentIndex<Graph::IndexType>,
+    ) -> impl Iterator<Item = Graph::EdgeIndex> {
+        let component = &self.components[component_index];
+        component.iter_edges().chain(
+            component
+                .blocks
+                .iter()
+                .flat_map(|block_index| self.iter_edges_in_block(*block_index)),
+        )
+    }
+
     pub fn iter_nodes(&self) -> impl Iterator<Item = Graph::NodeIndex> {
-        self.graph.node_indices()
+        self.graph_ref().node_indices()
     }
 
     pub fn node_extra_data(&self, node_index: Graph::NodeIndex) -> &str {
@@ -231,12 +550,79 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
         &self.edge_data[edge_index].extra_data
     }
 
+    pub fn component_extra_data(&self, component_index: ComponentIndex<Graph::IndexType>) -> &str {
+        &self.components[component_index].extra_data
+    }
+
+    pub fn block_extra_data(&self, block_index: BlockIndex<Graph::IndexType>) -> &str {
+        &self.blocks[block_index].extra_data
+    }
+
+    pub fn cut_node_extra_data(&self, cut_node_index: CutNodeIndex<Graph::IndexType>) -> &str {
+        &self.cut_nodes[cut_node_index].extra_data
+    }
+
+    pub fn spqr_node_extra_data(&self, spqr_node_index: SPQRNodeIndex<Graph::IndexType>) -> &str {
+        &self.spqr_nodes[spqr_node_index].extra_data
+    }
+
+    /// Replaces the extra data of the given node.
+    pub fn set_node_extra_data(
+        &mut self,
+        node_index: Graph::NodeIndex,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        self.node_data[node_index].extra_data = extra_data.into();
+    }
+
+    /// Replaces the extra data of the given edge.
+    pub fn set_edge_extra_data(
+        &mut self,
+        edge_index: Graph::EdgeIndex,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        self.edge_data[edge_index].extra_data = extra_data.into();
+    }
+
+    /// Replaces the extra data of the given component.
+    pub fn set_component_extra_data(
+        &mut self,
+        component_index: ComponentIndex<Graph::IndexType>,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        self.components[component_index].extra_data = extra_data.into();
+    }
+
+    /// Replaces the extra data of the given block.
+    pub fn set_block_extra_data(
+        &mut self,
+        block_index: BlockIndex<Graph::IndexType>,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        self.blocks[block_index].extra_data = extra_data.into();
+    }
+
+    /// Replaces the extra data of the given cut node.
+    pub fn set_cut_node_extra_data(
+        &mut self,
+        cut_node_index: CutNodeIndex<Graph::IndexType>,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        self.cut_nodes[cut_node_index].extra_data = extra_data.into();
+    }
+
+    /// Replaces the extra data of the given SPQR node.
+    pub fn set_spqr_node_extra_data(
+        &mut self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        self.spqr_nodes[spqr_node_index].extra_data = extra_data.into();
+    }
+
     pub fn spqr_node_name(&self, spqr_node_index: SPQRNodeIndex<Graph::IndexType>) -> String {
-        match self.spqr_nodes[spqr_node_index].spqr_node_type() {
-            SPQRNodeType::SNode => format!("S{spqr_node_index}"),
-            SPQRNodeType::PNode => format!("P{spqr_node_index}"),
-            SPQRNodeType::RNode => format!("R{spqr_node_index}"),
-        }
+        let spqr_node_type = self.spqr_nodes[spqr_node_index].spqr_node_type();
+        format!("{spqr_node_type}{spqr_node_index}")
     }
 
     pub fn node_component_index(
@@ -267,6 +653,27 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
         self.cut_nodes[cut_node_index].node
     }
 
+    pub fn component(
+        &self,
+        component_index: ComponentIndex<Graph::IndexType>,
+    ) -> &Component<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType> {
+        &self.components[component_index]
+    }
+
+    pub fn block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> &Block<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType> {
+        &self.blocks[block_index]
+    }
+
+    pub fn spqr_node(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+    ) -> &SPQRNode<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType> {
+        &self.spqr_nodes[spqr_node_index]
+    }
+
     pub fn cut_node(
         &self,
         cut_node_index: CutNodeIndex<Graph::IndexType>,
@@ -286,6 +693,36 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
         &self.spqr_edges[spqr_edge_index]
     }
 
+    /// Returns an iterator over the edges of the skeleton graph of the given SPQR node together with their endpoints.
+    ///
+    /// Real edges are returned before virtual edges.
+    pub fn iter_skeleton_edges(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+    ) -> impl Iterator<
+        Item = (
+            SkeletonEdge<Graph::EdgeIndex, Graph::IndexType>,
+            (Graph::NodeIndex, Graph::NodeIndex),
+        ),
+    > {
+        self.spqr_nodes[spqr_node_index]
+            .iter_skeleton_edges()
+            .map(|skeleton_edge| (skeleton_edge, self.skeleton_edge_endpoints(skeleton_edge)))
+    }
+
+    /// Returns the endpoints of the given skeleton edge.
+    pub fn skeleton_edge_endpoints(
+        &self,
+        skeleton_edge: SkeletonEdge<Graph::EdgeIndex, Graph::IndexType>,
+    ) -> (Graph::NodeIndex, Graph::NodeIndex) {
+        match skeleton_edge {
+            SkeletonEdge::Real(edge_index) => self.graph_ref().edge_endpoints(edge_index),
+            SkeletonEdge::Virtual(spqr_edge_index) => {
+                self.spqr_edges[spqr_edge_index].virtual_edge()
+            }
+        }
+    }
+
     /// Returns true if the given node has an incident virtual edge.
     ///
     /// This also means that the node is part of at least two SPQR nodes.
@@ -294,6 +731,100 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
     }
 }
 
+impl<
+    'graph,
+    'other_graph,
+    Graph: StaticGraph,
+    GraphStorage: Borrow<Graph>,
+    OtherGraphStorage: Borrow<Graph>,
+> PartialEq<SPQRDecomposition<'other_graph, Graph, OtherGraphStorage>>
+    for SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    fn eq(&self, other: &SPQRDecomposition<'other_graph, Graph, OtherGraphStorage>) -> bool {
+        self.graph_ref().node_count() == other.graph_ref().node_count()
+            && self.graph_ref().edge_count() == other.graph_ref().edge_count()
+            && self.components == other.components
+            && self.blocks == other.blocks
+            && self.cut_nodes == other.cut_nodes
+            && self.spqr_nodes == other.spqr_nodes
+            && self.spqr_edges == other.spqr_edges
+            && self.node_data == other.node_data
+            && self.edge_data == other.edge_data
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>> Eq
+    for SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+}
+
+impl<
+    'graph,
+    'other_graph,
+    Graph: StaticGraph,
+    GraphStorage: Borrow<Graph>,
+    OtherGraphStorage: Borrow<Graph>,
+> PartialOrd<SPQRDecomposition<'other_graph, Graph, OtherGraphStorage>>
+    for SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    fn partial_cmp(
+        &self,
+        other: &SPQRDecomposition<'other_graph, Graph, OtherGraphStorage>,
+    ) -> Option<Ordering> {
+        Some(self.compare(other))
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>> Ord
+    for SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other)
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Compares the data that is also compared by [`PartialEq`].
+    fn compare<OtherGraphStorage: Borrow<Graph>>(
+        &self,
+        other: &SPQRDecomposition<'_, Graph, OtherGraphStorage>,
+    ) -> Ordering {
+        self.graph_ref()
+            .node_count()
+            .cmp(&other.graph_ref().node_count())
+            .then_with(|| {
+                self.graph_ref()
+                    .edge_count()
+                    .cmp(&other.graph_ref().edge_count())
+            })
+            .then_with(|| self.components.cmp(&other.components))
+            .then_with(|| self.blocks.cmp(&other.blocks))
+            .then_with(|| self.cut_nodes.cmp(&other.cut_nodes))
+            .then_with(|| self.spqr_nodes.cmp(&other.spqr_nodes))
+            .then_with(|| self.spqr_edges.cmp(&other.spqr_edges))
+            .then_with(|| self.node_data.cmp(&other.node_data))
+            .then_with(|| self.edge_data.cmp(&other.edge_data))
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>> Hash
+    for SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.graph_ref().node_count().hash(state);
+        self.graph_ref().edge_count().hash(state);
+        self.components.hash(state);
+        self.blocks.hash(state);
+        self.cut_nodes.hash(state);
+        self.spqr_nodes.hash(state);
+        self.spqr_edges.hash(state);
+        self.node_data.hash(state);
+        self.edge_data.hash(state);
+    }
+}
+
 impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> Component<NodeIndex, EdgeIndex, IndexType> {
     pub fn node_count(&self) -> usize {
         self.nodes.len()
@@ -318,6 +849,10 @@ impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> Component<NodeIndex, Edg
     pub fn iter_cut_nodes(&self) -> impl Iterator<Item = CutNodeIndex<IndexType>> {
         self.cut_nodes.iter().copied()
     }
+
+    pub fn extra_data(&self) -> &str {
+        &self.extra_data
+    }
 }
 
 impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> Block<NodeIndex, EdgeIndex, IndexType> {
@@ -344,6 +879,10 @@ impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> Block<NodeIndex, EdgeInd
     pub fn iter_edges(&self) -> impl Iterator<Item = EdgeIndex> {
         self.edges.iter().copied()
     }
+
+    pub fn extra_data(&self) -> &str {
+        &self.extra_data
+    }
 }
 
 impl<NodeIndex: Copy, IndexType: Copy> CutNode<NodeIndex, IndexType> {
@@ -358,6 +897,10 @@ impl<NodeIndex: Copy, IndexType: Copy> CutNode<NodeIndex, IndexType> {
     pub fn iter_adjacent_blocks(&self) -> impl Iterator<Item = BlockIndex<IndexType>> {
         self.adjacent_blocks.iter().copied()
     }
+
+    pub fn extra_data(&self) -> &str {
+        &self.extra_data
+    }
 }
 
 impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> SPQRNode<NodeIndex, EdgeIndex, IndexType> {
@@ -408,6 +951,13 @@ impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> SPQRNode<NodeIndex, Edge
         self.spqr_edges.iter().copied()
     }
 
+    /// Returns an iterator over the edges of the skeleton graph, i.e. the real edges followed by the virtual edges.
+    pub fn iter_skeleton_edges(&self) -> impl Iterator<Item = SkeletonEdge<EdgeIndex, IndexType>> {
+        self.iter_edges()
+            .map(SkeletonEdge::Real)
+            .chain(self.iter_incident_spqr_edges().map(SkeletonEdge::Virtual))
+    }
+
     pub fn is_s_node(&self) -> bool {
         self.spqr_node_type == SPQRNodeType::SNode
     }
@@ -419,6 +969,42 @@ impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> SPQRNode<NodeIndex, Edge
     pub fn is_r_node(&self) -> bool {
         self.spqr_node_type == SPQRNodeType::RNode
     }
+
+    pub fn extra_data(&self) -> &str {
+        &self.extra_data
+    }
+}
+
+impl SPQRNodeType {
+    /// Returns the letter used for this type of SPQR node in the plain SPQR file format.
+    pub fn letter(&self) -> &'static str {
+        match self {
+            Self::SNode => "S",
+            Self::PNode => "P",
+            Self::RNode => "R",
+        }
+    }
+}
+
+impl Display for SPQRNodeType {
+    /// Writes the letter used for this type of SPQR node in the plain SPQR file format.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+impl FromStr for SPQRNodeType {
+    type Err = ParseSPQRNodeTypeError;
+
+    /// Parses the letter used for a type of SPQR node in the plain SPQR file format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "S" => Ok(Self::SNode),
+            "P" => Ok(Self::PNode),
+            "R" => Ok(Self::RNode),
+            other => Err(ParseSPQRNodeTypeError(other.to_string())),
+        }
+    }
 }
 
 impl<NodeIndex: GraphIndex, IndexType: GraphIndexInteger> SPQREdge<NodeIndex, IndexType> {
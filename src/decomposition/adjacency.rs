@@ -0,0 +1,101 @@
+use std::{borrow::Borrow, sync::OnceLock};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        indices::{GraphIndexInteger, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A neighbour of an SPQR node in its SPQR tree, see [`SPQRDecomposition::spqr_tree_neighbors`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SPQRTreeNeighbor<NodeIndex, IndexType> {
+    /// The neighbouring SPQR node.
+    pub spqr_node: SPQRNodeIndex<IndexType>,
+    /// The SPQR edge connecting the two SPQR nodes.
+    pub spqr_edge: SPQREdgeIndex<IndexType>,
+    /// The virtual edge shared by the two SPQR nodes.
+    pub virtual_edge: (NodeIndex, NodeIndex),
+}
+
+/// The neighbours of all SPQR nodes, stored consecutively per SPQR node.
+#[derive(Debug, Clone)]
+pub(crate) struct SPQRTreeAdjacency<NodeIndex, IndexType> {
+    /// The neighbours of SPQR node `i` are at `offsets[i]..offsets[i + 1]`.
+    offsets: Box<[usize]>,
+    neighbors: Box<[SPQRTreeNeighbor<NodeIndex, IndexType>]>,
+}
+
+/// A lazily built [`SPQRTreeAdjacency`].
+///
+/// The cache is filled on demand through shared references, and must be cleared whenever the decomposition is modified.
+#[derive(Debug, Clone)]
+pub(crate) struct SPQRTreeAdjacencyCache<NodeIndex, IndexType> {
+    adjacency: OnceLock<SPQRTreeAdjacency<NodeIndex, IndexType>>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the neighbours of the given SPQR node in its SPQR tree, together with the connecting SPQR edges and shared virtual edges.
+    ///
+    /// The neighbours of all SPQR nodes are computed on first access and cached afterwards,
+    /// such that subsequent queries take time linear in the degree of the SPQR node.
+    pub fn spqr_tree_neighbors(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+    ) -> &[SPQRTreeNeighbor<Graph::NodeIndex, Graph::IndexType>] {
+        let adjacency = self
+            .cache
+            .spqr_tree_adjacency
+            .adjacency
+            .get_or_init(|| self.build_spqr_tree_adjacency());
+        let index = spqr_node_index.into_usize();
+        &adjacency.neighbors[adjacency.offsets[index]..adjacency.offsets[index + 1]]
+    }
+
+    fn build_spqr_tree_adjacency(&self) -> SPQRTreeAdjacency<Graph::NodeIndex, Graph::IndexType> {
+        let mut offsets = Vec::with_capacity(self.spqr_nodes.len() + 1);
+        let mut neighbors = Vec::with_capacity(2 * self.spqr_edges.len());
+        offsets.push(0);
+
+        for (spqr_node_index, spqr_node) in self.spqr_nodes.iter(..) {
+            neighbors.extend(spqr_node.spqr_edges.iter().map(|&spqr_edge_index| {
+                let spqr_edge = &self.spqr_edges[spqr_edge_index];
+                let (u, v) = spqr_edge.endpoints;
+                SPQRTreeNeighbor {
+                    spqr_node: if u == spqr_node_index { v } else { u },
+                    spqr_edge: spqr_edge_index,
+                    virtual_edge: spqr_edge.virtual_edge,
+                }
+            }));
+            offsets.push(neighbors.len());
+        }
+
+        SPQRTreeAdjacency {
+            offsets: offsets.into(),
+            neighbors: neighbors.into(),
+        }
+    }
+}
+
+impl<NodeIndex, IndexType: GraphIndexInteger> SPQRTreeAdjacencyCache<NodeIndex, IndexType> {
+    /// Returns the amount of heap memory used by the cached adjacency in bytes.
+    pub(crate) fn heap_size(&self) -> usize {
+        self.adjacency.get().map_or(0, |adjacency| {
+            size_of_val(&*adjacency.offsets) + size_of_val(&*adjacency.neighbors)
+        })
+    }
+}
+
+impl<NodeIndex, IndexType> Default for SPQRTreeAdjacencyCache<NodeIndex, IndexType> {
+    fn default() -> Self {
+        Self {
+            adjacency: OnceLock::new(),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::decomposition::tests::{read_decomposition, read_graph};
+
+#[test]
+fn test_spqr_tree_neighbors() {
+    let graph = read_graph("Ultrabubble_dataset_chr_22_G182870");
+    let mut spqr_decomposition = read_decomposition(&graph, "Ultrabubble_dataset_chr_22_G182870");
+
+    for (spqr_node_index, spqr_node) in spqr_decomposition.iter_spqr_nodes() {
+        let neighbors = spqr_decomposition.spqr_tree_neighbors(spqr_node_index);
+        assert_eq!(
+            neighbors.len(),
+            spqr_node.iter_incident_spqr_edges().count()
+        );
+        for (neighbor, spqr_edge_index) in
+            neighbors.iter().zip(spqr_node.iter_incident_spqr_edges())
+        {
+            let spqr_edge = spqr_decomposition.spqr_edge(spqr_edge_index);
+            let (u, v) = spqr_edge.endpoints();
+            assert_eq!(neighbor.spqr_edge, spqr_edge_index);
+            assert_eq!(neighbor.virtual_edge, spqr_edge.virtual_edge());
+            assert_eq!((neighbor.spqr_node == u), (spqr_node_index == v));
+            assert!(neighbor.spqr_node == u || neighbor.spqr_node == v);
+        }
+    }
+
+    if spqr_decomposition.spqr_edge_count() > 0 {
+        let merged = spqr_decomposition.contract_spqr_edge(0.into());
+        assert_eq!(
+            spqr_decomposition.spqr_tree_neighbors(merged).len(),
+            spqr_decomposition
+                .spqr_node(merged)
+                .iter_incident_spqr_edges()
+                .count()
+        );
+    }
+}
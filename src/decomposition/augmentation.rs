@@ -0,0 +1,233 @@
+//! Suggesting edges whose addition increases the connectivity of the graph.
+
+use std::borrow::Borrow;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, ComponentIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns a set of edges whose addition makes the given component biconnected.
+    ///
+    /// The leaf blocks of the block-cut tree are ordered by a depth-first traversal, and each is connected to the next one in a cycle.
+    /// Removing a cut node leaves each part with at least one leaf block, and the cycle connects them again.
+    /// Since each leaf block needs an incident new edge, this is at most twice the minimum.
+    ///
+    /// Each new edge connects two nodes of its leaf blocks that are not cut nodes.
+    /// Among those, the pair with the lowest `cost` is chosen, and ties are broken in favour of the pair that comes first in the blocks.
+    /// This takes time proportional to the product of the sizes of the two leaf blocks.
+    ///
+    /// Components that are already biconnected yield no edges.
+    pub fn suggest_biconnectivity_augmentation<Cost: PartialOrd>(
+        &self,
+        component_index: ComponentIndex<Graph::IndexType>,
+        mut cost: impl FnMut(Graph::NodeIndex, Graph::NodeIndex) -> Cost,
+    ) -> Vec<(Graph::NodeIndex, Graph::NodeIndex)> {
+        let component = &self.components[component_index];
+        let Some(&root) = component.blocks.first() else {
+            return Vec::new();
+        };
+
+        let mut leaves = Vec::new();
+        let mut stack = vec![(root, None)];
+        while let Some((block_index, parent_cut_node)) = stack.pop() {
+            let block = &self.blocks[block_index];
+            if block.cut_nodes.len() == 1 {
+                leaves.push(block_index);
+            }
+            for &cut_node_index in block.cut_nodes.iter().rev() {
+                if Some(cut_node_index) != parent_cut_node {
+                    for &child in self.cut_nodes[cut_node_index].adjacent_blocks.iter().rev() {
+                        if child != block_index {
+                            stack.push((child, Some(cut_node_index)));
+                        }
+                    }
+                }
+            }
+        }
+
+        let non_cut_nodes = |block_index: BlockIndex<_>| {
+            self.blocks[block_index]
+                .iter_nodes()
+                .filter(|&node_index| !self.is_cut_node(node_index))
+        };
+        let pairs = match leaves.len() {
+            0 | 1 => 0,
+            2 => 1,
+            leaf_count => leaf_count,
+        };
+        (0..pairs)
+            .map(|i| {
+                let (from, to) = (leaves[i], leaves[(i + 1) % leaves.len()]);
+                let mut best: Option<((Graph::NodeIndex, Graph::NodeIndex), Cost)> = None;
+                for u in non_cut_nodes(from) {
+                    for v in non_cut_nodes(to) {
+                        let pair_cost = cost(u, v);
+                        if best
+                            .as_ref()
+                            .is_none_or(|(_, best_cost)| pair_cost < *best_cost)
+                        {
+                            best = Some(((u, v), pair_cost));
+                        }
+                    }
+                }
+                best.unwrap().0
+            })
+            .collect()
+    }
+
+    /// Returns a small set of edges whose addition makes the given block triconnected.
+    ///
+    /// The edges connect terminals, which are nodes that each separation pair separates from some other terminal:
+    /// one node of each leaf of the SPQR tree that is not a pole of its virtual edge,
+    /// and the nodes of S-nodes whose two incident skeleton edges are real, which includes all nodes of degree two.
+    /// P-nodes whose other skeleton edges are all real only represent parallel edges and are treated like real edges.
+    ///
+    /// The terminals are ordered by a depth-first traversal of the SPQR tree that walks along the cycles of S-nodes,
+    /// such that the terminals separated by a separation pair form intervals of this order.
+    /// Then each terminal is connected to its successor and to the terminal half way around the order,
+    /// which keeps the terminals connected when removing any two nodes.
+    /// Pairs of terminals that are already adjacent are skipped.
+    /// This yields at most `3k / 2` edges for `k` terminals, while at least `k / 2` edges are necessary,
+    /// so the result is small, but not necessarily minimum.
+    ///
+    /// Blocks that are already triconnected, and blocks with fewer than four nodes, which cannot become triconnected, yield no edges.
+    pub fn suggest_triconnectivity_augmentation(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> Vec<(Graph::NodeIndex, Graph::NodeIndex)> {
+        let block = &self.blocks[block_index];
+        if block.nodes.len() < 4 || block.spqr_nodes.is_empty() {
+            return Vec::new();
+        }
+
+        let mut terminals = Vec::new();
+        self.collect_terminals(block.spqr_nodes[0], None, &mut terminals);
+        let k = terminals.len();
+        if k < 2 {
+            return Vec::new();
+        }
+
+        let is_adjacent = |u, v| {
+            self.graph_ref().incident_edges(u).any(|edge_index| {
+                let (a, b) = self.graph_ref().edge_endpoints(edge_index);
+                (a, b) == (u, v) || (a, b) == (v, u)
+            })
+        };
+        let mut edges = Vec::new();
+        for i in 0..k {
+            for j in [(i + 1) % k, (i + k / 2) % k] {
+                let (u, v) = (terminals[i], terminals[j]);
+                if u != v
+                    && !is_adjacent(u, v)
+                    && !edges.contains(&(u, v))
+                    && !edges.contains(&(v, u))
+                {
+                    edges.push((u, v));
+                }
+            }
+        }
+        edges
+    }
+
+    /// Returns true if the given SPQR edge leads from the given SPQR node to a P-node whose other skeleton edges are all real.
+    fn leads_to_parallel_edges(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+    ) -> bool {
+        let spqr_edge = &self.spqr_edges[spqr_edge_index];
+        let other = if spqr_edge.endpoints.0 == spqr_node_index {
+            spqr_edge.endpoints.1
+        } else {
+            spqr_edge.endpoints.0
+        };
+        self.spqr_nodes[other].spqr_node_type == SPQRNodeType::PNode
+            && self.spqr_nodes[other].spqr_edges.len() == 1
+    }
+
+    /// Collects the terminals of the subtree of the SPQR tree below the given SPQR node in depth-first order,
+    /// see [`suggest_triconnectivity_augmentation`](Self::suggest_triconnectivity_augmentation).
+    fn collect_terminals(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        parent_edge: Option<SPQREdgeIndex<Graph::IndexType>>,
+        terminals: &mut Vec<Graph::NodeIndex>,
+    ) {
+        let spqr_node = &self.spqr_nodes[spqr_node_index];
+        let is_real = |skeleton_edge| match skeleton_edge {
+            SkeletonEdge::Real(_) => true,
+            SkeletonEdge::Virtual(spqr_edge_index) => {
+                self.leads_to_parallel_edges(spqr_node_index, spqr_edge_index)
+            }
+        };
+        let visit_child = |spqr_edge_index, terminals: &mut Vec<_>| {
+            let spqr_edge = &self.spqr_edges[spqr_edge_index];
+            let child = if spqr_edge.endpoints.0 == spqr_node_index {
+                spqr_edge.endpoints.1
+            } else {
+                spqr_edge.endpoints.0
+            };
+            self.collect_terminals(child, Some(spqr_edge_index), terminals);
+        };
+
+        match spqr_node.spqr_node_type {
+            SPQRNodeType::SNode => {
+                let mut cycle = self.s_node_cycle(spqr_node_index);
+                if let Some(parent_position) = cycle.iter().position(|(skeleton_edge, _)| {
+                    Some(*skeleton_edge) == parent_edge.map(SkeletonEdge::Virtual)
+                }) {
+                    cycle.rotate_left(parent_position + 1);
+                }
+                for position in 0..cycle.len() {
+                    let (skeleton_edge, node_index) = cycle[position];
+                    let previous_edge = cycle[(position + cycle.len() - 1) % cycle.len()].0;
+                    if is_real(previous_edge) && is_real(skeleton_edge) {
+                        terminals.push(node_index);
+                    }
+                    match skeleton_edge {
+                        SkeletonEdge::Virtual(spqr_edge_index)
+                            if Some(spqr_edge_index) != parent_edge =>
+                        {
+                            visit_child(spqr_edge_index, terminals)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            SPQRNodeType::PNode | SPQRNodeType::RNode => {
+                let mut branches = spqr_node
+                    .iter_incident_spqr_edges()
+                    .filter(|&spqr_edge_index| !is_real(SkeletonEdge::Virtual(spqr_edge_index)));
+                if spqr_node.spqr_node_type == SPQRNodeType::RNode {
+                    match (branches.next(), branches.next()) {
+                        (None, _) => terminals.extend(spqr_node.iter_nodes().next()),
+                        (Some(spqr_edge_index), None) => {
+                            let (u, v) = self.spqr_edges[spqr_edge_index].virtual_edge;
+                            terminals.extend(
+                                spqr_node
+                                    .iter_nodes()
+                                    .find(|&node_index| node_index != u && node_index != v),
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+                for spqr_edge_index in spqr_node.iter_incident_spqr_edges() {
+                    if Some(spqr_edge_index) != parent_edge {
+                        visit_child(spqr_edge_index, terminals);
+                    }
+                }
+            }
+        }
+    }
+}
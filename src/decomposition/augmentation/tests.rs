@@ -0,0 +1,116 @@
+use std::io::BufReader;
+
+use bidirected_adjacency_array::graph::BidirectedAdjacencyArray;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{
+            TestGraph, is_biconnected, is_triconnected, read_decomposition, read_graph,
+            read_subdivided_wheel,
+        },
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_suggest_triconnectivity_augmentation() {
+    let (graph, spqr_decomposition_file) = read_subdivided_wheel();
+    let wheel =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let graphs = [
+        "tiny1",
+        "multiedge",
+        "random3_5_False_0",
+        "random5_10_False_0",
+    ]
+    .map(read_graph);
+    let decompositions = [
+        "tiny1",
+        "multiedge",
+        "random3_5_False_0",
+        "random5_10_False_0",
+    ]
+    .into_iter()
+    .zip(&graphs)
+    .map(|(name, graph)| read_decomposition(graph, name))
+    .chain([wheel]);
+
+    // A cycle, and a K4 with one edge subdivided twice.
+    let gfa = "H\tVN:Z:1.0\nS\t1\tA\nS\t2\tA\nS\t3\tA\nS\t4\tA\nS\t5\tA\n\
+        L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t4\t+\t5\t+\t0M\nL\t5\t+\t1\t+\t0M\n";
+    let cycle: TestGraph =
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+    let cycle_file = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 1 2 3 4 5\nB B0 G0 1 2 3 4 5\nS S0 B0 1 2 3 4 5\n\
+        E E0 S0 1 2\nE E1 S0 2 3\nE E2 S0 3 4\nE E3 S0 4 5\nE E4 S0 5 1\n";
+    let gfa = "H\tVN:Z:1.0\nS\tu\tA\nS\tv\tA\nS\tw\tA\nS\tr\tA\nS\ta\tA\nS\tb\tA\n\
+        L\tu\t+\tw\t+\t0M\nL\tu\t+\tr\t+\t0M\nL\tv\t+\tw\t+\t0M\nL\tv\t+\tr\t+\t0M\nL\tw\t+\tr\t+\t0M\n\
+        L\tu\t+\ta\t+\t0M\nL\ta\t+\tb\t+\t0M\nL\tb\t+\tv\t+\t0M\n";
+    let subdivided: TestGraph =
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+    let subdivided_file = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 u v w r a b\nB B0 G0 u v w r a b\nR R0 B0 u v w r\nS S1 B0 u a b v\nV V0 R0 S1 u v\n\
+        E E0 R0 u w\nE E1 R0 u r\nE E2 R0 v w\nE E3 R0 v r\nE E4 R0 w r\n\
+        E E5 S1 u a\nE E6 S1 a b\nE E7 S1 b v\n";
+    let decompositions =
+        decompositions.chain([(&cycle, cycle_file), (&subdivided, subdivided_file)].map(
+            |(graph, file)| SPQRDecomposition::read_plain_spqr(graph, file.as_bytes()).unwrap(),
+        ));
+
+    for spqr_decomposition in decompositions {
+        for (block_index, block) in spqr_decomposition.iter_blocks() {
+            let nodes = block.iter_nodes().collect::<Vec<_>>();
+            let mut edges = spqr_decomposition
+                .iter_edges_in_block(block_index)
+                .map(|edge_index| spqr_decomposition.graph().edge_endpoints(edge_index))
+                .collect::<Vec<_>>();
+            let augmentation = spqr_decomposition.suggest_triconnectivity_augmentation(block_index);
+            if is_triconnected(&nodes, &edges) || nodes.len() < 4 {
+                assert!(augmentation.is_empty());
+            } else {
+                edges.extend(augmentation);
+                assert!(is_triconnected(&nodes, &edges), "{block_index:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_suggest_biconnectivity_augmentation() {
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random3_5_False_0",
+        "random5_10_False_0",
+    ] {
+        let graph = read_graph(name);
+        let spqr_decomposition = read_decomposition(&graph, name);
+        for (component_index, component) in spqr_decomposition.iter_components() {
+            let nodes = component.iter_nodes().collect::<Vec<_>>();
+            let mut edges = spqr_decomposition
+                .iter_edges_in_component(component_index)
+                .map(|edge_index| spqr_decomposition.graph().edge_endpoints(edge_index))
+                .collect::<Vec<_>>();
+            // Prefer edges between nodes with close indices.
+            let augmentation = spqr_decomposition
+                .suggest_biconnectivity_augmentation(component_index, |u, v| {
+                    usize::from(u).abs_diff(usize::from(v))
+                });
+            let leaf_count = spqr_decomposition
+                .iter_blocks_in_component(component_index)
+                .filter(|(_, block)| block.iter_cut_nodes().count() == 1)
+                .count();
+            assert_eq!(
+                augmentation.len(),
+                if leaf_count == 2 { 1 } else { leaf_count }
+            );
+            assert!(augmentation.iter().all(|&(u, v)| {
+                !spqr_decomposition.is_cut_node(u) && !spqr_decomposition.is_cut_node(v)
+            }));
+
+            edges.extend(augmentation);
+            assert!(is_biconnected(&nodes, &edges), "{name} {component_index:?}");
+        }
+    }
+}
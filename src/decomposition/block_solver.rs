@@ -0,0 +1,102 @@
+//! Solving problems per block and composing the solutions along the block-cut tree.
+//!
+//! Many problems on graphs can be solved on each block separately, if the solutions of the blocks attached at its cut nodes are known.
+//! Rooting the block-cut tree of each component at a block, the blocks are solved from the leaves towards the root,
+//! such that the solutions of the blocks below each cut node are available when solving the block above it.
+
+use std::borrow::Borrow;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        indices::{BlockIndex, ComponentIndex},
+    },
+    graph::{StaticGraph, subgraph::Subgraph},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A block together with the solutions of the blocks below it, see [`SPQRDecomposition::solve_per_block`].
+#[derive(Debug)]
+pub struct BlockProblem<'graph, Graph: StaticGraph, Solution> {
+    pub block: BlockIndex<Graph::IndexType>,
+    /// The nodes and edges of the block.
+    pub subgraph: Subgraph<'graph, Graph>,
+    /// The cut node that attaches the block to the block above it, or `None` if the block is the root of its component.
+    pub parent_cut_node: Option<Graph::NodeIndex>,
+    /// The other cut nodes of the block, each with the solutions of the blocks attached below it.
+    pub child_cut_nodes: Vec<(Graph::NodeIndex, Vec<Solution>)>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Solves a problem on each block with `solve`, and returns the solution of the root block of each component.
+    ///
+    /// The block-cut tree of each component is rooted at its first block, and each block is solved after the blocks below it,
+    /// see the [module documentation](self).
+    /// Components without blocks, which consist of a single node, are skipped.
+    pub fn solve_per_block<Solution>(
+        &self,
+        mut solve: impl FnMut(BlockProblem<'_, Graph, Solution>) -> Solution,
+    ) -> impl Iterator<Item = (ComponentIndex<Graph::IndexType>, Solution)> {
+        let mut root_solutions = Vec::new();
+        for (component_index, component) in self.iter_components() {
+            let Some(&root) = component.blocks.first() else {
+                continue;
+            };
+
+            // The blocks in breadth-first order, each with the cut node above it.
+            let mut order = vec![(root, None)];
+            let mut position = 0;
+            while let Some(&(block_index, parent_cut_node)) = order.get(position) {
+                position += 1;
+                for &cut_node_index in &self.blocks[block_index].cut_nodes {
+                    if Some(cut_node_index) != parent_cut_node {
+                        for &child in &self.cut_nodes[cut_node_index].adjacent_blocks {
+                            if child != block_index {
+                                order.push((child, Some(cut_node_index)));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let mut solutions = FxHashMap::default();
+            for &(block_index, parent_cut_node) in order.iter().rev() {
+                let block = &self.blocks[block_index];
+                let child_cut_nodes = block
+                    .cut_nodes
+                    .iter()
+                    .filter(|&&cut_node_index| Some(cut_node_index) != parent_cut_node)
+                    .map(|&cut_node_index| {
+                        let children = self.cut_nodes[cut_node_index]
+                            .adjacent_blocks
+                            .iter()
+                            .filter(|&&child| child != block_index)
+                            .map(|child| solutions.remove(child).unwrap())
+                            .collect();
+                        (self.cut_nodes[cut_node_index].node, children)
+                    })
+                    .collect();
+                let solution = solve(BlockProblem {
+                    block: block_index,
+                    subgraph: Subgraph::new(
+                        self.graph_ref(),
+                        block.iter_nodes(),
+                        self.iter_edges_in_block(block_index),
+                    ),
+                    parent_cut_node: parent_cut_node
+                        .map(|cut_node_index| self.cut_nodes[cut_node_index].node),
+                    child_cut_nodes,
+                });
+                solutions.insert(block_index, solution);
+            }
+            root_solutions.push((component_index, solutions.remove(&root).unwrap()));
+        }
+        root_solutions.into_iter()
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{
+    decomposition::tests::{TEST_FILES, read_decomposition, read_graph},
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_solve_per_block() {
+    for name in TEST_FILES {
+        let graph = read_graph(name);
+        let spqr_decomposition = read_decomposition(&graph, name);
+
+        // Each block below a cut node shares the cut node with the block above it.
+        let node_counts = spqr_decomposition
+            .solve_per_block(|problem| {
+                let block = spqr_decomposition.block(problem.block);
+                assert_eq!(problem.subgraph.node_count(), block.iter_nodes().count());
+                if let Some(parent_cut_node) = problem.parent_cut_node {
+                    assert!(
+                        block
+                            .iter_nodes()
+                            .any(|node_index| node_index == parent_cut_node)
+                    );
+                }
+                problem.subgraph.node_count()
+                    + problem
+                        .child_cut_nodes
+                        .iter()
+                        .flat_map(|(_, solutions)| solutions)
+                        .map(|node_count| node_count - 1)
+                        .sum::<usize>()
+            })
+            .collect::<Vec<_>>();
+
+        let expected = spqr_decomposition
+            .iter_components()
+            .filter(|&(component_index, _)| {
+                spqr_decomposition
+                    .iter_blocks_in_component(component_index)
+                    .next()
+                    .is_some()
+            })
+            .map(|(component_index, component)| (component_index, component.iter_nodes().count()))
+            .collect::<Vec<_>>();
+        assert_eq!(node_counts, expected);
+    }
+}
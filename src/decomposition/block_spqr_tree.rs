@@ -0,0 +1,214 @@
+use std::borrow::Borrow;
+
+use rustc_hash::FxHashMap;
+use tagged_vec::TaggedVec;
+
+use crate::{
+    decomposition::{
+        NodeIndexList, SPQRDecomposition, SPQREdge, SPQRNode,
+        indices::{BlockIndex, GraphIndex, GraphIndexInteger, SPQREdgeIndex, SPQRNodeIndex},
+        transform::positions,
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The SPQR tree of a single block, detached from its decomposition.
+///
+/// The SPQR nodes and edges are numbered consecutively starting from zero.
+/// These local indices can be translated to and from the indices in the decomposition.
+/// Since the tree owns all its data, it can be cloned cheaply and sent to other threads.
+#[derive(Debug, Clone)]
+pub struct BlockSPQRTree<NodeIndex, EdgeIndex, IndexType> {
+    block: BlockIndex<IndexType>,
+    nodes: Vec<NodeIndex>,
+    /// The SPQR nodes with local SPQR edge indices.
+    spqr_nodes: TaggedVec<SPQRNodeIndex<IndexType>, SPQRNode<NodeIndex, EdgeIndex, IndexType>>,
+    /// The SPQR edges with local SPQR node indices.
+    spqr_edges: TaggedVec<SPQREdgeIndex<IndexType>, SPQREdge<NodeIndex, IndexType>>,
+    global_spqr_node_indices: TaggedVec<SPQRNodeIndex<IndexType>, SPQRNodeIndex<IndexType>>,
+    global_spqr_edge_indices: TaggedVec<SPQREdgeIndex<IndexType>, SPQREdgeIndex<IndexType>>,
+    local_spqr_node_indices: FxHashMap<SPQRNodeIndex<IndexType>, SPQRNodeIndex<IndexType>>,
+    local_spqr_edge_indices: FxHashMap<SPQREdgeIndex<IndexType>, SPQREdgeIndex<IndexType>>,
+    node_spqr_node_indices: FxHashMap<NodeIndex, NodeIndexList<SPQRNodeIndex<IndexType>>>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Extracts the SPQR tree of the given block.
+    ///
+    /// Blocks with less than three nodes have no SPQR tree, so for them the result is empty.
+    pub fn block_spqr_tree(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> BlockSPQRTree<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType> {
+        let block = &self.blocks[block_index];
+        let local_spqr_node_indices = positions(&block.spqr_nodes);
+        let local_spqr_edge_indices = positions(&block.spqr_edges);
+
+        let spqr_nodes = block
+            .spqr_nodes
+            .iter()
+            .map(|&spqr_node_index| {
+                let spqr_node = &self.spqr_nodes[spqr_node_index];
+                SPQRNode {
+                    spqr_edges: spqr_node
+                        .spqr_edges
+                        .iter()
+                        .map(|spqr_edge_index| local_spqr_edge_indices[spqr_edge_index])
+                        .collect(),
+                    ..spqr_node.clone()
+                }
+            })
+            .collect::<TaggedVec<_, _>>();
+        let spqr_edges = block
+            .spqr_edges
+            .iter()
+            .map(|&spqr_edge_index| {
+                let spqr_edge = &self.spqr_edges[spqr_edge_index];
+                SPQREdge {
+                    endpoints: (
+                        local_spqr_node_indices[&spqr_edge.endpoints.0],
+                        local_spqr_node_indices[&spqr_edge.endpoints.1],
+                    ),
+                    virtual_edge: spqr_edge.virtual_edge,
+                }
+            })
+            .collect();
+
+        let mut node_spqr_node_indices = FxHashMap::<_, NodeIndexList<_>>::default();
+        for (spqr_node_index, spqr_node) in spqr_nodes.iter(..) {
+            for node_index in spqr_node.iter_nodes() {
+                node_spqr_node_indices
+                    .entry(node_index)
+                    .or_default()
+                    .push(spqr_node_index);
+            }
+        }
+
+        BlockSPQRTree {
+            block: block_index,
+            nodes: block.nodes.clone(),
+            spqr_nodes,
+            spqr_edges,
+            global_spqr_node_indices: block.spqr_nodes.clone().into(),
+            global_spqr_edge_indices: block.spqr_edges.clone().into(),
+            local_spqr_node_indices,
+            local_spqr_edge_indices,
+            node_spqr_node_indices,
+        }
+    }
+}
+
+impl<NodeIndex: GraphIndex, EdgeIndex: GraphIndex, IndexType: GraphIndexInteger>
+    BlockSPQRTree<NodeIndex, EdgeIndex, IndexType>
+{
+    /// Returns the index of the block in the decomposition.
+    pub fn block(&self) -> BlockIndex<IndexType> {
+        self.block
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn spqr_node_count(&self) -> usize {
+        self.spqr_nodes.len()
+    }
+
+    pub fn spqr_edge_count(&self) -> usize {
+        self.spqr_edges.len()
+    }
+
+    pub fn iter_nodes(&self) -> impl Iterator<Item = NodeIndex> {
+        self.nodes.iter().copied()
+    }
+
+    /// Returns an iterator over the SPQR nodes with their local indices.
+    ///
+    /// The SPQR edges referenced by the SPQR nodes are local indices as well.
+    pub fn iter_spqr_nodes(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            SPQRNodeIndex<IndexType>,
+            &SPQRNode<NodeIndex, EdgeIndex, IndexType>,
+        ),
+    > {
+        self.spqr_nodes.iter(..)
+    }
+
+    /// Returns an iterator over the SPQR edges with their local indices.
+    ///
+    /// The endpoints of the SPQR edges are local indices as well.
+    pub fn iter_spqr_edges(
+        &self,
+    ) -> impl Iterator<Item = (SPQREdgeIndex<IndexType>, &SPQREdge<NodeIndex, IndexType>)> {
+        self.spqr_edges.iter(..)
+    }
+
+    pub fn spqr_node(
+        &self,
+        spqr_node_index: SPQRNodeIndex<IndexType>,
+    ) -> &SPQRNode<NodeIndex, EdgeIndex, IndexType> {
+        &self.spqr_nodes[spqr_node_index]
+    }
+
+    pub fn spqr_edge(
+        &self,
+        spqr_edge_index: SPQREdgeIndex<IndexType>,
+    ) -> &SPQREdge<NodeIndex, IndexType> {
+        &self.spqr_edges[spqr_edge_index]
+    }
+
+    /// Returns the local indices of the SPQR nodes that contain the given node.
+    pub fn node_spqr_node_indices(
+        &self,
+        node_index: NodeIndex,
+    ) -> impl Iterator<Item = SPQRNodeIndex<IndexType>> {
+        self.node_spqr_node_indices
+            .get(&node_index)
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    /// Translates a local SPQR node index into the corresponding index in the decomposition.
+    pub fn global_spqr_node_index(
+        &self,
+        spqr_node_index: SPQRNodeIndex<IndexType>,
+    ) -> SPQRNodeIndex<IndexType> {
+        self.global_spqr_node_indices[spqr_node_index]
+    }
+
+    /// Translates a local SPQR edge index into the corresponding index in the decomposition.
+    pub fn global_spqr_edge_index(
+        &self,
+        spqr_edge_index: SPQREdgeIndex<IndexType>,
+    ) -> SPQREdgeIndex<IndexType> {
+        self.global_spqr_edge_indices[spqr_edge_index]
+    }
+
+    /// Translates an SPQR node index of the decomposition into the corresponding local index.
+    ///
+    /// Returns `None` if the SPQR node is not part of this tree.
+    pub fn local_spqr_node_index(
+        &self,
+        spqr_node_index: SPQRNodeIndex<IndexType>,
+    ) -> Option<SPQRNodeIndex<IndexType>> {
+        self.local_spqr_node_indices.get(&spqr_node_index).copied()
+    }
+
+    /// Translates an SPQR edge index of the decomposition into the corresponding local index.
+    ///
+    /// Returns `None` if the SPQR edge is not part of this tree.
+    pub fn local_spqr_edge_index(
+        &self,
+        spqr_edge_index: SPQREdgeIndex<IndexType>,
+    ) -> Option<SPQREdgeIndex<IndexType>> {
+        self.local_spqr_edge_indices.get(&spqr_edge_index).copied()
+    }
+}
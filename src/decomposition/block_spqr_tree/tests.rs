@@ -0,0 +1,70 @@
+use std::thread;
+
+use crate::decomposition::tests::{read_decomposition, read_graph};
+
+#[test]
+fn test_block_spqr_tree() {
+    let graph = read_graph("random5_10_False_0");
+    let spqr_decomposition = read_decomposition(&graph, "random5_10_False_0");
+
+    for (block_index, block) in spqr_decomposition.iter_blocks() {
+        let tree = spqr_decomposition.block_spqr_tree(block_index);
+        assert_eq!(tree.block(), block_index);
+        assert_eq!(tree.node_count(), block.node_count());
+        assert_eq!(tree.spqr_node_count(), block.spqr_node_count());
+        assert_eq!(
+            tree.spqr_edge_count(),
+            spqr_decomposition
+                .iter_spqr_edges_in_block(block_index)
+                .count()
+        );
+
+        for (local_index, spqr_node) in tree.iter_spqr_nodes() {
+            let global_index = tree.global_spqr_node_index(local_index);
+            assert_eq!(tree.local_spqr_node_index(global_index), Some(local_index));
+            let global_spqr_node = spqr_decomposition.spqr_node(global_index);
+            assert_eq!(
+                spqr_node.iter_nodes().collect::<Vec<_>>(),
+                global_spqr_node.iter_nodes().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                spqr_node
+                    .iter_incident_spqr_edges()
+                    .map(|spqr_edge_index| tree.global_spqr_edge_index(spqr_edge_index))
+                    .collect::<Vec<_>>(),
+                global_spqr_node
+                    .iter_incident_spqr_edges()
+                    .collect::<Vec<_>>()
+            );
+            for node_index in spqr_node.iter_nodes() {
+                assert!(
+                    tree.node_spqr_node_indices(node_index)
+                        .any(|i| i == local_index)
+                );
+            }
+        }
+
+        for (local_index, spqr_edge) in tree.iter_spqr_edges() {
+            let global_spqr_edge =
+                spqr_decomposition.spqr_edge(tree.global_spqr_edge_index(local_index));
+            let (u, v) = spqr_edge.endpoints();
+            assert_eq!(
+                (
+                    tree.global_spqr_node_index(u),
+                    tree.global_spqr_node_index(v)
+                ),
+                global_spqr_edge.endpoints()
+            );
+            assert_eq!(spqr_edge.virtual_edge(), global_spqr_edge.virtual_edge());
+        }
+
+        let spqr_node_count = tree.spqr_node_count();
+        let sent = tree.clone();
+        assert_eq!(
+            thread::spawn(move || sent.spqr_node_count())
+                .join()
+                .unwrap(),
+            spqr_node_count
+        );
+    }
+}
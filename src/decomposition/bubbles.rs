@@ -0,0 +1,182 @@
+use std::borrow::Borrow;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A maximal chain of simple bubbles, see [`SPQRDecomposition::iter_bubble_chains`].
+///
+/// A simple bubble is a P-node whose branches are single edges or simple paths, i.e. S-nodes without further neighbours in the SPQR tree.
+/// Consecutive bubbles of a chain share a pole.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BubbleChain<NodeIndex, IndexType> {
+    /// The S-node whose cycle contains the chain, or `None` if the block consists of a single bubble.
+    pub s_node: Option<SPQRNodeIndex<IndexType>>,
+    /// The P-nodes of the bubbles in the order of the chain.
+    pub bubbles: Vec<SPQRNodeIndex<IndexType>>,
+    /// The poles of the bubbles in the order of the chain, one more than there are bubbles.
+    ///
+    /// If the whole cycle of the S-node consists of bubbles, then the first and the last node are equal.
+    pub nodes: Vec<NodeIndex>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns an iterator over the maximal chains of simple bubbles in the given block.
+    ///
+    /// Chains are found by walking the cycle of each S-node and collecting runs of consecutive virtual edges that lead to simple bubbles.
+    /// A P-node whose neighbours in the SPQR tree are all simple paths forms a chain on its own.
+    /// P-nodes containing self-loops are never simple bubbles.
+    pub fn iter_bubble_chains(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> impl Iterator<Item = BubbleChain<Graph::NodeIndex, Graph::IndexType>> {
+        let mut chains = Vec::new();
+
+        for &spqr_node_index in &self.blocks[block_index].spqr_nodes {
+            let spqr_node = &self.spqr_nodes[spqr_node_index];
+            match spqr_node.spqr_node_type {
+                SPQRNodeType::PNode if self.is_simple_bubble(spqr_node_index, None) => {
+                    let (source, sink) = spqr_node.p_node_poles().unwrap();
+                    chains.push(BubbleChain {
+                        s_node: None,
+                        bubbles: vec![spqr_node_index],
+                        nodes: vec![source, sink],
+                    });
+                }
+                // S-nodes with a single neighbour are branches of a bubble rather than chains.
+                SPQRNodeType::SNode if spqr_node.spqr_edges.len() >= 2 => {
+                    self.collect_bubble_chains_in_s_node(spqr_node_index, &mut chains);
+                }
+                _ => {}
+            }
+        }
+
+        chains.into_iter()
+    }
+
+    /// Returns true if the given P-node is a simple bubble, ignoring its neighbour `outside` in the SPQR tree.
+    fn is_simple_bubble(
+        &self,
+        p_node_index: SPQRNodeIndex<Graph::IndexType>,
+        outside: Option<SPQRNodeIndex<Graph::IndexType>>,
+    ) -> bool {
+        let p_node = &self.spqr_nodes[p_node_index];
+        p_node.spqr_node_type == SPQRNodeType::PNode
+            && p_node.iter_edges().all(|edge_index| {
+                let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+                u != v
+            })
+            && self
+                .spqr_tree_neighbors(p_node_index)
+                .iter()
+                .all(|neighbor| {
+                    let branch = &self.spqr_nodes[neighbor.spqr_node];
+                    Some(neighbor.spqr_node) == outside
+                        || (branch.spqr_node_type == SPQRNodeType::SNode
+                            && branch.spqr_edges.len() == 1)
+                })
+    }
+
+    /// Walks the cycle of the given S-node and appends its maximal runs of simple bubbles to `chains`.
+    fn collect_bubble_chains_in_s_node(
+        &self,
+        s_node_index: SPQRNodeIndex<Graph::IndexType>,
+        chains: &mut Vec<BubbleChain<Graph::NodeIndex, Graph::IndexType>>,
+    ) {
+        let cycle = self.s_node_cycle(s_node_index);
+        let bubble_at = |position: usize| -> Option<SPQRNodeIndex<Graph::IndexType>> {
+            let SkeletonEdge::Virtual(spqr_edge_index) = cycle[position].0 else {
+                return None;
+            };
+            let (u, v) = self.spqr_edges[spqr_edge_index].endpoints;
+            let p_node_index = if u == s_node_index { v } else { u };
+            self.is_simple_bubble(p_node_index, Some(s_node_index))
+                .then_some(p_node_index)
+        };
+        let bubbles = (0..cycle.len()).map(bubble_at).collect::<Vec<_>>();
+
+        let Some(first_gap) = bubbles.iter().position(Option::is_none) else {
+            chains.push(BubbleChain {
+                s_node: Some(s_node_index),
+                bubbles: bubbles.into_iter().flatten().collect(),
+                nodes: cycle
+                    .iter()
+                    .map(|(_, source)| *source)
+                    .chain([cycle[0].1])
+                    .collect(),
+            });
+            return;
+        };
+
+        // Start after a gap, such that no run wraps around the end of the cycle.
+        let mut current: Option<BubbleChain<_, _>> = None;
+        for offset in 1..=cycle.len() {
+            let position = (first_gap + offset) % cycle.len();
+            let source = cycle[position].1;
+            let sink = cycle[(position + 1) % cycle.len()].1;
+            match (bubbles[position], &mut current) {
+                (Some(bubble), Some(chain)) => {
+                    chain.bubbles.push(bubble);
+                    chain.nodes.push(sink);
+                }
+                (Some(bubble), None) => {
+                    current = Some(BubbleChain {
+                        s_node: Some(s_node_index),
+                        bubbles: vec![bubble],
+                        nodes: vec![source, sink],
+                    });
+                }
+                (None, _) => chains.extend(current.take()),
+            }
+        }
+        chains.extend(current);
+    }
+
+    /// Returns the skeleton edges of the given S-node in the order of its cycle, each together with the node it starts at.
+    ///
+    /// The edge at position `i` connects the node of position `i` with the node of position `i + 1`, cyclically.
+    /// Self-loops are not part of the cycle and are skipped.
+    pub(crate) fn s_node_cycle(
+        &self,
+        s_node_index: SPQRNodeIndex<Graph::IndexType>,
+    ) -> Vec<(
+        SkeletonEdge<Graph::EdgeIndex, Graph::IndexType>,
+        Graph::NodeIndex,
+    )> {
+        let skeleton_edges = self
+            .iter_skeleton_edges(s_node_index)
+            .filter(|(_, (u, v))| u != v)
+            .collect::<Vec<_>>();
+        let mut incident_edges = FxHashMap::<Graph::NodeIndex, Vec<usize>>::default();
+        for (position, (_, (u, v))) in skeleton_edges.iter().enumerate() {
+            incident_edges.entry(*u).or_default().push(position);
+            incident_edges.entry(*v).or_default().push(position);
+        }
+
+        let mut cycle = Vec::with_capacity(skeleton_edges.len());
+        let mut used = vec![false; skeleton_edges.len()];
+        let mut node = skeleton_edges[0].1.0;
+        while let Some(&position) = incident_edges[&node]
+            .iter()
+            .find(|&&position| !used[position])
+        {
+            used[position] = true;
+            let (skeleton_edge, (u, v)) = skeleton_edges[position];
+            cycle.push((skeleton_edge, node));
+            node = if u == node { v } else { u };
+        }
+
+        cycle
+    }
+}
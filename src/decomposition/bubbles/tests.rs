@@ -0,0 +1,64 @@
+use std::io::BufReader;
+
+use bidirected_adjacency_array::graph::BidirectedAdjacencyArray;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{TestGraph, read_decomposition, read_graph},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_iter_bubble_chains() {
+    let graph = read_graph("multiedge");
+    let spqr_decomposition = read_decomposition(&graph, "multiedge");
+    let chains = spqr_decomposition
+        .iter_bubble_chains(0.into())
+        .collect::<Vec<_>>();
+    assert_eq!(chains.len(), 1);
+    assert_eq!(chains[0].s_node, None);
+    assert_eq!(chains[0].bubbles, [0.into()]);
+    assert_eq!(
+        chains[0]
+            .nodes
+            .iter()
+            .map(|node_index| graph.node_name(*node_index))
+            .collect::<Vec<_>>(),
+        ["2", "1"]
+    );
+
+    // Two consecutive bubbles 1-2-3 in the cycle 1-2-3-4.
+    let gfa = "H\tVN:Z:1.0\nS\t1\tA\nS\t2\tA\nS\t3\tA\nS\t4\tA\n\
+        L\t1\t+\t2\t+\t0M\nL\t1\t+\t2\t-\t0M\nL\t2\t+\t3\t+\t0M\nL\t2\t+\t3\t-\t0M\n\
+        L\t3\t+\t4\t+\t0M\nL\t4\t+\t1\t+\t0M\n";
+    let graph: TestGraph =
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+    let spqr_decomposition_file = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 1 2 3 4\nB B0 G0 1 2 3 4\n\
+        P P0 B0 1 2\nP P1 B0 2 3\nS S2 B0 1 2 3 4\n\
+        V V0 S2 P0 1 2\nV V1 S2 P1 2 3\n\
+        E E0 P0 1 2\nE E1 P0 1 2\nE E2 P1 2 3\nE E3 P1 2 3\nE E4 S2 3 4\nE E5 S2 4 1\n";
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let mut chains = spqr_decomposition
+        .iter_bubble_chains(0.into())
+        .collect::<Vec<_>>();
+    assert_eq!(chains.len(), 1);
+    let chain = &mut chains[0];
+    assert_eq!(chain.s_node, Some(2.into()));
+    if chain.bubbles[0] != 0.into() {
+        chain.bubbles.reverse();
+        chain.nodes.reverse();
+    }
+    assert_eq!(chain.bubbles, [0.into(), 1.into()]);
+    assert_eq!(
+        chain
+            .nodes
+            .iter()
+            .map(|node_index| graph.node_name(*node_index))
+            .collect::<Vec<_>>(),
+        ["1", "2", "3"]
+    );
+}
@@ -1,23 +1,45 @@
-use std::iter;
+use std::{
+    borrow::Borrow,
+    collections::VecDeque,
+    fmt::{Debug, Display},
+    iter,
+    marker::PhantomData,
+};
 
-use log::{debug, trace};
+use log::{debug, trace, warn};
+use rustc_hash::{FxHashMap, FxHashSet};
 use smallvec::SmallVec;
 use tagged_vec::TaggedVec;
 use thiserror::Error;
 
 use crate::{
     decomposition::{
-        Block, Component, CutNode, SPQRDecomposition, SPQRDecompositionEdgeData,
+        Block, Component, CutNode, NodeIndexList, SPQRDecomposition, SPQRDecompositionEdgeData,
         SPQRDecompositionNodeData, SPQREdge, SPQRNode, SPQRNodeType,
+        extra_data::ExtraData,
         indices::{
-            BlockIndex, ComponentIndex, CutNodeIndex, OptionalBlockIndex, OptionalComponentIndex,
-            OptionalCutNodeIndex, OptionalSPQRNodeIndex, SPQREdgeIndex, SPQRNodeIndex,
+            BlockIndex, ComponentIndex, CutNodeIndex, IndexOverflowError, OptionalBlockIndex,
+            OptionalComponentIndex, OptionalCutNodeIndex, OptionalSPQRNodeIndex, SPQREdgeIndex,
+            SPQRNodeIndex,
         },
     },
     graph::StaticGraph,
+    profile::{Phase, Profile},
+    progress::ProgressSink,
+    strictness::Strictness,
 };
-
-pub struct SPQRDecompositionBuilder<'graph, Graph: StaticGraph> {
+use arena::BuilderArena;
+use observer::BuilderObserver;
+
+mod arena;
+pub mod observer;
+#[cfg(test)]
+mod tests;
+
+/// Builds an [`SPQRDecomposition`] entity by entity.
+///
+/// The `Observer` is notified of the added entities, see the [`observer`] module.
+pub struct SPQRDecompositionBuilder<'graph, Graph: StaticGraph, Observer = ()> {
     graph: &'graph Graph,
     components: TaggedVec<
         ComponentIndex<Graph::IndexType>,
@@ -37,6 +59,9 @@ pub struct SPQRDecompositionBuilder<'graph, Graph: StaticGraph> {
         TaggedVec<SPQREdgeIndex<Graph::IndexType>, SPQREdge<Graph::NodeIndex, Graph::IndexType>>,
     node_data: TaggedVec<Graph::NodeIndex, SPQRDecompositionNodeDataBuilder<Graph>>,
     edge_data: TaggedVec<Graph::EdgeIndex, SPQRDecompositionEdgeDataBuilder<Graph>>,
+    /// If set, the lists that grow one entry at a time are stored here instead of in the entities.
+    arena: Option<BuilderArena<Graph>>,
+    observer: Observer,
 }
 
 #[derive(Error, Debug)]
@@ -49,88 +74,532 @@ pub enum AddEdgeError {
 
     #[error("the edge was already added to an SPQR node")]
     AlreadyAddedToSPQRNode,
+
+    #[error("the edge was already added to a different component")]
+    AddedToDifferentComponent,
+
+    #[error("the edge was already added to a different block")]
+    AddedToDifferentBlock,
+
+    #[error("the edge was already added to a different SPQR node")]
+    AddedToDifferentSPQRNode,
+
+    #[error("the edge is not a self-loop, so it cannot be added to a component")]
+    NotASelfLoop,
+
+    #[error("the component has blocks, so edges cannot be added to it directly")]
+    ComponentHasBlocks,
+
+    #[error("the block has SPQR nodes or SPQR edges, so edges cannot be added to it directly")]
+    BlockHasSPQRTree,
+
+    #[error("an endpoint of the edge is not in the block")]
+    EndpointNotInBlock,
+
+    #[error("an endpoint of the edge is not in the SPQR node")]
+    EndpointNotInSPQRNode,
+}
+
+/// The error returned by the `try_add_*` methods of [`SPQRDecompositionBuilder`]
+/// if the added entity is inconsistent with the entities added before.
+///
+/// The corresponding methods without the `try_` prefix panic instead.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AddError<NodeIndex, EdgeIndex, IndexType> {
+    #[error("the entity has too few nodes or blocks")]
+    TooSmall,
+
+    #[error("node {0} is listed twice")]
+    DuplicateNode(NodeIndex),
+
+    #[error("node {0} is already assigned to a component")]
+    NodeInOtherComponent(NodeIndex),
+
+    #[error("node {0} is not assigned to a component")]
+    NodeWithoutComponent(NodeIndex),
+
+    #[error("edge {0} connects the component to a node in a different component")]
+    EdgeToOtherComponent(EdgeIndex),
+
+    #[error("node {node} is not in component {component}")]
+    NodeNotInComponent {
+        node: NodeIndex,
+        component: ComponentIndex<IndexType>,
+    },
+
+    #[error("node {node} is not in block {block}")]
+    NodeNotInBlock {
+        node: NodeIndex,
+        block: BlockIndex<IndexType>,
+    },
+
+    #[error("node {node} is not in SPQR node {spqr_node}")]
+    NodeNotInSPQRNode {
+        node: NodeIndex,
+        spqr_node: SPQRNodeIndex<IndexType>,
+    },
+
+    #[error("edge {0} has both endpoints in the block, but was already added to a different block")]
+    EdgeInOtherBlock(EdgeIndex),
+
+    #[error("node {0} is already a cut node")]
+    DuplicateCutNode(NodeIndex),
+
+    #[error("block {0} is listed twice")]
+    DuplicateBlock(BlockIndex<IndexType>),
+
+    #[error("SPQR nodes {0} and {1} are in different blocks")]
+    SPQRNodesInDifferentBlocks(SPQRNodeIndex<IndexType>, SPQRNodeIndex<IndexType>),
+
+    #[error("the SPQR edge connects SPQR node {0} to itself")]
+    SPQREdgeLoop(SPQRNodeIndex<IndexType>),
+
+    #[error("node {0} already has extra data")]
+    DuplicateExtraData(NodeIndex),
+}
+
+/// The [`AddError`] of the given graph.
+type GraphAddError<Graph> = AddError<
+    <Graph as StaticGraph>::NodeIndex,
+    <Graph as StaticGraph>::EdgeIndex,
+    <Graph as StaticGraph>::IndexType,
+>;
+
+/// The amounts of entities to preallocate space for in an [`SPQRDecompositionBuilder`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SPQRDecompositionBuilderCapacities {
+    pub components: usize,
+    pub blocks: usize,
+    pub cut_nodes: usize,
+    pub spqr_nodes: usize,
+    pub spqr_edges: usize,
+}
+
+/// The error returned by [`SPQRDecompositionBuilder::try_build`], listing all failed sanity checks.
+#[derive(Debug)]
+pub struct BuildError<NodeIndex, EdgeIndex, IndexType> {
+    pub problems: Vec<BuildProblem<NodeIndex, EdgeIndex, IndexType>>,
+}
+
+/// A single failed sanity check of an SPQR decomposition under construction.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BuildProblem<NodeIndex, EdgeIndex, IndexType> {
+    #[error("node {0} is not assigned to any component")]
+    NodeWithoutComponent(NodeIndex),
+
+    #[error("node {0} is in a component with at least two nodes, but not assigned to any block")]
+    NodeWithoutBlock(NodeIndex),
+
+    #[error("node {0} is in a block with at least three nodes, but not assigned to any SPQR node")]
+    NodeWithoutSPQRNode(NodeIndex),
+
+    #[error("edge {0} is not assigned to any component")]
+    EdgeWithoutComponent(EdgeIndex),
+
+    #[error("edge {0} is in a component with at least two nodes, but not assigned to any block")]
+    EdgeWithoutBlock(EdgeIndex),
+
+    #[error("edge {0} is in a block with at least three nodes, but not assigned to any SPQR node")]
+    EdgeWithoutSPQRNode(EdgeIndex),
+
+    #[error("component {0} has at least two nodes, but no blocks")]
+    ComponentWithoutBlocks(ComponentIndex<IndexType>),
+
+    #[error("component {0} has at least two nodes, but edges were added to it directly")]
+    EdgesInMultiNodeComponent(ComponentIndex<IndexType>),
+
+    #[error("block {0} does not have exactly two nodes, but has no SPQR nodes")]
+    BlockWithoutSPQRNodes(BlockIndex<IndexType>),
+
+    #[error("block {0} does not have exactly two nodes, but edges were added to it directly")]
+    EdgesInLargeBlock(BlockIndex<IndexType>),
 }
 
 struct SPQRDecompositionNodeDataBuilder<Graph: StaticGraph> {
     component_index: OptionalComponentIndex<Graph::IndexType>,
-    block_indices: SmallVec<[BlockIndex<Graph::IndexType>; 1]>,
+    block_indices: NodeIndexList<BlockIndex<Graph::IndexType>>,
     cut_node_index: OptionalCutNodeIndex<Graph::IndexType>,
-    spqr_node_indices: SmallVec<[SPQRNodeIndex<Graph::IndexType>; 1]>,
-    extra_data: String,
+    spqr_node_indices: NodeIndexList<SPQRNodeIndex<Graph::IndexType>>,
+    extra_data: ExtraData,
 }
 
 struct SPQRDecompositionEdgeDataBuilder<Graph: StaticGraph> {
     component_index: OptionalComponentIndex<Graph::IndexType>,
     block_index: OptionalBlockIndex<Graph::IndexType>,
     spqr_node_index: OptionalSPQRNodeIndex<Graph::IndexType>,
-    extra_data: String,
+    extra_data: ExtraData,
 }
 
 impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
     pub fn new(graph: &'graph Graph) -> Self {
+        Self::with_capacities(graph, Default::default())
+    }
+
+    /// Like [`new`](Self::new), but returns an error if the decomposition of the graph might not be indexable by `Graph::IndexType`.
+    ///
+    /// A decomposition has at most as many entities of each kind as the graph has nodes and edges together,
+    /// so this checks that this sum fits into the index type.
+    pub fn try_new(graph: &'graph Graph) -> Result<Self, IndexOverflowError> {
+        IndexOverflowError::check::<Graph::IndexType>(
+            graph.node_count().saturating_add(graph.edge_count()),
+        )?;
+        Ok(Self::new(graph))
+    }
+
+    /// Creates a new builder with preallocated space for the given amounts of entities.
+    ///
+    /// For very large decompositions, this avoids repeated reallocation while adding entities.
+    pub fn with_capacities(
+        graph: &'graph Graph,
+        capacities: SPQRDecompositionBuilderCapacities,
+    ) -> Self {
+        Self {
+            graph,
+            components: TaggedVec::with_capacity(capacities.components),
+            blocks: TaggedVec::with_capacity(capacities.blocks),
+            cut_nodes: TaggedVec::with_capacity(capacities.cut_nodes),
+            spqr_nodes: TaggedVec::with_capacity(capacities.spqr_nodes),
+            spqr_edges: TaggedVec::with_capacity(capacities.spqr_edges),
+            node_data: Self::initial_node_data(graph, Vec::new()),
+            edge_data: Self::initial_edge_data(graph, Vec::new()),
+            arena: None,
+            observer: (),
+        }
+    }
+
+    /// Creates a new builder that stores the lists growing one entry at a time in an arena.
+    ///
+    /// These are the blocks and SPQR nodes of each node and the real edges of each SPQR node.
+    /// Instead of reallocating each list whenever it grows, all entries are appended to a few large buffers,
+    /// and each list is allocated exactly once with its final size when the decomposition is built.
+    /// The arena is freed as a whole afterwards.
+    ///
+    /// This reduces the pressure on the allocator for decompositions with tens of millions of SPQR nodes.
+    pub fn with_arena(
+        graph: &'graph Graph,
+        capacities: SPQRDecompositionBuilderCapacities,
+    ) -> Self {
+        Self {
+            arena: Some(BuilderArena::new(graph, capacities)),
+            ..Self::with_capacities(graph, capacities)
+        }
+    }
+
+    /// Creates a new builder for the given graph, reusing the memory allocated by a decomposition that is no longer needed.
+    pub fn recycle(
+        decomposition: SPQRDecomposition<'_, Graph, impl Borrow<Graph>>,
+        graph: &'graph Graph,
+    ) -> Self {
         Self {
             graph,
-            components: TaggedVec::new(),
-            blocks: TaggedVec::new(),
-            cut_nodes: TaggedVec::new(),
-            spqr_nodes: TaggedVec::new(),
-            spqr_edges: TaggedVec::new(),
-            node_data: iter::repeat_with(|| SPQRDecompositionNodeDataBuilder {
+            components: cleared(decomposition.components),
+            blocks: cleared(decomposition.blocks),
+            cut_nodes: cleared(decomposition.cut_nodes),
+            spqr_nodes: cleared(decomposition.spqr_nodes),
+            spqr_edges: cleared(decomposition.spqr_edges),
+            node_data: Self::initial_node_data(graph, Vec::new()),
+            edge_data: Self::initial_edge_data(graph, Vec::new()),
+            arena: None,
+            observer: (),
+        }
+    }
+
+    fn initial_node_data(
+        graph: &Graph,
+        mut buffer: Vec<SPQRDecompositionNodeDataBuilder<Graph>>,
+    ) -> TaggedVec<Graph::NodeIndex, SPQRDecompositionNodeDataBuilder<Graph>> {
+        buffer.extend(
+            iter::repeat_with(|| SPQRDecompositionNodeDataBuilder {
                 component_index: OptionalComponentIndex::new_none(),
                 block_indices: SmallVec::new(),
                 cut_node_index: OptionalCutNodeIndex::new_none(),
                 spqr_node_indices: SmallVec::new(),
-                extra_data: String::new(),
+                extra_data: ExtraData::default(),
             })
-            .take(graph.node_count())
-            .collect(),
-            edge_data: iter::repeat_with(|| SPQRDecompositionEdgeDataBuilder {
+            .take(graph.node_count()),
+        );
+        buffer.into()
+    }
+
+    fn initial_edge_data(
+        graph: &Graph,
+        mut buffer: Vec<SPQRDecompositionEdgeDataBuilder<Graph>>,
+    ) -> TaggedVec<Graph::EdgeIndex, SPQRDecompositionEdgeDataBuilder<Graph>> {
+        buffer.extend(
+            iter::repeat_with(|| SPQRDecompositionEdgeDataBuilder {
                 component_index: OptionalComponentIndex::new_none(),
                 block_index: OptionalBlockIndex::new_none(),
                 spqr_node_index: OptionalSPQRNodeIndex::new_none(),
-                extra_data: String::new(),
+                extra_data: ExtraData::default(),
             })
-            .take(graph.edge_count())
-            .collect(),
+            .take(graph.edge_count()),
+        );
+        buffer.into()
+    }
+}
+
+impl<'graph, Graph: StaticGraph, Observer: BuilderObserver<Graph>>
+    SPQRDecompositionBuilder<'graph, Graph, Observer>
+{
+    /// Attaches an observer that is notified of all entities added from now on, replacing the current observer.
+    ///
+    /// Pass a mutable reference to keep access to the observer after the builder was consumed by [`build`](Self::build).
+    pub fn with_observer<NewObserver: BuilderObserver<Graph>>(
+        self,
+        observer: NewObserver,
+    ) -> SPQRDecompositionBuilder<'graph, Graph, NewObserver> {
+        SPQRDecompositionBuilder {
+            graph: self.graph,
+            components: self.components,
+            blocks: self.blocks,
+            cut_nodes: self.cut_nodes,
+            spqr_nodes: self.spqr_nodes,
+            spqr_edges: self.spqr_edges,
+            node_data: self.node_data,
+            edge_data: self.edge_data,
+            arena: self.arena,
+            observer,
         }
     }
 
+    /// Returns the observer of this builder.
+    pub fn observer(&self) -> &Observer {
+        &self.observer
+    }
+
+    /// Returns the observer of this builder mutably.
+    pub fn observer_mut(&mut self) -> &mut Observer {
+        &mut self.observer
+    }
+
+    /// Returns true if this builder was created with [`with_arena`](Self::with_arena).
+    pub fn uses_arena(&self) -> bool {
+        self.arena.is_some()
+    }
+
+    /// Clears the builder and prepares it for a new graph, while retaining the allocated memory.
+    ///
+    /// This is useful for pipelines that decompose many graphs of similar size.
+    pub fn reset<'new_graph>(
+        self,
+        graph: &'new_graph Graph,
+    ) -> SPQRDecompositionBuilder<'new_graph, Graph, Observer> {
+        let mut node_data = Vec::from(self.node_data);
+        node_data.clear();
+        let mut edge_data = Vec::from(self.edge_data);
+        edge_data.clear();
+
+        SPQRDecompositionBuilder {
+            graph,
+            components: cleared(self.components),
+            blocks: cleared(self.blocks),
+            cut_nodes: cleared(self.cut_nodes),
+            spqr_nodes: cleared(self.spqr_nodes),
+            spqr_edges: cleared(self.spqr_edges),
+            node_data: SPQRDecompositionBuilder::initial_node_data(graph, node_data),
+            edge_data: SPQRDecompositionBuilder::initial_edge_data(graph, edge_data),
+            arena: self.arena.map(|mut arena| {
+                arena.reset(graph);
+                arena
+            }),
+            observer: self.observer,
+        }
+    }
+
+    /// Reserves space for at least the given amounts of additional entities.
+    pub fn reserve(&mut self, additional: SPQRDecompositionBuilderCapacities) {
+        reserve(&mut self.components, additional.components);
+        reserve(&mut self.blocks, additional.blocks);
+        reserve(&mut self.cut_nodes, additional.cut_nodes);
+        reserve(&mut self.spqr_nodes, additional.spqr_nodes);
+        reserve(&mut self.spqr_edges, additional.spqr_edges);
+    }
+
     /// Adds a component to the decomposition.
     ///
     /// Edges are automatically assigned to the component based on their endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`try_add_component`](Self::try_add_component) would return an error.
     pub fn add_component(
         &mut self,
         nodes: Vec<Graph::NodeIndex>,
     ) -> ComponentIndex<Graph::IndexType> {
+        self.try_add_component(nodes)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`add_component`](Self::add_component), but returns an error if the nodes are empty,
+    /// if a node is already in a component, or if a node is adjacent to a node of a different component.
+    ///
+    /// If an error is returned, the builder may have been modified partially and should be discarded.
+    pub fn try_add_component(
+        &mut self,
+        nodes: Vec<Graph::NodeIndex>,
+    ) -> Result<ComponentIndex<Graph::IndexType>, GraphAddError<Graph>> {
         trace!("Adding component with {} nodes", nodes.len());
-        assert!(!nodes.is_empty());
+        if nodes.is_empty() {
+            return Err(AddError::TooSmall);
+        }
+
+        let index = ComponentIndex::from(self.components.len());
+        for node in nodes.iter().copied() {
+            if self.node_data[node].component_index.is_some() {
+                return Err(AddError::NodeInOtherComponent(node));
+            }
+            self.node_data[node].component_index = index.into();
+
+            for edge in self.graph.incident_edges(node) {
+                if self.edge_data[edge].component_index != index.into()
+                    && self.edge_data[edge].component_index.is_some()
+                {
+                    return Err(AddError::EdgeToOtherComponent(edge));
+                }
+            }
+        }
+
+        trace!("Component added with index {index}");
+        self.observer.on_component_added(index, &nodes);
+        Ok(self.components.push(Component {
+            nodes,
+            edges: Vec::new(),
+            blocks: Vec::new(),
+            cut_nodes: Vec::new(),
+            extra_data: ExtraData::default(),
+        }))
+    }
+
+    /// Adds one component for each connected component of the graph.
+    ///
+    /// The components are found by a breadth-first search over the graph.
+    /// Nodes that were already assigned to a component are skipped.
+    /// Edges are not added to the components, see [`add_edge_to_component`](Self::add_edge_to_component).
+    pub fn add_components_from_graph(&mut self) -> Vec<ComponentIndex<Graph::IndexType>> {
+        debug!("Detecting connected components...");
+        let mut visited: TaggedVec<Graph::NodeIndex, bool> = self
+            .node_data
+            .iter_values()
+            .map(|node_data| node_data.component_index.is_some())
+            .collect();
+        let mut queue = VecDeque::new();
+        let mut component_indices = Vec::new();
+
+        for root in self.graph.node_indices() {
+            if visited[root] {
+                continue;
+            }
 
-        self.components.push_in_place(|index| {
-            for node in nodes.iter().copied() {
-                assert!(self.node_data[node].component_index.is_none());
-                self.node_data[node].component_index = index.into();
+            visited[root] = true;
+            queue.push_back(root);
+            let mut nodes = Vec::new();
+
+            while let Some(node) = queue.pop_front() {
+                nodes.push(node);
 
                 for edge in self.graph.incident_edges(node) {
-                    if self.edge_data[edge].component_index != index.into() {
-                        assert!(self.edge_data[edge].component_index.is_none());
+                    let (a, b) = self.graph.edge_endpoints(edge);
+                    let neighbor = if a == node { b } else { a };
+                    if !visited[neighbor] {
+                        assert!(
+                            self.node_data[neighbor].component_index.is_none(),
+                            "Node {neighbor} is already assigned to a component, but is connected to unassigned node {node}",
+                        );
+                        visited[neighbor] = true;
+                        queue.push_back(neighbor);
                     }
                 }
             }
 
-            trace!("Component added with index {index}");
-            Component {
-                nodes,
-                edges: Vec::new(),
-                blocks: Vec::new(),
-                cut_nodes: Vec::new(),
-            }
-        })
+            component_indices.push(self.add_component(nodes));
+        }
+
+        debug!("Detected {} connected components", component_indices.len());
+        component_indices
     }
 
     /// Adds extra data to the given node.
-    pub fn add_extra_data_to_node(&mut self, node: Graph::NodeIndex, extra_data: String) {
-        assert!(self.node_data[node].extra_data.is_empty());
-        self.node_data[node].extra_data = extra_data;
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node already has extra data.
+    pub fn add_extra_data_to_node(
+        &mut self,
+        node: Graph::NodeIndex,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        self.try_add_extra_data_to_node(node, extra_data)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`add_extra_data_to_node`](Self::add_extra_data_to_node), but returns an error if the node already has extra data.
+    pub fn try_add_extra_data_to_node(
+        &mut self,
+        node: Graph::NodeIndex,
+        extra_data: impl Into<ExtraData>,
+    ) -> Result<(), GraphAddError<Graph>> {
+        if !self.node_data[node].extra_data.is_empty() {
+            return Err(AddError::DuplicateExtraData(node));
+        }
+        self.node_data[node].extra_data = extra_data.into();
+        Ok(())
+    }
+
+    /// Adds extra data to the given edge.
+    pub fn add_extra_data_to_edge(
+        &mut self,
+        edge: Graph::EdgeIndex,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        assert!(self.edge_data[edge].extra_data.is_empty());
+        self.edge_data[edge].extra_data = extra_data.into();
+    }
+
+    /// Returns the extra data of the given edge, or an empty string if it has none.
+    pub fn edge_extra_data(&self, edge: Graph::EdgeIndex) -> &str {
+        &self.edge_data[edge].extra_data
+    }
+
+    /// Adds extra data to the given component.
+    pub fn add_extra_data_to_component(
+        &mut self,
+        component: ComponentIndex<Graph::IndexType>,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        assert!(self.components[component].extra_data.is_empty());
+        self.components[component].extra_data = extra_data.into();
+    }
+
+    /// Adds extra data to the given block.
+    pub fn add_extra_data_to_block(
+        &mut self,
+        block: BlockIndex<Graph::IndexType>,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        assert!(self.blocks[block].extra_data.is_empty());
+        self.blocks[block].extra_data = extra_data.into();
+    }
+
+    /// Adds extra data to the given cut node.
+    ///
+    /// Only cut nodes that were added manually via [`add_cut_node`](Self::add_cut_node) exist before [`build`](Self::build) is called.
+    pub fn add_extra_data_to_cut_node(
+        &mut self,
+        cut_node: CutNodeIndex<Graph::IndexType>,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        assert!(self.cut_nodes[cut_node].extra_data.is_empty());
+        self.cut_nodes[cut_node].extra_data = extra_data.into();
+    }
+
+    /// Adds extra data to the given SPQR node.
+    pub fn add_extra_data_to_spqr_node(
+        &mut self,
+        spqr_node: SPQRNodeIndex<Graph::IndexType>,
+        extra_data: impl Into<ExtraData>,
+    ) {
+        assert!(self.spqr_nodes[spqr_node].extra_data.is_empty());
+        self.spqr_nodes[spqr_node].extra_data = extra_data.into();
     }
 
     /// Adds an edge into a component.
@@ -145,18 +614,26 @@ impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
         component: ComponentIndex<Graph::IndexType>,
     ) -> Result<(), AddEdgeError> {
         if self.edge_data[edge].component_index.is_some() {
-            assert_eq!(self.edge_data[edge].component_index, component.into());
-            return Err(AddEdgeError::AlreadyAddedToComponent);
+            return Err(
+                if self.edge_data[edge].component_index == component.into() {
+                    AddEdgeError::AlreadyAddedToComponent
+                } else {
+                    AddEdgeError::AddedToDifferentComponent
+                },
+            );
         }
 
         let (a, b) = self.graph.edge_endpoints(edge);
-        assert_eq!(a, b);
+        if a != b {
+            return Err(AddEdgeError::NotASelfLoop);
+        }
+        if !self.components[component].blocks.is_empty() {
+            return Err(AddEdgeError::ComponentHasBlocks);
+        }
 
         self.edge_data[edge].component_index = component.into();
         self.components[component].edges.push(edge);
 
-        assert!(self.components[component].blocks.is_empty());
-
         Ok(())
     }
 
@@ -164,84 +641,263 @@ impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
     ///
     /// Edges are automatically assigned to the block if both of their endpoints are in the block.
     /// Cut nodes are identified once [`build`](Self::build) is called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`try_add_block`](Self::try_add_block) would return an error.
     pub fn add_block(
         &mut self,
         component: ComponentIndex<Graph::IndexType>,
         nodes: Vec<Graph::NodeIndex>,
     ) -> BlockIndex<Graph::IndexType> {
-        assert!(!nodes.is_empty());
+        self.try_add_block(component, nodes)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`add_block`](Self::add_block), but returns an error if the nodes are empty, contain duplicates or are not in the component,
+    /// or if an edge between two nodes of the block was already added to a different block.
+    ///
+    /// If an error is returned, the builder may have been modified partially and should be discarded.
+    pub fn try_add_block(
+        &mut self,
+        component: ComponentIndex<Graph::IndexType>,
+        nodes: Vec<Graph::NodeIndex>,
+    ) -> Result<BlockIndex<Graph::IndexType>, GraphAddError<Graph>> {
+        if nodes.is_empty() {
+            return Err(AddError::TooSmall);
+        }
+
+        let index = BlockIndex::from(self.blocks.len());
+        for node in nodes.iter().copied() {
+            if self.node_data[node].component_index != component.into() {
+                return Err(AddError::NodeNotInComponent { node, component });
+            }
+            if Self::node_has_block(&self.node_data, &self.arena, node, index) {
+                return Err(AddError::DuplicateNode(node));
+            }
+            Self::push_node_block(&mut self.node_data, &mut self.arena, node, index);
+        }
 
-        self.blocks.push_in_place(|index| {
-            self.components[component].blocks.push(index);
+        for node in nodes.iter().copied() {
+            for edge in self.graph.incident_edges(node) {
+                if self.edge_data[edge].block_index != index.into() {
+                    let (a, b) = self.graph.edge_endpoints(edge);
 
-            for node in nodes.iter().copied() {
-                assert_eq!(self.node_data[node].component_index, component.into());
-                assert!(!self.node_data[node].block_indices.contains(&index));
-                self.node_data[node].block_indices.push(index);
+                    if Self::node_has_block(&self.node_data, &self.arena, a, index)
+                        && Self::node_has_block(&self.node_data, &self.arena, b, index)
+                        && self.edge_data[edge].block_index.is_some()
+                    {
+                        return Err(AddError::EdgeInOtherBlock(edge));
+                    }
+                }
             }
+        }
 
-            for node in nodes.iter().copied() {
-                for edge in self.graph.incident_edges(node) {
-                    if self.edge_data[edge].block_index != index.into() {
-                        let (a, b) = self.graph.edge_endpoints(edge);
-
-                        if self.node_data[a].block_indices.contains(&index)
-                            && self.node_data[b].block_indices.contains(&index)
-                            && self.edge_data[edge].block_index.is_some() {
-                            assert_eq!(
-                                self.edge_data[edge].block_index,
-                                index.into(),
-                                "Edge {edge} is assigned to block {}, but both endpoints {} and {} are in block {}",
-                                self.edge_data[edge].block_index.unwrap(),
-                                a,
-                                b,
-                                index,
-                            );
+        self.components[component].blocks.push(index);
+        self.observer.on_block_added(index, component, &nodes);
+        Ok(self.blocks.push(Block {
+            component,
+            nodes,
+            cut_nodes: Vec::new(),
+            edges: Vec::new(),
+            spqr_nodes: Vec::new(),
+            spqr_edges: Vec::new(),
+            extra_data: ExtraData::default(),
+        }))
+    }
+
+    /// Adds one block for each biconnected component of the given component, and adds all cut nodes of the component.
+    ///
+    /// The blocks are found by an iterative variant of the algorithm by Hopcroft and Tarjan.
+    /// Edges of blocks with exactly two nodes are added to the blocks, since these blocks have no SPQR tree.
+    /// Edges of larger blocks still need to be added to SPQR nodes using [`add_edge_to_spqr_node`](Self::add_edge_to_spqr_node).
+    /// Self-loops are ignored.
+    pub fn add_blocks_from_graph(
+        &mut self,
+        component: ComponentIndex<Graph::IndexType>,
+    ) -> Vec<BlockIndex<Graph::IndexType>> {
+        debug!("Detecting blocks of component {component}...");
+        assert!(self.components[component].blocks.is_empty());
+
+        struct Frame<NodeIndex, EdgeIndex> {
+            node: NodeIndex,
+            parent_edge: Option<EdgeIndex>,
+            incident_edges: Vec<EdgeIndex>,
+            next_incident_edge: usize,
+        }
+
+        let mut discovery_time: FxHashMap<Graph::NodeIndex, usize> = FxHashMap::default();
+        let mut low: FxHashMap<Graph::NodeIndex, usize> = FxHashMap::default();
+        let mut stack = Vec::new();
+        let mut edge_stack = Vec::new();
+        let mut block_edge_lists = Vec::new();
+
+        for root in self.components[component].nodes.iter().copied() {
+            if discovery_time.contains_key(&root) {
+                continue;
+            }
+
+            discovery_time.insert(root, discovery_time.len());
+            low.insert(root, discovery_time[&root]);
+            stack.push(Frame {
+                node: root,
+                parent_edge: None,
+                incident_edges: self.graph.incident_edges(root).collect(),
+                next_incident_edge: 0,
+            });
+
+            while let Some(frame) = stack.last_mut() {
+                let node = frame.node;
+
+                if let Some(edge) = frame.incident_edges.get(frame.next_incident_edge).copied() {
+                    frame.next_incident_edge += 1;
+                    if Some(edge) == frame.parent_edge {
+                        continue;
+                    }
+
+                    let (a, b) = self.graph.edge_endpoints(edge);
+                    let neighbor = if a == node { b } else { a };
+                    if neighbor == node {
+                        continue;
+                    }
+
+                    if let Some(neighbor_discovery_time) = discovery_time.get(&neighbor).copied() {
+                        if neighbor_discovery_time < discovery_time[&node] {
+                            // Back edge, the other direction is ignored.
+                            edge_stack.push(edge);
+                            let node_low = low.get_mut(&node).unwrap();
+                            *node_low = (*node_low).min(neighbor_discovery_time);
+                        }
+                    } else {
+                        edge_stack.push(edge);
+                        discovery_time.insert(neighbor, discovery_time.len());
+                        low.insert(neighbor, discovery_time[&neighbor]);
+                        stack.push(Frame {
+                            node: neighbor,
+                            parent_edge: Some(edge),
+                            incident_edges: self.graph.incident_edges(neighbor).collect(),
+                            next_incident_edge: 0,
+                        });
+                    }
+                } else {
+                    let parent_edge = frame.parent_edge;
+                    stack.pop();
+
+                    if let Some(parent) = stack.last() {
+                        let parent = parent.node;
+                        let node_low = low[&node];
+                        let parent_low = low.get_mut(&parent).unwrap();
+                        *parent_low = (*parent_low).min(node_low);
+
+                        if node_low >= discovery_time[&parent] {
+                            // The parent separates the subtree of the node, so the edges above the parent edge form a block.
+                            let parent_edge = parent_edge.unwrap();
+                            let mut block_edges = Vec::new();
+                            loop {
+                                let edge = edge_stack.pop().unwrap();
+                                block_edges.push(edge);
+                                if edge == parent_edge {
+                                    break;
+                                }
+                            }
+                            block_edge_lists.push(block_edges);
                         }
                     }
                 }
             }
+        }
 
-            Block {
-                component,
-                nodes,
-                cut_nodes: Vec::new(),
-                edges: Vec::new(),
-                spqr_nodes: Vec::new(),
-                spqr_edges: Vec::new(),
+        let mut block_indices = Vec::new();
+        let mut block_nodes = FxHashSet::default();
+        for block_edges in block_edge_lists {
+            let mut nodes = Vec::new();
+            block_nodes.clear();
+            for edge in block_edges.iter().copied() {
+                let (a, b) = self.graph.edge_endpoints(edge);
+                for node in [a, b] {
+                    if block_nodes.insert(node) {
+                        nodes.push(node);
+                    }
+                }
             }
-        })
+
+            let is_small = nodes.len() == 2;
+            let block_index = self.add_block(component, nodes);
+            trace!("Detected block {block_index}");
+            if is_small {
+                for edge in block_edges {
+                    self.add_edge_to_block(edge, block_index).unwrap();
+                }
+            }
+            block_indices.push(block_index);
+        }
+
+        // Nodes in multiple blocks are cut nodes.
+        for node in self.components[component].nodes.clone() {
+            let blocks = self.node_blocks(node);
+            if blocks.len() >= 2 && self.node_data[node].cut_node_index.is_none() {
+                self.add_cut_node(node, blocks);
+            }
+        }
+
+        debug!(
+            "Detected {} blocks in component {component}",
+            block_indices.len()
+        );
+        block_indices
     }
 
     /// Manually add a cut node to the decomposition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`try_add_cut_node`](Self::try_add_cut_node) would return an error.
     pub fn add_cut_node(
         &mut self,
         cut_node: Graph::NodeIndex,
         blocks: Vec<BlockIndex<Graph::IndexType>>,
     ) -> CutNodeIndex<Graph::IndexType> {
-        assert!(!blocks.is_empty());
-
-        self.cut_nodes.push_in_place(|cut_node_index| {
-            assert!(self.node_data[cut_node].cut_node_index.is_none());
-            self.node_data[cut_node].cut_node_index = cut_node_index.into();
-
-            let component_index = self.node_data[cut_node].component_index.unwrap();
-
-            self.components[component_index]
-                .cut_nodes
-                .push(cut_node_index);
-
-            for block_index in blocks.iter().copied() {
-                debug_assert!(!self.blocks[block_index].cut_nodes.contains(&cut_node_index));
-                self.blocks[block_index].cut_nodes.push(cut_node_index);
-            }
+        self.try_add_cut_node(cut_node, blocks)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
 
-            CutNode {
-                component: component_index,
-                node: cut_node,
-                adjacent_blocks: blocks.into(),
+    /// Like [`add_cut_node`](Self::add_cut_node), but returns an error if the blocks are empty or contain duplicates,
+    /// or if the node is not in a component or already a cut node.
+    ///
+    /// If an error is returned, the builder may have been modified partially and should be discarded.
+    pub fn try_add_cut_node(
+        &mut self,
+        cut_node: Graph::NodeIndex,
+        blocks: Vec<BlockIndex<Graph::IndexType>>,
+    ) -> Result<CutNodeIndex<Graph::IndexType>, GraphAddError<Graph>> {
+        if blocks.is_empty() {
+            return Err(AddError::TooSmall);
+        }
+        if self.node_data[cut_node].cut_node_index.is_some() {
+            return Err(AddError::DuplicateCutNode(cut_node));
+        }
+        let Some(component_index) = self.node_data[cut_node].component_index.into_option() else {
+            return Err(AddError::NodeWithoutComponent(cut_node));
+        };
+
+        let cut_node_index = CutNodeIndex::from(self.cut_nodes.len());
+        for block_index in blocks.iter().copied() {
+            if self.blocks[block_index].cut_nodes.last() == Some(&cut_node_index) {
+                return Err(AddError::DuplicateBlock(block_index));
             }
-        })
+            self.blocks[block_index].cut_nodes.push(cut_node_index);
+        }
+        self.node_data[cut_node].cut_node_index = cut_node_index.into();
+        self.components[component_index]
+            .cut_nodes
+            .push(cut_node_index);
+
+        Ok(self.cut_nodes.push(CutNode {
+            component: component_index,
+            node: cut_node,
+            adjacent_blocks: blocks.into(),
+            extra_data: ExtraData::default(),
+        }))
     }
 
     /// Adds an edge into a block.
@@ -256,59 +912,164 @@ impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
         block: BlockIndex<Graph::IndexType>,
     ) -> Result<(), AddEdgeError> {
         if self.edge_data[edge].block_index.is_some() {
-            assert_eq!(self.edge_data[edge].block_index, block.into());
-            return Err(AddEdgeError::AlreadyAddedToBlock);
+            return Err(if self.edge_data[edge].block_index == block.into() {
+                AddEdgeError::AlreadyAddedToBlock
+            } else {
+                AddEdgeError::AddedToDifferentBlock
+            });
         }
 
         let (a, b) = self.graph.edge_endpoints(edge);
-        assert!(self.node_data[a].block_indices.contains(&block));
-        assert!(
-            self.node_data[b].block_indices.contains(&block),
-            "Edge {edge} has endpoints {a} and {b}, but only {a} is in block {block} while {b} is not.",
-        );
+        if !Self::node_has_block(&self.node_data, &self.arena, a, block)
+            || !Self::node_has_block(&self.node_data, &self.arena, b, block)
+        {
+            return Err(AddEdgeError::EndpointNotInBlock);
+        }
+        if !self.blocks[block].spqr_nodes.is_empty() || !self.blocks[block].spqr_edges.is_empty() {
+            return Err(AddEdgeError::BlockHasSPQRTree);
+        }
 
         self.edge_data[edge].component_index = self.blocks[block].component.into();
         self.edge_data[edge].block_index = block.into();
         self.blocks[block].edges.push(edge);
 
-        assert!(self.blocks[block].spqr_nodes.is_empty());
-        assert!(self.blocks[block].spqr_edges.is_empty());
-
         Ok(())
     }
 
     /// Adds an SPQR node into a block.
     ///
     /// Edges are not added to the component and must be added separately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`try_add_spqr_node`](Self::try_add_spqr_node) would return an error.
     pub fn add_spqr_node(
         &mut self,
         block: BlockIndex<Graph::IndexType>,
         nodes: Vec<Graph::NodeIndex>,
         spqr_node_type: SPQRNodeType,
     ) -> SPQRNodeIndex<Graph::IndexType> {
-        assert!(nodes.len() >= 2);
+        self.try_add_spqr_node(block, nodes, spqr_node_type)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
 
-        self.spqr_nodes.push_in_place(|index| {
-            self.blocks[block].spqr_nodes.push(index);
+    /// Like [`add_spqr_node`](Self::add_spqr_node), but returns an error if there are less than two nodes,
+    /// or if the nodes contain duplicates or are not in the block.
+    ///
+    /// If an error is returned, the builder may have been modified partially and should be discarded.
+    pub fn try_add_spqr_node(
+        &mut self,
+        block: BlockIndex<Graph::IndexType>,
+        nodes: Vec<Graph::NodeIndex>,
+        spqr_node_type: SPQRNodeType,
+    ) -> Result<SPQRNodeIndex<Graph::IndexType>, GraphAddError<Graph>> {
+        if nodes.len() < 2 {
+            return Err(AddError::TooSmall);
+        }
 
-            for node in nodes.iter().copied() {
-                assert_eq!(
-                    self.node_data[node].component_index,
-                    self.blocks[block].component.into()
-                );
-                assert!(self.node_data[node].block_indices.contains(&block));
-                assert!(!self.node_data[node].spqr_node_indices.contains(&index));
-                self.node_data[node].spqr_node_indices.push(index);
+        let index = SPQRNodeIndex::from(self.spqr_nodes.len());
+        for node in nodes.iter().copied() {
+            if !Self::node_has_block(&self.node_data, &self.arena, node, block) {
+                return Err(AddError::NodeNotInBlock { node, block });
+            }
+            if Self::node_has_spqr_node(&self.node_data, &self.arena, node, index) {
+                return Err(AddError::DuplicateNode(node));
             }
+            Self::push_node_spqr_node(&mut self.node_data, &mut self.arena, node, index);
+        }
+
+        self.blocks[block].spqr_nodes.push(index);
+        self.observer
+            .on_spqr_node_added(index, block, spqr_node_type, &nodes);
+        Ok(self.spqr_nodes.push(SPQRNode {
+            block,
+            nodes,
+            edges: Vec::new(),
+            spqr_node_type,
+            spqr_edges: SmallVec::new(),
+            extra_data: ExtraData::default(),
+        }))
+    }
+
+    /// Adds a single S-node containing all nodes and edges of the given block, if the block is a simple cycle.
+    ///
+    /// The SPQR tree of a cycle consists of exactly one S-node, so this is a cheap fast path that avoids computing the triconnected components,
+    /// taking time linear in the size of the block.
+    /// Returns `None` without modifying the builder if the block is not a cycle or already has SPQR nodes.
+    /// Self-loops are ignored, like in [`add_blocks_from_graph`](Self::add_blocks_from_graph).
+    pub fn add_spqr_node_if_cycle(
+        &mut self,
+        block: BlockIndex<Graph::IndexType>,
+    ) -> Option<SPQRNodeIndex<Graph::IndexType>> {
+        let nodes = &self.blocks[block].nodes;
+        if nodes.len() < 3 || !self.blocks[block].spqr_nodes.is_empty() {
+            return None;
+        }
+
+        // Since the block is biconnected, it is a cycle if and only if all its nodes have exactly two incident edges in the block.
+        let mut edges = Vec::with_capacity(nodes.len());
+        for node in nodes.iter().copied() {
+            let mut degree = 0;
+            for edge in self.graph.incident_edges(node) {
+                let (a, b) = self.graph.edge_endpoints(edge);
+                let other = if a == node { b } else { a };
+                if a == b || !Self::node_has_block(&self.node_data, &self.arena, other, block) {
+                    continue;
+                }
 
-            SPQRNode {
-                block,
-                nodes,
-                edges: Vec::new(),
-                spqr_node_type,
-                spqr_edges: SmallVec::new(),
+                degree += 1;
+                if degree > 2 {
+                    return None;
+                }
+                // Each edge is collected from its first endpoint only.
+                if a == node {
+                    edges.push(edge);
+                }
+            }
+            if degree != 2 {
+                return None;
+            }
+        }
+
+        trace!("Block {block} is a cycle");
+        let spqr_node = self.add_spqr_node(block, nodes.clone(), SPQRNodeType::SNode);
+        for edge in edges {
+            self.add_edge_to_spqr_node(edge, spqr_node).unwrap();
+        }
+        Some(spqr_node)
+    }
+
+    /// Adds all self-loops of the given component that were not added yet.
+    ///
+    /// Self-loops of a component with a single node are added to the component.
+    /// Otherwise, each self-loop is added to the first block of its node,
+    /// or if that block has SPQR nodes, to the first of them that contains the node.
+    /// Hence, this should be called after the SPQR nodes of the blocks of the component have been added.
+    pub fn add_self_loops_from_graph(&mut self, component: ComponentIndex<Graph::IndexType>) {
+        for node in self.components[component].nodes.clone() {
+            for edge in self.graph.incident_edges(node) {
+                let (a, b) = self.graph.edge_endpoints(edge);
+                if a != b || self.edge_data[edge].component_index.is_some() {
+                    continue;
+                }
+
+                let Some(&block) = self.node_blocks(node).first() else {
+                    self.add_edge_to_component(edge, component).unwrap();
+                    continue;
+                };
+                let spqr_node = self.blocks[block]
+                    .spqr_nodes
+                    .iter()
+                    .copied()
+                    .find(|&spqr_node| {
+                        Self::node_has_spqr_node(&self.node_data, &self.arena, node, spqr_node)
+                    });
+                match spqr_node {
+                    Some(spqr_node) => self.add_edge_to_spqr_node(edge, spqr_node).unwrap(),
+                    None => self.add_edge_to_block(edge, block).unwrap(),
+                }
             }
-        })
+        }
     }
 
     /// Adds an edge into an SPQR node.
@@ -322,20 +1083,31 @@ impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
         spqr_node: SPQRNodeIndex<Graph::IndexType>,
     ) -> Result<(), AddEdgeError> {
         if self.edge_data[edge].spqr_node_index.is_some() {
-            assert_eq!(self.edge_data[edge].spqr_node_index, spqr_node.into());
-            return Err(AddEdgeError::AlreadyAddedToSPQRNode);
+            return Err(
+                if self.edge_data[edge].spqr_node_index == spqr_node.into() {
+                    AddEdgeError::AlreadyAddedToSPQRNode
+                } else {
+                    AddEdgeError::AddedToDifferentSPQRNode
+                },
+            );
         }
 
         let (a, b) = self.graph.edge_endpoints(edge);
-        assert!(self.node_data[a].spqr_node_indices.contains(&spqr_node));
-        assert!(self.node_data[b].spqr_node_indices.contains(&spqr_node));
+        if !Self::node_has_spqr_node(&self.node_data, &self.arena, a, spqr_node)
+            || !Self::node_has_spqr_node(&self.node_data, &self.arena, b, spqr_node)
+        {
+            return Err(AddEdgeError::EndpointNotInSPQRNode);
+        }
 
         self.edge_data[edge].component_index = self.blocks[self.spqr_nodes[spqr_node].block]
             .component
             .into();
         self.edge_data[edge].block_index = self.spqr_nodes[spqr_node].block.into();
         self.edge_data[edge].spqr_node_index = spqr_node.into();
-        self.spqr_nodes[spqr_node].edges.push(edge);
+        match &mut self.arena {
+            Some(arena) => arena.spqr_node_edges.push(spqr_node.into_usize(), edge),
+            None => self.spqr_nodes[spqr_node].edges.push(edge),
+        }
 
         Ok(())
     }
@@ -345,6 +1117,10 @@ impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
     /// These edges connect two SPQR nodes `endpoints` and correspond to the virtual edge `virtual_edge` in the two SPQR nodes.
     ///
     /// If the block index is `None`, it is inferred from the SPQR nodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`try_add_spqr_edge`](Self::try_add_spqr_edge) would return an error.
     pub fn add_spqr_edge(
         &mut self,
         block: OptionalBlockIndex<Graph::IndexType>,
@@ -354,56 +1130,232 @@ impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
         ),
         virtual_edge: (Graph::NodeIndex, Graph::NodeIndex),
     ) -> SPQREdgeIndex<Graph::IndexType> {
-        let block = block.unwrap_or_else(|| {
-            let block_u = self.spqr_nodes[endpoints.0].block;
-            let block_v = self.spqr_nodes[endpoints.1].block;
-            assert_eq!(block_u, block_v);
-            block_u
-        });
-
-        self.spqr_edges.push_in_place(|index| {
-            assert_eq!(self.spqr_nodes[endpoints.0].block, block);
-            assert_eq!(self.spqr_nodes[endpoints.1].block, block);
-            self.blocks[block].spqr_edges.push(index);
+        self.try_add_spqr_edge(block, endpoints, virtual_edge)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
 
-            assert!(
-                self.node_data[virtual_edge.0]
-                    .spqr_node_indices
-                    .contains(&endpoints.0)
-            );
-            assert!(
-                self.node_data[virtual_edge.0]
-                    .spqr_node_indices
-                    .contains(&endpoints.1)
-            );
-            assert!(
-                self.node_data[virtual_edge.1]
-                    .spqr_node_indices
-                    .contains(&endpoints.0)
-            );
-            assert!(
-                self.node_data[virtual_edge.1]
-                    .spqr_node_indices
-                    .contains(&endpoints.1)
-            );
+    /// Like [`add_spqr_edge`](Self::add_spqr_edge), but returns an error if the SPQR nodes are equal or not both in the block,
+    /// or if an endpoint of the virtual edge is missing from one of the SPQR nodes.
+    ///
+    /// The builder is not modified if an error is returned.
+    pub fn try_add_spqr_edge(
+        &mut self,
+        block: OptionalBlockIndex<Graph::IndexType>,
+        endpoints: (
+            SPQRNodeIndex<Graph::IndexType>,
+            SPQRNodeIndex<Graph::IndexType>,
+        ),
+        virtual_edge: (Graph::NodeIndex, Graph::NodeIndex),
+    ) -> Result<SPQREdgeIndex<Graph::IndexType>, GraphAddError<Graph>> {
+        if endpoints.0 == endpoints.1 {
+            return Err(AddError::SPQREdgeLoop(endpoints.0));
+        }
+        let block_u = self.spqr_nodes[endpoints.0].block;
+        let block_v = self.spqr_nodes[endpoints.1].block;
+        if block_u != block_v || block.into_option().is_some_and(|block| block != block_u) {
+            return Err(AddError::SPQRNodesInDifferentBlocks(
+                endpoints.0,
+                endpoints.1,
+            ));
+        }
+        for node in [virtual_edge.0, virtual_edge.1] {
+            for spqr_node in [endpoints.0, endpoints.1] {
+                if !Self::node_has_spqr_node(&self.node_data, &self.arena, node, spqr_node) {
+                    return Err(AddError::NodeNotInSPQRNode { node, spqr_node });
+                }
+            }
+        }
 
-            assert!(!self.spqr_nodes[endpoints.0].spqr_edges.contains(&index));
-            assert!(!self.spqr_nodes[endpoints.1].spqr_edges.contains(&index));
-            self.spqr_nodes[endpoints.0].spqr_edges.push(index);
-            self.spqr_nodes[endpoints.1].spqr_edges.push(index);
+        let index = self.spqr_edges.push(SPQREdge {
+            endpoints,
+            virtual_edge,
+        });
+        self.blocks[block_u].spqr_edges.push(index);
+        self.spqr_nodes[endpoints.0].spqr_edges.push(index);
+        self.spqr_nodes[endpoints.1].spqr_edges.push(index);
+        Ok(index)
+    }
 
-            SPQREdge {
-                endpoints,
-                virtual_edge,
-            }
-        })
+    /// Finalize the SPQR decomposition.
+    ///
+    /// This method performs some sanity checks and identifies remaining cut nodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sanity checks fail. Use [`try_build`](Self::try_build) to receive the failed checks as an error instead.
+    pub fn build(self) -> SPQRDecomposition<'graph, Graph> {
+        self.try_build().unwrap_or_else(|error| panic!("{error}"))
     }
 
     /// Finalize the SPQR decomposition.
     ///
     /// This method performs some sanity checks and identifies remaining cut nodes.
-    pub fn build(mut self) -> SPQRDecomposition<'graph, Graph> {
+    /// If any of the sanity checks fail, then all failed checks are returned as an error.
+    pub fn try_build(
+        self,
+    ) -> Result<
+        SPQRDecomposition<'graph, Graph>,
+        BuildError<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+    > {
+        self.try_build_with_progress(&mut ())
+    }
+
+    /// Like [`try_build`](Self::try_build), but reports the start and finish of each phase to `progress`.
+    pub fn try_build_with_progress(
+        self,
+        progress: &mut impl ProgressSink,
+    ) -> Result<
+        SPQRDecomposition<'graph, Graph>,
+        BuildError<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+    > {
+        self.build_with(Strictness::Error, progress)
+    }
+
+    /// Like [`try_build`](Self::try_build), but reacts to failed sanity checks according to `strictness`.
+    ///
+    /// With [`Strictness::Warn`], failed checks are logged and the decomposition is built anyway,
+    /// except if a node or edge is not assigned to any component, in which case all failed checks are returned as an error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sanity checks fail and `strictness` is [`Strictness::Panic`].
+    pub fn try_build_with_strictness(
+        self,
+        strictness: Strictness,
+    ) -> Result<
+        SPQRDecomposition<'graph, Graph>,
+        BuildError<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+    > {
+        self.build_with(strictness, &mut ())
+    }
+
+    fn build_with(
+        mut self,
+        strictness: Strictness,
+        progress: &mut impl ProgressSink,
+    ) -> Result<
+        SPQRDecomposition<'graph, Graph>,
+        BuildError<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+    > {
+        debug!("Finalizing SPQR decomposition...");
+        self.release_arena();
+        progress.start_phase(Phase::Validation, None);
+        let problems = self.find_problems();
+        progress.finish_phase(Phase::Validation);
+        Self::handle_problems(problems, strictness)?;
+
+        progress.start_phase(Phase::CutNodeIdentification, None);
+        self.identify_cut_nodes();
+        progress.finish_phase(Phase::CutNodeIdentification);
+        progress.start_phase(Phase::Build, None);
+        let spqr_decomposition = self.finish();
+        progress.finish_phase(Phase::Build);
+        debug!("SPQR decomposition finalized.");
+        Ok(spqr_decomposition)
+    }
+
+    /// Like [`try_build`](Self::try_build), but records the duration and allocations of each phase in `profile`.
+    pub fn try_build_with_profile(
+        mut self,
+        profile: &mut Profile,
+    ) -> Result<
+        SPQRDecomposition<'graph, Graph>,
+        BuildError<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+    > {
         debug!("Finalizing SPQR decomposition...");
+        if self.uses_arena() {
+            profile.record(Phase::Build, || self.release_arena());
+        }
+        let problems = profile.record(Phase::Validation, || self.find_problems());
+        Self::handle_problems(problems, Strictness::Error)?;
+
+        profile.record(Phase::CutNodeIdentification, || self.identify_cut_nodes());
+        debug!("SPQR decomposition finalized.");
+        Ok(profile.record(Phase::Build, || self.finish()))
+    }
+
+    /// Moves the lists stored in the arena into their entities, allocating each list exactly once, and frees the arena.
+    fn release_arena(&mut self) {
+        let Some(arena) = self.arena.take() else {
+            return;
+        };
+
+        for (node_index, node_data) in self.node_data.iter_values_mut().enumerate() {
+            arena
+                .node_blocks
+                .extend_into(node_index, &mut node_data.block_indices);
+            arena
+                .node_spqr_nodes
+                .extend_into(node_index, &mut node_data.spqr_node_indices);
+        }
+        for (spqr_node_index, spqr_node) in self.spqr_nodes.iter_values_mut().enumerate() {
+            arena
+                .spqr_node_edges
+                .extend_into(spqr_node_index, &mut spqr_node.edges);
+        }
+    }
+
+    fn push_node_block(
+        node_data: &mut TaggedVec<Graph::NodeIndex, SPQRDecompositionNodeDataBuilder<Graph>>,
+        arena: &mut Option<BuilderArena<Graph>>,
+        node: Graph::NodeIndex,
+        block: BlockIndex<Graph::IndexType>,
+    ) {
+        match arena {
+            Some(arena) => arena.node_blocks.push(node.into(), block),
+            None => node_data[node].block_indices.push(block),
+        }
+    }
+
+    fn node_has_block(
+        node_data: &TaggedVec<Graph::NodeIndex, SPQRDecompositionNodeDataBuilder<Graph>>,
+        arena: &Option<BuilderArena<Graph>>,
+        node: Graph::NodeIndex,
+        block: BlockIndex<Graph::IndexType>,
+    ) -> bool {
+        match arena {
+            Some(arena) => arena.node_blocks.contains(node.into(), &block),
+            None => node_data[node].block_indices.contains(&block),
+        }
+    }
+
+    fn push_node_spqr_node(
+        node_data: &mut TaggedVec<Graph::NodeIndex, SPQRDecompositionNodeDataBuilder<Graph>>,
+        arena: &mut Option<BuilderArena<Graph>>,
+        node: Graph::NodeIndex,
+        spqr_node: SPQRNodeIndex<Graph::IndexType>,
+    ) {
+        match arena {
+            Some(arena) => arena.node_spqr_nodes.push(node.into(), spqr_node),
+            None => node_data[node].spqr_node_indices.push(spqr_node),
+        }
+    }
+
+    fn node_has_spqr_node(
+        node_data: &TaggedVec<Graph::NodeIndex, SPQRDecompositionNodeDataBuilder<Graph>>,
+        arena: &Option<BuilderArena<Graph>>,
+        node: Graph::NodeIndex,
+        spqr_node: SPQRNodeIndex<Graph::IndexType>,
+    ) -> bool {
+        match arena {
+            Some(arena) => arena.node_spqr_nodes.contains(node.into(), &spqr_node),
+            None => node_data[node].spqr_node_indices.contains(&spqr_node),
+        }
+    }
+
+    /// Returns the blocks the given node was added to so far.
+    fn node_blocks(&self, node: Graph::NodeIndex) -> Vec<BlockIndex<Graph::IndexType>> {
+        match &self.arena {
+            Some(arena) => arena.node_blocks.iter(node.into()).collect(),
+            None => self.node_data[node].block_indices.to_vec(),
+        }
+    }
+
+    /// Performs the sanity checks of [`try_build`](Self::try_build), returning all failed checks.
+    fn find_problems(
+        &self,
+    ) -> Vec<BuildProblem<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>> {
+        let mut problems = Vec::new();
 
         // Ensure that all nodes have actually been assigned to components, blocks, and SPQR nodes.
         for node_index in self.graph.node_indices() {
@@ -414,19 +1366,22 @@ impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
                 ..
             } = &self.node_data[node_index];
 
-            assert!(component_index.is_some());
+            let Some(component_index) = component_index.into_option() else {
+                problems.push(BuildProblem::NodeWithoutComponent(node_index));
+                continue;
+            };
             // Components containing only one node cannot contain blocks.
-            assert!(
-                !block_indices.is_empty()
-                    || self.components[component_index.unwrap()].nodes.len() == 1
-            );
+            if block_indices.is_empty() && self.components[component_index].nodes.len() != 1 {
+                problems.push(BuildProblem::NodeWithoutBlock(node_index));
+            }
             // Blocks containing at most two nodes cannot contain SPQR nodes.
-            assert!(
-                !spqr_node_indices.is_empty()
-                    || block_indices
-                        .iter()
-                        .all(|block_index| self.blocks[*block_index].node_count() <= 2)
-            );
+            if spqr_node_indices.is_empty()
+                && !block_indices
+                    .iter()
+                    .all(|block_index| self.blocks[*block_index].node_count() <= 2)
+            {
+                problems.push(BuildProblem::NodeWithoutSPQRNode(node_index));
+            }
         }
 
         // Ensure that all edges have actually been assigned to components, blocks, and SPQR nodes.
@@ -438,28 +1393,91 @@ impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
                 ..
             } = &self.edge_data[edge_index];
 
-            assert!(
-                component_index.is_some(),
-                "Edge {edge_index} from {} to {} is not assigned to any component",
-                self.graph
-                    .node_name(self.graph.edge_endpoints(edge_index).0),
-                self.graph
-                    .node_name(self.graph.edge_endpoints(edge_index).1),
-            );
+            let Some(component_index) = component_index.into_option() else {
+                trace!(
+                    "Edge {edge_index} from {} to {} is not assigned to any component",
+                    self.graph
+                        .node_name(self.graph.edge_endpoints(edge_index).0),
+                    self.graph
+                        .node_name(self.graph.edge_endpoints(edge_index).1),
+                );
+                problems.push(BuildProblem::EdgeWithoutComponent(edge_index));
+                continue;
+            };
             // Components containing only one node cannot contain blocks.
-            assert!(
-                block_index.is_some() || self.components[component_index.unwrap()].nodes.len() == 1
-            );
+            if block_index.is_none() && self.components[component_index].nodes.len() != 1 {
+                problems.push(BuildProblem::EdgeWithoutBlock(edge_index));
+            }
             // Blocks containing at most two nodes cannot contain SPQR nodes.
-            assert!(
-                spqr_node_index.is_some()
-                    || block_index
-                        .into_iter()
-                        .all(|block_index| self.blocks[block_index].node_count() <= 2)
-            );
+            if spqr_node_index.is_none()
+                && !block_index
+                    .into_iter()
+                    .all(|block_index| self.blocks[block_index].node_count() <= 2)
+            {
+                problems.push(BuildProblem::EdgeWithoutSPQRNode(edge_index));
+            }
+        }
+
+        for (component_index, component) in self.components.iter(..) {
+            if component.nodes.len() != 1 && component.blocks.is_empty() {
+                problems.push(BuildProblem::ComponentWithoutBlocks(component_index));
+            }
+            if component.nodes.len() != 1 && !component.edges.is_empty() {
+                problems.push(BuildProblem::EdgesInMultiNodeComponent(component_index));
+            }
+        }
+
+        for (block_index, block) in self.blocks.iter(..) {
+            if block.nodes.len() != 2 && block.spqr_nodes.is_empty() {
+                problems.push(BuildProblem::BlockWithoutSPQRNodes(block_index));
+            }
+            if block.nodes.len() != 2 && !block.edges.is_empty() {
+                problems.push(BuildProblem::EdgesInLargeBlock(block_index));
+            }
+        }
+
+        problems
+    }
+
+    /// Reacts to the failed sanity checks according to `strictness`, returning an error if building cannot continue.
+    fn handle_problems(
+        problems: Vec<BuildProblem<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>>,
+        strictness: Strictness,
+    ) -> Result<(), BuildError<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>> {
+        if problems.is_empty() {
+            return Ok(());
         }
+        debug!(
+            "Found {} problems in the SPQR decomposition",
+            problems.len()
+        );
 
-        // Identify cut nodes.
+        let error = BuildError { problems };
+        match strictness {
+            Strictness::Panic => panic!("{error}"),
+            Strictness::Error => Err(error),
+            Strictness::Warn => {
+                for problem in &error.problems {
+                    warn!("{problem}");
+                }
+                // Every node and edge needs a component to build the decomposition.
+                if error.problems.iter().any(|problem| {
+                    matches!(
+                        problem,
+                        BuildProblem::NodeWithoutComponent(_)
+                            | BuildProblem::EdgeWithoutComponent(_)
+                    )
+                }) {
+                    Err(error)
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Declares all nodes that are in multiple blocks as cut nodes, unless they were declared explicitly.
+    fn identify_cut_nodes(&mut self) {
         for node_index in self.graph.node_indices() {
             if self.node_data[node_index].cut_node_index.is_some() {
                 // Skip nodes that are already declared as cut nodes.
@@ -492,35 +1510,17 @@ impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
                         component: component_index,
                         node: node_index,
                         adjacent_blocks: block_indices,
+                        extra_data: ExtraData::default(),
                     }
                 });
             }
         }
+    }
 
-        assert!(
-            self.components
-                .iter_values()
-                .all(|component| { component.nodes.len() == 1 || !component.blocks.is_empty() })
-        );
-        assert!(
-            self.components
-                .iter_values()
-                .all(|component| { component.edges.is_empty() || component.nodes.len() == 1 })
-        );
-        assert!(
-            self.blocks
-                .iter_values()
-                .all(|block| { block.nodes.len() == 2 || !block.spqr_nodes.is_empty() })
-        );
-        assert!(
-            self.blocks
-                .iter_values()
-                .all(|block| { block.edges.is_empty() || block.nodes.len() == 2 })
-        );
-
-        debug!("SPQR decomposition finalized.");
+    fn finish(self) -> SPQRDecomposition<'graph, Graph> {
         SPQRDecomposition {
             graph: self.graph,
+            graph_lifetime: PhantomData,
             components: self.components,
             blocks: self.blocks,
             cut_nodes: self.cut_nodes,
@@ -536,6 +1536,7 @@ impl<'graph, Graph: StaticGraph> SPQRDecompositionBuilder<'graph, Graph> {
                 .into_values_iter()
                 .map(SPQRDecompositionEdgeDataBuilder::build)
                 .collect(),
+            cache: Default::default(),
         }
     }
 
@@ -570,3 +1571,51 @@ impl<Graph: StaticGraph> SPQRDecompositionEdgeDataBuilder<Graph> {
         }
     }
 }
+
+impl<NodeIndex: Display, EdgeIndex: Display, IndexType: Display> Display
+    for BuildError<NodeIndex, EdgeIndex, IndexType>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        /// Listing millions of problems is not helpful, so only the first few are printed.
+        const MAX_PRINTED_PROBLEMS: usize = 10;
+
+        write!(
+            f,
+            "the SPQR decomposition is inconsistent ({} problems)",
+            self.problems.len()
+        )?;
+        for problem in self.problems.iter().take(MAX_PRINTED_PROBLEMS) {
+            write!(f, "; {problem}")?;
+        }
+        if self.problems.len() > MAX_PRINTED_PROBLEMS {
+            write!(
+                f,
+                "; and {} more",
+                self.problems.len() - MAX_PRINTED_PROBLEMS
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<NodeIndex: Debug + Display, EdgeIndex: Debug + Display, IndexType: Debug + Display>
+    std::error::Error for BuildError<NodeIndex, EdgeIndex, IndexType>
+{
+}
+
+/// [`TaggedVec`] has no `reserve` method, so the values are moved into a larger vector instead.
+fn reserve<Index, Value>(tagged_vec: &mut TaggedVec<Index, Value>, additional: usize) {
+    if tagged_vec.capacity() - tagged_vec.len() >= additional {
+        return;
+    }
+
+    let mut values = Vec::from(std::mem::take(tagged_vec));
+    values.reserve(additional);
+    *tagged_vec = values.into();
+}
+
+fn cleared<Index, Value>(tagged_vec: TaggedVec<Index, Value>) -> TaggedVec<Index, Value> {
+    let mut values = Vec::from(tagged_vec);
+    values.clear();
+    values.into()
+}
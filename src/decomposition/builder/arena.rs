@@ -0,0 +1,143 @@
+use crate::{
+    decomposition::{
+        builder::SPQRDecompositionBuilderCapacities,
+        indices::{BlockIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+/// Marks the end of a list.
+const NONE: usize = usize::MAX;
+
+/// The lists of the builder that grow one entry at a time, see [`SPQRDecompositionBuilder::with_arena`](super::SPQRDecompositionBuilder::with_arena).
+pub(super) struct BuilderArena<Graph: StaticGraph> {
+    /// The blocks of each node.
+    pub(super) node_blocks: ArenaLists<BlockIndex<Graph::IndexType>>,
+    /// The SPQR nodes of each node.
+    pub(super) node_spqr_nodes: ArenaLists<SPQRNodeIndex<Graph::IndexType>>,
+    /// The real edges of each SPQR node.
+    pub(super) spqr_node_edges: ArenaLists<Graph::EdgeIndex>,
+}
+
+/// Many singly linked lists of items, one per owner, stored in a single buffer.
+///
+/// Appending to a list never allocates on its own, and all lists are freed together with the arena.
+pub(super) struct ArenaLists<Item> {
+    heads: Vec<ListHead>,
+    entries: Vec<Entry<Item>>,
+}
+
+#[derive(Clone, Copy)]
+struct ListHead {
+    first: usize,
+    last: usize,
+    len: usize,
+}
+
+struct Entry<Item> {
+    item: Item,
+    next: usize,
+}
+
+impl<Graph: StaticGraph> BuilderArena<Graph> {
+    pub(super) fn new(graph: &Graph, capacities: SPQRDecompositionBuilderCapacities) -> Self {
+        Self {
+            node_blocks: ArenaLists::new(graph.node_count(), graph.node_count()),
+            node_spqr_nodes: ArenaLists::new(graph.node_count(), graph.node_count()),
+            spqr_node_edges: ArenaLists::new(capacities.spqr_nodes, graph.edge_count()),
+        }
+    }
+
+    /// Empties all lists and prepares the arena for a new graph, while retaining the allocated memory.
+    pub(super) fn reset(&mut self, graph: &Graph) {
+        self.node_blocks.reset(graph.node_count());
+        self.node_spqr_nodes.reset(graph.node_count());
+        self.spqr_node_edges.reset(0);
+    }
+}
+
+impl<Item: Copy + Eq> ArenaLists<Item> {
+    fn new(owner_count: usize, entry_capacity: usize) -> Self {
+        Self {
+            heads: vec![ListHead::EMPTY; owner_count],
+            entries: Vec::with_capacity(entry_capacity),
+        }
+    }
+
+    fn reset(&mut self, owner_count: usize) {
+        self.heads.clear();
+        self.heads.resize(owner_count, ListHead::EMPTY);
+        self.entries.clear();
+    }
+
+    /// Appends `item` to the list of `owner`.
+    pub(super) fn push(&mut self, owner: usize, item: Item) {
+        if owner >= self.heads.len() {
+            self.heads.resize(owner + 1, ListHead::EMPTY);
+        }
+
+        let entry = self.entries.len();
+        self.entries.push(Entry { item, next: NONE });
+        let head = &mut self.heads[owner];
+        if head.len == 0 {
+            head.first = entry;
+        } else {
+            self.entries[head.last].next = entry;
+        }
+        head.last = entry;
+        head.len += 1;
+    }
+
+    pub(super) fn len(&self, owner: usize) -> usize {
+        self.heads.get(owner).map_or(0, |head| head.len)
+    }
+
+    /// Returns the items of the list of `owner` in the order they were appended.
+    pub(super) fn iter(&self, owner: usize) -> impl Iterator<Item = Item> {
+        let mut entry = self
+            .heads
+            .get(owner)
+            .filter(|head| head.len > 0)
+            .map_or(NONE, |head| head.first);
+        std::iter::from_fn(move || {
+            let Entry { item, next } = self.entries.get(entry)?;
+            entry = *next;
+            Some(*item)
+        })
+    }
+
+    pub(super) fn contains(&self, owner: usize, item: &Item) -> bool {
+        self.iter(owner).any(|other| other == *item)
+    }
+
+    /// Appends the items of the list of `owner` to `target` after reserving the exact amount of space needed.
+    pub(super) fn extend_into(&self, owner: usize, target: &mut (impl Extend<Item> + Reserve)) {
+        target.reserve_exact(self.len(owner));
+        target.extend(self.iter(owner));
+    }
+}
+
+impl ListHead {
+    const EMPTY: Self = Self {
+        first: NONE,
+        last: NONE,
+        len: 0,
+    };
+}
+
+/// Collections that can reserve space for an exact amount of additional items.
+pub(super) trait Reserve {
+    fn reserve_exact(&mut self, additional: usize);
+}
+
+impl<Item> Reserve for Vec<Item> {
+    fn reserve_exact(&mut self, additional: usize) {
+        Vec::reserve_exact(self, additional);
+    }
+}
+
+impl<Array: smallvec::Array> Reserve for smallvec::SmallVec<Array> {
+    fn reserve_exact(&mut self, additional: usize) {
+        smallvec::SmallVec::reserve_exact(self, additional);
+    }
+}
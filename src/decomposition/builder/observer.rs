@@ -0,0 +1,85 @@
+//! Callbacks for the entities added to an [`SPQRDecompositionBuilder`](super::SPQRDecompositionBuilder).
+//!
+//! Attach an observer with [`with_observer`](super::SPQRDecompositionBuilder::with_observer)
+//! to maintain side tables, such as reverse maps or statistics, while the decomposition is built,
+//! instead of iterating over the finished decomposition again.
+
+use crate::{
+    decomposition::{
+        SPQRNodeType,
+        indices::{BlockIndex, ComponentIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+/// Receives the entities added to an [`SPQRDecompositionBuilder`](super::SPQRDecompositionBuilder).
+///
+/// Each method is called once the entity was added successfully, with the index it has in the finished decomposition.
+/// Entities added by helpers such as [`add_blocks_from_graph`](super::SPQRDecompositionBuilder::add_blocks_from_graph) are reported as well.
+///
+/// All methods do nothing by default, and `()` is an observer that ignores all entities.
+/// A mutable reference to an observer is an observer too, so the observer can be inspected after the builder was consumed.
+pub trait BuilderObserver<Graph: StaticGraph> {
+    /// Called when a component with the given nodes was added.
+    fn on_component_added(
+        &mut self,
+        component: ComponentIndex<Graph::IndexType>,
+        nodes: &[Graph::NodeIndex],
+    ) {
+        let _ = (component, nodes);
+    }
+
+    /// Called when a block with the given nodes was added to the given component.
+    fn on_block_added(
+        &mut self,
+        block: BlockIndex<Graph::IndexType>,
+        component: ComponentIndex<Graph::IndexType>,
+        nodes: &[Graph::NodeIndex],
+    ) {
+        let _ = (block, component, nodes);
+    }
+
+    /// Called when an SPQR node of the given type with the given nodes was added to the given block.
+    fn on_spqr_node_added(
+        &mut self,
+        spqr_node: SPQRNodeIndex<Graph::IndexType>,
+        block: BlockIndex<Graph::IndexType>,
+        spqr_node_type: SPQRNodeType,
+        nodes: &[Graph::NodeIndex],
+    ) {
+        let _ = (spqr_node, block, spqr_node_type, nodes);
+    }
+}
+
+impl<Graph: StaticGraph> BuilderObserver<Graph> for () {}
+
+impl<Graph: StaticGraph, Observer: BuilderObserver<Graph> + ?Sized> BuilderObserver<Graph>
+    for &mut Observer
+{
+    fn on_component_added(
+        &mut self,
+        component: ComponentIndex<Graph::IndexType>,
+        nodes: &[Graph::NodeIndex],
+    ) {
+        (**self).on_component_added(component, nodes);
+    }
+
+    fn on_block_added(
+        &mut self,
+        block: BlockIndex<Graph::IndexType>,
+        component: ComponentIndex<Graph::IndexType>,
+        nodes: &[Graph::NodeIndex],
+    ) {
+        (**self).on_block_added(block, component, nodes);
+    }
+
+    fn on_spqr_node_added(
+        &mut self,
+        spqr_node: SPQRNodeIndex<Graph::IndexType>,
+        block: BlockIndex<Graph::IndexType>,
+        spqr_node_type: SPQRNodeType,
+        nodes: &[Graph::NodeIndex],
+    ) {
+        (**self).on_spqr_node_added(spqr_node, block, spqr_node_type, nodes);
+    }
+}
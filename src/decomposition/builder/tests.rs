@@ -0,0 +1,573 @@
+use std::{
+    fs::{self, File},
+    io::BufReader,
+};
+
+use bidirected_adjacency_array::{
+    graph::BidirectedAdjacencyArray,
+    io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
+};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        builder::{
+            AddEdgeError, AddError, BuildProblem, SPQRDecompositionBuilder,
+            observer::BuilderObserver,
+        },
+        indices::{BlockIndex, ComponentIndex, OptionalBlockIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+    strictness::Strictness,
+};
+
+type TestGraph = BidirectedAdjacencyArray<u8, PlainGfaNodeData, PlainGfaEdgeData>;
+
+fn read_graph(path: &str) -> TestGraph {
+    BidirectedAdjacencyArray::read_gfa1(BufReader::new(File::open(path).unwrap())).unwrap()
+}
+
+#[test]
+fn test_try_build_reports_unassigned() {
+    let graph = read_graph("test_files/tiny1.gfa");
+    let error = SPQRDecompositionBuilder::new(&graph)
+        .try_build()
+        .unwrap_err();
+
+    let expected_problems = graph
+        .node_indices()
+        .map(BuildProblem::NodeWithoutComponent)
+        .chain(graph.edge_indices().map(BuildProblem::EdgeWithoutComponent))
+        .collect::<Vec<_>>();
+    assert_eq!(error.problems, expected_problems);
+}
+
+#[test]
+fn test_add_components_from_graph() {
+    let graph = read_graph("test_files/random5_10_False_0.gfa");
+    let mut builder = SPQRDecompositionBuilder::new(&graph);
+    let component_indices = builder.add_components_from_graph();
+    assert_eq!(component_indices.len(), 1);
+
+    for edge_index in graph.edge_indices() {
+        let (a, b) = graph.edge_endpoints(edge_index);
+        assert_eq!(
+            builder.node_data[a].component_index,
+            builder.node_data[b].component_index,
+        );
+    }
+    assert_eq!(
+        component_indices
+            .iter()
+            .map(|component_index| builder.components[*component_index].nodes.len())
+            .sum::<usize>(),
+        graph.node_count(),
+    );
+    assert!(builder.add_components_from_graph().is_empty());
+}
+
+#[test]
+fn test_add_blocks_from_graph() {
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random1_1_False_0",
+        "random3_5_False_0",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = read_graph(&format!("test_files/{name}.gfa"));
+        let spqr_decomposition_file =
+            fs::read_to_string(format!("test_files/{name}.spqr")).unwrap();
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(&graph, &mut spqr_decomposition_file.as_bytes())
+                .unwrap();
+
+        let mut builder = SPQRDecompositionBuilder::new(&graph);
+        for component_index in builder.add_components_from_graph() {
+            builder.add_blocks_from_graph(component_index);
+        }
+
+        let mut expected_blocks = spqr_decomposition
+            .iter_blocks()
+            .map(|(_, block)| {
+                let mut nodes = block.iter_nodes().collect::<Vec<_>>();
+                nodes.sort_unstable();
+                nodes
+            })
+            .collect::<Vec<_>>();
+        expected_blocks.sort_unstable();
+        let mut blocks = builder
+            .blocks
+            .iter_values()
+            .map(|block| {
+                let mut nodes = block.nodes.clone();
+                nodes.sort_unstable();
+                nodes
+            })
+            .collect::<Vec<_>>();
+        blocks.sort_unstable();
+        assert_eq!(expected_blocks, blocks, "{name}");
+
+        let mut expected_cut_nodes = spqr_decomposition
+            .iter_nodes()
+            .filter(|node| spqr_decomposition.is_cut_node(*node))
+            .collect::<Vec<_>>();
+        expected_cut_nodes.sort_unstable();
+        let mut cut_nodes = builder
+            .cut_nodes
+            .iter_values()
+            .map(|cut_node| cut_node.node)
+            .collect::<Vec<_>>();
+        cut_nodes.sort_unstable();
+        assert_eq!(expected_cut_nodes, cut_nodes, "{name}");
+    }
+}
+
+#[test]
+fn test_reset_and_recycle() {
+    let graph = read_graph("test_files/tiny1.gfa");
+    let other_graph = read_graph("test_files/random3_5_False_0.gfa");
+
+    let mut builder = SPQRDecompositionBuilder::new(&graph);
+    for component_index in builder.add_components_from_graph() {
+        builder.add_blocks_from_graph(component_index);
+    }
+    let capacity = builder.blocks.capacity();
+
+    let builder = builder.reset(&other_graph);
+    assert!(builder.components.is_empty());
+    assert!(builder.blocks.is_empty());
+    assert!(builder.cut_nodes.is_empty());
+    assert_eq!(builder.blocks.capacity(), capacity);
+    assert_eq!(builder.node_data.len(), other_graph.node_count());
+    assert_eq!(builder.edge_data.len(), other_graph.edge_count());
+
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, &mut spqr_decomposition_file.as_bytes())
+            .unwrap();
+    let spqr_node_capacity = spqr_decomposition.spqr_nodes.capacity();
+    let builder = SPQRDecompositionBuilder::recycle(spqr_decomposition, &other_graph);
+    assert!(builder.spqr_nodes.is_empty());
+    assert_eq!(builder.spqr_nodes.capacity(), spqr_node_capacity);
+}
+
+#[test]
+fn test_with_arena() {
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random3_5_False_0",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = read_graph(&format!("test_files/{name}.gfa"));
+        let spqr_decomposition_file =
+            fs::read_to_string(format!("test_files/{name}.spqr")).unwrap();
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+        let builder = SPQRDecompositionBuilder::with_arena(&graph, Default::default());
+        assert!(builder.uses_arena());
+        assert_eq!(
+            replay(&spqr_decomposition, builder),
+            replay(&spqr_decomposition, SPQRDecompositionBuilder::new(&graph)),
+            "{name}"
+        );
+
+        let builder = SPQRDecompositionBuilder::with_arena(&graph, Default::default());
+        let builder = builder.reset(&graph);
+        assert!(builder.uses_arena());
+        assert_eq!(
+            replay(&spqr_decomposition, builder),
+            replay(&spqr_decomposition, SPQRDecompositionBuilder::new(&graph)),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_add_spqr_node_if_cycle() {
+    let mut cycle_count = 0;
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random1_1_False_0",
+        "random3_5_False_0",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = read_graph(&format!("test_files/{name}.gfa"));
+        let spqr_decomposition_file =
+            fs::read_to_string(format!("test_files/{name}.spqr")).unwrap();
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+        let mut builder = SPQRDecompositionBuilder::new(&graph);
+        let block_indices = builder
+            .add_components_from_graph()
+            .into_iter()
+            .flat_map(|component_index| builder.add_blocks_from_graph(component_index))
+            .collect::<Vec<_>>();
+
+        for block_index in block_indices {
+            let spqr_node_index = builder.add_spqr_node_if_cycle(block_index);
+
+            let node = builder.blocks[block_index].nodes[0];
+            let expected_block_index = spqr_decomposition
+                .node_block_indices(node)
+                .find(|expected_block_index| {
+                    let mut expected_nodes = spqr_decomposition
+                        .block(*expected_block_index)
+                        .iter_nodes()
+                        .collect::<Vec<_>>();
+                    let mut nodes = builder.blocks[block_index].nodes.clone();
+                    expected_nodes.sort_unstable();
+                    nodes.sort_unstable();
+                    expected_nodes == nodes
+                })
+                .unwrap();
+            let expected_spqr_nodes = spqr_decomposition
+                .iter_spqr_nodes_in_block(expected_block_index)
+                .collect::<Vec<_>>();
+            let is_cycle = expected_spqr_nodes.len() == 1 && expected_spqr_nodes[0].1.is_s_node();
+            assert_eq!(spqr_node_index.is_some(), is_cycle, "{name}");
+
+            if let Some(spqr_node_index) = spqr_node_index {
+                cycle_count += 1;
+                assert_eq!(
+                    builder.spqr_nodes[spqr_node_index].edges.len(),
+                    builder.spqr_nodes[spqr_node_index].nodes.len()
+                );
+                assert_eq!(builder.add_spqr_node_if_cycle(block_index), None);
+            }
+        }
+    }
+    assert!(cycle_count > 0);
+}
+
+#[derive(Default)]
+struct RecordingObserver {
+    components: Vec<(
+        ComponentIndex<u8>,
+        Vec<<TestGraph as StaticGraph>::NodeIndex>,
+    )>,
+    blocks: Vec<(BlockIndex<u8>, ComponentIndex<u8>, usize)>,
+    spqr_nodes: Vec<(SPQRNodeIndex<u8>, BlockIndex<u8>, SPQRNodeType)>,
+}
+
+impl BuilderObserver<TestGraph> for RecordingObserver {
+    fn on_component_added(
+        &mut self,
+        component: ComponentIndex<u8>,
+        nodes: &[<TestGraph as StaticGraph>::NodeIndex],
+    ) {
+        self.components.push((component, nodes.to_vec()));
+    }
+
+    fn on_block_added(
+        &mut self,
+        block: BlockIndex<u8>,
+        component: ComponentIndex<u8>,
+        nodes: &[<TestGraph as StaticGraph>::NodeIndex],
+    ) {
+        self.blocks.push((block, component, nodes.len()));
+    }
+
+    fn on_spqr_node_added(
+        &mut self,
+        spqr_node: SPQRNodeIndex<u8>,
+        block: BlockIndex<u8>,
+        spqr_node_type: SPQRNodeType,
+        _nodes: &[<TestGraph as StaticGraph>::NodeIndex],
+    ) {
+        self.spqr_nodes.push((spqr_node, block, spqr_node_type));
+    }
+}
+
+#[test]
+fn test_observer() {
+    let graph = read_graph("test_files/tiny1.gfa");
+    let mut observer = RecordingObserver::default();
+    let mut builder = SPQRDecompositionBuilder::new(&graph).with_observer(&mut observer);
+    for component_index in builder.add_components_from_graph() {
+        for block_index in builder.add_blocks_from_graph(component_index) {
+            builder.add_spqr_node_if_cycle(block_index).unwrap();
+        }
+    }
+    assert_eq!(builder.observer().spqr_nodes.len(), 5);
+
+    // Failed additions are not reported.
+    assert!(builder.try_add_block(0.into(), Vec::new()).is_err());
+    let decomposition = builder.build();
+
+    assert_eq!(
+        observer.components,
+        decomposition
+            .iter_components()
+            .map(|(index, component)| (index, component.iter_nodes().collect()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        observer.blocks,
+        decomposition
+            .iter_blocks()
+            .map(|(index, block)| (index, block.component, block.iter_nodes().count()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        observer.spqr_nodes,
+        decomposition
+            .iter_spqr_nodes()
+            .map(|(index, spqr_node)| (index, spqr_node.block(), spqr_node.spqr_node_type()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_add_self_loops_from_graph() {
+    let gfa = "H\tVN:Z:1.0\n\
+        S\ta\t*\nS\tb\t*\nS\tc\t*\nS\td\t*\nS\te\t*\n\
+        L\ta\t+\tb\t+\t0M\nL\tb\t+\tc\t+\t0M\nL\tc\t+\ta\t+\t0M\nL\tc\t+\td\t+\t0M\n\
+        L\ta\t+\ta\t+\t0M\nL\td\t+\td\t-\t0M\nL\te\t+\te\t+\t0M\n";
+    let graph: TestGraph = BidirectedAdjacencyArray::read_gfa1(gfa.as_bytes()).unwrap();
+
+    let mut builder = SPQRDecompositionBuilder::new(&graph);
+    for component_index in builder.add_components_from_graph() {
+        for block_index in builder.add_blocks_from_graph(component_index) {
+            builder.add_spqr_node_if_cycle(block_index);
+        }
+        builder.add_self_loops_from_graph(component_index);
+        builder.add_self_loops_from_graph(component_index);
+    }
+    let spqr_decomposition = builder.try_build().unwrap();
+
+    let loops = graph
+        .edge_indices()
+        .filter(|&edge_index| {
+            let (u, v) = graph.edge_endpoints(edge_index);
+            u == v
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(loops.len(), 3);
+    let (a, d, e) = (0.into(), 3.into(), 4.into());
+    for edge_index in loops {
+        let (node_index, _) = graph.edge_endpoints(edge_index);
+        let edge_data = &spqr_decomposition.edge_data[edge_index];
+        assert_eq!(
+            edge_data.component_index,
+            spqr_decomposition.node_component_index(node_index)
+        );
+        if node_index == a {
+            assert!(edge_data.spqr_node_index.is_some());
+        } else if node_index == d {
+            assert!(edge_data.block_index.is_some());
+            assert!(edge_data.spqr_node_index.is_none());
+        } else {
+            assert_eq!(node_index, e);
+            assert!(edge_data.block_index.is_none());
+        }
+    }
+}
+
+/// Adds all entities of `spqr_decomposition` to `builder`, letting the builder identify the cut nodes.
+fn replay<'graph>(
+    spqr_decomposition: &SPQRDecomposition<'_, TestGraph>,
+    mut builder: SPQRDecompositionBuilder<'graph, TestGraph>,
+) -> SPQRDecomposition<'graph, TestGraph> {
+    for (_, component) in spqr_decomposition.iter_components() {
+        builder.add_component(component.iter_nodes().collect());
+    }
+    // Edges are only added once all blocks exist, since adding a block checks the edges that are already assigned.
+    for (_, block) in spqr_decomposition.iter_blocks() {
+        builder.add_block(block.component, block.nodes.clone());
+    }
+    for (block_index, block) in spqr_decomposition.iter_blocks() {
+        for edge_index in block.iter_edges() {
+            builder.add_edge_to_block(edge_index, block_index).unwrap();
+        }
+    }
+    for (_, spqr_node) in spqr_decomposition.iter_spqr_nodes() {
+        let spqr_node_index = builder.add_spqr_node(
+            spqr_node.block(),
+            spqr_node.nodes.clone(),
+            spqr_node.spqr_node_type(),
+        );
+        for edge_index in spqr_node.iter_edges() {
+            builder
+                .add_edge_to_spqr_node(edge_index, spqr_node_index)
+                .unwrap();
+        }
+    }
+    for (_, spqr_edge) in spqr_decomposition.iter_spqr_edges() {
+        builder.add_spqr_edge(
+            OptionalBlockIndex::new_none(),
+            spqr_edge.endpoints(),
+            spqr_edge.virtual_edge(),
+        );
+    }
+    builder.build()
+}
+
+#[test]
+fn test_try_add_errors() {
+    let graph = read_graph("test_files/tiny1.gfa");
+    let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| {
+        graph
+            .node_indices()
+            .find(|&node| graph.node_name(node) == name)
+            .unwrap()
+    });
+    let edge = |u, v| graph.edges_between(u, v).next().unwrap();
+    // Failed additions may leave the builder partially modified, so each case uses a fresh builder.
+    let component_builder = || {
+        let mut builder = SPQRDecompositionBuilder::new(&graph);
+        let component = builder
+            .try_add_component(graph.node_indices().collect())
+            .unwrap();
+        (builder, component)
+    };
+
+    let mut builder = SPQRDecompositionBuilder::new(&graph);
+    assert_eq!(
+        builder.try_add_component(Vec::new()),
+        Err(AddError::TooSmall)
+    );
+    assert_eq!(
+        builder.try_add_component(vec![a, a]),
+        Err(AddError::NodeInOtherComponent(a))
+    );
+
+    let (mut builder, component) = component_builder();
+    assert_eq!(
+        builder.try_add_block(component, vec![a, b, a]),
+        Err(AddError::DuplicateNode(a))
+    );
+
+    let (mut builder, component) = component_builder();
+    let block = builder.try_add_block(component, vec![a, b, c]).unwrap();
+    assert_eq!(
+        builder.try_add_spqr_node(block, vec![a, d], SPQRNodeType::SNode),
+        Err(AddError::NodeNotInBlock { node: d, block })
+    );
+    assert_eq!(
+        builder.try_add_cut_node(c, vec![block, block]),
+        Err(AddError::DuplicateBlock(block))
+    );
+
+    let (mut builder, component) = component_builder();
+    let block = builder.try_add_block(component, vec![a, b, c]).unwrap();
+    let spqr_node = builder
+        .try_add_spqr_node(block, vec![a, b, c], SPQRNodeType::SNode)
+        .unwrap();
+    assert_eq!(
+        builder.try_add_spqr_edge(
+            OptionalBlockIndex::new_none(),
+            (spqr_node, spqr_node),
+            (a, b)
+        ),
+        Err(AddError::SPQREdgeLoop(spqr_node))
+    );
+    assert!(matches!(
+        builder.add_edge_to_block(edge(a, b), block),
+        Err(AddEdgeError::BlockHasSPQRTree)
+    ));
+    assert!(matches!(
+        builder.add_edge_to_spqr_node(edge(c, d), spqr_node),
+        Err(AddEdgeError::EndpointNotInSPQRNode)
+    ));
+    builder
+        .add_edge_to_spqr_node(edge(a, b), spqr_node)
+        .unwrap();
+}
+
+#[test]
+fn test_try_build_with_strictness() {
+    let graph = read_graph("test_files/tiny1.gfa");
+    let error = SPQRDecompositionBuilder::new(&graph)
+        .try_build_with_strictness(Strictness::Error)
+        .unwrap_err();
+    assert_eq!(
+        error.problems.len(),
+        graph.node_count() + graph.edge_count()
+    );
+
+    // Without components, the decomposition cannot be built even when only warning.
+    let error = SPQRDecompositionBuilder::new(&graph)
+        .try_build_with_strictness(Strictness::Warn)
+        .unwrap_err();
+    assert_eq!(
+        error.problems.len(),
+        graph.node_count() + graph.edge_count()
+    );
+}
+
+#[test]
+#[should_panic(expected = "the SPQR decomposition is inconsistent")]
+fn test_try_build_with_strictness_panic() {
+    let graph = read_graph("test_files/tiny1.gfa");
+    let _ = SPQRDecompositionBuilder::new(&graph).try_build_with_strictness(Strictness::Panic);
+}
+
+#[test]
+fn test_add_extra_data_to_entities() {
+    let graph = read_graph("test_files/tiny1.gfa");
+    let mut builder = SPQRDecompositionBuilder::new(&graph);
+    let component_index = builder.add_components_from_graph()[0];
+    let block_indices = builder.add_blocks_from_graph(component_index);
+    let spqr_node_index = builder.add_spqr_node_if_cycle(block_indices[0]).unwrap();
+    for block_index in &block_indices[1..] {
+        builder.add_spqr_node_if_cycle(*block_index).unwrap();
+    }
+    let cut_node_index = 0.into();
+    builder.add_extra_data_to_component(component_index, "component");
+    builder.add_extra_data_to_block(block_indices[0], "block");
+    builder.add_extra_data_to_cut_node(cut_node_index, "cut node");
+    builder.add_extra_data_to_spqr_node(spqr_node_index, "spqr node");
+    let mut spqr_decomposition = builder.build();
+
+    assert_eq!(
+        spqr_decomposition.component_extra_data(component_index),
+        "component"
+    );
+    assert_eq!(
+        spqr_decomposition.block_extra_data(block_indices[0]),
+        "block"
+    );
+    assert_eq!(spqr_decomposition.block_extra_data(block_indices[1]), "");
+    assert_eq!(
+        spqr_decomposition.cut_node_extra_data(cut_node_index),
+        "cut node"
+    );
+    assert_eq!(
+        spqr_decomposition.spqr_node_extra_data(spqr_node_index),
+        "spqr node"
+    );
+    assert_eq!(
+        spqr_decomposition.block(block_indices[0]).extra_data(),
+        "block"
+    );
+
+    // The extra data survives the binary formats.
+    let mut binary = Vec::new();
+    spqr_decomposition.write_binary(&mut binary).unwrap();
+    assert_eq!(
+        SPQRDecomposition::read_binary(&graph, &binary[..]).unwrap(),
+        spqr_decomposition
+    );
+    let mut portable = Vec::new();
+    spqr_decomposition
+        .write_portable_binary(&mut portable)
+        .unwrap();
+    let read = SPQRDecomposition::read_portable_binary(&graph, &portable[..]).unwrap();
+    assert_eq!(read.cut_node_extra_data(cut_node_index), "cut node");
+
+    spqr_decomposition.set_component_extra_data(component_index, "replaced");
+    spqr_decomposition.set_cut_node_extra_data(cut_node_index, String::new());
+    assert_eq!(
+        spqr_decomposition.component_extra_data(component_index),
+        "replaced"
+    );
+    assert_eq!(spqr_decomposition.cut_node_extra_data(cut_node_index), "");
+}
@@ -0,0 +1,83 @@
+use std::{borrow::Borrow, sync::OnceLock};
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, adjacency::SPQRTreeAdjacencyCache, indices::GraphIndexInteger,
+        node_set::NodeSetCache, rooted::RootedSPQRTrees,
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Data derived from a decomposition that is expensive to compute, but not needed by every user.
+///
+/// Each part is computed on first use through a shared reference and reused by all subsequent queries.
+/// Since all parts are stored in [`OnceLock`]s, the decomposition stays `Send + Sync`.
+#[derive(Debug, Clone)]
+pub(crate) struct DerivedDataCache<NodeIndex, IndexType: GraphIndexInteger> {
+    pub(crate) node_sets: NodeSetCache<NodeIndex>,
+    pub(crate) spqr_tree_adjacency: SPQRTreeAdjacencyCache<NodeIndex, IndexType>,
+    pub(crate) rooted_spqr_trees: OnceLock<RootedSPQRTrees<IndexType>>,
+    /// Maps the names of the nodes of the graph to their indices.
+    pub(crate) node_names: OnceLock<FxHashMap<String, NodeIndex>>,
+}
+
+impl<NodeIndex, IndexType: GraphIndexInteger> DerivedDataCache<NodeIndex, IndexType> {
+    /// Clears all parts that depend on the structure of the decomposition.
+    ///
+    /// This must be called whenever the decomposition is modified.
+    /// Parts that only depend on the graph are kept.
+    pub(crate) fn invalidate(&mut self) {
+        self.node_sets = Default::default();
+        self.spqr_tree_adjacency = Default::default();
+        self.rooted_spqr_trees = OnceLock::new();
+    }
+
+    /// Returns the amount of heap memory used by the cached node name map in bytes.
+    pub(crate) fn node_names_heap_size(&self) -> usize {
+        self.node_names.get().map_or(0, |node_names| {
+            node_names.capacity() * (size_of::<String>() + size_of::<NodeIndex>() + 1)
+                + node_names.keys().map(String::capacity).sum::<usize>()
+        })
+    }
+}
+
+impl<NodeIndex, IndexType: GraphIndexInteger> Default for DerivedDataCache<NodeIndex, IndexType> {
+    fn default() -> Self {
+        Self {
+            node_sets: Default::default(),
+            spqr_tree_adjacency: Default::default(),
+            rooted_spqr_trees: OnceLock::new(),
+            node_names: OnceLock::new(),
+        }
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the node of the graph with the given name, if any.
+    ///
+    /// A map from names to nodes is built on first access and cached afterwards.
+    pub fn node_index_by_name(&self, name: &str) -> Option<Graph::NodeIndex> {
+        self.cache
+            .node_names
+            .get_or_init(|| {
+                self.graph_ref()
+                    .node_indices()
+                    .map(|node_index| {
+                        (
+                            self.graph_ref().node_name(node_index).into_owned(),
+                            node_index,
+                        )
+                    })
+                    .collect()
+            })
+            .get(name)
+            .copied()
+    }
+}
@@ -0,0 +1,76 @@
+use std::{sync::Arc, thread};
+
+use crate::{
+    decomposition::{
+        SharedSPQRDecomposition,
+        tests::{TestGraph, read_decomposition, read_graph},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_node_index_by_name() {
+    let graph = read_graph("random5_10_False_0");
+    let spqr_decomposition = read_decomposition(&graph, "random5_10_False_0");
+
+    for node_index in graph.node_indices() {
+        assert_eq!(
+            spqr_decomposition.node_index_by_name(&graph.node_name(node_index)),
+            Some(node_index)
+        );
+    }
+    assert_eq!(spqr_decomposition.node_index_by_name("missing"), None);
+    assert!(spqr_decomposition.memory_usage().node_name_cache > 0);
+}
+
+#[test]
+fn test_concurrent_queries() {
+    let graph = Arc::new(read_graph("Ultrabubble_dataset_chr_22_G182870"));
+    let spqr_decomposition = Arc::new(
+        read_decomposition(&graph, "Ultrabubble_dataset_chr_22_G182870").into_owned(graph.clone()),
+    );
+
+    // Each thread triggers the computation of all caches, and then compares its answers to the sequential ones.
+    let query = |spqr_decomposition: &SharedSPQRDecomposition<TestGraph>| {
+        let mut answers = Vec::new();
+        for (spqr_node_index, _) in spqr_decomposition.iter_spqr_nodes() {
+            answers.push((
+                spqr_decomposition.spqr_node_node_set(spqr_node_index).len(),
+                spqr_decomposition
+                    .spqr_tree_neighbors(spqr_node_index)
+                    .len(),
+                spqr_decomposition.spqr_tree_depth(spqr_node_index),
+                spqr_decomposition.spqr_tree_lowest_common_ancestor(
+                    spqr_node_index,
+                    spqr_decomposition
+                        .spqr_tree_root(spqr_decomposition.spqr_node(spqr_node_index).block())
+                        .unwrap(),
+                ),
+            ));
+        }
+        for node_index in spqr_decomposition.iter_nodes() {
+            let name = spqr_decomposition.graph_ref().node_name(node_index);
+            assert_eq!(
+                spqr_decomposition.node_index_by_name(&name),
+                Some(node_index)
+            );
+        }
+        answers
+    };
+
+    let handles = (0..4)
+        .map(|_| {
+            let spqr_decomposition = spqr_decomposition.clone();
+            thread::spawn(move || query(&spqr_decomposition))
+        })
+        .collect::<Vec<_>>();
+    let answers = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>();
+
+    let expected = query(&spqr_decomposition);
+    for answers in answers {
+        assert_eq!(answers, expected);
+    }
+}
@@ -0,0 +1,252 @@
+use std::{borrow::Borrow, hash::Hash};
+
+use rustc_hash::FxHashMap;
+use tagged_vec::TaggedVec;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        indices::{BlockIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A representation of an SPQR decomposition that does not depend on the numbering of its components, blocks, cut nodes and SPQR nodes.
+///
+/// All lists are sorted, and SPQR edges refer to SPQR nodes by their position in the sorted list of SPQR nodes.
+/// Extra data is not part of the canonical form.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalSPQRDecomposition<NodeIndex, EdgeIndex> {
+    pub components: Vec<CanonicalComponent<NodeIndex, EdgeIndex>>,
+    pub blocks: Vec<CanonicalBlock<NodeIndex, EdgeIndex>>,
+    pub cut_nodes: Vec<NodeIndex>,
+    pub spqr_nodes: Vec<CanonicalSPQRNode<NodeIndex, EdgeIndex>>,
+    pub spqr_edges: Vec<CanonicalSPQREdge<NodeIndex>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalComponent<NodeIndex, EdgeIndex> {
+    pub nodes: Vec<NodeIndex>,
+    pub edges: Vec<EdgeIndex>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalBlock<NodeIndex, EdgeIndex> {
+    pub nodes: Vec<NodeIndex>,
+    pub edges: Vec<EdgeIndex>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalSPQRNode<NodeIndex, EdgeIndex> {
+    pub spqr_node_type: SPQRNodeType,
+    pub nodes: Vec<NodeIndex>,
+    pub edges: Vec<EdgeIndex>,
+    /// The virtual edges with their endpoints ordered.
+    pub virtual_edges: Vec<(NodeIndex, NodeIndex)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalSPQREdge<NodeIndex> {
+    /// The positions of the endpoints in the sorted list of SPQR nodes, with the smaller position first.
+    pub endpoints: (usize, usize),
+    /// The virtual edge with its endpoints ordered.
+    pub virtual_edge: (NodeIndex, NodeIndex),
+}
+
+/// The blocks and SPQR nodes that are present in only one of two decompositions, see [`SPQRDecomposition::diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecompositionDiff<IndexType> {
+    pub blocks_only_in_self: Vec<BlockIndex<IndexType>>,
+    pub blocks_only_in_other: Vec<BlockIndex<IndexType>>,
+    pub spqr_nodes_only_in_self: Vec<SPQRNodeIndex<IndexType>>,
+    pub spqr_nodes_only_in_other: Vec<SPQRNodeIndex<IndexType>>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Computes the canonical form of this decomposition.
+    pub fn canonical_form(&self) -> CanonicalSPQRDecomposition<Graph::NodeIndex, Graph::EdgeIndex> {
+        let mut components = self
+            .components
+            .iter(..)
+            .map(|(component_index, component)| CanonicalComponent {
+                nodes: sorted(component.iter_nodes()),
+                edges: sorted(self.iter_edges_in_component(component_index)),
+            })
+            .collect::<Vec<_>>();
+        components.sort_unstable();
+
+        let mut blocks = self
+            .blocks
+            .iter_indices(..)
+            .map(|block_index| self.canonical_block(block_index))
+            .collect::<Vec<_>>();
+        blocks.sort_unstable();
+
+        let cut_nodes = sorted(self.cut_nodes.iter_values().map(|cut_node| cut_node.node()));
+
+        let mut spqr_nodes = self
+            .spqr_nodes
+            .iter_indices(..)
+            .map(|spqr_node_index| (self.canonical_spqr_node(spqr_node_index), spqr_node_index))
+            .collect::<Vec<_>>();
+        spqr_nodes.sort_unstable();
+
+        let mut positions: TaggedVec<SPQRNodeIndex<Graph::IndexType>, usize> =
+            TaggedVec::from(vec![0; spqr_nodes.len()]);
+        for (position, (_, spqr_node_index)) in spqr_nodes.iter().enumerate() {
+            positions[*spqr_node_index] = position;
+        }
+
+        let mut spqr_edges = self
+            .spqr_edges
+            .iter_values()
+            .map(|spqr_edge| {
+                let (u, v) = spqr_edge.endpoints();
+                let (u, v) = (positions[u], positions[v]);
+                CanonicalSPQREdge {
+                    endpoints: (u.min(v), u.max(v)),
+                    virtual_edge: spqr_edge.ordered_virtual_edge(),
+                }
+            })
+            .collect::<Vec<_>>();
+        spqr_edges.sort_unstable();
+
+        CanonicalSPQRDecomposition {
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes: spqr_nodes
+                .into_iter()
+                .map(|(spqr_node, _)| spqr_node)
+                .collect(),
+            spqr_edges,
+        }
+    }
+
+    /// Lists the blocks and SPQR nodes that are present in only one of both decompositions, comparing them by their canonical form.
+    ///
+    /// This helps to debug discrepancies between different tools that decomposed the same graph.
+    /// Entities that occur several times with the same canonical form are matched by multiplicity.
+    /// Both decompositions must be decompositions of the same graph, otherwise the result is meaningless.
+    pub fn diff<OtherGraphStorage: Borrow<Graph>>(
+        &self,
+        other: &SPQRDecomposition<'_, Graph, OtherGraphStorage>,
+    ) -> DecompositionDiff<Graph::IndexType> {
+        let (blocks_only_in_self, blocks_only_in_other) = unmatched(
+            self.blocks
+                .iter_indices(..)
+                .map(|block_index| (self.canonical_block(block_index), block_index)),
+            other
+                .blocks
+                .iter_indices(..)
+                .map(|block_index| (other.canonical_block(block_index), block_index)),
+        );
+        let (spqr_nodes_only_in_self, spqr_nodes_only_in_other) = unmatched(
+            self.spqr_nodes.iter_indices(..).map(|spqr_node_index| {
+                (self.canonical_spqr_node(spqr_node_index), spqr_node_index)
+            }),
+            other.spqr_nodes.iter_indices(..).map(|spqr_node_index| {
+                (other.canonical_spqr_node(spqr_node_index), spqr_node_index)
+            }),
+        );
+
+        DecompositionDiff {
+            blocks_only_in_self,
+            blocks_only_in_other,
+            spqr_nodes_only_in_self,
+            spqr_nodes_only_in_other,
+        }
+    }
+
+    /// Returns true if both decompositions describe the same structure up to the numbering of their components, blocks, cut nodes and SPQR nodes.
+    ///
+    /// Both decompositions must be decompositions of the same graph, otherwise the result is meaningless.
+    /// Extra data is ignored.
+    pub fn is_equivalent_to<OtherGraphStorage: Borrow<Graph>>(
+        &self,
+        other: &SPQRDecomposition<'_, Graph, OtherGraphStorage>,
+    ) -> bool {
+        self.graph_ref().node_count() == other.graph_ref().node_count()
+            && self.graph_ref().edge_count() == other.graph_ref().edge_count()
+            && self.component_count() == other.component_count()
+            && self.block_count() == other.block_count()
+            && self.cut_node_count() == other.cut_node_count()
+            && self.spqr_node_count() == other.spqr_node_count()
+            && self.spqr_edge_count() == other.spqr_edge_count()
+            && self.canonical_form() == other.canonical_form()
+    }
+
+    fn canonical_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> CanonicalBlock<Graph::NodeIndex, Graph::EdgeIndex> {
+        CanonicalBlock {
+            nodes: sorted(self.blocks[block_index].iter_nodes()),
+            edges: sorted(self.iter_edges_in_block(block_index)),
+        }
+    }
+
+    fn canonical_spqr_node(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+    ) -> CanonicalSPQRNode<Graph::NodeIndex, Graph::EdgeIndex> {
+        let spqr_node = &self.spqr_nodes[spqr_node_index];
+        CanonicalSPQRNode {
+            spqr_node_type: spqr_node.spqr_node_type(),
+            nodes: sorted(spqr_node.iter_nodes()),
+            edges: sorted(spqr_node.iter_edges()),
+            virtual_edges: sorted(
+                spqr_node
+                    .iter_incident_spqr_edges()
+                    .map(|spqr_edge_index| self.spqr_edges[spqr_edge_index].ordered_virtual_edge()),
+            ),
+        }
+    }
+}
+
+impl<IndexType> DecompositionDiff<IndexType> {
+    /// Returns true if both decompositions have the same blocks and SPQR nodes.
+    pub fn is_empty(&self) -> bool {
+        self.blocks_only_in_self.is_empty()
+            && self.blocks_only_in_other.is_empty()
+            && self.spqr_nodes_only_in_self.is_empty()
+            && self.spqr_nodes_only_in_other.is_empty()
+    }
+}
+
+/// Matches the keys of both sides by multiplicity, and returns the indices of the unmatched entries of each side in ascending order.
+fn unmatched<Key: Hash + Eq, Index: Ord>(
+    left: impl IntoIterator<Item = (Key, Index)>,
+    right: impl IntoIterator<Item = (Key, Index)>,
+) -> (Vec<Index>, Vec<Index>) {
+    let mut unmatched_left = FxHashMap::<Key, Vec<Index>>::default();
+    for (key, index) in left {
+        unmatched_left.entry(key).or_default().push(index);
+    }
+
+    let mut only_in_right = Vec::new();
+    for (key, index) in right {
+        if let Some(indices) = unmatched_left.get_mut(&key)
+            && indices.pop().is_some()
+        {
+            continue;
+        }
+        only_in_right.push(index);
+    }
+
+    (
+        sorted(unmatched_left.into_values().flatten()),
+        sorted(only_in_right),
+    )
+}
+
+fn sorted<T: Ord>(iter: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut result = iter.into_iter().collect::<Vec<_>>();
+    result.sort_unstable();
+    result
+}
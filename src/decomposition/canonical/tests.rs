@@ -0,0 +1,66 @@
+use std::fs;
+
+use crate::decomposition::{
+    SPQRDecomposition, SPQRNodeType,
+    tests::{read_decomposition, read_graph},
+};
+
+#[test]
+fn test_is_equivalent_to() {
+    let graph = read_graph("tiny1");
+    let spqr_decomposition = read_decomposition(&graph, "tiny1");
+
+    // Declare the blocks and SPQR nodes in reverse order.
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let lines = spqr_decomposition_file.lines().collect::<Vec<_>>();
+    let mut reordered = lines
+        .iter()
+        .filter(|line| line.starts_with(['H', 'G']))
+        .copied()
+        .collect::<Vec<_>>();
+    reordered.extend(lines.iter().rev().filter(|line| line.starts_with('B')));
+    reordered.extend(lines.iter().filter(|line| line.starts_with('C')));
+    let mut spqr_node_groups = Vec::new();
+    for line in lines.iter().filter(|line| line.starts_with(['S', 'E'])) {
+        if line.starts_with('S') {
+            spqr_node_groups.push(Vec::new());
+        }
+        spqr_node_groups.last_mut().unwrap().push(*line);
+    }
+    reordered.extend(spqr_node_groups.into_iter().rev().flatten());
+    let reordered = reordered.join("\n") + "\n";
+    let reordered_spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, reordered.as_bytes()).unwrap();
+
+    assert_ne!(spqr_decomposition, reordered_spqr_decomposition);
+    assert!(spqr_decomposition.is_equivalent_to(&reordered_spqr_decomposition));
+
+    let mut modified = reordered_spqr_decomposition.clone();
+    let (spqr_node_index, _) = modified.iter_spqr_nodes().next().unwrap();
+    modified.spqr_nodes[spqr_node_index].spqr_node_type = SPQRNodeType::RNode;
+    assert!(!spqr_decomposition.is_equivalent_to(&modified));
+}
+
+#[test]
+fn test_diff() {
+    let graph = read_graph("multiedge");
+    let spqr_decomposition = read_decomposition(&graph, "multiedge");
+    assert!(
+        spqr_decomposition
+            .diff(&spqr_decomposition.clone())
+            .is_empty()
+    );
+
+    let mut modified = spqr_decomposition.clone();
+    let merged = modified.contract_spqr_edge(0.into());
+    let diff = spqr_decomposition.diff(&modified);
+    assert!(!diff.is_empty());
+    assert!(diff.blocks_only_in_self.is_empty());
+    assert!(diff.blocks_only_in_other.is_empty());
+    assert_eq!(diff.spqr_nodes_only_in_self, [0.into(), 1.into()]);
+    assert_eq!(diff.spqr_nodes_only_in_other, [merged]);
+
+    let diff = modified.diff(&spqr_decomposition);
+    assert_eq!(diff.spqr_nodes_only_in_self, [merged]);
+    assert_eq!(diff.spqr_nodes_only_in_other, [0.into(), 1.into()]);
+}
@@ -0,0 +1,230 @@
+//! Enumerating the chordless cycles of the graph along the SPQR trees.
+//!
+//! Like any cycle of a block, a chordless cycle corresponds to a cycle in the skeleton of each SPQR node it passes through,
+//! in which each virtual edge is replaced by a path between its poles through the SPQR nodes on the other side.
+//! Each cycle is built from the skeleton of the first of these SPQR nodes, by only expanding virtual edges into later SPQR nodes.
+//! The skeleton of an S-node is a single cycle, and a P-node has only pairs of skeleton edges as cycles,
+//! so only the skeletons of R-nodes require searching for cycles and paths.
+
+use std::borrow::Borrow;
+
+use rustc_hash::FxHashSet;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns an iterator over the chordless cycles of the graph, i.e. the cycles of at least three nodes
+    /// that have no edge between two nodes that are not consecutive on the cycle.
+    ///
+    /// Each cycle is returned once as the sequence of its nodes, starting at an arbitrary node and in an arbitrary direction.
+    /// Parallel edges and self-loops are ignored.
+    ///
+    /// The candidate cycles are built per skeleton as described in the [module documentation](self), and those with a chord are filtered out.
+    /// The number of chordless cycles, and of candidates, can be exponential in the size of the graph.
+    pub fn iter_chordless_cycles(&self) -> impl Iterator<Item = Vec<Graph::NodeIndex>> {
+        let mut cycles = Vec::new();
+        for (spqr_node_index, spqr_node) in self.iter_spqr_nodes() {
+            let edges = self.simple_skeleton_edges(spqr_node_index, None);
+            let skeleton_cycles = match spqr_node.spqr_node_type {
+                SPQRNodeType::SNode => vec![
+                    self.s_node_cycle(spqr_node_index)
+                        .into_iter()
+                        .map(|(skeleton_edge, node_index)| {
+                            let position = edges
+                                .iter()
+                                .position(|&(edge, _, _)| edge == skeleton_edge)
+                                .unwrap();
+                            (position, node_index)
+                        })
+                        .collect(),
+                ],
+                SPQRNodeType::PNode | SPQRNodeType::RNode => (0..edges.len())
+                    .flat_map(|first| {
+                        let (_, u, v) = edges[first];
+                        skeleton_paths(&edges, v, u, |position| position > first)
+                            .into_iter()
+                            .map(move |mut path| {
+                                path.insert(0, (first, u));
+                                path
+                            })
+                    })
+                    .collect(),
+            };
+
+            for skeleton_cycle in skeleton_cycles {
+                for mut cycle in self.expand_skeleton_path(
+                    spqr_node_index,
+                    spqr_node_index,
+                    &edges,
+                    &skeleton_cycle,
+                ) {
+                    cycle.pop();
+                    if cycle.len() >= 3 && self.is_chordless(&cycle) {
+                        cycles.push(cycle);
+                    }
+                }
+            }
+        }
+        cycles.into_iter()
+    }
+
+    /// Returns the skeleton edges of the given SPQR node with their endpoints,
+    /// without self-loops, without the excluded virtual edge, and with only one of each set of parallel real edges.
+    #[expect(clippy::type_complexity)]
+    fn simple_skeleton_edges(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        excluded: Option<SPQREdgeIndex<Graph::IndexType>>,
+    ) -> Vec<(
+        SkeletonEdge<Graph::EdgeIndex, Graph::IndexType>,
+        Graph::NodeIndex,
+        Graph::NodeIndex,
+    )> {
+        let mut edges: Vec<(SkeletonEdge<_, _>, _, _)> = Vec::new();
+        for (skeleton_edge, (u, v)) in self.iter_skeleton_edges(spqr_node_index) {
+            let is_parallel_real_edge = matches!(skeleton_edge, SkeletonEdge::Real(_))
+                && edges.iter().any(|&(other, a, b)| {
+                    matches!(other, SkeletonEdge::Real(_)) && ((a, b) == (u, v) || (a, b) == (v, u))
+                });
+            if u != v
+                && Some(skeleton_edge) != excluded.map(SkeletonEdge::Virtual)
+                && !is_parallel_real_edge
+            {
+                edges.push((skeleton_edge, u, v));
+            }
+        }
+        edges
+    }
+
+    /// Returns all node sequences of paths in the graph that correspond to the given path in the skeleton of the given SPQR node,
+    /// given as positions in `edges` together with the node each edge is traversed from.
+    ///
+    /// Each virtual edge is replaced by each path between its poles on its other side that passes only through SPQR nodes after `first`.
+    #[expect(clippy::type_complexity)]
+    fn expand_skeleton_path(
+        &self,
+        first: SPQRNodeIndex<Graph::IndexType>,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        edges: &[(
+            SkeletonEdge<Graph::EdgeIndex, Graph::IndexType>,
+            Graph::NodeIndex,
+            Graph::NodeIndex,
+        )],
+        skeleton_path: &[(usize, Graph::NodeIndex)],
+    ) -> Vec<Vec<Graph::NodeIndex>> {
+        let mut paths = vec![vec![skeleton_path[0].1]];
+        for &(position, from) in skeleton_path {
+            let (skeleton_edge, u, v) = edges[position];
+            let to = if from == u { v } else { u };
+            let pieces = match skeleton_edge {
+                SkeletonEdge::Real(_) => vec![vec![from, to]],
+                SkeletonEdge::Virtual(spqr_edge_index) => {
+                    self.pole_paths(first, spqr_node_index, spqr_edge_index, from, to)
+                }
+            };
+            paths = paths
+                .iter()
+                .flat_map(|path| {
+                    pieces.iter().map(move |piece| {
+                        let mut path = path.clone();
+                        path.extend_from_slice(&piece[1..]);
+                        path
+                    })
+                })
+                .collect();
+        }
+        paths
+    }
+
+    /// Returns the node sequences of all paths from `from` to `to` through the SPQR nodes on the other side of the given virtual edge,
+    /// as seen from the given SPQR node, that pass only through SPQR nodes after `first`.
+    fn pole_paths(
+        &self,
+        first: SPQRNodeIndex<Graph::IndexType>,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+        from: Graph::NodeIndex,
+        to: Graph::NodeIndex,
+    ) -> Vec<Vec<Graph::NodeIndex>> {
+        let (a, b) = self.spqr_edges[spqr_edge_index].endpoints;
+        let other = if a == spqr_node_index { b } else { a };
+        if other < first {
+            return Vec::new();
+        }
+        let edges = self.simple_skeleton_edges(other, Some(spqr_edge_index));
+        skeleton_paths(&edges, from, to, |_| true)
+            .into_iter()
+            .flat_map(|skeleton_path| {
+                self.expand_skeleton_path(first, other, &edges, &skeleton_path)
+            })
+            .collect()
+    }
+
+    /// Returns true if the graph has no edge between two nodes of the given cycle that are not consecutive on it.
+    fn is_chordless(&self, cycle: &[Graph::NodeIndex]) -> bool {
+        let nodes = cycle.iter().copied().collect::<FxHashSet<_>>();
+        let mut adjacent_pairs = FxHashSet::default();
+        for &node_index in cycle {
+            for edge_index in self.graph_ref().incident_edges(node_index) {
+                let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+                if u != v && nodes.contains(&u) && nodes.contains(&v) {
+                    adjacent_pairs.insert((u.min(v), u.max(v)));
+                }
+            }
+        }
+        adjacent_pairs.len() == cycle.len()
+    }
+}
+
+/// Returns all simple paths from `from` to `to` over the allowed edges,
+/// each as the positions of its edges together with the node each edge is traversed from.
+fn skeleton_paths<EdgeType, NodeIndex: Copy + Eq>(
+    edges: &[(EdgeType, NodeIndex, NodeIndex)],
+    from: NodeIndex,
+    to: NodeIndex,
+    allowed: impl Fn(usize) -> bool,
+) -> Vec<Vec<(usize, NodeIndex)>> {
+    let mut paths = Vec::new();
+    let mut path = Vec::new();
+    let mut visited = vec![from];
+    // Each stack entry is the next edge position to try from the current end of the path.
+    let mut stack = vec![0];
+    while let Some(next) = stack.last_mut() {
+        let node_index = *visited.last().unwrap();
+        let Some(position) = (*next..edges.len()).find(|&position| {
+            let (_, u, v) = edges[position];
+            allowed(position)
+                && (u == node_index || v == node_index)
+                && !visited.contains(&if u == node_index { v } else { u })
+        }) else {
+            stack.pop();
+            visited.pop();
+            path.pop();
+            continue;
+        };
+        *next = position + 1;
+
+        let (_, u, v) = edges[position];
+        let neighbor = if u == node_index { v } else { u };
+        path.push((position, node_index));
+        if neighbor == to {
+            paths.push(path.clone());
+            path.pop();
+        } else {
+            visited.push(neighbor);
+            stack.push(0);
+        }
+    }
+    paths
+}
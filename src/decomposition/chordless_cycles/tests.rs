@@ -0,0 +1,65 @@
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{TestGraph, all_test_inputs},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_iter_chordless_cycles() {
+    /// Finds the node sets that induce a cycle.
+    fn brute_force_chordless_cycles(graph: &TestGraph) -> Vec<Vec<usize>> {
+        let node_count = graph.node_count();
+        let mut adjacent = vec![vec![false; node_count]; node_count];
+        for edge_index in graph.edge_indices() {
+            let (u, v) = graph.edge_endpoints(edge_index);
+            let (u, v) = (usize::from(u), usize::from(v));
+            if u != v {
+                adjacent[u][v] = true;
+                adjacent[v][u] = true;
+            }
+        }
+
+        (1..1usize << node_count)
+            .filter(|subset| subset.count_ones() >= 3)
+            .filter_map(|subset| {
+                let nodes = (0..node_count)
+                    .filter(|&node| subset & 1 << node != 0)
+                    .collect::<Vec<_>>();
+                let is_cycle = nodes
+                    .iter()
+                    .all(|&u| nodes.iter().filter(|&&v| adjacent[u][v]).count() == 2)
+                    && {
+                        let mut visited = vec![nodes[0]];
+                        while let Some(&next) = nodes.iter().find(|&&v| {
+                            !visited.contains(&v) && adjacent[*visited.last().unwrap()][v]
+                        }) {
+                            visited.push(next);
+                        }
+                        visited.len() == nodes.len()
+                    };
+                is_cycle.then_some(nodes)
+            })
+            .collect()
+    }
+
+    let graphs = all_test_inputs();
+
+    for (graph, spqr_decomposition_file) in &graphs {
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(graph, spqr_decomposition_file.as_bytes()).unwrap();
+        let mut cycles = spqr_decomposition
+            .iter_chordless_cycles()
+            .map(|cycle| {
+                let mut nodes = cycle.into_iter().map(usize::from).collect::<Vec<_>>();
+                nodes.sort_unstable();
+                nodes
+            })
+            .collect::<Vec<_>>();
+        cycles.sort_unstable();
+        let mut expected = brute_force_chordless_cycles(graph);
+        expected.sort_unstable();
+        assert_eq!(cycles, expected);
+    }
+}
@@ -0,0 +1,102 @@
+//! Answering queries about the nodes of a graph from the decomposition of its [condensed graph](CondensedGraph).
+//!
+//! Contracting the chains of nodes of degree two into single edges does not change the block-cut trees or the SPQR trees,
+//! except that each chain is represented by a single edge.
+//! Hence, a node that was kept belongs to the same blocks and SPQR nodes as its condensed node,
+//! and a node that was contracted belongs to the block and the SPQR node of its chain edge,
+//! where it is a cut node exactly if the chain edge is a bridge.
+
+use std::borrow::Borrow;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        indices::{BlockIndex, ComponentIndex, SPQRNodeIndex},
+    },
+    graph::{
+        StaticGraph,
+        condensed::{CondensedGraph, CondensedNode},
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, 'parent, Graph: StaticGraph, GraphStorage: Borrow<CondensedGraph<'parent, Graph>>>
+    SPQRDecomposition<'graph, CondensedGraph<'parent, Graph>, GraphStorage>
+{
+    /// Returns the component of the given node of the parent graph.
+    pub fn original_node_component_index(
+        &self,
+        parent_node_index: Graph::NodeIndex,
+    ) -> ComponentIndex<Graph::IndexType> {
+        match self.graph_ref().condensed_node(parent_node_index) {
+            CondensedNode::Node(node_index) => self.node_component_index(node_index),
+            CondensedNode::Chain { edge, .. } => self.edge_data[edge].component_index,
+        }
+    }
+
+    /// Returns the blocks of the given node of the parent graph.
+    pub fn original_node_block_indices(
+        &self,
+        parent_node_index: Graph::NodeIndex,
+    ) -> impl Iterator<Item = BlockIndex<Graph::IndexType>> {
+        let (node_index, edge_index) = self.split_condensed_node(parent_node_index);
+        node_index
+            .into_iter()
+            .flat_map(|node_index| self.node_block_indices(node_index))
+            .chain(
+                edge_index
+                    .and_then(|edge_index| self.edge_data[edge_index].block_index.into_option()),
+            )
+    }
+
+    /// Returns the SPQR nodes whose skeletons contain the given node of the parent graph,
+    /// or for a contracted node, the SPQR node that contains its chain edge.
+    pub fn original_node_spqr_node_indices(
+        &self,
+        parent_node_index: Graph::NodeIndex,
+    ) -> impl Iterator<Item = SPQRNodeIndex<Graph::IndexType>> {
+        let (node_index, edge_index) = self.split_condensed_node(parent_node_index);
+        node_index
+            .into_iter()
+            .flat_map(|node_index| self.node_spqr_node_indices(node_index))
+            .chain(
+                edge_index.and_then(|edge_index| {
+                    self.edge_data[edge_index].spqr_node_index.into_option()
+                }),
+            )
+    }
+
+    /// Returns true if removing the given node of the parent graph disconnects its component.
+    pub fn is_original_cut_node(&self, parent_node_index: Graph::NodeIndex) -> bool {
+        match self.graph_ref().condensed_node(parent_node_index) {
+            CondensedNode::Node(node_index) => self.is_cut_node(node_index),
+            CondensedNode::Chain { edge, .. } => self.edge_data[edge]
+                .block_index
+                .into_option()
+                .is_some_and(|block_index| {
+                    self.blocks[block_index].nodes.len() == 2
+                        && self
+                            .iter_edges_in_block(block_index)
+                            .filter(|&edge_index| {
+                                let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+                                u != v
+                            })
+                            .count()
+                            == 1
+                }),
+        }
+    }
+
+    /// Returns the condensed node of a kept node, or the chain edge of a contracted node.
+    fn split_condensed_node(
+        &self,
+        parent_node_index: Graph::NodeIndex,
+    ) -> (Option<Graph::NodeIndex>, Option<Graph::EdgeIndex>) {
+        match self.graph_ref().condensed_node(parent_node_index) {
+            CondensedNode::Node(node_index) => (Some(node_index), None),
+            CondensedNode::Chain { edge, .. } => (None, Some(edge)),
+        }
+    }
+}
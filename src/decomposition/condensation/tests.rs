@@ -0,0 +1,125 @@
+use std::io::BufReader;
+
+use bidirected_adjacency_array::graph::BidirectedAdjacencyArray;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        builder::SPQRDecompositionBuilder,
+        tests::{TestGraph, read_subdivided_wheel},
+    },
+    graph::{
+        StaticGraph,
+        condensed::{CondensedGraph, CondensedNode},
+    },
+};
+
+#[test]
+fn test_condensed_graph() {
+    fn node(graph: &TestGraph, name: &str) -> <TestGraph as StaticGraph>::NodeIndex {
+        graph
+            .node_indices()
+            .find(|&node_index| graph.node_name(node_index) == name)
+            .unwrap()
+    }
+
+    // A triangle with a pendant path, and a separate cycle.
+    let gfa = "H\tVN:Z:1.0\nS\ta\tA\nS\tb\tA\nS\tc\tA\nS\td\tA\nS\te\tA\nS\tf\tA\n\
+        S\tp\tA\nS\tq\tA\nS\tr\tA\nS\ts\tA\n\
+        L\ta\t+\tb\t+\t0M\nL\tb\t+\tc\t+\t0M\nL\tc\t+\ta\t+\t0M\n\
+        L\tc\t+\td\t+\t0M\nL\td\t+\te\t+\t0M\nL\te\t+\tf\t+\t0M\n\
+        L\tp\t+\tq\t+\t0M\nL\tq\t+\tr\t+\t0M\nL\tr\t+\ts\t+\t0M\nL\ts\t+\tp\t+\t0M\n";
+    let graph = BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+    let condensed = CondensedGraph::new(&graph);
+    assert_eq!(condensed.node_count(), 5);
+    assert_eq!(condensed.edge_count(), 5);
+    assert_eq!(
+        condensed
+            .edge_indices()
+            .map(|edge_index| condensed.edge_weight(edge_index))
+            .sum::<usize>(),
+        graph.edge_count(),
+    );
+    for edge_index in graph.edge_indices() {
+        let condensed_edge = condensed.condensed_edge(edge_index);
+        assert!(condensed.chain_edges(condensed_edge).contains(&edge_index));
+    }
+    let CondensedNode::Chain { edge, position } = condensed.condensed_node(node(&graph, "e"))
+    else {
+        panic!("e is not contracted");
+    };
+    assert_eq!(condensed.chain_nodes(edge)[position], node(&graph, "e"));
+    assert_eq!(condensed.edge_weight(edge), 3);
+    for name in ["c", "f"] {
+        let CondensedNode::Node(node_index) = condensed.condensed_node(node(&graph, name)) else {
+            panic!("{name} is contracted");
+        };
+        assert_eq!(condensed.parent_node(node_index), node(&graph, name));
+    }
+
+    let mut builder = SPQRDecompositionBuilder::new(&condensed);
+    for component_index in builder.add_components_from_graph() {
+        builder.add_blocks_from_graph(component_index);
+    }
+    let spqr_decomposition = builder.build();
+    assert_eq!(spqr_decomposition.block_count(), 3);
+    for (name, is_cut_node) in [
+        ("a", false),
+        ("b", false),
+        ("c", true),
+        ("d", true),
+        ("e", true),
+        ("f", false),
+        ("p", false),
+        ("q", false),
+    ] {
+        assert_eq!(
+            spqr_decomposition.is_original_cut_node(node(&graph, name)),
+            is_cut_node,
+            "{name}",
+        );
+    }
+    let blocks = |name| {
+        spqr_decomposition
+            .original_node_block_indices(node(&graph, name))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(blocks("a"), blocks("b"));
+    assert_eq!(blocks("d"), blocks("e"));
+    assert_eq!(blocks("c").len(), 2);
+    assert!(blocks("c").contains(&blocks("a")[0]));
+    assert!(blocks("c").contains(&blocks("d")[0]));
+    assert_eq!(
+        spqr_decomposition.original_node_component_index(node(&graph, "q")),
+        spqr_decomposition.original_node_component_index(node(&graph, "s")),
+    );
+    assert_ne!(
+        spqr_decomposition.original_node_component_index(node(&graph, "a")),
+        spqr_decomposition.original_node_component_index(node(&graph, "s")),
+    );
+
+    // The subdivided wheel condenses to a wheel, whose R-node contains the subdividing node.
+    let (graph, _) = read_subdivided_wheel();
+    let condensed = CondensedGraph::new(&graph);
+    assert_eq!(condensed.node_count(), 5);
+    let spqr_decomposition_file = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 h 1 2 3 4\nB B0 G0 h 1 2 3 4\nR R0 B0 h 1 2 3 4\n\
+        E E0 R0 h 1\nE E1 R0 h 2\nE E2 R0 h 3\nE E3 R0 h 4\nE E4 R0 2 3\nE E5 R0 3 4\nE E6 R0 4 1\n\
+        E E7 R0 1 2\n";
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&condensed, spqr_decomposition_file.as_bytes()).unwrap();
+    let r_node = spqr_decomposition.iter_spqr_nodes().next().unwrap().0;
+    assert_eq!(
+        spqr_decomposition
+            .original_node_spqr_node_indices(node(&graph, "x"))
+            .collect::<Vec<_>>(),
+        [r_node],
+    );
+    assert_eq!(
+        spqr_decomposition
+            .original_node_spqr_node_indices(node(&graph, "h"))
+            .collect::<Vec<_>>(),
+        [r_node],
+    );
+    assert!(!spqr_decomposition.is_original_cut_node(node(&graph, "x")));
+}
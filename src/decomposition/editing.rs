@@ -0,0 +1,160 @@
+use std::borrow::Borrow;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        indices::{SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Contracts the given SPQR edge, merging the skeletons of its endpoints across their shared virtual edge.
+    ///
+    /// The merged SPQR node keeps the smaller of the two indices and is returned.
+    /// Merging two S-nodes results in an S-node and merging two P-nodes results in a P-node.
+    /// Any other merged skeleton is neither a cycle nor a bond, so it is marked as an R-node, even though it is not necessarily triconnected.
+    ///
+    /// To keep the indices consecutive, the last SPQR node takes over the index of the removed SPQR node,
+    /// and the last SPQR edge takes over the index of the contracted SPQR edge.
+    /// The extra data of the removed SPQR node is discarded.
+    pub fn contract_spqr_edge(
+        &mut self,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+    ) -> SPQRNodeIndex<Graph::IndexType> {
+        // The node sets, neighbours and ancestors of SPQR nodes change, so the cached ones become invalid.
+        self.cache.invalidate();
+
+        let spqr_edge = &self.spqr_edges[spqr_edge_index];
+        let kept = spqr_edge.min_endpoint();
+        let removed = spqr_edge.max_endpoint();
+        debug_assert_ne!(kept, removed);
+        let block_index = self.spqr_nodes[kept].block;
+
+        // Merge the removed SPQR node into the kept one.
+        let removed_spqr_node = &mut self.spqr_nodes[removed];
+        let removed_nodes = std::mem::take(&mut removed_spqr_node.nodes);
+        let removed_edges = std::mem::take(&mut removed_spqr_node.edges);
+        let removed_spqr_edges = std::mem::take(&mut removed_spqr_node.spqr_edges);
+        let removed_spqr_node_type = removed_spqr_node.spqr_node_type;
+
+        let kept_spqr_node = &mut self.spqr_nodes[kept];
+        kept_spqr_node.spqr_node_type =
+            match (kept_spqr_node.spqr_node_type, removed_spqr_node_type) {
+                (SPQRNodeType::SNode, SPQRNodeType::SNode) => SPQRNodeType::SNode,
+                (SPQRNodeType::PNode, SPQRNodeType::PNode) => SPQRNodeType::PNode,
+                _ => SPQRNodeType::RNode,
+            };
+        kept_spqr_node
+            .spqr_edges
+            .retain(|other_spqr_edge_index| *other_spqr_edge_index != spqr_edge_index);
+        kept_spqr_node.edges.extend(removed_edges.iter().copied());
+
+        for node_index in removed_nodes {
+            let spqr_node_indices = &mut self.node_data[node_index].spqr_node_indices;
+            spqr_node_indices.retain(|spqr_node_index| *spqr_node_index != removed);
+            if !spqr_node_indices.contains(&kept) {
+                spqr_node_indices.push(kept);
+                self.spqr_nodes[kept].nodes.push(node_index);
+            }
+        }
+        for edge_index in removed_edges {
+            self.edge_data[edge_index].spqr_node_index = kept.into();
+        }
+        for other_spqr_edge_index in removed_spqr_edges {
+            if other_spqr_edge_index == spqr_edge_index {
+                continue;
+            }
+
+            self.replace_spqr_edge_endpoint(other_spqr_edge_index, removed, kept);
+            self.spqr_nodes[kept].spqr_edges.push(other_spqr_edge_index);
+        }
+
+        let block = &mut self.blocks[block_index];
+        block
+            .spqr_nodes
+            .retain(|spqr_node_index| *spqr_node_index != removed);
+        block
+            .spqr_edges
+            .retain(|other_spqr_edge_index| *other_spqr_edge_index != spqr_edge_index);
+
+        // Remove the contracted SPQR edge and the removed SPQR node.
+        self.swap_remove_spqr_edge(spqr_edge_index);
+        self.swap_remove_spqr_node(removed);
+
+        kept
+    }
+
+    fn replace_spqr_edge_endpoint(
+        &mut self,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+        old: SPQRNodeIndex<Graph::IndexType>,
+        new: SPQRNodeIndex<Graph::IndexType>,
+    ) {
+        let endpoints = &mut self.spqr_edges[spqr_edge_index].endpoints;
+        if endpoints.0 == old {
+            endpoints.0 = new;
+        } else {
+            debug_assert_eq!(endpoints.1, old);
+            endpoints.1 = new;
+        }
+    }
+
+    /// Removes an SPQR edge that is not referenced anymore, moving the last SPQR edge into its place.
+    fn swap_remove_spqr_edge(&mut self, spqr_edge_index: SPQREdgeIndex<Graph::IndexType>) {
+        let (last_index, last) = self.spqr_edges.pop().unwrap();
+        if last_index == spqr_edge_index {
+            return;
+        }
+
+        for spqr_node_index in [last.endpoints.0, last.endpoints.1] {
+            for other_spqr_edge_index in &mut self.spqr_nodes[spqr_node_index].spqr_edges {
+                if *other_spqr_edge_index == last_index {
+                    *other_spqr_edge_index = spqr_edge_index;
+                }
+            }
+        }
+        let block_index = self.spqr_nodes[last.endpoints.0].block;
+        for other_spqr_edge_index in &mut self.blocks[block_index].spqr_edges {
+            if *other_spqr_edge_index == last_index {
+                *other_spqr_edge_index = spqr_edge_index;
+            }
+        }
+
+        self.spqr_edges[spqr_edge_index] = last;
+    }
+
+    /// Removes an SPQR node that is not referenced anymore, moving the last SPQR node into its place.
+    fn swap_remove_spqr_node(&mut self, spqr_node_index: SPQRNodeIndex<Graph::IndexType>) {
+        let (last_index, last) = self.spqr_nodes.pop().unwrap();
+        if last_index == spqr_node_index {
+            return;
+        }
+
+        for &spqr_edge_index in &last.spqr_edges {
+            self.replace_spqr_edge_endpoint(spqr_edge_index, last_index, spqr_node_index);
+        }
+        for &node_index in &last.nodes {
+            for other_spqr_node_index in &mut self.node_data[node_index].spqr_node_indices {
+                if *other_spqr_node_index == last_index {
+                    *other_spqr_node_index = spqr_node_index;
+                }
+            }
+        }
+        for &edge_index in &last.edges {
+            self.edge_data[edge_index].spqr_node_index = spqr_node_index.into();
+        }
+        for other_spqr_node_index in &mut self.blocks[last.block].spqr_nodes {
+            if *other_spqr_node_index == last_index {
+                *other_spqr_node_index = spqr_node_index;
+            }
+        }
+
+        self.spqr_nodes[spqr_node_index] = last;
+    }
+}
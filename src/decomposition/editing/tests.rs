@@ -0,0 +1,62 @@
+use crate::decomposition::{
+    SPQRNodeType,
+    tests::{assert_spqr_tables_consistent, read_decomposition, read_graph},
+};
+
+#[test]
+fn test_contract_spqr_edge() {
+    let graph = read_graph("multiedge");
+    let mut spqr_decomposition = read_decomposition(&graph, "multiedge");
+    let (spqr_edge_index, _) = spqr_decomposition.iter_spqr_edges().next().unwrap();
+    let merged = spqr_decomposition.contract_spqr_edge(spqr_edge_index);
+    assert_spqr_tables_consistent(&spqr_decomposition);
+    assert_eq!(spqr_decomposition.spqr_node_count(), 1);
+    assert_eq!(spqr_decomposition.spqr_edge_count(), 0);
+    let spqr_node = spqr_decomposition.spqr_node(merged);
+    assert_eq!(spqr_node.spqr_node_type(), SPQRNodeType::RNode);
+    assert_eq!(spqr_node.node_count(), 3);
+    assert_eq!(spqr_node.real_edge_count(), 4);
+
+    for name in [
+        "tiny1",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = read_graph(name);
+        let mut spqr_decomposition = read_decomposition(&graph, name);
+
+        // Merge all S-chains first.
+        loop {
+            let Some(spqr_edge_index) = spqr_decomposition
+                .iter_spqr_edges()
+                .find(|(_, spqr_edge)| {
+                    let (u, v) = spqr_edge.endpoints();
+                    spqr_decomposition.spqr_node(u).is_s_node()
+                        && spqr_decomposition.spqr_node(v).is_s_node()
+                })
+                .map(|(spqr_edge_index, _)| spqr_edge_index)
+            else {
+                break;
+            };
+            let merged = spqr_decomposition.contract_spqr_edge(spqr_edge_index);
+            assert!(spqr_decomposition.spqr_node(merged).is_s_node());
+            assert_spqr_tables_consistent(&spqr_decomposition);
+        }
+
+        while spqr_decomposition.spqr_edge_count() > 0 {
+            spqr_decomposition.contract_spqr_edge(0.into());
+            assert_spqr_tables_consistent(&spqr_decomposition);
+        }
+        for (_, block) in spqr_decomposition.iter_blocks() {
+            assert!(block.spqr_node_count() <= 1);
+            if block.spqr_node_count() == 1 {
+                assert_eq!(
+                    spqr_decomposition
+                        .spqr_node(block.spqr_nodes[0])
+                        .node_count(),
+                    block.node_count()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Choosing a planar embedding of a block by a user-defined objective.
+//!
+//! The SPQR tree of a planar block describes all of its planar embeddings:
+//! the branches of each P-node can be permuted freely, and the skeleton of each R-node has a unique embedding up to mirroring.
+//! The skeletons of S-nodes are cycles and admit only one embedding.
+//!
+//! The decomposition does not store a reference embedding of the R-node skeletons,
+//! so an [`Embedding`] only records for each R-node whether its skeleton is mirrored relative to a reference chosen by the caller.
+
+use std::borrow::Borrow;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Up to this amount of embeddings, [`SPQRDecomposition::choose_embedding`] enumerates all of them.
+const EXHAUSTIVE_SEARCH_LIMIT: usize = 1 << 16;
+
+/// A planar embedding of a block, given by the choices at its P-nodes and R-nodes,
+/// see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Embedding<EdgeIndex, IndexType> {
+    /// The cyclic order of the skeleton edges around the first pole of each P-node.
+    pub p_node_orders: Vec<(
+        SPQRNodeIndex<IndexType>,
+        Vec<SkeletonEdge<EdgeIndex, IndexType>>,
+    )>,
+    /// Whether the skeleton of each R-node is mirrored.
+    pub r_node_flips: Vec<(SPQRNodeIndex<IndexType>, bool)>,
+}
+
+impl<EdgeIndex, IndexType: Eq> Embedding<EdgeIndex, IndexType> {
+    /// Returns the cyclic order of the skeleton edges around the first pole of the given P-node,
+    /// or `None` if the SPQR node is not a P-node of the embedded block.
+    pub fn p_node_order(
+        &self,
+        p_node: SPQRNodeIndex<IndexType>,
+    ) -> Option<&[SkeletonEdge<EdgeIndex, IndexType>]> {
+        self.p_node_orders
+            .iter()
+            .find(|(spqr_node_index, _)| *spqr_node_index == p_node)
+            .map(|(_, order)| order.as_slice())
+    }
+
+    /// Returns true if the skeleton of the given R-node is mirrored.
+    pub fn is_flipped(&self, r_node: SPQRNodeIndex<IndexType>) -> bool {
+        self.r_node_flips
+            .iter()
+            .any(|(spqr_node_index, flipped)| *spqr_node_index == r_node && *flipped)
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the embedding of the given block with the highest score.
+    ///
+    /// If the block has at most 65536 embeddings, all of them are scored.
+    /// Otherwise, a local search starts from the embedding with sorted P-node branches and no mirrored R-nodes,
+    /// and moves to the first neighbouring embedding with a higher score until there is none.
+    /// Neighbouring embeddings differ by swapping two branches of one P-node, or by mirroring one R-node.
+    /// Ties are broken in favour of the embedding that was scored first.
+    ///
+    /// Rotations of the cyclic orders around P-nodes do not change the embedding,
+    /// so the first skeleton edge of each P-node is always first in its order.
+    pub fn choose_embedding<Score: PartialOrd>(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        mut score: impl FnMut(&Embedding<Graph::EdgeIndex, Graph::IndexType>) -> Score,
+    ) -> Embedding<Graph::EdgeIndex, Graph::IndexType> {
+        let spqr_nodes = &self.blocks[block_index].spqr_nodes;
+        let mut embedding = Embedding {
+            p_node_orders: spqr_nodes
+                .iter()
+                .filter(|&&spqr_node_index| {
+                    self.spqr_nodes[spqr_node_index].spqr_node_type == SPQRNodeType::PNode
+                })
+                .map(|&spqr_node_index| {
+                    let mut order = self.spqr_nodes[spqr_node_index]
+                        .iter_skeleton_edges()
+                        .collect::<Vec<_>>();
+                    order[1..].sort_unstable();
+                    (spqr_node_index, order)
+                })
+                .collect(),
+            r_node_flips: spqr_nodes
+                .iter()
+                .filter(|&&spqr_node_index| {
+                    self.spqr_nodes[spqr_node_index].spqr_node_type == SPQRNodeType::RNode
+                })
+                .map(|&spqr_node_index| (spqr_node_index, false))
+                .collect(),
+        };
+
+        let embedding_count = embedding
+            .p_node_orders
+            .iter()
+            .map(|(_, order)| (1..order.len()).fold(1usize, usize::saturating_mul))
+            .chain(embedding.r_node_flips.iter().map(|_| 2))
+            .fold(1usize, usize::saturating_mul);
+
+        let mut best_score = score(&embedding);
+        if embedding_count <= EXHAUSTIVE_SEARCH_LIMIT {
+            let mut best = embedding.clone();
+            while next_embedding(&mut embedding) {
+                let current_score = score(&embedding);
+                if current_score > best_score {
+                    best_score = current_score;
+                    best = embedding.clone();
+                }
+            }
+            best
+        } else {
+            'search: loop {
+                for p_node in 0..embedding.p_node_orders.len() {
+                    let order_len = embedding.p_node_orders[p_node].1.len();
+                    for i in 1..order_len {
+                        for j in i + 1..order_len {
+                            embedding.p_node_orders[p_node].1.swap(i, j);
+                            let current_score = score(&embedding);
+                            if current_score > best_score {
+                                best_score = current_score;
+                                continue 'search;
+                            }
+                            embedding.p_node_orders[p_node].1.swap(i, j);
+                        }
+                    }
+                }
+                for r_node in 0..embedding.r_node_flips.len() {
+                    embedding.r_node_flips[r_node].1 ^= true;
+                    let current_score = score(&embedding);
+                    if current_score > best_score {
+                        best_score = current_score;
+                        continue 'search;
+                    }
+                    embedding.r_node_flips[r_node].1 ^= true;
+                }
+                break embedding;
+            }
+        }
+    }
+}
+
+/// Advances to the next embedding like an odometer, or returns false if all embeddings have been visited.
+///
+/// The orders of P-nodes are advanced to their next permutation, keeping the first skeleton edge in place.
+fn next_embedding<EdgeIndex: Ord, IndexType: Ord>(
+    embedding: &mut Embedding<EdgeIndex, IndexType>,
+) -> bool {
+    for (_, order) in &mut embedding.p_node_orders {
+        if next_permutation(&mut order[1..]) {
+            return true;
+        }
+    }
+    for (_, flipped) in &mut embedding.r_node_flips {
+        *flipped ^= true;
+        if *flipped {
+            return true;
+        }
+    }
+    false
+}
+
+/// Rearranges the slice into the lexicographically next permutation.
+/// If there is none, the slice is sorted and false is returned.
+fn next_permutation<T: Ord>(slice: &mut [T]) -> bool {
+    let Some(pivot) = slice.windows(2).rposition(|pair| pair[0] < pair[1]) else {
+        slice.reverse();
+        return false;
+    };
+    let successor = slice.iter().rposition(|item| *item > slice[pivot]).unwrap();
+    slice.swap(pivot, successor);
+    slice[pivot + 1..].reverse();
+    true
+}
@@ -0,0 +1,55 @@
+use std::io::BufReader;
+
+use bidirected_adjacency_array::graph::BidirectedAdjacencyArray;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SkeletonEdge,
+        tests::{TestGraph, read_subdivided_wheel},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_choose_embedding() {
+    // Three parallel paths and an edge between u and v.
+    let gfa = "H\tVN:Z:1.0\nS\tu\tA\nS\tv\tA\nS\ta\tA\nS\tb\tA\nS\tc\tA\n\
+        L\tu\t+\tv\t+\t0M\nL\tu\t+\ta\t+\t0M\nL\ta\t+\tv\t+\t0M\n\
+        L\tu\t+\tb\t+\t0M\nL\tb\t+\tv\t+\t0M\nL\tu\t+\tc\t+\t0M\nL\tc\t+\tv\t+\t0M\n";
+    let graph: TestGraph =
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+    let spqr_decomposition_file = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 u v a b c\nB B0 G0 u v a b c\n\
+        P P0 B0 u v\nS S1 B0 u a v\nS S2 B0 u b v\nS S3 B0 u c v\n\
+        V V0 P0 S1 u v\nV V1 P0 S2 u v\nV V2 P0 S3 u v\n\
+        E E0 P0 u v\nE E1 S1 u a\nE E2 S1 a v\nE E3 S2 u b\nE E4 S2 b v\nE E5 S3 u c\nE E6 S3 c v\n";
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+    // Prefer the virtual edges in descending order.
+    let mut scored = Vec::new();
+    let embedding = spqr_decomposition.choose_embedding(0.into(), |embedding| {
+        scored.push(embedding.clone());
+        let order = embedding.p_node_order(0.into()).unwrap();
+        order.windows(2).filter(|pair| pair[0] > pair[1]).count()
+    });
+    assert_eq!(scored.len(), 6);
+    assert_eq!(
+        embedding.p_node_order(0.into()).unwrap(),
+        [
+            SkeletonEdge::Real(spqr_decomposition.graph().edge_indices().next().unwrap()),
+            SkeletonEdge::Virtual(2.into()),
+            SkeletonEdge::Virtual(1.into()),
+            SkeletonEdge::Virtual(0.into()),
+        ]
+    );
+    assert!(embedding.r_node_flips.is_empty());
+
+    let (graph, spqr_decomposition_file) = read_subdivided_wheel();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let embedding =
+        spqr_decomposition.choose_embedding(0.into(), |embedding| embedding.is_flipped(0.into()));
+    assert!(embedding.p_node_orders.is_empty());
+    assert!(embedding.is_flipped(0.into()));
+}
@@ -0,0 +1,109 @@
+//! Shared storage for the extra data of the entities of a decomposition.
+
+use std::{fmt::Display, ops::Deref, sync::Arc};
+
+use rustc_hash::FxHashSet;
+
+/// The extra data of an entity of a decomposition.
+///
+/// Extra data is immutable and reference-counted, such that entities with identical extra data can share a single allocation,
+/// see [`ExtraDataPool`].
+/// Empty extra data does not allocate.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExtraData(Option<Arc<str>>);
+
+/// Deduplicates identical extra data strings while reading a decomposition.
+///
+/// Files often repeat a handful of annotations for millions of entities, which then share a single allocation each.
+#[derive(Debug, Default)]
+pub struct ExtraDataPool {
+    strings: FxHashSet<Arc<str>>,
+}
+
+impl ExtraData {
+    pub fn as_str(&self) -> &str {
+        self.0.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the address of the shared allocation, or `None` if the extra data is empty.
+    pub(crate) fn allocation(&self) -> Option<*const u8> {
+        self.0.as_ref().map(|string| string.as_ptr())
+    }
+
+    /// Returns the size of the shared allocation in bytes, including the reference counts.
+    pub(crate) fn heap_size(&self) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |string| 2 * size_of::<usize>() + string.len())
+    }
+}
+
+impl ExtraDataPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns extra data with the given content, sharing the allocation with earlier extra data of the same content.
+    pub fn intern(&mut self, string: &str) -> ExtraData {
+        if string.is_empty() {
+            return ExtraData::default();
+        }
+
+        if let Some(shared) = self.strings.get(string) {
+            ExtraData(Some(shared.clone()))
+        } else {
+            let shared: Arc<str> = Arc::from(string);
+            self.strings.insert(shared.clone());
+            ExtraData(Some(shared))
+        }
+    }
+
+    /// The amount of distinct non-empty strings in the pool.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl Deref for ExtraData {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for ExtraData {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for ExtraData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for ExtraData {
+    fn from(string: &str) -> Self {
+        if string.is_empty() {
+            Self::default()
+        } else {
+            Self(Some(Arc::from(string)))
+        }
+    }
+}
+
+impl From<String> for ExtraData {
+    fn from(string: String) -> Self {
+        if string.is_empty() {
+            Self::default()
+        } else {
+            Self(Some(Arc::from(string)))
+        }
+    }
+}
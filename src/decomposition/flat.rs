@@ -0,0 +1,738 @@
+//! A read-only representation of an SPQR decomposition that stores all membership lists in a few shared arenas.
+//!
+//! In an [`SPQRDecomposition`], every component, block and SPQR node owns its lists of nodes, edges etc.,
+//! which results in millions of small allocations for large graphs.
+//! A [`FlatSPQRDecomposition`] instead stores all lists of the same kind consecutively in one arena,
+//! and each entity only stores the range of its entries.
+//! It offers the same accessors as [`SPQRDecomposition`], but returns lightweight views instead of references to the entities.
+
+use std::{borrow::Borrow, marker::PhantomData, ops::Range};
+
+use tagged_vec::TaggedVec;
+
+use crate::{
+    decomposition::{
+        Block, Component, CutNode, SPQRDecomposition, SPQRDecompositionEdgeData,
+        SPQRDecompositionNodeData, SPQREdge, SPQRNode, SPQRNodeType, SkeletonEdge,
+        extra_data::ExtraData,
+        indices::{BlockIndex, ComponentIndex, CutNodeIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// An SPQR decomposition whose membership lists are stored in shared arenas.
+///
+/// Created with [`SPQRDecomposition::flatten`] and converted back with [`FlatSPQRDecomposition::unflatten`].
+#[derive(Debug, Clone)]
+pub struct FlatSPQRDecomposition<
+    'graph,
+    Graph: StaticGraph,
+    GraphStorage: Borrow<Graph> = &'graph Graph,
+> {
+    graph: GraphStorage,
+    graph_lifetime: PhantomData<&'graph Graph>,
+    components: TaggedVec<ComponentIndex<Graph::IndexType>, FlatComponentData>,
+    blocks: TaggedVec<BlockIndex<Graph::IndexType>, FlatBlockData<Graph::IndexType>>,
+    cut_nodes:
+        TaggedVec<CutNodeIndex<Graph::IndexType>, CutNode<Graph::NodeIndex, Graph::IndexType>>,
+    spqr_nodes: TaggedVec<SPQRNodeIndex<Graph::IndexType>, FlatSPQRNodeData<Graph::IndexType>>,
+    spqr_edges:
+        TaggedVec<SPQREdgeIndex<Graph::IndexType>, SPQREdge<Graph::NodeIndex, Graph::IndexType>>,
+    node_data: TaggedVec<Graph::NodeIndex, SPQRDecompositionNodeData<Graph::IndexType>>,
+    edge_data: TaggedVec<Graph::EdgeIndex, SPQRDecompositionEdgeData<Graph::IndexType>>,
+    arenas: Arenas<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+}
+
+/// The arenas holding the membership lists of all entities.
+#[derive(Debug, Clone)]
+struct Arenas<NodeIndex, EdgeIndex, IndexType> {
+    nodes: Vec<NodeIndex>,
+    edges: Vec<EdgeIndex>,
+    blocks: Vec<BlockIndex<IndexType>>,
+    cut_nodes: Vec<CutNodeIndex<IndexType>>,
+    spqr_nodes: Vec<SPQRNodeIndex<IndexType>>,
+    spqr_edges: Vec<SPQREdgeIndex<IndexType>>,
+}
+
+/// The `(offset, len)` range of a list inside an arena.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ArenaRange {
+    offset: usize,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct FlatComponentData {
+    nodes: ArenaRange,
+    edges: ArenaRange,
+    blocks: ArenaRange,
+    cut_nodes: ArenaRange,
+    extra_data: ExtraData,
+}
+
+#[derive(Debug, Clone)]
+struct FlatBlockData<IndexType> {
+    component: ComponentIndex<IndexType>,
+    nodes: ArenaRange,
+    cut_nodes: ArenaRange,
+    edges: ArenaRange,
+    spqr_nodes: ArenaRange,
+    spqr_edges: ArenaRange,
+    extra_data: ExtraData,
+}
+
+#[derive(Debug, Clone)]
+struct FlatSPQRNodeData<IndexType> {
+    block: BlockIndex<IndexType>,
+    nodes: ArenaRange,
+    edges: ArenaRange,
+    spqr_node_type: SPQRNodeType,
+    spqr_edges: ArenaRange,
+    extra_data: ExtraData,
+}
+
+/// A view of a component of a [`FlatSPQRDecomposition`], offering the same accessors as [`Component`].
+#[derive(Debug, Clone, Copy)]
+pub struct FlatComponent<'a, NodeIndex, EdgeIndex, IndexType> {
+    data: &'a FlatComponentData,
+    arenas: &'a Arenas<NodeIndex, EdgeIndex, IndexType>,
+}
+
+/// A view of a block of a [`FlatSPQRDecomposition`], offering the same accessors as [`Block`].
+#[derive(Debug, Clone, Copy)]
+pub struct FlatBlock<'a, NodeIndex, EdgeIndex, IndexType> {
+    data: &'a FlatBlockData<IndexType>,
+    arenas: &'a Arenas<NodeIndex, EdgeIndex, IndexType>,
+}
+
+/// A view of an SPQR node of a [`FlatSPQRDecomposition`], offering the same accessors as [`SPQRNode`].
+#[derive(Debug, Clone, Copy)]
+pub struct FlatSPQRNode<'a, NodeIndex, EdgeIndex, IndexType> {
+    data: &'a FlatSPQRNodeData<IndexType>,
+    arenas: &'a Arenas<NodeIndex, EdgeIndex, IndexType>,
+}
+
+impl ArenaRange {
+    /// Appends the given list to the arena and returns its range.
+    fn push<T>(arena: &mut Vec<T>, list: impl IntoIterator<Item = T>) -> Self {
+        let offset = arena.len();
+        arena.extend(list);
+        Self {
+            offset,
+            len: arena.len() - offset,
+        }
+    }
+
+    fn range(self) -> Range<usize> {
+        self.offset..self.offset + self.len
+    }
+
+    fn get<T>(self, arena: &[T]) -> &[T] {
+        &arena[self.range()]
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Converts this decomposition into a [`FlatSPQRDecomposition`], which stores all membership lists in shared arenas.
+    ///
+    /// All indices stay the same.
+    pub fn flatten(self) -> FlatSPQRDecomposition<'graph, Graph, GraphStorage> {
+        let mut arenas = Arenas {
+            nodes: Vec::with_capacity(
+                self.components
+                    .iter_values()
+                    .map(|component| component.nodes.len())
+                    .sum::<usize>()
+                    + self
+                        .blocks
+                        .iter_values()
+                        .map(|block| block.nodes.len())
+                        .sum::<usize>()
+                    + self
+                        .spqr_nodes
+                        .iter_values()
+                        .map(|spqr_node| spqr_node.nodes.len())
+                        .sum::<usize>(),
+            ),
+            edges: Vec::with_capacity(self.edge_data.len()),
+            blocks: Vec::with_capacity(self.blocks.len()),
+            cut_nodes: Vec::new(),
+            spqr_nodes: Vec::with_capacity(self.spqr_nodes.len()),
+            spqr_edges: Vec::with_capacity(3 * self.spqr_edges.len()),
+        };
+
+        let components = self
+            .components
+            .into_values_iter()
+            .map(|component| FlatComponentData {
+                nodes: ArenaRange::push(&mut arenas.nodes, component.nodes),
+                edges: ArenaRange::push(&mut arenas.edges, component.edges),
+                blocks: ArenaRange::push(&mut arenas.blocks, component.blocks),
+                cut_nodes: ArenaRange::push(&mut arenas.cut_nodes, component.cut_nodes),
+                extra_data: component.extra_data,
+            })
+            .collect();
+        let blocks = self
+            .blocks
+            .into_values_iter()
+            .map(|block| FlatBlockData {
+                component: block.component,
+                nodes: ArenaRange::push(&mut arenas.nodes, block.nodes),
+                cut_nodes: ArenaRange::push(&mut arenas.cut_nodes, block.cut_nodes),
+                edges: ArenaRange::push(&mut arenas.edges, block.edges),
+                spqr_nodes: ArenaRange::push(&mut arenas.spqr_nodes, block.spqr_nodes),
+                spqr_edges: ArenaRange::push(&mut arenas.spqr_edges, block.spqr_edges),
+                extra_data: block.extra_data,
+            })
+            .collect();
+        let spqr_nodes = self
+            .spqr_nodes
+            .into_values_iter()
+            .map(|spqr_node| FlatSPQRNodeData {
+                block: spqr_node.block,
+                nodes: ArenaRange::push(&mut arenas.nodes, spqr_node.nodes),
+                edges: ArenaRange::push(&mut arenas.edges, spqr_node.edges),
+                spqr_node_type: spqr_node.spqr_node_type,
+                spqr_edges: ArenaRange::push(&mut arenas.spqr_edges, spqr_node.spqr_edges),
+                extra_data: spqr_node.extra_data,
+            })
+            .collect();
+
+        FlatSPQRDecomposition {
+            graph: self.graph,
+            graph_lifetime: PhantomData,
+            components,
+            blocks,
+            cut_nodes: self.cut_nodes,
+            spqr_nodes,
+            spqr_edges: self.spqr_edges,
+            node_data: self.node_data,
+            edge_data: self.edge_data,
+            arenas,
+        }
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    FlatSPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Converts this decomposition back into an [`SPQRDecomposition`].
+    ///
+    /// All indices stay the same.
+    pub fn unflatten(self) -> SPQRDecomposition<'graph, Graph, GraphStorage> {
+        let arenas = &self.arenas;
+
+        SPQRDecomposition {
+            graph: self.graph,
+            graph_lifetime: PhantomData,
+            components: self
+                .components
+                .into_values_iter()
+                .map(|component| Component {
+                    nodes: component.nodes.get(&arenas.nodes).to_vec(),
+                    edges: component.edges.get(&arenas.edges).to_vec(),
+                    blocks: component.blocks.get(&arenas.blocks).to_vec(),
+                    cut_nodes: component.cut_nodes.get(&arenas.cut_nodes).to_vec(),
+                    extra_data: component.extra_data,
+                })
+                .collect(),
+            blocks: self
+                .blocks
+                .into_values_iter()
+                .map(|block| Block {
+                    component: block.component,
+                    nodes: block.nodes.get(&arenas.nodes).to_vec(),
+                    cut_nodes: block.cut_nodes.get(&arenas.cut_nodes).to_vec(),
+                    edges: block.edges.get(&arenas.edges).to_vec(),
+                    spqr_nodes: block.spqr_nodes.get(&arenas.spqr_nodes).to_vec(),
+                    spqr_edges: block.spqr_edges.get(&arenas.spqr_edges).to_vec(),
+                    extra_data: block.extra_data,
+                })
+                .collect(),
+            cut_nodes: self.cut_nodes,
+            spqr_nodes: self
+                .spqr_nodes
+                .into_values_iter()
+                .map(|spqr_node| SPQRNode {
+                    block: spqr_node.block,
+                    nodes: spqr_node.nodes.get(&arenas.nodes).to_vec(),
+                    edges: spqr_node.edges.get(&arenas.edges).to_vec(),
+                    spqr_node_type: spqr_node.spqr_node_type,
+                    spqr_edges: spqr_node.spqr_edges.get(&arenas.spqr_edges).into(),
+                    extra_data: spqr_node.extra_data,
+                })
+                .collect(),
+            spqr_edges: self.spqr_edges,
+            node_data: self.node_data,
+            edge_data: self.edge_data,
+            cache: Default::default(),
+        }
+    }
+
+    pub fn graph(&self) -> &Graph {
+        self.graph.borrow()
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn cut_node_count(&self) -> usize {
+        self.cut_nodes.len()
+    }
+
+    pub fn spqr_node_count(&self) -> usize {
+        self.spqr_nodes.len()
+    }
+
+    pub fn spqr_edge_count(&self) -> usize {
+        self.spqr_edges.len()
+    }
+
+    pub fn iter_component_indices(&self) -> impl Iterator<Item = ComponentIndex<Graph::IndexType>> {
+        self.components.iter_indices(..)
+    }
+
+    pub fn iter_components(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            ComponentIndex<Graph::IndexType>,
+            FlatComponent<'_, Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.components
+            .iter_indices(..)
+            .map(|component_index| (component_index, self.component(component_index)))
+    }
+
+    pub fn iter_blocks(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            BlockIndex<Graph::IndexType>,
+            FlatBlock<'_, Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.blocks
+            .iter_indices(..)
+            .map(|block_index| (block_index, self.block(block_index)))
+    }
+
+    pub fn iter_cut_nodes(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            CutNodeIndex<Graph::IndexType>,
+            &CutNode<Graph::NodeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.cut_nodes.iter(..)
+    }
+
+    pub fn iter_spqr_nodes(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            SPQRNodeIndex<Graph::IndexType>,
+            FlatSPQRNode<'_, Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.spqr_nodes
+            .iter_indices(..)
+            .map(|spqr_node_index| (spqr_node_index, self.spqr_node(spqr_node_index)))
+    }
+
+    pub fn iter_spqr_edges(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            SPQREdgeIndex<Graph::IndexType>,
+            &SPQREdge<Graph::NodeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.spqr_edges.iter(..)
+    }
+
+    pub fn iter_blocks_in_component(
+        &self,
+        component_index: ComponentIndex<Graph::IndexType>,
+    ) -> impl Iterator<
+        Item = (
+            BlockIndex<Graph::IndexType>,
+            FlatBlock<'_, Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.components[component_index]
+            .blocks
+            .get(&self.arenas.blocks)
+            .iter()
+            .map(|&block_index| (block_index, self.block(block_index)))
+    }
+
+    pub fn iter_spqr_nodes_in_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> impl Iterator<
+        Item = (
+            SPQRNodeIndex<Graph::IndexType>,
+            FlatSPQRNode<'_, Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.blocks[block_index]
+            .spqr_nodes
+            .get(&self.arenas.spqr_nodes)
+            .iter()
+            .map(|&spqr_node_index| (spqr_node_index, self.spqr_node(spqr_node_index)))
+    }
+
+    pub fn iter_spqr_edges_in_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> impl Iterator<
+        Item = (
+            SPQREdgeIndex<Graph::IndexType>,
+            &SPQREdge<Graph::NodeIndex, Graph::IndexType>,
+        ),
+    > {
+        self.blocks[block_index]
+            .spqr_edges
+            .get(&self.arenas.spqr_edges)
+            .iter()
+            .map(|&spqr_edge_index| (spqr_edge_index, &self.spqr_edges[spqr_edge_index]))
+    }
+
+    /// Returns an iterator over all edges of the graph that belong to the given block.
+    ///
+    /// Each edge is returned exactly once, regardless of whether it is stored in the block itself or in one of its SPQR nodes.
+    pub fn iter_edges_in_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> impl Iterator<Item = Graph::EdgeIndex> {
+        let block = self.block(block_index);
+        block.iter_edges().chain(
+            block
+                .iter_spqr_nodes()
+                .flat_map(|spqr_node_index| self.spqr_node(spqr_node_index).iter_edges()),
+        )
+    }
+
+    /// Returns an iterator over all edges of the graph that belong to the given component.
+    ///
+    /// Each edge is returned exactly once, regardless of whether it is stored in the component itself, in one of its blocks or in one of its SPQR nodes.
+    pub fn iter_edges_in_component(
+        &self,
+        component_index: ComponentIndex<Graph::IndexType>,
+    ) -> impl Iterator<Item = Graph::EdgeIndex> {
+        let component = self.component(component_index);
+        component.iter_edges().chain(
+            component
+                .iter_blocks()
+                .flat_map(|block_index| self.iter_edges_in_block(block_index)),
+        )
+    }
+
+    pub fn iter_nodes(&self) -> impl Iterator<Item = Graph::NodeIndex> {
+        self.graph().node_indices()
+    }
+
+    pub fn node_extra_data(&self, node_index: Graph::NodeIndex) -> &str {
+        &self.node_data[node_index].extra_data
+    }
+
+    pub fn edge_extra_data(&self, edge_index: Graph::EdgeIndex) -> &str {
+        &self.edge_data[edge_index].extra_data
+    }
+
+    pub fn spqr_node_name(&self, spqr_node_index: SPQRNodeIndex<Graph::IndexType>) -> String {
+        let spqr_node_type = self.spqr_nodes[spqr_node_index].spqr_node_type;
+        format!("{spqr_node_type}{spqr_node_index}")
+    }
+
+    pub fn node_component_index(
+        &self,
+        node_index: Graph::NodeIndex,
+    ) -> ComponentIndex<Graph::IndexType> {
+        self.node_data[node_index].component_index
+    }
+
+    pub fn node_block_indices(
+        &self,
+        node_index: Graph::NodeIndex,
+    ) -> impl Iterator<Item = BlockIndex<Graph::IndexType>> {
+        self.node_data[node_index].block_indices.iter().copied()
+    }
+
+    pub fn node_spqr_node_indices(
+        &self,
+        node_index: Graph::NodeIndex,
+    ) -> impl Iterator<Item = SPQRNodeIndex<Graph::IndexType>> {
+        self.node_data[node_index].spqr_node_indices.iter().copied()
+    }
+
+    pub fn component(
+        &self,
+        component_index: ComponentIndex<Graph::IndexType>,
+    ) -> FlatComponent<'_, Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType> {
+        FlatComponent {
+            data: &self.components[component_index],
+            arenas: &self.arenas,
+        }
+    }
+
+    pub fn block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> FlatBlock<'_, Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType> {
+        FlatBlock {
+            data: &self.blocks[block_index],
+            arenas: &self.arenas,
+        }
+    }
+
+    pub fn spqr_node(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+    ) -> FlatSPQRNode<'_, Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType> {
+        FlatSPQRNode {
+            data: &self.spqr_nodes[spqr_node_index],
+            arenas: &self.arenas,
+        }
+    }
+
+    pub fn cut_node(
+        &self,
+        cut_node_index: CutNodeIndex<Graph::IndexType>,
+    ) -> &CutNode<Graph::NodeIndex, Graph::IndexType> {
+        &self.cut_nodes[cut_node_index]
+    }
+
+    /// Returns true if the given node is a cut node.
+    pub fn is_cut_node(&self, node_index: Graph::NodeIndex) -> bool {
+        self.node_data[node_index].cut_node_index.is_some()
+    }
+
+    pub fn spqr_edge(
+        &self,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+    ) -> &SPQREdge<Graph::NodeIndex, Graph::IndexType> {
+        &self.spqr_edges[spqr_edge_index]
+    }
+}
+
+impl<'a, NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy>
+    FlatComponent<'a, NodeIndex, EdgeIndex, IndexType>
+{
+    pub fn node_count(&self) -> usize {
+        self.data.nodes.len
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.data.edges.len
+    }
+
+    pub fn block_count(&self) -> usize {
+        self.data.blocks.len
+    }
+
+    pub fn iter_nodes(
+        &self,
+    ) -> impl Iterator<Item = NodeIndex> + use<'a, NodeIndex, EdgeIndex, IndexType> {
+        self.data.nodes.get(&self.arenas.nodes).iter().copied()
+    }
+
+    pub fn iter_edges(
+        &self,
+    ) -> impl Iterator<Item = EdgeIndex> + use<'a, NodeIndex, EdgeIndex, IndexType> {
+        self.data.edges.get(&self.arenas.edges).iter().copied()
+    }
+
+    pub fn iter_blocks(
+        &self,
+    ) -> impl Iterator<Item = BlockIndex<IndexType>> + use<'a, NodeIndex, EdgeIndex, IndexType>
+    {
+        self.data.blocks.get(&self.arenas.blocks).iter().copied()
+    }
+
+    pub fn iter_cut_nodes(
+        &self,
+    ) -> impl Iterator<Item = CutNodeIndex<IndexType>> + use<'a, NodeIndex, EdgeIndex, IndexType>
+    {
+        self.data
+            .cut_nodes
+            .get(&self.arenas.cut_nodes)
+            .iter()
+            .copied()
+    }
+
+    pub fn extra_data(&self) -> &'a str {
+        &self.data.extra_data
+    }
+}
+
+impl<'a, NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy>
+    FlatBlock<'a, NodeIndex, EdgeIndex, IndexType>
+{
+    pub fn component(&self) -> ComponentIndex<IndexType> {
+        self.data.component
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.data.nodes.len
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.data.edges.len
+    }
+
+    pub fn spqr_node_count(&self) -> usize {
+        self.data.spqr_nodes.len
+    }
+
+    pub fn iter_nodes(
+        &self,
+    ) -> impl Iterator<Item = NodeIndex> + use<'a, NodeIndex, EdgeIndex, IndexType> {
+        self.data.nodes.get(&self.arenas.nodes).iter().copied()
+    }
+
+    pub fn iter_cut_nodes(
+        &self,
+    ) -> impl Iterator<Item = CutNodeIndex<IndexType>> + use<'a, NodeIndex, EdgeIndex, IndexType>
+    {
+        self.data
+            .cut_nodes
+            .get(&self.arenas.cut_nodes)
+            .iter()
+            .copied()
+    }
+
+    pub fn iter_edges(
+        &self,
+    ) -> impl Iterator<Item = EdgeIndex> + use<'a, NodeIndex, EdgeIndex, IndexType> {
+        self.data.edges.get(&self.arenas.edges).iter().copied()
+    }
+
+    pub fn iter_spqr_nodes(
+        &self,
+    ) -> impl Iterator<Item = SPQRNodeIndex<IndexType>> + use<'a, NodeIndex, EdgeIndex, IndexType>
+    {
+        self.data
+            .spqr_nodes
+            .get(&self.arenas.spqr_nodes)
+            .iter()
+            .copied()
+    }
+
+    pub fn iter_spqr_edges(
+        &self,
+    ) -> impl Iterator<Item = SPQREdgeIndex<IndexType>> + use<'a, NodeIndex, EdgeIndex, IndexType>
+    {
+        self.data
+            .spqr_edges
+            .get(&self.arenas.spqr_edges)
+            .iter()
+            .copied()
+    }
+
+    pub fn extra_data(&self) -> &'a str {
+        &self.data.extra_data
+    }
+}
+
+impl<'a, NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy>
+    FlatSPQRNode<'a, NodeIndex, EdgeIndex, IndexType>
+{
+    pub fn node_count(&self) -> usize {
+        self.data.nodes.len
+    }
+
+    /// The amount of real edges in the SPQR node, also known as Q-nodes.
+    pub fn real_edge_count(&self) -> usize {
+        self.data.edges.len
+    }
+
+    /// The amount of virtual edges in the SPQR node.
+    pub fn virtual_edge_count(&self) -> usize {
+        self.data.spqr_edges.len
+    }
+
+    /// The amount of edges in the skeleton graph of the SPQR node.
+    pub fn skeleton_edge_count(&self) -> usize {
+        self.real_edge_count() + self.virtual_edge_count()
+    }
+
+    pub fn block(&self) -> BlockIndex<IndexType> {
+        self.data.block
+    }
+
+    pub fn iter_nodes(
+        &self,
+    ) -> impl Iterator<Item = NodeIndex> + use<'a, NodeIndex, EdgeIndex, IndexType> {
+        self.data.nodes.get(&self.arenas.nodes).iter().copied()
+    }
+
+    pub fn p_node_poles(&self) -> Option<(NodeIndex, NodeIndex)> {
+        if self.data.spqr_node_type == SPQRNodeType::PNode {
+            let nodes = self.data.nodes.get(&self.arenas.nodes);
+            Some((nodes[0], nodes[1]))
+        } else {
+            None
+        }
+    }
+
+    pub fn iter_edges(
+        &self,
+    ) -> impl Iterator<Item = EdgeIndex> + use<'a, NodeIndex, EdgeIndex, IndexType> {
+        self.data.edges.get(&self.arenas.edges).iter().copied()
+    }
+
+    pub fn spqr_node_type(&self) -> SPQRNodeType {
+        self.data.spqr_node_type
+    }
+
+    pub fn iter_incident_spqr_edges(
+        &self,
+    ) -> impl Iterator<Item = SPQREdgeIndex<IndexType>> + use<'a, NodeIndex, EdgeIndex, IndexType>
+    {
+        self.data
+            .spqr_edges
+            .get(&self.arenas.spqr_edges)
+            .iter()
+            .copied()
+    }
+
+    /// Returns an iterator over the edges of the skeleton graph, i.e. the real edges followed by the virtual edges.
+    pub fn iter_skeleton_edges(
+        &self,
+    ) -> impl Iterator<Item = SkeletonEdge<EdgeIndex, IndexType>>
+    + use<'a, NodeIndex, EdgeIndex, IndexType> {
+        self.iter_edges()
+            .map(SkeletonEdge::Real)
+            .chain(self.iter_incident_spqr_edges().map(SkeletonEdge::Virtual))
+    }
+
+    pub fn is_s_node(&self) -> bool {
+        self.data.spqr_node_type == SPQRNodeType::SNode
+    }
+
+    pub fn is_p_node(&self) -> bool {
+        self.data.spqr_node_type == SPQRNodeType::PNode
+    }
+
+    pub fn is_r_node(&self) -> bool {
+        self.data.spqr_node_type == SPQRNodeType::RNode
+    }
+
+    pub fn extra_data(&self) -> &'a str {
+        &self.data.extra_data
+    }
+}
@@ -0,0 +1,56 @@
+use crate::decomposition::tests::{read_decomposition, read_graph};
+
+#[test]
+fn test_flatten() {
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = read_graph(name);
+        let spqr_decomposition = read_decomposition(&graph, name);
+        let flat = spqr_decomposition.clone().flatten();
+
+        assert_eq!(flat.component_count(), spqr_decomposition.component_count());
+        assert_eq!(flat.block_count(), spqr_decomposition.block_count());
+        assert_eq!(flat.cut_node_count(), spqr_decomposition.cut_node_count());
+        assert_eq!(flat.spqr_node_count(), spqr_decomposition.spqr_node_count());
+        assert_eq!(flat.spqr_edge_count(), spqr_decomposition.spqr_edge_count());
+
+        for (component_index, component) in flat.iter_components() {
+            let expected = spqr_decomposition.component(component_index);
+            assert!(component.iter_nodes().eq(expected.iter_nodes()));
+            assert!(component.iter_cut_nodes().eq(expected.iter_cut_nodes()));
+            assert!(
+                flat.iter_edges_in_component(component_index)
+                    .eq(spqr_decomposition.iter_edges_in_component(component_index))
+            );
+        }
+        for (block_index, block) in flat.iter_blocks() {
+            let expected = spqr_decomposition.block(block_index);
+            assert!(block.iter_nodes().eq(expected.iter_nodes()));
+            assert!(block.iter_edges().eq(expected.iter_edges()));
+            assert!(
+                flat.iter_spqr_nodes_in_block(block_index)
+                    .map(|(spqr_node_index, _)| spqr_node_index)
+                    .eq(spqr_decomposition
+                        .iter_spqr_nodes_in_block(block_index)
+                        .map(|(spqr_node_index, _)| spqr_node_index))
+            );
+        }
+        for (spqr_node_index, spqr_node) in flat.iter_spqr_nodes() {
+            let expected = spqr_decomposition.spqr_node(spqr_node_index);
+            assert_eq!(spqr_node.spqr_node_type(), expected.spqr_node_type());
+            assert!(spqr_node.iter_nodes().eq(expected.iter_nodes()));
+            assert!(
+                spqr_node
+                    .iter_skeleton_edges()
+                    .eq(expected.iter_skeleton_edges())
+            );
+            assert_eq!(spqr_node.p_node_poles(), expected.p_node_poles());
+        }
+
+        assert_eq!(flat.unflatten(), spqr_decomposition);
+    }
+}
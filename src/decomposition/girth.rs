@@ -0,0 +1,219 @@
+//! Computing the girth of the graph along the SPQR trees.
+//!
+//! Each cycle of a block corresponds to a cycle in the skeleton of exactly one SPQR node,
+//! in which its virtual edges are replaced by paths between their poles through the rest of the block.
+//! Hence the girth of a block is the smallest girth of its skeletons,
+//! when weighting each virtual edge with the length of a shortest path between its poles on the other side of the virtual edge.
+//! These lengths are computed for both sides of each virtual edge by rerooting the SPQR tree.
+
+use std::{borrow::Borrow, cmp::Reverse, collections::BinaryHeap};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the length of a shortest cycle of the graph, or `None` if the graph has no cycle.
+    ///
+    /// Self-loops are cycles of length one, and parallel edges are cycles of length two.
+    /// See the [module documentation](self) for how the decomposition is used.
+    /// Shortest paths are only searched within the skeletons of R-nodes,
+    /// and finding a shortest cycle through an R-node skeleton takes one search per skeleton edge.
+    pub fn girth(&self) -> Option<usize> {
+        let has_self_loop = self.graph_ref().edge_indices().any(|edge_index| {
+            let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+            u == v
+        });
+        if has_self_loop {
+            return Some(1);
+        }
+
+        self.blocks
+            .iter_indices(..)
+            .filter_map(|block_index| self.block_girth(block_index))
+            .min()
+    }
+
+    /// Returns the length of a shortest cycle of the given block, ignoring self-loops.
+    fn block_girth(&self, block_index: BlockIndex<Graph::IndexType>) -> Option<usize> {
+        let block = &self.blocks[block_index];
+        if block.spqr_nodes.is_empty() {
+            let edge_count = block
+                .iter_edges()
+                .filter(|&edge_index| {
+                    let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+                    u != v
+                })
+                .count();
+            return (edge_count >= 2).then_some(2);
+        }
+
+        let mut order = vec![(block.spqr_nodes[0], None)];
+        let mut position = 0;
+        while let Some(&(spqr_node_index, parent_edge)) = order.get(position) {
+            position += 1;
+            for spqr_edge_index in self.spqr_nodes[spqr_node_index].iter_incident_spqr_edges() {
+                if Some(spqr_edge_index) != parent_edge {
+                    order.push((
+                        self.opposite_spqr_node(spqr_node_index, spqr_edge_index),
+                        Some(spqr_edge_index),
+                    ));
+                }
+            }
+        }
+
+        // The length of a shortest path between the poles of each virtual edge, on the side opposite to the given SPQR node.
+        let mut path_lengths = FxHashMap::default();
+        for &(spqr_node_index, parent_edge) in order.iter().rev() {
+            if let Some(parent_edge) = parent_edge {
+                let parent = self.opposite_spqr_node(spqr_node_index, parent_edge);
+                let length = self.pole_distance(spqr_node_index, parent_edge, &path_lengths);
+                path_lengths.insert((parent_edge, parent), length);
+            }
+        }
+        for &(spqr_node_index, parent_edge) in &order {
+            for spqr_edge_index in self.spqr_nodes[spqr_node_index].iter_incident_spqr_edges() {
+                if Some(spqr_edge_index) != parent_edge {
+                    let child = self.opposite_spqr_node(spqr_node_index, spqr_edge_index);
+                    let length =
+                        self.pole_distance(spqr_node_index, spqr_edge_index, &path_lengths);
+                    path_lengths.insert((spqr_edge_index, child), length);
+                }
+            }
+        }
+
+        block
+            .spqr_nodes
+            .iter()
+            .map(|&spqr_node_index| {
+                let edges = self.weighted_skeleton_edges(spqr_node_index, None, &path_lengths);
+                match self.spqr_nodes[spqr_node_index].spqr_node_type {
+                    SPQRNodeType::SNode => edges.iter().map(|&(_, _, length)| length).sum(),
+                    SPQRNodeType::PNode => {
+                        let mut lengths = edges
+                            .iter()
+                            .map(|&(_, _, length)| length)
+                            .collect::<Vec<_>>();
+                        lengths.sort_unstable();
+                        lengths[0] + lengths[1]
+                    }
+                    SPQRNodeType::RNode => (0..edges.len())
+                        .map(|excluded| {
+                            let (u, v, length) = edges[excluded];
+                            let remaining = edges
+                                .iter()
+                                .enumerate()
+                                .filter(|&(position, _)| position != excluded)
+                                .map(|(_, &edge)| edge);
+                            length + shortest_path_length(remaining, u, v)
+                        })
+                        .min()
+                        .unwrap(),
+                }
+            })
+            .min()
+    }
+
+    pub(crate) fn opposite_spqr_node(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+    ) -> SPQRNodeIndex<Graph::IndexType> {
+        let (a, b) = self.spqr_edges[spqr_edge_index].endpoints;
+        if a == spqr_node_index { b } else { a }
+    }
+
+    /// Returns the length of a shortest path between the poles of the given virtual edge in the skeleton of the given SPQR node without that virtual edge.
+    fn pole_distance(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+        path_lengths: &FxHashMap<
+            (
+                SPQREdgeIndex<Graph::IndexType>,
+                SPQRNodeIndex<Graph::IndexType>,
+            ),
+            usize,
+        >,
+    ) -> usize {
+        let edges =
+            self.weighted_skeleton_edges(spqr_node_index, Some(spqr_edge_index), path_lengths);
+        match self.spqr_nodes[spqr_node_index].spqr_node_type {
+            SPQRNodeType::SNode => edges.iter().map(|&(_, _, length)| length).sum(),
+            SPQRNodeType::PNode => edges.iter().map(|&(_, _, length)| length).min().unwrap(),
+            SPQRNodeType::RNode => {
+                let (s, t) = self.spqr_edges[spqr_edge_index].virtual_edge;
+                shortest_path_length(edges.into_iter(), s, t)
+            }
+        }
+    }
+
+    /// Returns the edges of the skeleton of the given SPQR node without self-loops and without the excluded virtual edge,
+    /// with the length of a real edge being one and the length of a virtual edge being the length of a shortest path behind it.
+    fn weighted_skeleton_edges(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        excluded: Option<SPQREdgeIndex<Graph::IndexType>>,
+        path_lengths: &FxHashMap<
+            (
+                SPQREdgeIndex<Graph::IndexType>,
+                SPQRNodeIndex<Graph::IndexType>,
+            ),
+            usize,
+        >,
+    ) -> Vec<(Graph::NodeIndex, Graph::NodeIndex, usize)> {
+        self.iter_skeleton_edges(spqr_node_index)
+            .filter(|&(skeleton_edge, (u, v))| {
+                u != v && Some(skeleton_edge) != excluded.map(SkeletonEdge::Virtual)
+            })
+            .map(|(skeleton_edge, (u, v))| match skeleton_edge {
+                SkeletonEdge::Real(_) => (u, v, 1),
+                SkeletonEdge::Virtual(spqr_edge_index) => {
+                    (u, v, path_lengths[&(spqr_edge_index, spqr_node_index)])
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns the length of a shortest path between the given nodes using Dijkstra's algorithm.
+///
+/// The nodes must be connected.
+fn shortest_path_length<NodeIndex: Copy + Ord + std::hash::Hash>(
+    edges: impl Iterator<Item = (NodeIndex, NodeIndex, usize)>,
+    source: NodeIndex,
+    target: NodeIndex,
+) -> usize {
+    let mut adjacency = FxHashMap::<NodeIndex, Vec<(NodeIndex, usize)>>::default();
+    for (u, v, length) in edges {
+        adjacency.entry(u).or_default().push((v, length));
+        adjacency.entry(v).or_default().push((u, length));
+    }
+
+    let mut settled = FxHashSet::default();
+    let mut queue = BinaryHeap::from([(Reverse(0), source)]);
+    while let Some((Reverse(distance), node_index)) = queue.pop() {
+        if node_index == target {
+            return distance;
+        }
+        if settled.insert(node_index) {
+            for &(neighbor, length) in &adjacency[&node_index] {
+                if !settled.contains(&neighbor) {
+                    queue.push((Reverse(distance + length), neighbor));
+                }
+            }
+        }
+    }
+    unreachable!("the nodes are connected")
+}
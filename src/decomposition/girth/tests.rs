@@ -0,0 +1,54 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{TEST_FILES, TestGraph, read_decomposition, read_graph, read_subdivided_wheel},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_girth() {
+    /// Finds a shortest cycle through each edge by a breadth-first search between its endpoints without the edge.
+    fn brute_force_girth(graph: &TestGraph) -> Option<usize> {
+        graph
+            .edge_indices()
+            .filter_map(|excluded| {
+                let (source, target) = graph.edge_endpoints(excluded);
+                if source == target {
+                    return Some(1);
+                }
+                let mut distances = HashMap::from([(source, 0)]);
+                let mut queue = VecDeque::from([source]);
+                while let Some(node) = queue.pop_front() {
+                    for edge_index in graph.incident_edges(node) {
+                        let (u, v) = graph.edge_endpoints(edge_index);
+                        let neighbor = if u == node { v } else { u };
+                        if edge_index != excluded && !distances.contains_key(&neighbor) {
+                            distances.insert(neighbor, distances[&node] + 1);
+                            queue.push_back(neighbor);
+                        }
+                    }
+                }
+                distances.get(&target).map(|distance| distance + 1)
+            })
+            .min()
+    }
+
+    for name in TEST_FILES {
+        let graph = read_graph(name);
+        let spqr_decomposition = read_decomposition(&graph, name);
+        assert_eq!(
+            spqr_decomposition.girth(),
+            brute_force_girth(&graph),
+            "{name}"
+        );
+    }
+
+    let (graph, spqr_decomposition_file) = read_subdivided_wheel();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    assert_eq!(spqr_decomposition.girth(), Some(3));
+    assert_eq!(brute_force_girth(&graph), Some(3));
+}
@@ -5,6 +5,10 @@ use std::{
 
 use num_traits::{Bounded, PrimInt};
 use optional_numeric_index::implement_generic_index;
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
 
 pub trait GraphIndexInteger:
     PrimInt + Bounded + Hash + Debug + Display + From<u8> + TryFrom<usize> + TryInto<usize>
@@ -16,6 +20,29 @@ impl<T: PrimInt + Bounded + Hash + Debug + Display + From<u8> + TryFrom<usize> +
 {
 }
 
+/// The error returned if an amount of entities cannot be indexed by an index type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("{amount} entities cannot be indexed with {bits}-bit indices")]
+pub struct IndexOverflowError {
+    pub amount: usize,
+    pub bits: u32,
+}
+
+impl IndexOverflowError {
+    /// Returns an error if `amount` entities cannot be indexed by `IndexType`.
+    ///
+    /// The maximum value of the index type is reserved for optional indices, so it cannot be used as an index.
+    pub fn check<IndexType: GraphIndexInteger>(amount: usize) -> Result<(), Self> {
+        match IndexType::max_value().try_into() {
+            Ok(max) if amount > max => Err(Self {
+                amount,
+                bits: IndexType::max_value().count_ones(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
 pub trait GraphIndex: Copy + Eq + Ord + Hash + Debug + Display + From<usize> + Into<usize> {}
 
 impl<T: Copy + Eq + Ord + Hash + Debug + Display + From<usize> + Into<usize>> GraphIndex for T {}
@@ -25,3 +52,21 @@ implement_generic_index!(pub BlockIndex, pub OptionalBlockIndex);
 implement_generic_index!(pub CutNodeIndex, pub OptionalCutNodeIndex);
 implement_generic_index!(pub SPQRNodeIndex, pub OptionalSPQRNodeIndex);
 implement_generic_index!(pub SPQREdgeIndex, pub OptionalSPQREdgeIndex);
+
+/// The integer type for indices selected at compile time.
+///
+/// This is `u32` by default or with the `index-u32` feature, halving the memory of indices compared to `usize` on 64-bit platforms.
+/// The `index-u64` feature selects `u64` for extremely large graphs, and takes precedence if both features are enabled.
+#[cfg(not(feature = "index-u64"))]
+pub type DefaultIndexType = u32;
+/// The integer type for indices selected at compile time.
+///
+/// This is `u64` because the `index-u64` feature is enabled.
+#[cfg(feature = "index-u64")]
+pub type DefaultIndexType = u64;
+
+pub type DefaultComponentIndex = ComponentIndex<DefaultIndexType>;
+pub type DefaultBlockIndex = BlockIndex<DefaultIndexType>;
+pub type DefaultCutNodeIndex = CutNodeIndex<DefaultIndexType>;
+pub type DefaultSPQRNodeIndex = SPQRNodeIndex<DefaultIndexType>;
+pub type DefaultSPQREdgeIndex = SPQREdgeIndex<DefaultIndexType>;
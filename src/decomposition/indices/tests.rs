@@ -0,0 +1,35 @@
+use std::{
+    fs::{self, File},
+    io::BufReader,
+};
+
+use bidirected_adjacency_array::io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData};
+
+use crate::decomposition::SPQRDecomposition;
+
+#[test]
+fn test_default_index_type() {
+    use crate::{
+        decomposition::indices::{DefaultBlockIndex, DefaultIndexType},
+        graph::implementations::bidirected_adjacency_array::DefaultBidirectedAdjacencyArray,
+    };
+
+    #[cfg(not(feature = "index-u64"))]
+    assert_eq!(size_of::<DefaultIndexType>(), 4);
+    #[cfg(feature = "index-u64")]
+    assert_eq!(size_of::<DefaultIndexType>(), 8);
+    assert_eq!(
+        size_of::<DefaultBlockIndex>(),
+        size_of::<DefaultIndexType>()
+    );
+
+    let graph = DefaultBidirectedAdjacencyArray::<PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let (block_index, _): (DefaultBlockIndex, _) = spqr_decomposition.iter_blocks().next().unwrap();
+    assert_eq!(block_index.into_usize(), 0);
+}
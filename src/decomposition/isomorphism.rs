@@ -0,0 +1,544 @@
+//! Canonical codes of blocks for isomorphism testing and hashing.
+//!
+//! The code of a block is built bottom-up along its SPQR tree, rooted at the center of the tree.
+//! Each SPQR node is encoded together with the subtrees below it, relative to the poles of the virtual edge to its parent:
+//! S-nodes as the sequence of edges along their cycle from one pole to the other,
+//! P-nodes as the multiset of their branches,
+//! and R-nodes as the canonical form of their skeleton, in which virtual edges are labelled with the codes of the subtrees behind them.
+//!
+//! Since decompositions do not store embeddings of their skeletons, R-node skeletons are not encoded with Weinberg's planar codes,
+//! but by individualisation and refinement, which takes exponential time in the worst case.
+//! Skeletons of R-nodes tend to be small in practice.
+//!
+//! Structural hashes of the whole decomposition combine the codes of the blocks along the block-cut trees of the components.
+
+use std::{
+    borrow::Borrow,
+    collections::VecDeque,
+    fmt::Write,
+    hash::{Hash, Hasher},
+};
+
+use rustc_hash::{FxHashMap, FxHasher};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, ComponentIndex, SPQREdgeIndex, SPQRNodeIndex},
+        symmetry::tree_centers,
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns a code of the given block that is equal for two blocks if and only if they are isomorphic as multigraphs,
+    /// see the [module documentation](self).
+    ///
+    /// Node names, edge orientations and extra data are not part of the code.
+    /// The code is only canonical if the SPQR tree is proper, i.e. if no two S-nodes and no two P-nodes are adjacent,
+    /// which holds for the decompositions computed by common tools.
+    pub fn canonical_code(&self, block_index: BlockIndex<Graph::IndexType>) -> String {
+        let block = &self.blocks[block_index];
+        if block.spqr_nodes.is_empty() {
+            return self.small_block_code(block_index);
+        }
+
+        let spqr_nodes = &block.spqr_nodes;
+        let local_index = spqr_nodes
+            .iter()
+            .enumerate()
+            .map(|(local, &spqr_node_index)| (spqr_node_index, local))
+            .collect::<FxHashMap<_, _>>();
+        let neighbors = |local: usize| {
+            self.spqr_tree_neighbors(spqr_nodes[local])
+                .iter()
+                .map(|neighbor| (local_index[&neighbor.spqr_node], neighbor.spqr_edge))
+        };
+        let centers = tree_centers(spqr_nodes.len(), neighbors);
+
+        let mut parent_edges = vec![None; spqr_nodes.len()];
+        let mut child_by_edge = FxHashMap::default();
+        let mut order = Vec::with_capacity(spqr_nodes.len());
+        let mut visited = vec![false; spqr_nodes.len()];
+        let mut queue = VecDeque::new();
+        for &center in &centers {
+            visited[center] = true;
+            queue.push_back(center);
+        }
+        while let Some(local) = queue.pop_front() {
+            order.push(local);
+            for (neighbor, spqr_edge_index) in neighbors(local) {
+                if !visited[neighbor] {
+                    visited[neighbor] = true;
+                    parent_edges[neighbor] = Some(spqr_edge_index);
+                    child_by_edge.insert(spqr_edge_index, neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        if let [first, second] = centers[..] {
+            let central_edge = neighbors(first)
+                .find(|&(neighbor, _)| neighbor == second)
+                .unwrap()
+                .1;
+            parent_edges[first] = Some(central_edge);
+            parent_edges[second] = Some(central_edge);
+        }
+
+        let mut loops = FxHashMap::<Graph::NodeIndex, usize>::default();
+        for edge_index in self.iter_edges_in_block(block_index) {
+            let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+            if u == v {
+                *loops.entry(u).or_default() += 1;
+            }
+        }
+        let loops = |node_index| loops.get(&node_index).copied().unwrap_or(0);
+
+        // The codes of each subtree relative to the virtual edge `(u, v)` to its parent, oriented from `u` to `v` and from `v` to `u`.
+        let mut oriented_codes: Vec<Option<[String; 2]>> = vec![None; spqr_nodes.len()];
+        let child_code = |oriented_codes: &[Option<[String; 2]>], spqr_edge_index, from| {
+            let direction = usize::from(self.spqr_edges[spqr_edge_index].virtual_edge.0 != from);
+            oriented_codes[child_by_edge[&spqr_edge_index]]
+                .as_ref()
+                .unwrap()[direction]
+                .clone()
+        };
+        for &local in order.iter().rev() {
+            let Some(parent_edge) = parent_edges[local] else {
+                continue;
+            };
+            let (u, v) = self.spqr_edges[parent_edge].virtual_edge;
+            let codes = [(u, v), (v, u)].map(|poles| {
+                self.pertinent_code(
+                    spqr_nodes[local],
+                    Some((parent_edge, poles)),
+                    &loops,
+                    &|spqr_edge_index, from| child_code(&oriented_codes, spqr_edge_index, from),
+                )
+            });
+            oriented_codes[local] = Some(codes);
+        }
+
+        if let [first, second] = centers[..] {
+            (0..2)
+                .map(|direction| {
+                    let mut halves = [first, second]
+                        .map(|center| oriented_codes[center].as_ref().unwrap()[direction].clone());
+                    halves.sort_unstable();
+                    format!("E({}|{})", halves[0], halves[1])
+                })
+                .min()
+                .unwrap()
+        } else {
+            self.pertinent_code(
+                spqr_nodes[centers[0]],
+                None,
+                &loops,
+                &|spqr_edge_index, from| child_code(&oriented_codes, spqr_edge_index, from),
+            )
+        }
+    }
+
+    /// Returns a hash of the [canonical code](Self::canonical_code) of the given block.
+    ///
+    /// Isomorphic blocks have equal hashes, and blocks with different hashes are not isomorphic.
+    /// The hash does not depend on the process, but may change between versions of this crate.
+    pub fn block_structural_hash(&self, block_index: BlockIndex<Graph::IndexType>) -> u64 {
+        structural_hash(&self.canonical_code(block_index))
+    }
+
+    /// Returns a hash of the structure of the decomposition, for deduplicating or bucketing large collections of graphs.
+    ///
+    /// The hash combines the canonical codes of the blocks along the block-cut tree of each component,
+    /// which is rooted at its center and encoded with the codes of its subtrees sorted, and the sorted codes of the components.
+    /// Isomorphic graphs have equal hashes, and graphs with different hashes are not isomorphic.
+    /// However, the hash does not encode which nodes of a block are its cut nodes,
+    /// so non-isomorphic graphs whose blocks are only attached differently may have equal hashes.
+    /// The hash does not depend on the process, but may change between versions of this crate.
+    pub fn structural_hash(&self) -> u64 {
+        let mut codes = self
+            .components
+            .iter_indices(..)
+            .map(|component_index| self.component_code(component_index))
+            .collect::<Vec<_>>();
+        codes.sort_unstable();
+        structural_hash(&codes.join(","))
+    }
+
+    /// Encodes the block-cut tree of the given component with each block labelled by its canonical code.
+    fn component_code(&self, component_index: ComponentIndex<Graph::IndexType>) -> String {
+        let component = &self.components[component_index];
+        if component.blocks.is_empty() {
+            return "N".to_string();
+        }
+
+        // Blocks come first, followed by cut nodes.
+        let block_count = component.blocks.len();
+        let block_local = component
+            .blocks
+            .iter()
+            .enumerate()
+            .map(|(local, &block_index)| (block_index, local))
+            .collect::<FxHashMap<_, _>>();
+        let cut_node_local = component
+            .cut_nodes
+            .iter()
+            .enumerate()
+            .map(|(local, &cut_node_index)| (cut_node_index, block_count + local))
+            .collect::<FxHashMap<_, _>>();
+        let neighbors = |local: usize| -> Vec<(usize, ())> {
+            if local < block_count {
+                self.blocks[component.blocks[local]]
+                    .cut_nodes
+                    .iter()
+                    .map(|cut_node_index| (cut_node_local[cut_node_index], ()))
+                    .collect()
+            } else {
+                self.cut_nodes[component.cut_nodes[local - block_count]]
+                    .adjacent_blocks
+                    .iter()
+                    .map(|block_index| (block_local[block_index], ()))
+                    .collect()
+            }
+        };
+        let node_count = block_count + component.cut_nodes.len();
+        let centers = tree_centers(node_count, |local| neighbors(local).into_iter());
+
+        let mut parents = vec![None; node_count];
+        let mut order = centers.clone();
+        let mut position = 0;
+        while let Some(&local) = order.get(position) {
+            position += 1;
+            for (neighbor, ()) in neighbors(local) {
+                if parents[local] != Some(neighbor) && !centers.contains(&neighbor) {
+                    parents[neighbor] = Some(local);
+                    order.push(neighbor);
+                }
+            }
+        }
+
+        let mut child_codes = vec![Vec::new(); node_count];
+        let mut codes = vec![String::new(); node_count];
+        for &local in order.iter().rev() {
+            let mut children = std::mem::take(&mut child_codes[local]);
+            children.sort_unstable();
+            let label = if local < block_count {
+                self.canonical_code(component.blocks[local])
+            } else {
+                "C".to_string()
+            };
+            codes[local] = format!("{label}[{}]", children.join(","));
+            if let Some(parent) = parents[local] {
+                child_codes[parent].push(codes[local].clone());
+            }
+        }
+
+        let mut center_codes = centers
+            .iter()
+            .map(|&center| codes[center].clone())
+            .collect::<Vec<_>>();
+        center_codes.sort_unstable();
+        center_codes.join("|")
+    }
+
+    /// Encodes the given SPQR node together with its subtrees.
+    ///
+    /// If `parent` is given, the code is relative to the poles of the virtual edge to the parent, in the given order.
+    /// Otherwise, the SPQR node is the root, and the code is minimised over all choices of poles.
+    /// Nodes are labelled with their amount of self-loops in the block, and virtual edges with the codes returned by `child_code`
+    /// for the subtree behind them, oriented away from the given node.
+    fn pertinent_code(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        parent: Option<(
+            SPQREdgeIndex<Graph::IndexType>,
+            (Graph::NodeIndex, Graph::NodeIndex),
+        )>,
+        loops: &impl Fn(Graph::NodeIndex) -> usize,
+        child_code: &impl Fn(SPQREdgeIndex<Graph::IndexType>, Graph::NodeIndex) -> String,
+    ) -> String {
+        let spqr_node = &self.spqr_nodes[spqr_node_index];
+        let parent_edge = parent.map(|(parent_edge, _)| parent_edge);
+
+        match spqr_node.spqr_node_type {
+            SPQRNodeType::SNode => {
+                let cycle = self.s_node_cycle(spqr_node_index);
+                let len = cycle.len();
+                // Walks along the cycle from the node at position `start`, and encodes the visited nodes and edges.
+                let walk = |start: usize, forward: bool, edge_count: usize| {
+                    let mut position = start;
+                    let mut code = format!("S(l{}", loops(cycle[position].1));
+                    for _ in 0..edge_count {
+                        let (edge_position, next) = if forward {
+                            (position, (position + 1) % len)
+                        } else {
+                            ((position + len - 1) % len, (position + len - 1) % len)
+                        };
+                        match cycle[edge_position].0 {
+                            SkeletonEdge::Real(_) => code.push_str(",r"),
+                            SkeletonEdge::Virtual(spqr_edge_index) => {
+                                write!(code, ",v{}", child_code(spqr_edge_index, cycle[position].1))
+                                    .unwrap()
+                            }
+                        }
+                        write!(code, ",l{}", loops(cycle[next].1)).unwrap();
+                        position = next;
+                    }
+                    code.push(')');
+                    code
+                };
+
+                if let Some((parent_edge, (source, _))) = parent {
+                    let parent_position = cycle
+                        .iter()
+                        .position(|(skeleton_edge, _)| {
+                            *skeleton_edge == SkeletonEdge::Virtual(parent_edge)
+                        })
+                        .unwrap();
+                    if cycle[(parent_position + 1) % len].1 == source {
+                        walk((parent_position + 1) % len, true, len - 1)
+                    } else {
+                        walk(parent_position, false, len - 1)
+                    }
+                } else {
+                    (0..len)
+                        .flat_map(|start| [walk(start, true, len), walk(start, false, len)])
+                        .min()
+                        .unwrap()
+                }
+            }
+            SPQRNodeType::PNode => {
+                let (u, v) = spqr_node.p_node_poles().unwrap();
+                let real_edge_count = spqr_node
+                    .iter_edges()
+                    .filter(|&edge_index| {
+                        let (a, b) = self.graph_ref().edge_endpoints(edge_index);
+                        a != b
+                    })
+                    .count();
+                let encode = |source, sink| {
+                    let mut branches = spqr_node
+                        .iter_incident_spqr_edges()
+                        .filter(|&spqr_edge_index| Some(spqr_edge_index) != parent_edge)
+                        .map(|spqr_edge_index| child_code(spqr_edge_index, source))
+                        .collect::<Vec<_>>();
+                    branches.sort_unstable();
+                    let mut code =
+                        format!("P(l{},l{},r{real_edge_count}", loops(source), loops(sink));
+                    for branch in branches {
+                        write!(code, ",v{branch}").unwrap();
+                    }
+                    code.push(')');
+                    code
+                };
+
+                if let Some((_, (source, sink))) = parent {
+                    encode(source, sink)
+                } else {
+                    encode(u, v).min(encode(v, u))
+                }
+            }
+            SPQRNodeType::RNode => {
+                let nodes = spqr_node.iter_nodes().collect::<Vec<_>>();
+                let local = |node_index| nodes.iter().position(|&node| node == node_index).unwrap();
+                let colors = nodes
+                    .iter()
+                    .map(|&node_index| {
+                        let role = match parent {
+                            Some((_, (source, _))) if source == node_index => 0,
+                            Some((_, (_, sink))) if sink == node_index => 1,
+                            _ => 2,
+                        };
+                        format!("{role}l{}", loops(node_index))
+                    })
+                    .collect();
+                let edges = self
+                    .iter_skeleton_edges(spqr_node_index)
+                    .filter(|(_, (u, v))| u != v)
+                    .map(|(skeleton_edge, (u, v))| {
+                        let (forward, backward) = match skeleton_edge {
+                            SkeletonEdge::Real(_) => ("r".to_string(), "r".to_string()),
+                            SkeletonEdge::Virtual(spqr_edge_index)
+                                if Some(spqr_edge_index) == parent_edge =>
+                            {
+                                ("p".to_string(), "p".to_string())
+                            }
+                            SkeletonEdge::Virtual(spqr_edge_index) => (
+                                format!("v{}", child_code(spqr_edge_index, u)),
+                                format!("v{}", child_code(spqr_edge_index, v)),
+                            ),
+                        };
+                        (local(u), local(v), forward, backward)
+                    })
+                    .collect();
+                format!("R({})", canonical_labelled_graph(colors, edges))
+            }
+        }
+    }
+
+    /// Encodes a block without SPQR tree, i.e. a block with at most two nodes.
+    fn small_block_code(&self, block_index: BlockIndex<Graph::IndexType>) -> String {
+        let block = &self.blocks[block_index];
+        let (u, v) = (block.nodes[0], *block.nodes.last().unwrap());
+        let mut loops = [0, 0];
+        let mut real_edge_count = 0;
+        for edge_index in self.iter_edges_in_block(block_index) {
+            match self.graph_ref().edge_endpoints(edge_index) {
+                (a, b) if a == b => loops[usize::from(a != u)] += 1,
+                _ => real_edge_count += 1,
+            }
+        }
+
+        if u == v {
+            format!("B(l{})", loops[0])
+        } else {
+            loops.sort_unstable();
+            format!("B(l{},l{},r{real_edge_count})", loops[0], loops[1])
+        }
+    }
+}
+
+fn structural_hash(code: &str) -> u64 {
+    let mut hasher = FxHasher::default();
+    code.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns a canonical form of a graph with coloured nodes and edges,
+/// where edge `(u, v, forward, backward)` has label `forward` from `u` to `v` and label `backward` from `v` to `u`.
+///
+/// The form is the lexicographically smallest encoding over all orders of the nodes that are explored
+/// by individualising the nodes of the first non-singleton colour class and refining the colours, recursively.
+fn canonical_labelled_graph(
+    colors: Vec<String>,
+    edges: Vec<(usize, usize, String, String)>,
+) -> String {
+    let mut labels = edges
+        .iter()
+        .flat_map(|(_, _, forward, backward)| [forward.clone(), backward.clone()])
+        .collect::<Vec<_>>();
+    labels.sort_unstable();
+    labels.dedup();
+    let label_id = |label: &String| labels.binary_search(label).unwrap();
+
+    let mut adjacency = vec![Vec::new(); colors.len()];
+    for (u, v, forward, backward) in &edges {
+        adjacency[*u].push((*v, label_id(forward)));
+        adjacency[*v].push((*u, label_id(backward)));
+    }
+
+    let mut color_names = colors.clone();
+    color_names.sort_unstable();
+    color_names.dedup();
+    let initial_colors = colors
+        .iter()
+        .map(|color| color_names.binary_search(color).unwrap())
+        .collect();
+
+    let mut code = labels.join("|");
+    code.push(';');
+    code.push_str(&search_canonical_order(
+        initial_colors,
+        &adjacency,
+        &|order: &[usize]| {
+            let mut position = vec![0; order.len()];
+            for (index, &node) in order.iter().enumerate() {
+                position[node] = index;
+            }
+            let position = &position;
+            let mut encoded_edges = adjacency
+                .iter()
+                .enumerate()
+                .flat_map(|(node, neighbors)| {
+                    neighbors
+                        .iter()
+                        .map(move |&(neighbor, label)| (position[node], position[neighbor], label))
+                })
+                .collect::<Vec<_>>();
+            encoded_edges.sort_unstable();
+
+            let mut code = String::new();
+            for &node in order {
+                write!(code, "{},", colors[node]).unwrap();
+            }
+            for (u, v, label) in encoded_edges {
+                write!(code, ";{u}>{v}={label}").unwrap();
+            }
+            code
+        },
+    ));
+    code
+}
+
+fn search_canonical_order(
+    mut colors: Vec<usize>,
+    adjacency: &[Vec<(usize, usize)>],
+    encode: &impl Fn(&[usize]) -> String,
+) -> String {
+    refine_colors(&mut colors, adjacency);
+
+    let mut class_sizes = vec![0; colors.len()];
+    for &color in &colors {
+        class_sizes[color] += 1;
+    }
+    let Some(target) = class_sizes.iter().position(|&size| size > 1) else {
+        let mut order = (0..colors.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&node| colors[node]);
+        return encode(&order);
+    };
+
+    (0..colors.len())
+        .filter(|&node| colors[node] == target)
+        .map(|individual| {
+            let individualised = colors
+                .iter()
+                .enumerate()
+                .map(|(node, &color)| {
+                    2 * color + usize::from(color == target && node != individual)
+                })
+                .collect();
+            search_canonical_order(individualised, adjacency, encode)
+        })
+        .min()
+        .unwrap()
+}
+
+/// Refines the colours until each colour class is equitable, and numbers the colour classes consecutively from zero.
+///
+/// The order of the new colours respects the order of the old colours.
+fn refine_colors(colors: &mut Vec<usize>, adjacency: &[Vec<(usize, usize)>]) {
+    loop {
+        let signatures = (0..colors.len())
+            .map(|node| {
+                let mut neighbors = adjacency[node]
+                    .iter()
+                    .map(|&(neighbor, label)| (label, colors[neighbor]))
+                    .collect::<Vec<_>>();
+                neighbors.sort_unstable();
+                (colors[node], neighbors)
+            })
+            .collect::<Vec<_>>();
+        let mut distinct = signatures.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+
+        let class_count = {
+            let mut old = colors.clone();
+            old.sort_unstable();
+            old.dedup();
+            old.len()
+        };
+        *colors = signatures
+            .iter()
+            .map(|signature| distinct.binary_search(signature).unwrap())
+            .collect();
+        if distinct.len() == class_count {
+            break;
+        }
+    }
+}
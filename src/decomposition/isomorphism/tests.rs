@@ -0,0 +1,119 @@
+use std::{collections::HashSet, io::BufReader};
+
+use bidirected_adjacency_array::graph::BidirectedAdjacencyArray;
+
+use crate::decomposition::{
+    SPQRDecomposition,
+    tests::{TestGraph, read_subdivided_wheel},
+};
+
+#[test]
+fn test_canonical_code() {
+    let (graph, spqr_decomposition_file) = read_subdivided_wheel();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let code = spqr_decomposition.canonical_code(0.into());
+
+    // The same graph with the rim edge 3-4 subdivided instead, and with nodes and edges in a different order.
+    let gfa = "H\tVN:Z:1.0\nS\tx\tA\nS\t4\tA\nS\t3\tA\nS\th\tA\nS\t2\tA\nS\t1\tA\n\
+        L\t4\t+\tx\t+\t0M\nL\t1\t+\t2\t+\t0M\nL\th\t+\t3\t+\t0M\nL\t2\t+\th\t+\t0M\n\
+        L\t1\t+\th\t+\t0M\nL\t3\t+\tx\t+\t0M\nL\t4\t+\th\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t1\t+\t4\t+\t0M\n";
+    let graph: TestGraph =
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+    let spqr_decomposition_file = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 x 4 3 h 2 1\nB B0 G0 x 4 3 h 2 1\n\
+        S S0 B0 4 x 3\nR R1 B0 4 3 h 2 1\nV V0 S0 R1 4 3\n\
+        E E0 S0 4 x\nE E1 S0 3 x\nE E2 R1 1 2\nE E3 R1 h 3\nE E4 R1 2 h\nE E5 R1 1 h\n\
+        E E6 R1 4 h\nE E7 R1 2 3\nE E8 R1 1 4\n";
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    assert_eq!(spqr_decomposition.canonical_code(0.into()), code);
+
+    // A wheel with the spoke h-1 subdivided, which has the same SPQR tree shape.
+    let gfa = "H\tVN:Z:1.0\nS\th\tA\nS\t1\tA\nS\t2\tA\nS\t3\tA\nS\t4\tA\nS\tx\tA\n\
+        L\th\t+\t2\t+\t0M\nL\th\t+\t3\t+\t0M\nL\th\t+\t4\t+\t0M\nL\t1\t+\t2\t+\t0M\n\
+        L\t2\t+\t3\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t4\t+\t1\t+\t0M\n\
+        L\th\t+\tx\t+\t0M\nL\tx\t+\t1\t+\t0M\n";
+    let graph: TestGraph =
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+    let spqr_decomposition_file = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 h 1 2 3 4 x\nB B0 G0 h 1 2 3 4 x\n\
+        R R0 B0 h 1 2 3 4\nS S1 B0 h x 1\nV V0 R0 S1 h 1\n\
+        E E0 R0 h 2\nE E1 R0 h 3\nE E2 R0 h 4\nE E3 R0 1 2\nE E4 R0 2 3\nE E5 R0 3 4\nE E6 R0 4 1\n\
+        E E7 S1 h x\nE E8 S1 x 1\n";
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    assert_ne!(spqr_decomposition.canonical_code(0.into()), code);
+
+    // Three parallel paths and an edge between u and v, with the poles of the P-node in either order.
+    let gfa = "H\tVN:Z:1.0\nS\tu\tA\nS\tv\tA\nS\ta\tA\nS\tb\tA\nS\tc\tA\n\
+        L\tu\t+\tv\t+\t0M\nL\tu\t+\ta\t+\t0M\nL\ta\t+\tv\t+\t0M\n\
+        L\tu\t+\tb\t+\t0M\nL\tb\t+\tv\t+\t0M\nL\tu\t+\tc\t+\t0M\nL\tc\t+\tv\t+\t0M\n";
+    let graph: TestGraph =
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+    let codes = ["u v", "v u"].map(|poles| {
+        let spqr_decomposition_file = format!(
+            "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+            G G0 u v a b c\nB B0 G0 u v a b c\n\
+            P P0 B0 {poles}\nS S1 B0 u a v\nS S2 B0 v b u\nS S3 B0 u c v\n\
+            V V0 P0 S1 u v\nV V1 P0 S2 v u\nV V2 P0 S3 u v\n\
+            E E0 P0 u v\nE E1 S1 u a\nE E2 S1 a v\nE E3 S2 u b\nE E4 S2 b v\nE E5 S3 u c\nE E6 S3 c v\n"
+        );
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes())
+            .unwrap()
+            .canonical_code(0.into())
+    });
+    assert_eq!(codes[0], codes[1]);
+    assert_ne!(codes[0], code);
+}
+
+#[test]
+fn test_structural_hash() {
+    let read = |gfa: &str, spqr_decomposition_file: &str| {
+        let graph: TestGraph =
+            BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+        let block_hashes = spqr_decomposition
+            .iter_blocks()
+            .map(|(block_index, _)| spqr_decomposition.block_structural_hash(block_index))
+            .collect::<HashSet<_>>();
+        (spqr_decomposition.structural_hash(), block_hashes)
+    };
+
+    // Two triangles sharing a node, with a pendant edge at the second triangle.
+    let chain = read(
+        "H\tVN:Z:1.0\nS\ta\tA\nS\tb\tA\nS\tc\tA\nS\td\tA\nS\te\tA\nS\tf\tA\n\
+        L\ta\t+\tb\t+\t0M\nL\tb\t+\tc\t+\t0M\nL\tc\t+\ta\t+\t0M\n\
+        L\tc\t+\td\t+\t0M\nL\td\t+\te\t+\t0M\nL\te\t+\tc\t+\t0M\nL\te\t+\tf\t+\t0M\n",
+        "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 a b c d e f\nB B0 G0 a b c\nB B1 G0 c d e\nB B2 G0 e f\nC c B0 B1\nC e B1 B2\n\
+        S S0 B0 a b c\nS S1 B1 c d e\n\
+        E E0 S0 a b\nE E1 S0 b c\nE E2 S0 c a\nE E3 S1 c d\nE E4 S1 d e\nE E5 S1 e c\nE E6 B2 e f\n",
+    );
+    // The same graph with nodes, edges and blocks in a different order.
+    let reordered = read(
+        "H\tVN:Z:1.0\nS\tf\tA\nS\te\tA\nS\td\tA\nS\tc\tA\nS\tb\tA\nS\ta\tA\n\
+        L\tf\t+\te\t+\t0M\nL\td\t+\te\t+\t0M\nL\ta\t+\tc\t+\t0M\nL\te\t+\tc\t+\t0M\n\
+        L\tb\t+\ta\t+\t0M\nL\tc\t+\td\t+\t0M\nL\tc\t+\tb\t+\t0M\n",
+        "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 f e d c b a\nB B0 G0 f e\nB B1 G0 e d c\nB B2 G0 c b a\nC e B0 B1\nC c B1 B2\n\
+        S S0 B1 e d c\nS S1 B2 c b a\n\
+        E E0 B0 f e\nE E1 S0 d e\nE E2 S1 a c\nE E3 S0 e c\nE E4 S1 b a\nE E5 S0 c d\nE E6 S1 c b\n",
+    );
+    // The pendant edge attached to the shared node instead.
+    let star = read(
+        "H\tVN:Z:1.0\nS\ta\tA\nS\tb\tA\nS\tc\tA\nS\td\tA\nS\te\tA\nS\tf\tA\n\
+        L\ta\t+\tb\t+\t0M\nL\tb\t+\tc\t+\t0M\nL\tc\t+\ta\t+\t0M\n\
+        L\tc\t+\td\t+\t0M\nL\td\t+\te\t+\t0M\nL\te\t+\tc\t+\t0M\nL\tc\t+\tf\t+\t0M\n",
+        "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 a b c d e f\nB B0 G0 a b c\nB B1 G0 c d e\nB B2 G0 c f\nC c B0 B1 B2\n\
+        S S0 B0 a b c\nS S1 B1 c d e\n\
+        E E0 S0 a b\nE E1 S0 b c\nE E2 S0 c a\nE E3 S1 c d\nE E4 S1 d e\nE E5 S1 e c\nE E6 B2 c f\n",
+    );
+
+    assert_eq!(chain, reordered);
+    assert_eq!(chain.1, star.1);
+    assert_eq!(chain.1.len(), 2);
+    assert_ne!(chain.0, star.0);
+}
@@ -0,0 +1,431 @@
+//! Coordinates for drawing a decomposed graph, and rendering to SVG.
+//!
+//! The decomposition does not store a planar embedding of its blocks yet, so the layout does not attempt to be planar.
+//! Instead, the nodes of each block are placed on a circle, and the blocks of a component are composed along their cut nodes,
+//! following the block-cut tree.
+//! Blocks that consist of a single S-node are drawn in the order of their cycle, and hence without crossings.
+//!
+//! The SPQR tree of a block is drawn in layers by depth below its root, in the style of Reingold and Tilford:
+//! the subtrees of the children of a node are placed next to each other from left to right,
+//! each as far left as possible without overlapping the previous ones on any layer, and the node is centered above its first and last child.
+
+use std::{borrow::Borrow, collections::VecDeque, f64::consts::TAU, fmt::Write, hash::Hash};
+
+use rustc_hash::FxHashMap;
+use tagged_vec::TaggedVec;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        indices::{BlockIndex, GraphIndexInteger, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The distance between neighbouring nodes on the circle of a block.
+const NODE_SPACING: f64 = 1.0;
+
+/// The horizontal distance between the bounding boxes of components.
+const COMPONENT_SPACING: f64 = 2.0;
+
+/// The vertical distance between the layers of an SPQR tree.
+const LAYER_SPACING: f64 = 1.0;
+
+/// The amount of SVG units per layout unit.
+const SVG_SCALE: f64 = 40.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// The positions of all nodes of a graph, see [`SPQRDecomposition::layout`].
+#[derive(Debug, Clone)]
+pub struct Layout<NodeIndex> {
+    positions: TaggedVec<NodeIndex, Point>,
+}
+
+impl<NodeIndex: Into<usize>> Layout<NodeIndex> {
+    pub fn position(&self, node_index: NodeIndex) -> Point {
+        self.positions[node_index]
+    }
+
+    /// Returns the minimum and maximum corner of the smallest axis-parallel rectangle containing all nodes.
+    ///
+    /// Returns `None` if the graph has no nodes.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        bounding_box(self.positions.iter_values().copied())
+    }
+}
+
+/// The positions of the SPQR nodes of a block, see [`SPQRDecomposition::layout_spqr_tree`].
+#[derive(Debug, Clone)]
+pub struct SPQRTreeLayout<IndexType> {
+    positions: FxHashMap<SPQRNodeIndex<IndexType>, Point>,
+}
+
+impl<IndexType: GraphIndexInteger> SPQRTreeLayout<IndexType> {
+    /// Returns the position of the given SPQR node, or `None` if it is not part of the laid out tree.
+    pub fn position(&self, spqr_node_index: SPQRNodeIndex<IndexType>) -> Option<Point> {
+        self.positions.get(&spqr_node_index).copied()
+    }
+
+    /// Returns an iterator over all SPQR nodes of the laid out tree with their positions, in arbitrary order.
+    pub fn iter_positions(&self) -> impl Iterator<Item = (SPQRNodeIndex<IndexType>, Point)> {
+        self.positions
+            .iter()
+            .map(|(&spqr_node_index, &position)| (spqr_node_index, position))
+    }
+
+    /// Returns the minimum and maximum corner of the smallest axis-parallel rectangle containing all SPQR nodes.
+    ///
+    /// Returns `None` if the block has no SPQR tree.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        bounding_box(self.positions.values().copied())
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Computes coordinates for the SPQR nodes of the given block, see the [module documentation](self) for the drawing style.
+    ///
+    /// The tree is rooted at its [root](Self::spqr_tree_root), which is placed at the top.
+    /// The y-coordinate grows with the depth, and the smallest x-coordinate is zero.
+    pub fn layout_spqr_tree(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> SPQRTreeLayout<Graph::IndexType> {
+        let mut children = FxHashMap::<_, Vec<_>>::default();
+        for &spqr_node_index in &self.blocks[block_index].spqr_nodes {
+            if let Some((parent, _)) = self.spqr_tree_parent(spqr_node_index) {
+                children.entry(parent).or_default().push(spqr_node_index);
+            }
+        }
+        let Some(root) = self.spqr_tree_root(block_index) else {
+            return SPQRTreeLayout {
+                positions: FxHashMap::default(),
+            };
+        };
+        SPQRTreeLayout {
+            positions: layered_tree_layout(root, &children),
+        }
+    }
+
+    /// Computes coordinates for all nodes of the graph, see the [module documentation](self) for the drawing style.
+    ///
+    /// Components are placed next to each other from left to right.
+    pub fn layout(&self) -> Layout<Graph::NodeIndex> {
+        let mut positions =
+            TaggedVec::<Graph::NodeIndex, Option<Point>>::from(vec![
+                None;
+                self.graph_ref().node_count()
+            ]);
+        let mut is_block_placed = vec![false; self.blocks.len()];
+        let mut component_offset = 0.0;
+
+        for component in self.components.iter_values() {
+            let mut queue = VecDeque::new();
+            if let Some(&root) = component.blocks.first() {
+                queue.push_back((root, None));
+                is_block_placed[root.into_usize()] = true;
+            } else {
+                positions[component.nodes[0]] = Some(Point::default());
+            }
+
+            // Breadth-first search through the block-cut tree, attaching each block at the cut node it shares with its parent.
+            while let Some((block_index, anchor)) = queue.pop_front() {
+                self.place_block(block_index, anchor, &mut positions);
+                let block = &self.blocks[block_index];
+                for &cut_node_index in &block.cut_nodes {
+                    let cut_node = &self.cut_nodes[cut_node_index];
+                    for &adjacent_block in &cut_node.adjacent_blocks {
+                        if !is_block_placed[adjacent_block.into_usize()] {
+                            is_block_placed[adjacent_block.into_usize()] = true;
+                            let center = block_center(
+                                block
+                                    .nodes
+                                    .iter()
+                                    .map(|&node_index| positions[node_index].unwrap()),
+                            );
+                            queue.push_back((adjacent_block, Some((cut_node.node, center))));
+                        }
+                    }
+                }
+            }
+
+            let (min, max) = bounding_box(
+                component
+                    .nodes
+                    .iter()
+                    .map(|&node_index| positions[node_index].unwrap()),
+            )
+            .unwrap();
+            for &node_index in &component.nodes {
+                let position = positions[node_index].as_mut().unwrap();
+                position.x += component_offset - min.x;
+                position.y -= min.y;
+            }
+            component_offset += max.x - min.x + COMPONENT_SPACING;
+        }
+
+        Layout {
+            positions: positions
+                .into_values_iter()
+                .map(|position| position.expect("node is not part of any component"))
+                .collect(),
+        }
+    }
+
+    /// Renders the graph as SVG, using the coordinates computed by [`layout`](Self::layout).
+    ///
+    /// Nodes are labelled with their names, and self-loops are drawn as small circles next to their node.
+    pub fn render_svg(&self) -> String {
+        let layout = self.layout();
+        let (min, max) = layout.bounding_box().unwrap_or_default();
+        let margin = NODE_SPACING;
+        let scale = |coordinate: f64, min: f64| (coordinate - min + margin) * SVG_SCALE;
+
+        let mut result = String::new();
+        writeln!(
+            result,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">"#,
+            (max.x - min.x + 2.0 * margin) * SVG_SCALE,
+            (max.y - min.y + 2.0 * margin) * SVG_SCALE,
+        )
+        .unwrap();
+
+        for edge_index in self.graph_ref().edge_indices() {
+            let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+            let (u, v) = (layout.position(u), layout.position(v));
+            if u == v {
+                writeln!(
+                    result,
+                    r#"  <circle cx="{}" cy="{}" r="{}" fill="none" stroke="black"/>"#,
+                    scale(u.x, min.x),
+                    scale(u.y - NODE_SPACING / 4.0, min.y),
+                    NODE_SPACING / 4.0 * SVG_SCALE,
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    result,
+                    r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="black"/>"#,
+                    scale(u.x, min.x),
+                    scale(u.y, min.y),
+                    scale(v.x, min.x),
+                    scale(v.y, min.y),
+                )
+                .unwrap();
+            }
+        }
+
+        for node_index in self.graph_ref().node_indices() {
+            let position = layout.position(node_index);
+            let (x, y) = (scale(position.x, min.x), scale(position.y, min.y));
+            writeln!(
+                result,
+                r#"  <circle cx="{x}" cy="{y}" r="{}" fill="white" stroke="black"/>"#,
+                NODE_SPACING / 6.0 * SVG_SCALE,
+            )
+            .unwrap();
+            writeln!(
+                result,
+                r#"  <text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="central" font-size="{}">{}</text>"#,
+                NODE_SPACING / 5.0 * SVG_SCALE,
+                escape_xml(&self.graph_ref().node_name(node_index)),
+            )
+            .unwrap();
+        }
+
+        result.push_str("</svg>\n");
+        result
+    }
+
+    /// Places the nodes of the given block on a circle.
+    ///
+    /// If the block is attached to its parent at a cut node, then the circle passes through the cut node
+    /// and its center lies on the ray from the center of the parent through the cut node.
+    fn place_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        anchor: Option<(Graph::NodeIndex, Point)>,
+        positions: &mut TaggedVec<Graph::NodeIndex, Option<Point>>,
+    ) {
+        let order = self.block_node_order(block_index);
+        let radius = (order.len() as f64 * NODE_SPACING / TAU).max(NODE_SPACING / 2.0);
+
+        let (center, first_angle, anchor_position) = if let Some((cut_node, parent_center)) = anchor
+        {
+            let cut_node_position = positions[cut_node].unwrap();
+            let (dx, dy) = (
+                cut_node_position.x - parent_center.x,
+                cut_node_position.y - parent_center.y,
+            );
+            let length = dx.hypot(dy);
+            let (dx, dy) = if length > 0.0 {
+                (dx / length, dy / length)
+            } else {
+                (1.0, 0.0)
+            };
+            let center = Point {
+                x: cut_node_position.x + dx * radius,
+                y: cut_node_position.y + dy * radius,
+            };
+            let anchor_position = order
+                .iter()
+                .position(|&node_index| node_index == cut_node)
+                .unwrap();
+            (center, (-dy).atan2(-dx), anchor_position)
+        } else {
+            (Point::default(), 0.0, 0)
+        };
+
+        for (position, &node_index) in order.iter().enumerate() {
+            if positions[node_index].is_none() {
+                let angle = first_angle
+                    + TAU * (position as f64 - anchor_position as f64) / order.len() as f64;
+                positions[node_index] = Some(Point {
+                    x: center.x + radius * angle.cos(),
+                    y: center.y + radius * angle.sin(),
+                });
+            }
+        }
+    }
+
+    /// Returns the nodes of the given block in the order in which they are placed on its circle.
+    fn block_node_order(&self, block_index: BlockIndex<Graph::IndexType>) -> Vec<Graph::NodeIndex> {
+        let block = &self.blocks[block_index];
+        if let [spqr_node_index] = block.spqr_nodes[..]
+            && self.spqr_nodes[spqr_node_index].spqr_node_type == SPQRNodeType::SNode
+        {
+            self.s_node_cycle(spqr_node_index)
+                .into_iter()
+                .map(|(_, node_index)| node_index)
+                .collect()
+        } else {
+            block.nodes.clone()
+        }
+    }
+}
+
+/// Computes layered coordinates for the tree with the given root and children, see the [module documentation](self).
+///
+/// The y-coordinate grows with the depth, starting at zero, and the smallest x-coordinate is zero.
+pub(crate) fn layered_tree_layout<Node: Copy + Eq + Hash>(
+    root: Node,
+    children: &FxHashMap<Node, Vec<Node>>,
+) -> FxHashMap<Node, Point> {
+    let mut order = vec![root];
+    let mut position = 0;
+    while let Some(&node) = order.get(position) {
+        position += 1;
+        order.extend(children.get(&node).into_iter().flatten());
+    }
+
+    // The horizontal offset of each node relative to its parent,
+    // and the horizontal extent of each subtree per layer relative to its root.
+    let mut offsets = FxHashMap::default();
+    let mut contours = FxHashMap::<_, Vec<(f64, f64)>>::default();
+    for &node in order.iter().rev() {
+        let mut merged: Vec<(f64, f64)> = Vec::new();
+        let mut child_positions = Vec::new();
+        for &child in children.get(&node).into_iter().flatten() {
+            let contour = contours.remove(&child).unwrap();
+            let x = merged
+                .iter()
+                .zip(&contour)
+                .map(|(&(_, right), &(left, _))| right - left + NODE_SPACING)
+                .fold(0.0, f64::max);
+            for (layer, &(left, right)) in contour.iter().enumerate() {
+                if let Some(extent) = merged.get_mut(layer) {
+                    *extent = (extent.0.min(x + left), extent.1.max(x + right));
+                } else {
+                    merged.push((x + left, x + right));
+                }
+            }
+            child_positions.push((child, x));
+        }
+
+        let center = match child_positions[..] {
+            [] => 0.0,
+            [(_, first), .., (_, last)] => (first + last) / 2.0,
+            [(_, only)] => only,
+        };
+        for (child, x) in child_positions {
+            offsets.insert(child, x - center);
+        }
+        let mut contour = vec![(0.0, 0.0)];
+        contour.extend(
+            merged
+                .into_iter()
+                .map(|(left, right)| (left - center, right - center)),
+        );
+        contours.insert(node, contour);
+    }
+
+    let mut positions = FxHashMap::default();
+    positions.insert(root, Point::default());
+    for &node in &order {
+        let position: Point = positions[&node];
+        for &child in children.get(&node).into_iter().flatten() {
+            positions.insert(
+                child,
+                Point {
+                    x: position.x + offsets[&child],
+                    y: position.y + LAYER_SPACING,
+                },
+            );
+        }
+    }
+    let min_x = positions
+        .values()
+        .map(|position| position.x)
+        .fold(f64::INFINITY, f64::min);
+    for position in positions.values_mut() {
+        position.x -= min_x;
+    }
+    positions
+}
+
+fn block_center(points: impl Iterator<Item = Point>) -> Point {
+    let (min, max) = bounding_box(points).unwrap();
+    Point {
+        x: (min.x + max.x) / 2.0,
+        y: (min.y + max.y) / 2.0,
+    }
+}
+
+fn bounding_box(mut points: impl Iterator<Item = Point>) -> Option<(Point, Point)> {
+    let first = points.next()?;
+    Some(points.fold((first, first), |(min, max), point| {
+        (
+            Point {
+                x: min.x.min(point.x),
+                y: min.y.min(point.y),
+            },
+            Point {
+                x: max.x.max(point.x),
+                y: max.y.max(point.y),
+            },
+        )
+    }))
+}
+
+pub(crate) fn escape_xml(string: &str) -> String {
+    let mut result = String::with_capacity(string.len());
+    for character in string.chars() {
+        match character {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(character),
+        }
+    }
+    result
+}
@@ -0,0 +1,95 @@
+use crate::{
+    decomposition::tests::{read_decomposition, read_graph},
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_layout() {
+    for name in ["tiny1", "multiedge", "random5_10_False_0"] {
+        let graph = read_graph(name);
+        let spqr_decomposition = read_decomposition(&graph, name);
+        let layout = spqr_decomposition.layout();
+
+        let (min, _) = layout.bounding_box().unwrap();
+        assert_eq!((min.x, min.y), (0.0, 0.0));
+        let positions = graph
+            .node_indices()
+            .map(|node_index| layout.position(node_index))
+            .collect::<Vec<_>>();
+        for (index, position) in positions.iter().enumerate() {
+            assert!(position.x.is_finite() && position.y.is_finite());
+            assert!(
+                !positions[..index].contains(position),
+                "{name}: two nodes share a position"
+            );
+        }
+
+        let svg = spqr_decomposition.render_svg();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<text").count(), graph.node_count(), "{name}");
+        assert_eq!(
+            svg.matches("<line").count() + svg.matches(r#"fill="none""#).count(),
+            graph.edge_count(),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_layout_spqr_tree() {
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = read_graph(name);
+        let spqr_decomposition = read_decomposition(&graph, name);
+        for (block_index, _) in spqr_decomposition.iter_blocks() {
+            let layout = spqr_decomposition.layout_spqr_tree(block_index);
+            let spqr_nodes = spqr_decomposition
+                .iter_spqr_nodes_in_block(block_index)
+                .map(|(spqr_node_index, _)| spqr_node_index)
+                .collect::<Vec<_>>();
+            assert_eq!(layout.iter_positions().count(), spqr_nodes.len());
+            let Some((min, _)) = layout.bounding_box() else {
+                continue;
+            };
+            assert_eq!((min.x, min.y), (0.0, 0.0));
+
+            for &spqr_node_index in &spqr_nodes {
+                let position = layout.position(spqr_node_index).unwrap();
+                assert_eq!(
+                    position.y,
+                    spqr_decomposition.spqr_tree_depth(spqr_node_index) as f64,
+                );
+                for &other in &spqr_nodes {
+                    let other_position = layout.position(other).unwrap();
+                    if other != spqr_node_index && other_position.y == position.y {
+                        assert!(
+                            (other_position.x - position.x).abs() >= 1.0 - 1e-9,
+                            "{name}: SPQR nodes of the same layer overlap",
+                        );
+                    }
+                }
+                let children = spqr_nodes
+                    .iter()
+                    .filter(|&&child| {
+                        spqr_decomposition
+                            .spqr_tree_parent(child)
+                            .is_some_and(|(parent, _)| parent == spqr_node_index)
+                    })
+                    .map(|&child| layout.position(child).unwrap().x)
+                    .collect::<Vec<_>>();
+                if !children.is_empty() {
+                    let (first, last) = children
+                        .iter()
+                        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &x| {
+                            (min.min(x), max.max(x))
+                        });
+                    assert!((position.x - (first + last) / 2.0).abs() < 1e-9);
+                }
+            }
+        }
+    }
+}
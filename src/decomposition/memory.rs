@@ -0,0 +1,226 @@
+use std::{borrow::Borrow, fmt::Display};
+
+use smallvec::{Array, SmallVec};
+use tagged_vec::TaggedVec;
+
+use rustc_hash::FxHashSet;
+
+use crate::{
+    decomposition::{SPQRDecomposition, extra_data::ExtraData},
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The heap memory used by an SPQR decomposition in bytes, broken down by container, see [`SPQRDecomposition::memory_usage`].
+///
+/// Each container includes the heap memory of the lists stored in its entries, except for their extra data,
+/// which is reported separately in [`extra_data`](Self::extra_data).
+/// Unused capacity is included, since it is allocated all the same.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryBreakdown {
+    pub components: usize,
+    pub blocks: usize,
+    pub cut_nodes: usize,
+    pub spqr_nodes: usize,
+    pub spqr_edges: usize,
+    pub node_data: usize,
+    pub edge_data: usize,
+    /// The extra data strings of all entities, counting strings that are shared between entities once.
+    pub extra_data: usize,
+    /// The cached node sets of blocks and SPQR nodes.
+    pub node_set_cache: usize,
+    /// The cached neighbours of SPQR nodes in their SPQR trees.
+    pub spqr_tree_adjacency_cache: usize,
+    /// The cached parents, depths and ancestor tables of the rooted SPQR trees.
+    pub rooted_spqr_tree_cache: usize,
+    /// The cached map from node names to nodes.
+    pub node_name_cache: usize,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Computes the heap memory used by this decomposition, broken down by container.
+    ///
+    /// The memory used by the graph itself is not included.
+    pub fn memory_usage(&self) -> MemoryBreakdown {
+        let mut extra_data = ExtraDataSize::default();
+
+        let components = tagged_vec_size(&self.components)
+            + self
+                .components
+                .iter_values()
+                .map(|component| {
+                    extra_data.add(&component.extra_data);
+                    vec_size(&component.nodes)
+                        + vec_size(&component.edges)
+                        + vec_size(&component.blocks)
+                        + vec_size(&component.cut_nodes)
+                })
+                .sum::<usize>();
+
+        let blocks = tagged_vec_size(&self.blocks)
+            + self
+                .blocks
+                .iter_values()
+                .map(|block| {
+                    extra_data.add(&block.extra_data);
+                    vec_size(&block.nodes)
+                        + vec_size(&block.cut_nodes)
+                        + vec_size(&block.edges)
+                        + vec_size(&block.spqr_nodes)
+                        + vec_size(&block.spqr_edges)
+                })
+                .sum::<usize>();
+
+        let cut_nodes = tagged_vec_size(&self.cut_nodes)
+            + self
+                .cut_nodes
+                .iter_values()
+                .map(|cut_node| {
+                    extra_data.add(&cut_node.extra_data);
+                    small_vec_size(&cut_node.adjacent_blocks)
+                })
+                .sum::<usize>();
+
+        let spqr_nodes = tagged_vec_size(&self.spqr_nodes)
+            + self
+                .spqr_nodes
+                .iter_values()
+                .map(|spqr_node| {
+                    extra_data.add(&spqr_node.extra_data);
+                    vec_size(&spqr_node.nodes)
+                        + vec_size(&spqr_node.edges)
+                        + small_vec_size(&spqr_node.spqr_edges)
+                })
+                .sum::<usize>();
+
+        let node_data = tagged_vec_size(&self.node_data)
+            + self
+                .node_data
+                .iter_values()
+                .map(|node_data| {
+                    extra_data.add(&node_data.extra_data);
+                    small_vec_size(&node_data.block_indices)
+                        + small_vec_size(&node_data.spqr_node_indices)
+                })
+                .sum::<usize>();
+
+        let edge_data = tagged_vec_size(&self.edge_data);
+        for edge_data in self.edge_data.iter_values() {
+            extra_data.add(&edge_data.extra_data);
+        }
+
+        MemoryBreakdown {
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges: tagged_vec_size(&self.spqr_edges),
+            node_data,
+            edge_data,
+            extra_data: extra_data.bytes,
+            node_set_cache: self.cache.node_sets.heap_size(),
+            spqr_tree_adjacency_cache: self.cache.spqr_tree_adjacency.heap_size(),
+            rooted_spqr_tree_cache: self
+                .cache
+                .rooted_spqr_trees
+                .get()
+                .map_or(0, |rooted_spqr_trees| rooted_spqr_trees.heap_size()),
+            node_name_cache: self.cache.node_names_heap_size(),
+        }
+    }
+}
+
+/// Sums the heap memory of extra data, counting each shared allocation once.
+#[derive(Default)]
+struct ExtraDataSize {
+    allocations: FxHashSet<*const u8>,
+    bytes: usize,
+}
+
+impl ExtraDataSize {
+    fn add(&mut self, extra_data: &ExtraData) {
+        if let Some(allocation) = extra_data.allocation()
+            && self.allocations.insert(allocation)
+        {
+            self.bytes += extra_data.heap_size();
+        }
+    }
+}
+
+impl MemoryBreakdown {
+    /// Returns the total heap memory in bytes.
+    pub fn total(&self) -> usize {
+        let Self {
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+            extra_data,
+            node_set_cache,
+            spqr_tree_adjacency_cache,
+            rooted_spqr_tree_cache,
+            node_name_cache,
+        } = self;
+        components
+            + blocks
+            + cut_nodes
+            + spqr_nodes
+            + spqr_edges
+            + node_data
+            + edge_data
+            + extra_data
+            + node_set_cache
+            + spqr_tree_adjacency_cache
+            + rooted_spqr_tree_cache
+            + node_name_cache
+    }
+}
+
+impl Display for MemoryBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Components: {} bytes", self.components)?;
+        writeln!(f, "Blocks: {} bytes", self.blocks)?;
+        writeln!(f, "Cut nodes: {} bytes", self.cut_nodes)?;
+        writeln!(f, "SPQR nodes: {} bytes", self.spqr_nodes)?;
+        writeln!(f, "SPQR edges: {} bytes", self.spqr_edges)?;
+        writeln!(f, "Node data: {} bytes", self.node_data)?;
+        writeln!(f, "Edge data: {} bytes", self.edge_data)?;
+        writeln!(f, "Extra data: {} bytes", self.extra_data)?;
+        writeln!(f, "Node set cache: {} bytes", self.node_set_cache)?;
+        writeln!(
+            f,
+            "SPQR tree adjacency cache: {} bytes",
+            self.spqr_tree_adjacency_cache
+        )?;
+        writeln!(
+            f,
+            "Rooted SPQR tree cache: {} bytes",
+            self.rooted_spqr_tree_cache
+        )?;
+        writeln!(f, "Node name cache: {} bytes", self.node_name_cache)?;
+        write!(f, "Total: {} bytes", self.total())
+    }
+}
+
+fn vec_size<T>(vec: &Vec<T>) -> usize {
+    vec.capacity() * size_of::<T>()
+}
+
+fn small_vec_size<A: Array>(small_vec: &SmallVec<A>) -> usize {
+    if small_vec.spilled() {
+        small_vec.capacity() * size_of::<A::Item>()
+    } else {
+        0
+    }
+}
+
+fn tagged_vec_size<Index, Value>(tagged_vec: &TaggedVec<Index, Value>) -> usize {
+    tagged_vec.capacity() * size_of::<Value>()
+}
@@ -0,0 +1,28 @@
+use crate::decomposition::tests::{read_decomposition, read_graph};
+
+#[test]
+fn test_memory_usage() {
+    let graph = read_graph("tiny1");
+    let mut spqr_decomposition = read_decomposition(&graph, "tiny1");
+
+    let memory_usage = spqr_decomposition.memory_usage();
+    assert!(memory_usage.blocks >= 5 * size_of::<crate::decomposition::Block<u8, u8, u8>>());
+    assert!(memory_usage.node_data > 0);
+    assert!(memory_usage.spqr_nodes > 0);
+    assert_eq!(memory_usage.node_set_cache, 0);
+    assert!(
+        memory_usage
+            .to_string()
+            .ends_with(&format!("Total: {} bytes", memory_usage.total()))
+    );
+
+    let (block_index, _) = spqr_decomposition.iter_blocks().next().unwrap();
+    spqr_decomposition.block_node_set(block_index);
+    assert!(spqr_decomposition.memory_usage().node_set_cache > 0);
+
+    spqr_decomposition.set_block_extra_data(block_index, "block".to_string());
+    assert_eq!(
+        spqr_decomposition.memory_usage().extra_data,
+        memory_usage.extra_data + 2 * size_of::<usize>() + "block".len()
+    );
+}
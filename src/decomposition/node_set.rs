@@ -0,0 +1,174 @@
+use std::{borrow::Borrow, marker::PhantomData, sync::OnceLock};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        indices::{BlockIndex, GraphIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A dense set of nodes, supporting fast intersection and containment queries.
+///
+/// Only the range of words between the smallest and the largest node is stored,
+/// so sets of nodes with nearby indices stay small even in large graphs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NodeBitSet<NodeIndex> {
+    first_word: usize,
+    words: Vec<u64>,
+    len: usize,
+    node_index: PhantomData<fn() -> NodeIndex>,
+}
+
+/// Lazily built node sets of blocks and SPQR nodes.
+///
+/// The cache is filled on demand through shared references, and must be cleared whenever the decomposition is modified.
+#[derive(Debug, Clone)]
+pub(crate) struct NodeSetCache<NodeIndex> {
+    blocks: OnceLock<Box<[OnceLock<NodeBitSet<NodeIndex>>]>>,
+    spqr_nodes: OnceLock<Box<[OnceLock<NodeBitSet<NodeIndex>>]>>,
+}
+
+impl<NodeIndex: GraphIndex> NodeBitSet<NodeIndex> {
+    pub fn from_nodes(nodes: impl IntoIterator<Item = NodeIndex>) -> Self {
+        let nodes = nodes.into_iter().map(Into::into).collect::<Vec<usize>>();
+        let (Some(&min), Some(&max)) = (nodes.iter().min(), nodes.iter().max()) else {
+            return Self {
+                first_word: 0,
+                words: Vec::new(),
+                len: 0,
+                node_index: PhantomData,
+            };
+        };
+
+        let first_word = min / 64;
+        let mut words = vec![0u64; max / 64 - first_word + 1];
+        for node in nodes {
+            words[node / 64 - first_word] |= 1 << (node % 64);
+        }
+        let len = words.iter().map(|word| word.count_ones() as usize).sum();
+
+        Self {
+            first_word,
+            words,
+            len,
+            node_index: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, node_index: NodeIndex) -> bool {
+        let node: usize = node_index.into();
+        self.word(node / 64) & (1 << (node % 64)) != 0
+    }
+
+    /// Returns the amount of nodes contained in both sets.
+    pub fn intersection_count(&self, other: &Self) -> usize {
+        let start = self.first_word.max(other.first_word);
+        let end = self.end_word().min(other.end_word());
+        (start..end)
+            .map(|word| (self.word(word) & other.word(word)).count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns true if all nodes of this set are contained in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.intersection_count(other) == self.len
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = NodeIndex> {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(offset, &word)| {
+                let base = (self.first_word + offset) * 64;
+                (0..64)
+                    .filter(move |bit| word & (1 << bit) != 0)
+                    .map(move |bit| NodeIndex::from(base + bit))
+            })
+    }
+
+    fn end_word(&self) -> usize {
+        self.first_word + self.words.len()
+    }
+
+    fn word(&self, word: usize) -> u64 {
+        word.checked_sub(self.first_word)
+            .and_then(|offset| self.words.get(offset))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl<NodeIndex> NodeSetCache<NodeIndex> {
+    /// Returns the amount of heap memory used by the cached sets in bytes.
+    pub(crate) fn heap_size(&self) -> usize {
+        [&self.blocks, &self.spqr_nodes]
+            .into_iter()
+            .filter_map(OnceLock::get)
+            .map(|sets| {
+                size_of_val(&**sets)
+                    + sets
+                        .iter()
+                        .filter_map(OnceLock::get)
+                        .map(|set| set.words.capacity() * size_of::<u64>())
+                        .sum::<usize>()
+            })
+            .sum()
+    }
+}
+
+impl<NodeIndex> Default for NodeSetCache<NodeIndex> {
+    fn default() -> Self {
+        Self {
+            blocks: OnceLock::new(),
+            spqr_nodes: OnceLock::new(),
+        }
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the set of nodes of the given block.
+    ///
+    /// The set is built on first access and cached afterwards.
+    pub fn block_node_set(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> &NodeBitSet<Graph::NodeIndex> {
+        let cache = self
+            .cache
+            .node_sets
+            .blocks
+            .get_or_init(|| (0..self.blocks.len()).map(|_| OnceLock::new()).collect());
+        cache[block_index.into_usize()]
+            .get_or_init(|| NodeBitSet::from_nodes(self.blocks[block_index].iter_nodes()))
+    }
+
+    /// Returns the set of nodes of the given SPQR node.
+    ///
+    /// The set is built on first access and cached afterwards.
+    pub fn spqr_node_node_set(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+    ) -> &NodeBitSet<Graph::NodeIndex> {
+        let cache = self.cache.node_sets.spqr_nodes.get_or_init(|| {
+            (0..self.spqr_nodes.len())
+                .map(|_| OnceLock::new())
+                .collect()
+        });
+        cache[spqr_node_index.into_usize()]
+            .get_or_init(|| NodeBitSet::from_nodes(self.spqr_nodes[spqr_node_index].iter_nodes()))
+    }
+}
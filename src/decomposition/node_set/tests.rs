@@ -0,0 +1,56 @@
+use crate::decomposition::tests::{read_decomposition, read_graph};
+
+#[test]
+fn test_node_sets() {
+    let graph = read_graph("random5_10_False_0");
+    let mut spqr_decomposition = read_decomposition(&graph, "random5_10_False_0");
+
+    for (block_index, block) in spqr_decomposition.iter_blocks() {
+        let node_set = spqr_decomposition.block_node_set(block_index);
+        assert_eq!(node_set.len(), block.node_count());
+        assert!(
+            block
+                .iter_nodes()
+                .all(|node_index| node_set.contains(node_index))
+        );
+        assert!(node_set.iter().eq({
+            let mut nodes = block.iter_nodes().collect::<Vec<_>>();
+            nodes.sort_unstable();
+            nodes
+        }));
+        assert!(std::ptr::eq(
+            node_set,
+            spqr_decomposition.block_node_set(block_index)
+        ));
+
+        for (spqr_node_index, _) in spqr_decomposition.iter_spqr_nodes_in_block(block_index) {
+            assert!(
+                spqr_decomposition
+                    .spqr_node_node_set(spqr_node_index)
+                    .is_subset(node_set)
+            );
+        }
+    }
+
+    for (_, cut_node) in spqr_decomposition.iter_cut_nodes() {
+        let blocks = cut_node.iter_adjacent_blocks().collect::<Vec<_>>();
+        let a = spqr_decomposition.block_node_set(blocks[0]);
+        let b = spqr_decomposition.block_node_set(blocks[1]);
+        assert_eq!(a.intersection_count(b), 1);
+    }
+
+    for (_, spqr_edge) in spqr_decomposition.iter_spqr_edges() {
+        let (u, v) = spqr_edge.endpoints();
+        let u = spqr_decomposition.spqr_node_node_set(u);
+        let v = spqr_decomposition.spqr_node_node_set(v);
+        assert_eq!(u.intersection_count(v), 2);
+    }
+
+    if spqr_decomposition.spqr_edge_count() > 0 {
+        let merged = spqr_decomposition.contract_spqr_edge(0.into());
+        assert_eq!(
+            spqr_decomposition.spqr_node_node_set(merged).len(),
+            spqr_decomposition.spqr_node(merged).node_count()
+        );
+    }
+}
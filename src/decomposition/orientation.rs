@@ -0,0 +1,111 @@
+//! Orienting the edges of the graph such that it becomes strongly connected where possible.
+//!
+//! By Robbins' theorem, a connected graph has a strongly connected orientation if and only if it has no bridges.
+//! The bridges of a graph are exactly its blocks that consist of a single edge, and all other blocks have no bridges.
+//! Orienting each such block along a depth-first search, with tree edges pointing away from the root and all other edges pointing back towards it,
+//! makes each block strongly connected, and hence each maximal part of the graph that is connected without using bridges.
+
+use std::borrow::Borrow;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use tagged_vec::TaggedVec;
+
+use crate::{
+    decomposition::{SPQRDecomposition, indices::BlockIndex},
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The orientation of an edge, see [`SPQRDecomposition::strong_orientation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeOrientation {
+    /// The edge points from the first to the second of its [endpoints](StaticGraph::edge_endpoints).
+    Forward,
+    /// The edge points from the second to the first of its [endpoints](StaticGraph::edge_endpoints).
+    Backward,
+    /// The edge is a bridge and needs to be traversable in both directions.
+    Bidirectional,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns an orientation of each edge, such that the nodes of each block that is not a bridge are strongly connected.
+    ///
+    /// Bridges cannot be oriented without disconnecting the graph, so they stay [bidirectional](EdgeOrientation::Bidirectional).
+    /// See the [module documentation](self) for how the other edges are oriented.
+    /// Self-loops are oriented [forward](EdgeOrientation::Forward).
+    pub fn strong_orientation(&self) -> TaggedVec<Graph::EdgeIndex, EdgeOrientation> {
+        let mut orientations: TaggedVec<Graph::EdgeIndex, Option<EdgeOrientation>> = self
+            .graph_ref()
+            .edge_indices()
+            .map(|edge_index| {
+                let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+                (u == v).then_some(EdgeOrientation::Forward)
+            })
+            .collect::<Vec<_>>()
+            .into();
+
+        for block_index in self.blocks.iter_indices(..) {
+            self.orient_block(block_index, &mut orientations);
+        }
+
+        orientations
+            .into_values_iter()
+            .map(|orientation| orientation.unwrap())
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Orients the edges of the given block that are not self-loops, see [`strong_orientation`](Self::strong_orientation).
+    fn orient_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        orientations: &mut TaggedVec<Graph::EdgeIndex, Option<EdgeOrientation>>,
+    ) {
+        let mut adjacency = FxHashMap::<_, Vec<_>>::default();
+        for edge_index in self.iter_edges_in_block(block_index) {
+            let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+            if u != v {
+                adjacency.entry(u).or_default().push((edge_index, v));
+                adjacency.entry(v).or_default().push((edge_index, u));
+            }
+        }
+        let Some((&root, root_edges)) = adjacency.iter().next() else {
+            return;
+        };
+        if adjacency.len() == 2 && root_edges.len() == 1 {
+            orientations[root_edges[0].0] = Some(EdgeOrientation::Bidirectional);
+            return;
+        }
+
+        let orient = |edge_index, from| {
+            if self.graph_ref().edge_endpoints(edge_index).0 == from {
+                EdgeOrientation::Forward
+            } else {
+                EdgeOrientation::Backward
+            }
+        };
+        let mut visited = FxHashSet::from_iter([root]);
+        // Each stack entry is a node of the search path together with the position of the next incident edge to consider.
+        let mut stack = vec![(root, 0)];
+        while let Some((node_index, next)) = stack.last_mut() {
+            let node_index = *node_index;
+            let Some(&(edge_index, neighbor)) = adjacency[&node_index].get(*next) else {
+                stack.pop();
+                continue;
+            };
+            *next += 1;
+
+            if orientations[edge_index].is_some() {
+                continue;
+            }
+            orientations[edge_index] = Some(orient(edge_index, node_index));
+            if visited.insert(neighbor) {
+                stack.push((neighbor, 0));
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::{
+    decomposition::{SPQRDecomposition, orientation::EdgeOrientation, tests::all_test_inputs},
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_strong_orientation() {
+    let graphs = all_test_inputs();
+
+    for (graph, spqr_decomposition_file) in &graphs {
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(graph, spqr_decomposition_file.as_bytes()).unwrap();
+        let orientations = spqr_decomposition.strong_orientation();
+
+        for (block_index, block) in spqr_decomposition.iter_blocks() {
+            let edges = spqr_decomposition
+                .iter_edges_in_block(block_index)
+                .filter(|&edge_index| {
+                    let (u, v) = graph.edge_endpoints(edge_index);
+                    u != v
+                })
+                .collect::<Vec<_>>();
+            if edges.len() == 1 {
+                assert_eq!(orientations[edges[0]], EdgeOrientation::Bidirectional);
+                continue;
+            }
+
+            // Each node of the block must reach and be reached from the first node using only the oriented edges of the block.
+            let root = block.iter_nodes().next().unwrap();
+            for reverse in [false, true] {
+                let mut reached = HashSet::from([root]);
+                let mut queue = VecDeque::from([root]);
+                while let Some(node_index) = queue.pop_front() {
+                    for &edge_index in &edges {
+                        let (u, v) = graph.edge_endpoints(edge_index);
+                        let (from, to) = match (orientations[edge_index], reverse) {
+                            (EdgeOrientation::Forward, false)
+                            | (EdgeOrientation::Backward, true) => (u, v),
+                            (EdgeOrientation::Backward, false)
+                            | (EdgeOrientation::Forward, true) => (v, u),
+                            (EdgeOrientation::Bidirectional, _) => {
+                                panic!("only bridges are bidirectional")
+                            }
+                        };
+                        if from == node_index && reached.insert(to) {
+                            queue.push_back(to);
+                        }
+                    }
+                }
+                assert_eq!(reached.len(), block.iter_nodes().count());
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::borrow::Borrow;
+
+use crate::{
+    decomposition::{
+        Block, SPQRDecomposition, SPQRNode,
+        indices::{BlockIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+    parallel::ParIter,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+where
+    Graph::NodeIndex: Sync,
+    Graph::EdgeIndex: Sync,
+    Graph::IndexType: Sync,
+{
+    /// Returns a parallel iterator over all blocks.
+    pub fn par_iter_blocks<'this>(
+        &'this self,
+    ) -> ParIter<
+        impl Fn(
+            usize,
+        ) -> (
+            BlockIndex<Graph::IndexType>,
+            &'this Block<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+        ) + Sync,
+    > {
+        let blocks = self.blocks.as_untagged_slice();
+        ParIter::new(blocks.len(), move |index| (index.into(), &blocks[index]))
+    }
+
+    /// Returns a parallel iterator over all SPQR nodes.
+    pub fn par_iter_spqr_nodes<'this>(
+        &'this self,
+    ) -> ParIter<
+        impl Fn(
+            usize,
+        ) -> (
+            SPQRNodeIndex<Graph::IndexType>,
+            &'this SPQRNode<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+        ) + Sync,
+    > {
+        let spqr_nodes = self.spqr_nodes.as_untagged_slice();
+        ParIter::new(spqr_nodes.len(), move |index| {
+            (index.into(), &spqr_nodes[index])
+        })
+    }
+
+    /// Applies `f` to all blocks in parallel and returns the results ordered by block index.
+    pub fn par_map_blocks<Result: Send>(
+        &self,
+        f: impl Fn(
+            BlockIndex<Graph::IndexType>,
+            &Block<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>,
+        ) -> Result
+        + Sync,
+    ) -> Vec<Result> {
+        self.par_iter_blocks()
+            .map(|(block_index, block)| f(block_index, block))
+    }
+}
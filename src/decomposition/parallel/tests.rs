@@ -0,0 +1,31 @@
+use crate::decomposition::tests::{read_decomposition, read_graph};
+
+#[test]
+fn test_parallel_iterators() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let graph = read_graph("Ultrabubble_dataset_chr_22_G182870");
+    let spqr_decomposition = read_decomposition(&graph, "Ultrabubble_dataset_chr_22_G182870");
+
+    assert_eq!(
+        spqr_decomposition.par_map_blocks(|block_index, block| (block_index, block.node_count())),
+        spqr_decomposition
+            .iter_blocks()
+            .map(|(block_index, block)| (block_index, block.node_count()))
+            .collect::<Vec<_>>()
+    );
+
+    let node_count = AtomicUsize::new(0);
+    let par_iter = spqr_decomposition.par_iter_spqr_nodes();
+    assert_eq!(par_iter.len(), spqr_decomposition.spqr_node_count());
+    par_iter.for_each(|(_, spqr_node)| {
+        node_count.fetch_add(spqr_node.node_count(), Ordering::Relaxed);
+    });
+    assert_eq!(
+        node_count.into_inner(),
+        spqr_decomposition
+            .iter_spqr_nodes()
+            .map(|(_, spqr_node)| spqr_node.node_count())
+            .sum::<usize>()
+    );
+}
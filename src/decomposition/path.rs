@@ -0,0 +1,126 @@
+use std::borrow::Borrow;
+
+use thiserror::Error;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        indices::{BlockIndex, ComponentIndex, CutNodeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// How a walk traverses the hierarchy of a decomposition, see [`SPQRDecomposition::classify_path`].
+///
+/// The lists are in the order of the walk, with consecutive repetitions merged.
+/// An entity that is left and entered again later appears again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathClassification<NodeIndex, IndexType> {
+    pub components: Vec<ComponentIndex<IndexType>>,
+    pub blocks: Vec<BlockIndex<IndexType>>,
+    /// The SPQR nodes containing the edges of the walk.
+    /// Edges of blocks with less than three nodes are not contained in any SPQR node and do not contribute.
+    pub spqr_nodes: Vec<SPQRNodeIndex<IndexType>>,
+    pub cut_node_crossings: Vec<CutNodeCrossing<NodeIndex, IndexType>>,
+}
+
+/// A step of a walk from one block into another through a cut node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CutNodeCrossing<NodeIndex, IndexType> {
+    /// The position in the walk of the first edge after the cut node.
+    pub position: usize,
+    pub node: NodeIndex,
+    pub cut_node: CutNodeIndex<IndexType>,
+    pub from_block: BlockIndex<IndexType>,
+    pub to_block: BlockIndex<IndexType>,
+}
+
+/// The error returned by [`SPQRDecomposition::classify_path`] if two consecutive edges of the walk do not share a node.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("edge {position} of the path does not share a node with its predecessor")]
+pub struct DisconnectedPathError {
+    pub position: usize,
+}
+
+impl<NodeIndex, IndexType> PathClassification<NodeIndex, IndexType> {
+    /// Returns true if the walk does not leave the block of its first edge.
+    pub fn is_within_single_block(&self) -> bool {
+        self.blocks.len() <= 1
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Reports which components, blocks and SPQR nodes the given walk passes through, and where it crosses cut nodes.
+    ///
+    /// The walk is given as a sequence of edges, where each edge shares a node with its predecessor.
+    /// The direction in which an edge is traversed is irrelevant.
+    pub fn classify_path(
+        &self,
+        path: impl IntoIterator<Item = Graph::EdgeIndex>,
+    ) -> Result<PathClassification<Graph::NodeIndex, Graph::IndexType>, DisconnectedPathError> {
+        let mut classification = PathClassification {
+            components: Vec::new(),
+            blocks: Vec::new(),
+            spqr_nodes: Vec::new(),
+            cut_node_crossings: Vec::new(),
+        };
+        let mut previous_edge = None;
+
+        for (position, edge_index) in path.into_iter().enumerate() {
+            let edge_data = &self.edge_data[edge_index];
+            let endpoints = self.graph_ref().edge_endpoints(edge_index);
+
+            if let Some(previous_endpoints) = previous_edge {
+                let shared_node = shared_endpoint(previous_endpoints, endpoints)
+                    .ok_or(DisconnectedPathError { position })?;
+                let from_block = classification.blocks.last().copied();
+                let to_block = edge_data.block_index.into_option();
+
+                if let (Some(from_block), Some(to_block)) = (from_block, to_block)
+                    && from_block != to_block
+                {
+                    classification.cut_node_crossings.push(CutNodeCrossing {
+                        position,
+                        node: shared_node,
+                        cut_node: self.node_data[shared_node]
+                            .cut_node_index
+                            .into_option()
+                            .expect("blocks meet in a node that is not a cut node"),
+                        from_block,
+                        to_block,
+                    });
+                }
+            }
+
+            push_distinct(&mut classification.components, edge_data.component_index);
+            if let Some(block_index) = edge_data.block_index.into_option() {
+                push_distinct(&mut classification.blocks, block_index);
+            }
+            if let Some(spqr_node_index) = edge_data.spqr_node_index.into_option() {
+                push_distinct(&mut classification.spqr_nodes, spqr_node_index);
+            }
+            previous_edge = Some(endpoints);
+        }
+
+        Ok(classification)
+    }
+}
+
+/// Returns a node shared by both edges, preferring the second endpoint of the first edge.
+fn shared_endpoint<NodeIndex: Eq + Copy>(
+    (u1, v1): (NodeIndex, NodeIndex),
+    (u2, v2): (NodeIndex, NodeIndex),
+) -> Option<NodeIndex> {
+    [v1, u1].into_iter().find(|node| *node == u2 || *node == v2)
+}
+
+fn push_distinct<T: Eq>(list: &mut Vec<T>, item: T) {
+    if list.last() != Some(&item) {
+        list.push(item);
+    }
+}
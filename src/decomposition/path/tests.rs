@@ -0,0 +1,50 @@
+use crate::{
+    decomposition::tests::{read_decomposition, read_graph},
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_classify_path() {
+    let graph = read_graph("tiny1");
+    let spqr_decomposition = read_decomposition(&graph, "tiny1");
+    let node = |name| spqr_decomposition.node_index_by_name(name).unwrap();
+    let edge = |u, v| graph.edges_between(node(u), node(v)).next().unwrap();
+
+    let path = [
+        edge("j", "k"),
+        edge("k", "i"),
+        edge("i", "g"),
+        edge("g", "h"),
+    ];
+    let classification = spqr_decomposition.classify_path(path).unwrap();
+    assert_eq!(classification.components, [0.into()]);
+    assert_eq!(classification.blocks, [0.into(), 1.into()]);
+    assert_eq!(classification.spqr_nodes, [0.into(), 1.into()]);
+    assert_eq!(classification.cut_node_crossings.len(), 1);
+    let crossing = classification.cut_node_crossings[0];
+    assert_eq!(crossing.position, 2);
+    assert_eq!(crossing.node, node("i"));
+    assert_eq!(
+        spqr_decomposition.cut_node(crossing.cut_node).node(),
+        node("i")
+    );
+    assert_eq!(
+        (crossing.from_block, crossing.to_block),
+        (0.into(), 1.into())
+    );
+    assert!(!classification.is_within_single_block());
+
+    let classification = spqr_decomposition
+        .classify_path([edge("j", "k"), edge("k", "i"), edge("i", "j")])
+        .unwrap();
+    assert!(classification.is_within_single_block());
+    assert!(classification.cut_node_crossings.is_empty());
+
+    assert_eq!(
+        spqr_decomposition
+            .classify_path([edge("j", "k"), edge("g", "h")])
+            .unwrap_err()
+            .position,
+        1
+    );
+}
@@ -0,0 +1,189 @@
+//! Deciding whether two nodes are connected by a path that avoids a set of forbidden nodes.
+//!
+//! Every path between two nodes passes through the cut nodes on the path between them in the block-cut tree,
+//! and within each block on that path, it only needs to connect the two nodes through which it enters and leaves the block.
+//! Since blocks are biconnected, a single forbidden node in a block never separates two other nodes of it.
+//! Two forbidden nodes in a block separate two other nodes only if they form a separation pair, which the SPQR tree describes:
+//! either they are two non-adjacent nodes of the cycle of an S-node, which splits into two arcs,
+//! or they are the poles of some virtual edges, whose removal splits the SPQR tree into parts.
+//! Blocks with more forbidden nodes are searched directly.
+
+use std::borrow::Borrow;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns true if there is a path from `u` to `v` that does not visit any of the forbidden nodes.
+    ///
+    /// There is no such path if `u` or `v` is forbidden, and otherwise there is always one from a node to itself.
+    /// See the [module documentation](self) for how the decomposition is used.
+    /// If a block on the way has at least three forbidden nodes, it is searched in time linear in its size.
+    pub fn exists_path_avoiding(
+        &self,
+        u: Graph::NodeIndex,
+        v: Graph::NodeIndex,
+        forbidden_nodes: impl IntoIterator<Item = Graph::NodeIndex>,
+    ) -> bool {
+        let forbidden_nodes = forbidden_nodes.into_iter().collect::<FxHashSet<_>>();
+        if forbidden_nodes.contains(&u) || forbidden_nodes.contains(&v) {
+            return false;
+        }
+        if u == v {
+            return true;
+        }
+        if self.node_component_index(u) != self.node_component_index(v) {
+            return false;
+        }
+
+        let path = self.block_cut_tree_path(u, v);
+        if path[1..]
+            .iter()
+            .any(|(_, from, _)| forbidden_nodes.contains(from))
+        {
+            return false;
+        }
+        let mut block_forbidden_nodes = FxHashMap::<_, Vec<_>>::default();
+        for &node_index in &forbidden_nodes {
+            for block_index in self.node_block_indices(node_index) {
+                block_forbidden_nodes
+                    .entry(block_index)
+                    .or_default()
+                    .push(node_index);
+            }
+        }
+
+        path.into_iter().all(|(block_index, from, to)| {
+            match block_forbidden_nodes
+                .get(&block_index)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+            {
+                [] | [_] => true,
+                &[a, b] => self.separation_pair_connects(block_index, (a, b), from, to),
+                _ => self.block_search_avoiding(block_index, from, to, &forbidden_nodes),
+            }
+        })
+    }
+
+    /// Returns true if the distinct nodes `x` and `y` of the given block are connected after removing the nodes of the given pair,
+    /// see the [module documentation](self).
+    fn separation_pair_connects(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        (a, b): (Graph::NodeIndex, Graph::NodeIndex),
+        x: Graph::NodeIndex,
+        y: Graph::NodeIndex,
+    ) -> bool {
+        let block_spqr_nodes = |node_index| {
+            self.node_spqr_node_indices(node_index)
+                .filter(move |&spqr_node_index| {
+                    self.spqr_nodes[spqr_node_index].block == block_index
+                })
+        };
+
+        for s_node_index in block_spqr_nodes(a) {
+            let s_node = &self.spqr_nodes[s_node_index];
+            if s_node.spqr_node_type != SPQRNodeType::SNode || !s_node.nodes.contains(&b) {
+                continue;
+            }
+            let cycle = self.s_node_cycle(s_node_index);
+            let position = |node_index| {
+                cycle
+                    .iter()
+                    .position(|&(_, cycle_node)| cycle_node == node_index)
+            };
+            let (position_a, position_b) = (position(a).unwrap(), position(b).unwrap());
+            let offset = (position_b + cycle.len() - position_a) % cycle.len();
+            if offset == 1 || offset == cycle.len() - 1 {
+                continue;
+            }
+
+            // The arc from `a` to `b` in the direction of the cycle contains the nodes and edges at these relative positions.
+            let relative = |position| (position + cycle.len() - position_a) % cycle.len();
+            let on_forward_arc = |node_index| {
+                if let Some(position) = position(node_index) {
+                    return relative(position) < offset;
+                }
+                let target = block_spqr_nodes(node_index).next().unwrap();
+                let (_, spqr_edge_index) = self.spqr_tree_path(s_node_index, target)[0];
+                let edge_position = cycle
+                    .iter()
+                    .position(|&(skeleton_edge, _)| {
+                        skeleton_edge == SkeletonEdge::Virtual(spqr_edge_index.unwrap())
+                    })
+                    .unwrap();
+                relative(edge_position) < offset
+            };
+            return on_forward_arc(x) == on_forward_arc(y);
+        }
+
+        // Otherwise, only the virtual edges with poles `a` and `b` separate the SPQR tree.
+        let is_separating = |spqr_edge_index| {
+            let (p, q) = self.spqr_edges[spqr_edge_index].virtual_edge;
+            (p, q) == (a, b) || (p, q) == (b, a)
+        };
+        let start: SPQRNodeIndex<_> = block_spqr_nodes(x).next().unwrap();
+        let targets = block_spqr_nodes(y).collect::<FxHashSet<_>>();
+        let mut visited = FxHashSet::from_iter([start]);
+        let mut stack = vec![start];
+        while let Some(spqr_node_index) = stack.pop() {
+            if targets.contains(&spqr_node_index) {
+                return true;
+            }
+            for spqr_edge_index in self.spqr_nodes[spqr_node_index].iter_incident_spqr_edges() {
+                if is_separating(spqr_edge_index) {
+                    continue;
+                }
+                let neighbor = self.opposite_spqr_node(spqr_node_index, spqr_edge_index);
+                if visited.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns true if the distinct nodes `x` and `y` of the given block are connected within the block without visiting forbidden nodes.
+    fn block_search_avoiding(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        x: Graph::NodeIndex,
+        y: Graph::NodeIndex,
+        forbidden_nodes: &FxHashSet<Graph::NodeIndex>,
+    ) -> bool {
+        let mut adjacency = FxHashMap::<_, Vec<_>>::default();
+        for edge_index in self.iter_edges_in_block(block_index) {
+            let (p, q) = self.graph_ref().edge_endpoints(edge_index);
+            if !forbidden_nodes.contains(&p) && !forbidden_nodes.contains(&q) {
+                adjacency.entry(p).or_default().push(q);
+                adjacency.entry(q).or_default().push(p);
+            }
+        }
+        let mut visited = FxHashSet::from_iter([x]);
+        let mut stack = vec![x];
+        while let Some(node_index) = stack.pop() {
+            if node_index == y {
+                return true;
+            }
+            for &neighbor in adjacency.get(&node_index).into_iter().flatten() {
+                if visited.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+        false
+    }
+}
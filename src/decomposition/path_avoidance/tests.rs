@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{TestGraph, all_test_inputs},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_exists_path_avoiding() {
+    fn brute_force_exists_path(
+        graph: &TestGraph,
+        u: <TestGraph as StaticGraph>::NodeIndex,
+        v: <TestGraph as StaticGraph>::NodeIndex,
+        forbidden_nodes: &[<TestGraph as StaticGraph>::NodeIndex],
+    ) -> bool {
+        if forbidden_nodes.contains(&u) {
+            return false;
+        }
+        let mut visited = HashSet::from([u]);
+        let mut stack = vec![u];
+        while let Some(node) = stack.pop() {
+            if node == v {
+                return true;
+            }
+            for edge_index in graph.incident_edges(node) {
+                let (a, b) = graph.edge_endpoints(edge_index);
+                let neighbor = if a == node { b } else { a };
+                if !forbidden_nodes.contains(&neighbor) && visited.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+        false
+    }
+
+    let graphs = all_test_inputs();
+
+    for (graph, spqr_decomposition_file) in &graphs {
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(graph, spqr_decomposition_file.as_bytes()).unwrap();
+        let nodes = graph.node_indices().collect::<Vec<_>>();
+        let mut forbidden_sets = vec![Vec::new()];
+        for (i, &a) in nodes.iter().enumerate() {
+            forbidden_sets.push(vec![a]);
+            for &b in &nodes[i + 1..] {
+                forbidden_sets.push(vec![a, b]);
+            }
+            forbidden_sets.push(nodes.iter().cycle().skip(i).take(3).copied().collect());
+        }
+        for forbidden_nodes in &forbidden_sets {
+            for &u in &nodes {
+                for &v in &nodes {
+                    assert_eq!(
+                        spqr_decomposition.exists_path_avoiding(
+                            u,
+                            v,
+                            forbidden_nodes.iter().copied()
+                        ),
+                        brute_force_exists_path(graph, u, v, forbidden_nodes),
+                        "{u} {v} {forbidden_nodes:?}",
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Counting the paths between two nodes along the block-cut tree and the SPQR trees.
+//!
+//! A path between two nodes passes through the blocks on the path between them in the block-cut tree,
+//! entering and leaving each block through a cut node, so the number of paths is a product over these blocks.
+//! Within a block, a path passes through the skeletons of the SPQR nodes on the path between the SPQR nodes containing its endpoints,
+//! moving from one skeleton to the next through the poles of the virtual edge between them.
+//! Each other virtual edge it uses can be replaced by any path between its poles on the other side of the virtual edge.
+//! The number of these follows from the series and parallel recurrences:
+//! in the skeleton of an S-node, the numbers of the other skeleton edges are multiplied, and in the skeleton of a P-node, they are added up.
+//! There is no such recurrence for the skeleton of an R-node, so counting fails if one is needed.
+
+use std::{borrow::Borrow, collections::hash_map::Entry};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use thiserror::Error;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, CutNodeIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The error returned by [`SPQRDecomposition::count_paths_mod`] if counting the paths requires the skeleton of an R-node.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("counting the paths requires the skeleton of R-node {r_node}")]
+pub struct RNodePathCountError<IndexType> {
+    pub r_node: SPQRNodeIndex<IndexType>,
+}
+
+/// A partial path that ends at a pole `c` of a virtual edge with poles `c` and `d`, together with whether it visits `d`.
+struct PoleState<NodeIndex> {
+    end: NodeIndex,
+    other: NodeIndex,
+    visits_other: bool,
+    count: u64,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the number of simple paths between `u` and `v` modulo `modulus`.
+    ///
+    /// Paths are sequences of edges, so paths that differ only in which of some parallel edges they use are counted separately.
+    /// Self-loops are never part of a simple path, and there is exactly one path from a node to itself.
+    /// See the [module documentation](self) for how the decomposition is used.
+    /// The modulus is usually a prime, but any positive modulus works.
+    ///
+    /// Returns an error if a block between `u` and `v` has an R-node whose skeleton is needed for counting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn count_paths_mod(
+        &self,
+        u: Graph::NodeIndex,
+        v: Graph::NodeIndex,
+        modulus: u64,
+    ) -> Result<u64, RNodePathCountError<Graph::IndexType>> {
+        assert_ne!(modulus, 0, "the modulus is zero");
+        if u == v {
+            return Ok(1 % modulus);
+        }
+        if self.node_component_index(u) != self.node_component_index(v) {
+            return Ok(0);
+        }
+
+        let mut count = 1 % modulus;
+        for (block_index, from, to) in self.block_cut_tree_path(u, v) {
+            let block_count = self.count_block_paths_mod(block_index, from, to, modulus)?;
+            count = mul_mod(count, block_count, modulus);
+        }
+        Ok(count)
+    }
+
+    /// Returns the blocks on the path from `u` to `v` in the block-cut tree,
+    /// each together with the nodes through which the path enters and leaves it.
+    ///
+    /// The nodes must be distinct and in the same component.
+    pub(crate) fn block_cut_tree_path(
+        &self,
+        u: Graph::NodeIndex,
+        v: Graph::NodeIndex,
+    ) -> Vec<(
+        BlockIndex<Graph::IndexType>,
+        Graph::NodeIndex,
+        Graph::NodeIndex,
+    )> {
+        let targets = self.node_block_indices(v).collect::<FxHashSet<_>>();
+        let mut parents: FxHashMap<
+            BlockIndex<Graph::IndexType>,
+            Option<(BlockIndex<Graph::IndexType>, CutNodeIndex<Graph::IndexType>)>,
+        > = self
+            .node_block_indices(u)
+            .map(|block_index| (block_index, None))
+            .collect();
+        let mut queue = self.node_block_indices(u).collect::<Vec<_>>();
+        let mut position = 0;
+        let target = loop {
+            let block_index = queue[position];
+            position += 1;
+            if targets.contains(&block_index) {
+                break block_index;
+            }
+            for &cut_node_index in &self.blocks[block_index].cut_nodes {
+                for &neighbor in &self.cut_nodes[cut_node_index].adjacent_blocks {
+                    if let Entry::Vacant(entry) = parents.entry(neighbor) {
+                        entry.insert(Some((block_index, cut_node_index)));
+                        queue.push(neighbor);
+                    }
+                }
+            }
+        };
+
+        let mut path = Vec::new();
+        let mut block_index = target;
+        let mut to = v;
+        while let Some(parent) = parents[&block_index] {
+            let (parent_block, cut_node_index) = parent;
+            let from = self.cut_nodes[cut_node_index].node;
+            path.push((block_index, from, to));
+            block_index = parent_block;
+            to = from;
+        }
+        path.push((block_index, u, to));
+        path.reverse();
+        path
+    }
+
+    /// Returns the number of simple paths between the distinct nodes `s` and `t` within the given block modulo `modulus`.
+    fn count_block_paths_mod(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        s: Graph::NodeIndex,
+        t: Graph::NodeIndex,
+        modulus: u64,
+    ) -> Result<u64, RNodePathCountError<Graph::IndexType>> {
+        let block = &self.blocks[block_index];
+        if block.spqr_nodes.is_empty() {
+            let edge_count = block
+                .iter_edges()
+                .filter(|&edge_index| {
+                    let (a, b) = self.graph_ref().edge_endpoints(edge_index);
+                    a != b
+                })
+                .count();
+            return Ok(edge_count as u64 % modulus);
+        }
+
+        let in_block = |node_index| {
+            self.node_spqr_node_indices(node_index)
+                .filter(|&spqr_node_index| self.spqr_nodes[spqr_node_index].block == block_index)
+                .collect::<Vec<_>>()
+        };
+        let (s_spqr_nodes, t_spqr_nodes) = (in_block(s), in_block(t));
+        let path = self.spqr_tree_path(s_spqr_nodes[0], t_spqr_nodes[0]);
+        // The SPQR nodes containing a node form a subtree, so the path enters and leaves each of them only once.
+        let start = path
+            .iter()
+            .rposition(|(spqr_node_index, _)| s_spqr_nodes.contains(spqr_node_index))
+            .unwrap();
+        let end = path
+            .iter()
+            .position(|(spqr_node_index, _)| t_spqr_nodes.contains(spqr_node_index))
+            .unwrap();
+        if start >= end {
+            // Some SPQR node contains both nodes.
+            let &(spqr_node_index, _) = path[end..=start]
+                .iter()
+                .find(|(spqr_node_index, _)| {
+                    s_spqr_nodes.contains(spqr_node_index) && t_spqr_nodes.contains(spqr_node_index)
+                })
+                .unwrap();
+            self.ensure_no_r_node(spqr_node_index)?;
+            let edges = self.counted_skeleton_edges(spqr_node_index, &[], modulus)?;
+            return Ok(weighted_paths(&edges, s, t, None, modulus)
+                .into_iter()
+                .fold(0, |sum, (count, _)| (sum + count) % modulus));
+        }
+
+        let mut states: Vec<PoleState<Graph::NodeIndex>> = vec![PoleState {
+            end: s,
+            other: s,
+            visits_other: false,
+            count: 1 % modulus,
+        }];
+        for position in start..=end {
+            let (spqr_node_index, next_edge) = path[position];
+            self.ensure_no_r_node(spqr_node_index)?;
+            let previous_edge = (position > start).then(|| path[position - 1].1.unwrap());
+            let excluded = previous_edge
+                .into_iter()
+                .chain(next_edge)
+                .collect::<Vec<_>>();
+            let edges = self.counted_skeleton_edges(spqr_node_index, &excluded, modulus)?;
+            let targets = if position == end {
+                vec![(t, t)]
+            } else {
+                let (a, b) = self.spqr_edges[next_edge.unwrap()].virtual_edge;
+                vec![(a, b), (b, a)]
+            };
+
+            let mut next_states = Vec::new();
+            for state in &states {
+                let forbidden = state.visits_other.then_some(state.other);
+                for &(end, other) in &targets {
+                    let mut next_state = PoleState {
+                        end,
+                        other,
+                        visits_other: false,
+                        count: 0,
+                    };
+                    let mut visiting_count = 0;
+                    for (count, visited) in
+                        weighted_paths(&edges, state.end, end, forbidden, modulus)
+                    {
+                        let count = mul_mod(count, state.count, modulus);
+                        if visited.contains(&other) || forbidden == Some(other) {
+                            visiting_count = (visiting_count + count) % modulus;
+                        } else {
+                            next_state.count = (next_state.count + count) % modulus;
+                        }
+                    }
+                    next_states.push(PoleState {
+                        end,
+                        other,
+                        visits_other: true,
+                        count: visiting_count,
+                    });
+                    next_states.push(next_state);
+                }
+            }
+            states = next_states;
+        }
+        Ok(states
+            .into_iter()
+            .fold(0, |sum, state| (sum + state.count) % modulus))
+    }
+
+    /// Returns an error if the given SPQR node is an R-node.
+    fn ensure_no_r_node(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+    ) -> Result<(), RNodePathCountError<Graph::IndexType>> {
+        if self.spqr_nodes[spqr_node_index].spqr_node_type == SPQRNodeType::RNode {
+            Err(RNodePathCountError {
+                r_node: spqr_node_index,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the edges of the skeleton of the given SPQR node without self-loops and without the excluded virtual edges,
+    /// each with the number of paths between its endpoints that it represents modulo `modulus`.
+    fn counted_skeleton_edges(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        excluded: &[SPQREdgeIndex<Graph::IndexType>],
+        modulus: u64,
+    ) -> Result<Vec<(Graph::NodeIndex, Graph::NodeIndex, u64)>, RNodePathCountError<Graph::IndexType>>
+    {
+        let mut edges = Vec::new();
+        for (skeleton_edge, (a, b)) in self.iter_skeleton_edges(spqr_node_index) {
+            if a == b {
+                continue;
+            }
+            match skeleton_edge {
+                SkeletonEdge::Real(_) => edges.push((a, b, 1 % modulus)),
+                SkeletonEdge::Virtual(spqr_edge_index) => {
+                    if !excluded.contains(&spqr_edge_index) {
+                        let count =
+                            self.count_pole_paths_mod(spqr_node_index, spqr_edge_index, modulus)?;
+                        edges.push((a, b, count));
+                    }
+                }
+            }
+        }
+        Ok(edges)
+    }
+
+    /// Returns the number of simple paths between the poles of the given virtual edge on its side opposite to the given SPQR node modulo `modulus`.
+    fn count_pole_paths_mod(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+        modulus: u64,
+    ) -> Result<u64, RNodePathCountError<Graph::IndexType>> {
+        self.reduce_pole_side(
+            spqr_node_index,
+            spqr_edge_index,
+            &mut |_| 1 % modulus,
+            &mut |a, b| mul_mod(a, b, modulus),
+            &mut |a, b| (a + b) % modulus,
+        )
+        .map_err(|r_node| RNodePathCountError { r_node })
+    }
+}
+
+/// Returns all simple paths from `from` to `to` over the given weighted edges that avoid the forbidden node,
+/// each as the product of its edge weights modulo `modulus` together with the nodes it visits.
+fn weighted_paths<NodeIndex: Copy + Eq>(
+    edges: &[(NodeIndex, NodeIndex, u64)],
+    from: NodeIndex,
+    to: NodeIndex,
+    forbidden: Option<NodeIndex>,
+    modulus: u64,
+) -> Vec<(u64, Vec<NodeIndex>)> {
+    let mut paths = Vec::new();
+    if forbidden == Some(from) || forbidden == Some(to) {
+        return paths;
+    }
+    if from == to {
+        paths.push((1 % modulus, vec![from]));
+        return paths;
+    }
+
+    let mut visited = vec![from];
+    let mut counts = vec![1 % modulus];
+    // Each stack entry is the next edge position to try from the current end of the path.
+    let mut stack = vec![0];
+    while let Some(next) = stack.last_mut() {
+        let node_index = *visited.last().unwrap();
+        let Some(position) = (*next..edges.len()).find(|&position| {
+            let (a, b, _) = edges[position];
+            let neighbor = if a == node_index { b } else { a };
+            (a == node_index || b == node_index)
+                && !visited.contains(&neighbor)
+                && forbidden != Some(neighbor)
+        }) else {
+            stack.pop();
+            visited.pop();
+            counts.pop();
+            continue;
+        };
+        *next = position + 1;
+
+        let (a, b, weight) = edges[position];
+        let neighbor = if a == node_index { b } else { a };
+        let count = mul_mod(*counts.last().unwrap(), weight, modulus);
+        if neighbor == to {
+            let mut path_nodes = visited.clone();
+            path_nodes.push(neighbor);
+            paths.push((count, path_nodes));
+        } else {
+            visited.push(neighbor);
+            counts.push(count);
+            stack.push(0);
+        }
+    }
+    paths
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((u128::from(a) * u128::from(b)) % u128::from(modulus)) as u64
+}
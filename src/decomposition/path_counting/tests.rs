@@ -0,0 +1,63 @@
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        tests::{TestGraph, all_test_inputs},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_count_paths_mod() {
+    /// Counts the simple paths as sequences of edges by depth-first search.
+    fn brute_force_path_count(
+        graph: &TestGraph,
+        node: <TestGraph as StaticGraph>::NodeIndex,
+        target: <TestGraph as StaticGraph>::NodeIndex,
+        visited: &mut Vec<<TestGraph as StaticGraph>::NodeIndex>,
+    ) -> u64 {
+        if node == target {
+            return 1;
+        }
+        visited.push(node);
+        let mut count = 0;
+        for edge_index in graph.incident_edges(node) {
+            let (u, v) = graph.edge_endpoints(edge_index);
+            let neighbor = if u == node { v } else { u };
+            if !visited.contains(&neighbor) {
+                count += brute_force_path_count(graph, neighbor, target, visited);
+            }
+        }
+        visited.pop();
+        count
+    }
+
+    let graphs = all_test_inputs();
+
+    let mut r_node_errors = 0;
+    for (graph, spqr_decomposition_file) in &graphs {
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(graph, spqr_decomposition_file.as_bytes()).unwrap();
+        for u in graph.node_indices() {
+            for v in graph.node_indices() {
+                let expected = brute_force_path_count(graph, u, v, &mut Vec::new());
+                match spqr_decomposition.count_paths_mod(u, v, 1_000_000_007) {
+                    Ok(count) => {
+                        assert_eq!(count, expected % 1_000_000_007);
+                        assert_eq!(
+                            spqr_decomposition.count_paths_mod(u, v, 7),
+                            Ok(expected % 7)
+                        );
+                    }
+                    Err(error) => {
+                        assert_eq!(
+                            spqr_decomposition.spqr_node(error.r_node).spqr_node_type(),
+                            SPQRNodeType::RNode
+                        );
+                        r_node_errors += 1;
+                    }
+                }
+            }
+        }
+    }
+    assert!(r_node_errors > 0);
+}
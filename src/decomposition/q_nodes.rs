@@ -0,0 +1,150 @@
+use std::borrow::Borrow;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        indices::{BlockIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A node of an SPQR tree in which every real edge is materialised as a Q-node.
+///
+/// In this view, the S-, P- and R-nodes contain no real edges, and each Q-node is a leaf attached to the S-, P- or R-node that contains its edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExplicitSPQRTreeNode<EdgeIndex, IndexType> {
+    /// An S-, P- or R-node.
+    SPQRNode(SPQRNodeIndex<IndexType>),
+    /// A Q-node, represented by its real edge.
+    QNode(EdgeIndex),
+}
+
+/// An edge of an SPQR tree in which every real edge is materialised as a Q-node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExplicitSPQRTreeEdge<EdgeIndex, IndexType> {
+    /// An edge between two S-, P- or R-nodes.
+    SPQREdge(SPQREdgeIndex<IndexType>),
+    /// An edge between a Q-node and the S-, P- or R-node containing its real edge.
+    QNodeEdge {
+        q_node: EdgeIndex,
+        spqr_node: SPQRNodeIndex<IndexType>,
+    },
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the S-, P- or R-node that the Q-node of the given edge is attached to.
+    ///
+    /// Returns `None` if the edge has no Q-node, because it belongs to a block or component without SPQR tree.
+    pub fn q_node_parent(
+        &self,
+        edge_index: Graph::EdgeIndex,
+    ) -> Option<SPQRNodeIndex<Graph::IndexType>> {
+        self.edge_data[edge_index].spqr_node_index.into_option()
+    }
+
+    /// Returns an iterator over the nodes of the SPQR tree of the given block with explicit Q-nodes.
+    ///
+    /// The S-, P- and R-nodes are returned before the Q-nodes.
+    pub fn iter_explicit_spqr_tree_nodes_in_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> impl Iterator<Item = ExplicitSPQRTreeNode<Graph::EdgeIndex, Graph::IndexType>> {
+        let block = &self.blocks[block_index];
+        block
+            .spqr_nodes
+            .iter()
+            .copied()
+            .map(ExplicitSPQRTreeNode::SPQRNode)
+            .chain(
+                block
+                    .spqr_nodes
+                    .iter()
+                    .flat_map(|spqr_node_index| self.spqr_nodes[*spqr_node_index].iter_edges())
+                    .map(ExplicitSPQRTreeNode::QNode),
+            )
+    }
+
+    /// Returns an iterator over the edges of the SPQR tree of the given block with explicit Q-nodes, together with their endpoints.
+    ///
+    /// The edges between S-, P- and R-nodes are returned before the edges to Q-nodes.
+    pub fn iter_explicit_spqr_tree_edges_in_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> impl Iterator<
+        Item = (
+            ExplicitSPQRTreeEdge<Graph::EdgeIndex, Graph::IndexType>,
+            (
+                ExplicitSPQRTreeNode<Graph::EdgeIndex, Graph::IndexType>,
+                ExplicitSPQRTreeNode<Graph::EdgeIndex, Graph::IndexType>,
+            ),
+        ),
+    > {
+        let block = &self.blocks[block_index];
+        block
+            .spqr_edges
+            .iter()
+            .map(|&spqr_edge_index| {
+                let (u, v) = self.spqr_edges[spqr_edge_index].endpoints();
+                (
+                    ExplicitSPQRTreeEdge::SPQREdge(spqr_edge_index),
+                    (
+                        ExplicitSPQRTreeNode::SPQRNode(u),
+                        ExplicitSPQRTreeNode::SPQRNode(v),
+                    ),
+                )
+            })
+            .chain(block.spqr_nodes.iter().flat_map(|&spqr_node_index| {
+                self.spqr_nodes[spqr_node_index]
+                    .iter_edges()
+                    .map(move |edge_index| {
+                        (
+                            ExplicitSPQRTreeEdge::QNodeEdge {
+                                q_node: edge_index,
+                                spqr_node: spqr_node_index,
+                            },
+                            (
+                                ExplicitSPQRTreeNode::SPQRNode(spqr_node_index),
+                                ExplicitSPQRTreeNode::QNode(edge_index),
+                            ),
+                        )
+                    })
+            }))
+    }
+
+    /// Returns an iterator over the neighbours of the given node in the SPQR tree with explicit Q-nodes.
+    pub fn iter_explicit_spqr_tree_neighbours(
+        &self,
+        tree_node: ExplicitSPQRTreeNode<Graph::EdgeIndex, Graph::IndexType>,
+    ) -> impl Iterator<Item = ExplicitSPQRTreeNode<Graph::EdgeIndex, Graph::IndexType>> {
+        let (spqr_neighbours, q_nodes, parent) = match tree_node {
+            ExplicitSPQRTreeNode::SPQRNode(spqr_node_index) => {
+                let spqr_node = &self.spqr_nodes[spqr_node_index];
+                (
+                    Some((spqr_node_index, spqr_node.spqr_edges.as_slice())),
+                    spqr_node.edges.as_slice(),
+                    None,
+                )
+            }
+            ExplicitSPQRTreeNode::QNode(edge_index) => {
+                (None, &[][..], self.q_node_parent(edge_index))
+            }
+        };
+
+        spqr_neighbours
+            .into_iter()
+            .flat_map(move |(spqr_node_index, spqr_edges)| {
+                spqr_edges.iter().map(move |spqr_edge_index| {
+                    let (u, v) = self.spqr_edges[*spqr_edge_index].endpoints();
+                    if u == spqr_node_index { v } else { u }
+                })
+            })
+            .chain(parent)
+            .map(ExplicitSPQRTreeNode::SPQRNode)
+            .chain(q_nodes.iter().copied().map(ExplicitSPQRTreeNode::QNode))
+    }
+}
@@ -0,0 +1,65 @@
+use crate::decomposition::{
+    q_nodes::{ExplicitSPQRTreeEdge, ExplicitSPQRTreeNode},
+    tests::{read_decomposition, read_graph},
+};
+
+#[test]
+fn test_explicit_q_nodes() {
+    let graph = read_graph("multiedge");
+    let spqr_decomposition = read_decomposition(&graph, "multiedge");
+    let (block_index, _) = spqr_decomposition.iter_blocks().next().unwrap();
+    let tree_nodes = spqr_decomposition
+        .iter_explicit_spqr_tree_nodes_in_block(block_index)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tree_nodes
+            .iter()
+            .filter(|tree_node| matches!(tree_node, ExplicitSPQRTreeNode::QNode(_)))
+            .count(),
+        4
+    );
+
+    for name in [
+        "multiedge",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = read_graph(name);
+        let spqr_decomposition = read_decomposition(&graph, name);
+
+        for (block_index, block) in spqr_decomposition.iter_blocks() {
+            let tree_nodes = spqr_decomposition
+                .iter_explicit_spqr_tree_nodes_in_block(block_index)
+                .collect::<Vec<_>>();
+            let tree_edges = spqr_decomposition
+                .iter_explicit_spqr_tree_edges_in_block(block_index)
+                .collect::<Vec<_>>();
+            if block.spqr_node_count() == 0 {
+                assert!(tree_nodes.is_empty());
+                continue;
+            }
+            assert_eq!(tree_edges.len() + 1, tree_nodes.len());
+
+            let neighbour_count = tree_nodes
+                .iter()
+                .map(|tree_node| {
+                    spqr_decomposition
+                        .iter_explicit_spqr_tree_neighbours(*tree_node)
+                        .count()
+                })
+                .sum::<usize>();
+            assert_eq!(neighbour_count, 2 * tree_edges.len());
+
+            for (tree_edge, (u, v)) in tree_edges {
+                if let ExplicitSPQRTreeEdge::QNodeEdge { q_node, spqr_node } = tree_edge {
+                    assert_eq!(spqr_decomposition.q_node_parent(q_node), Some(spqr_node));
+                }
+                assert!(
+                    spqr_decomposition
+                        .iter_explicit_spqr_tree_neighbours(u)
+                        .any(|neighbour| neighbour == v)
+                );
+            }
+        }
+    }
+}
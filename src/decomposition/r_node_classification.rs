@@ -0,0 +1,299 @@
+//! Recognising R-node skeletons with a special structure.
+//!
+//! The skeleton of an R-node is a simple triconnected graph, and a few such graphs appear often enough that algorithms treat them separately:
+//! the complete graph on four nodes, the wheels, which consist of a cycle and a hub adjacent to all nodes of the cycle,
+//! and the triangular prism, which consists of two triangles joined by a perfect matching.
+//! These are recognised from their degree sequences, which determine them among triconnected graphs with the same amounts of nodes and edges.
+//!
+//! Planarity is tested with the path addition algorithm by Demoucron, Malgrange and Pertuiset,
+//! which embeds a cycle and then repeatedly embeds a path of a fragment into a face that contains all of its attachments.
+//! It takes quadratic time in the size of the skeleton.
+
+use std::{borrow::Borrow, collections::VecDeque};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    decomposition::{SPQRDecomposition, SPQRNodeType, indices::SPQRNodeIndex},
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The structure of an R-node skeleton, see [`SPQRDecomposition::classify_r_node`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RNodeClass<NodeIndex> {
+    /// The complete graph on four nodes, which is also the smallest wheel.
+    K4,
+    /// A wheel with at least five nodes, with the given hub.
+    Wheel { hub: NodeIndex },
+    /// The triangular prism.
+    Prism,
+    /// Any other triconnected graph.
+    Generic,
+}
+
+/// The classification of an R-node skeleton, see [`SPQRDecomposition::classify_r_node`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RNodeClassification<NodeIndex> {
+    pub class: RNodeClass<NodeIndex>,
+    /// The amount of nodes of the skeleton.
+    pub node_count: usize,
+    /// The amount of real and virtual edges of the skeleton.
+    pub edge_count: usize,
+    pub is_planar: bool,
+    /// Whether the skeleton is a maximal planar graph, such that each face of its embedding is a triangle.
+    pub is_triangulation: bool,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Classifies the skeleton of the given R-node, see the [module documentation](self).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given SPQR node is not an R-node.
+    pub fn classify_r_node(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+    ) -> RNodeClassification<Graph::NodeIndex> {
+        let spqr_node = &self.spqr_nodes[spqr_node_index];
+        assert_eq!(
+            spqr_node.spqr_node_type,
+            SPQRNodeType::RNode,
+            "{} is not an R-node",
+            self.spqr_node_name(spqr_node_index),
+        );
+        let local_nodes: FxHashMap<_, _> = spqr_node
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(local, &node_index)| (node_index, local))
+            .collect();
+        let edges = self
+            .iter_skeleton_edges(spqr_node_index)
+            .map(|(_, (u, v))| (local_nodes[&u], local_nodes[&v]))
+            .collect::<Vec<_>>();
+        let node_count = spqr_node.nodes.len();
+        let edge_count = edges.len();
+
+        let mut degrees = vec![0; node_count];
+        for &(u, v) in &edges {
+            degrees[u] += 1;
+            degrees[v] += 1;
+        }
+        let class = if node_count == 4 && edge_count == 6 {
+            RNodeClass::K4
+        } else if let Some(hub) = (edge_count == 2 * (node_count - 1))
+            .then(|| degrees.iter().position(|&degree| degree == node_count - 1))
+            .flatten()
+            .filter(|&hub| (0..node_count).all(|node| node == hub || degrees[node] == 3))
+        {
+            // Removing the hub leaves a biconnected graph in which each node has degree two, hence a cycle.
+            RNodeClass::Wheel {
+                hub: spqr_node.nodes[hub],
+            }
+        } else if node_count == 6
+            && edge_count == 9
+            && degrees.iter().all(|&degree| degree == 3)
+            && has_triangle(node_count, &edges)
+        {
+            // The only cubic graphs on six nodes are the prism and the complete bipartite graph, which has no triangle.
+            RNodeClass::Prism
+        } else {
+            RNodeClass::Generic
+        };
+
+        let is_planar = is_planar(node_count, &edges);
+        RNodeClassification {
+            class,
+            node_count,
+            edge_count,
+            is_planar,
+            is_triangulation: is_planar && edge_count == 3 * node_count - 6,
+        }
+    }
+}
+
+/// Returns true if the given simple graph contains a triangle.
+fn has_triangle(node_count: usize, edges: &[(usize, usize)]) -> bool {
+    let adjacency = adjacency(node_count, edges);
+    edges.iter().any(|&(u, v)| {
+        adjacency[u]
+            .iter()
+            .any(|&(_, w)| adjacency[v].iter().any(|&(_, x)| x == w))
+    })
+}
+
+/// Returns the incident edges of each node, each with the opposite endpoint.
+fn adjacency(node_count: usize, edges: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
+    let mut adjacency = vec![Vec::new(); node_count];
+    for (edge, &(u, v)) in edges.iter().enumerate() {
+        adjacency[u].push((edge, v));
+        adjacency[v].push((edge, u));
+    }
+    adjacency
+}
+
+/// Returns true if the given simple biconnected graph is planar, see the [module documentation](self).
+fn is_planar(node_count: usize, edges: &[(usize, usize)]) -> bool {
+    if node_count < 5 {
+        return true;
+    }
+    if edges.len() > 3 * node_count - 6 {
+        return false;
+    }
+    let adjacency = adjacency(node_count, edges);
+
+    // Embed the cycle formed by the first edge and a shortest path between its endpoints that avoids it.
+    let (first_u, first_v) = edges[0];
+    let mut parents = vec![None; node_count];
+    parents[first_u] = Some(first_u);
+    let mut queue = VecDeque::from([first_u]);
+    while let Some(node) = queue.pop_front() {
+        for &(edge, neighbor) in &adjacency[node] {
+            if edge != 0 && parents[neighbor].is_none() {
+                parents[neighbor] = Some(node);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    let mut cycle = vec![first_v];
+    while *cycle.last().unwrap() != first_u {
+        cycle.push(parents[*cycle.last().unwrap()].expect("the graph is biconnected"));
+    }
+
+    let mut embedded_nodes = vec![false; node_count];
+    let mut embedded_edges = vec![false; edges.len()];
+    let mut embedded_edge_count = 0;
+    for (position, &node) in cycle.iter().enumerate() {
+        embedded_nodes[node] = true;
+        let next = cycle[(position + 1) % cycle.len()];
+        let &(edge, _) = adjacency[node]
+            .iter()
+            .find(|&&(_, neighbor)| neighbor == next)
+            .unwrap();
+        embedded_edges[edge] = true;
+        embedded_edge_count += 1;
+    }
+    let mut faces = vec![cycle.clone(), cycle];
+
+    while embedded_edge_count < edges.len() {
+        // Each fragment is given by its attachments and a path between two of them through the fragment.
+        let mut fragments = Vec::new();
+        for (edge, &(u, v)) in edges.iter().enumerate() {
+            if !embedded_edges[edge] && embedded_nodes[u] && embedded_nodes[v] {
+                fragments.push((FxHashSet::from_iter([u, v]), vec![u, v]));
+            }
+        }
+        let mut fragment_visited = vec![false; node_count];
+        for start in 0..node_count {
+            if embedded_nodes[start] || fragment_visited[start] {
+                continue;
+            }
+            fragment_visited[start] = true;
+            let mut attachments = FxHashSet::default();
+            let mut parents = FxHashMap::default();
+            let mut queue = vec![start];
+            while let Some(node) = queue.pop() {
+                for &(_, neighbor) in &adjacency[node] {
+                    if embedded_nodes[neighbor] {
+                        attachments.insert(neighbor);
+                        parents.entry(neighbor).or_insert(node);
+                    } else if !fragment_visited[neighbor] {
+                        fragment_visited[neighbor] = true;
+                        parents.insert(neighbor, node);
+                        queue.push(neighbor);
+                    }
+                }
+            }
+
+            // Connect two attachments through the tree of the search, which is rooted at `start`.
+            let mut attachment_iter = attachments.iter();
+            let (&a, &b) = (
+                attachment_iter.next().unwrap(),
+                attachment_iter.next().unwrap(),
+            );
+            let mut to_a = vec![a];
+            while *to_a.last().unwrap() != start {
+                to_a.push(parents[to_a.last().unwrap()]);
+            }
+            let mut to_b = vec![b];
+            while *to_b.last().unwrap() != start {
+                to_b.push(parents[to_b.last().unwrap()]);
+            }
+            while to_a.len() > 1 && to_b.len() > 1 && to_a[to_a.len() - 2] == to_b[to_b.len() - 2] {
+                to_a.pop();
+                to_b.pop();
+            }
+            to_b.pop();
+            to_a.extend(to_b.into_iter().rev());
+            fragments.push((attachments, to_a));
+        }
+
+        let admissible_faces = |attachments: &FxHashSet<usize>| {
+            faces
+                .iter()
+                .enumerate()
+                .filter(|(_, face)| attachments.iter().all(|node| face.contains(node)))
+                .map(|(face_index, _)| face_index)
+                .collect::<Vec<_>>()
+        };
+        let mut chosen = None;
+        for (attachments, path) in &fragments {
+            let admissible = admissible_faces(attachments);
+            match admissible.len() {
+                0 => return false,
+                1 => {
+                    chosen = Some((admissible[0], path));
+                    break;
+                }
+                _ => {
+                    if chosen.is_none() {
+                        chosen = Some((admissible[0], path));
+                    }
+                }
+            }
+        }
+        let (face_index, path) = chosen.unwrap();
+
+        // Split the face along the path.
+        let face = faces.swap_remove(face_index);
+        let (a, b) = (path[0], *path.last().unwrap());
+        let position_a = face.iter().position(|&node| node == a).unwrap();
+        let rotated = face_rotate(&face, position_a);
+        let position_b = rotated.iter().position(|&node| node == b).unwrap();
+        let interior = &path[1..path.len() - 1];
+        let mut first = rotated[..=position_b].to_vec();
+        first.extend(interior.iter().rev());
+        let mut second = rotated[position_b..].to_vec();
+        second.push(a);
+        second.extend(interior);
+        faces.push(first);
+        faces.push(second);
+
+        for window in path.windows(2) {
+            let &(edge, _) = adjacency[window[0]]
+                .iter()
+                .find(|&&(edge, neighbor)| neighbor == window[1] && !embedded_edges[edge])
+                .unwrap();
+            embedded_edges[edge] = true;
+            embedded_edge_count += 1;
+        }
+        for &node in interior {
+            embedded_nodes[node] = true;
+        }
+    }
+    true
+}
+
+/// Returns the given cyclic sequence rotated to start at the given position.
+fn face_rotate(face: &[usize], position: usize) -> Vec<usize> {
+    face[position..]
+        .iter()
+        .chain(&face[..position])
+        .copied()
+        .collect()
+}
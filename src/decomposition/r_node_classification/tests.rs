@@ -0,0 +1,147 @@
+use std::io::BufReader;
+
+use bidirected_adjacency_array::graph::BidirectedAdjacencyArray;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        r_node_classification::{RNodeClass, RNodeClassification},
+        tests::TestGraph,
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_classify_r_node() {
+    fn classify(node_count: usize, edges: &[(usize, usize)]) -> RNodeClassification<String> {
+        let mut gfa = "H\tVN:Z:1.0\n".to_string();
+        let mut spqr = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n".to_string();
+        let nodes = (0..node_count)
+            .map(|node| format!("n{node}"))
+            .collect::<Vec<_>>();
+        for node in &nodes {
+            gfa.push_str(&format!("S\t{node}\tA\n"));
+        }
+        for &(u, v) in edges {
+            gfa.push_str(&format!("L\tn{u}\t+\tn{v}\t+\t0M\n"));
+        }
+        let nodes = nodes.join(" ");
+        spqr.push_str(&format!("G G0 {nodes}\nB B0 G0 {nodes}\nR R0 B0 {nodes}\n"));
+        for (edge, &(u, v)) in edges.iter().enumerate() {
+            spqr.push_str(&format!("E E{edge} R0 n{u} n{v}\n"));
+        }
+
+        let graph: TestGraph =
+            BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(&graph, spqr.as_bytes()).unwrap();
+        let (r_node, _) = spqr_decomposition.iter_spqr_nodes().next().unwrap();
+        let classification = spqr_decomposition.classify_r_node(r_node);
+        RNodeClassification {
+            class: match classification.class {
+                RNodeClass::K4 => RNodeClass::K4,
+                RNodeClass::Wheel { hub } => RNodeClass::Wheel {
+                    hub: graph.node_name(hub).into_owned(),
+                },
+                RNodeClass::Prism => RNodeClass::Prism,
+                RNodeClass::Generic => RNodeClass::Generic,
+            },
+            node_count: classification.node_count,
+            edge_count: classification.edge_count,
+            is_planar: classification.is_planar,
+            is_triangulation: classification.is_triangulation,
+        }
+    }
+    fn complete(node_count: usize) -> Vec<(usize, usize)> {
+        (0..node_count)
+            .flat_map(|u| (u + 1..node_count).map(move |v| (u, v)))
+            .collect()
+    }
+
+    let k4 = classify(4, &complete(4));
+    assert_eq!(k4.class, RNodeClass::K4);
+    assert!(k4.is_planar && k4.is_triangulation);
+
+    let wheel = classify(
+        6,
+        &[
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (0, 4),
+            (0, 5),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 1),
+        ],
+    );
+    assert_eq!(
+        wheel.class,
+        RNodeClass::Wheel {
+            hub: "n0".to_string()
+        }
+    );
+    assert_eq!((wheel.node_count, wheel.edge_count), (6, 10));
+    assert!(wheel.is_planar && !wheel.is_triangulation);
+
+    let prism = classify(
+        6,
+        &[
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (0, 3),
+            (1, 4),
+            (2, 5),
+        ],
+    );
+    assert_eq!(prism.class, RNodeClass::Prism);
+    assert!(prism.is_planar && !prism.is_triangulation);
+
+    let k33 = classify(
+        6,
+        &(0..3)
+            .flat_map(|u| (3..6).map(move |v| (u, v)))
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(k33.class, RNodeClass::Generic);
+    assert!(!k33.is_planar);
+
+    let k5 = classify(5, &complete(5));
+    assert_eq!(k5.class, RNodeClass::Generic);
+    assert!(!k5.is_planar);
+
+    let octahedron = classify(
+        6,
+        &complete(6)
+            .into_iter()
+            .filter(|&(u, v)| v != u + 3)
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(octahedron.class, RNodeClass::Generic);
+    assert!(octahedron.is_planar && octahedron.is_triangulation);
+
+    let cube = classify(
+        8,
+        &(0..8)
+            .flat_map(|u| [1, 2, 4].map(|bit| (u, u ^ bit)))
+            .filter(|&(u, v)| u < v)
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(cube.class, RNodeClass::Generic);
+    assert!(cube.is_planar && !cube.is_triangulation);
+
+    let petersen = classify(
+        10,
+        &(0..5)
+            .flat_map(|i| [(i, (i + 1) % 5), (i, i + 5), (i + 5, (i + 2) % 5 + 5)])
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(petersen.class, RNodeClass::Generic);
+    assert!(!petersen.is_planar);
+}
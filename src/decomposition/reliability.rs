@@ -0,0 +1,386 @@
+//! Computing the probability that two nodes are connected when edges fail independently.
+//!
+//! Two nodes are connected if and only if each block on the path between them in the block-cut tree connects the nodes through which the path enters and leaves it.
+//! The blocks share no edges, so the probability is a product over these blocks.
+//!
+//! Within a block, the nodes are connected through the skeletons of the SPQR nodes on the path between the SPQR nodes containing them.
+//! Each other virtual edge only matters through whether its side connects its poles,
+//! so it works like a real edge that works with the probability that its side connects the poles.
+//! This probability follows from the series and parallel recurrences:
+//! the side of an S-node connects the poles if all of its other skeleton edges work, and the side of a P-node if any of them works.
+//! Along the path, the probability of each way in which the first node and the poles of the next virtual edge are connected is tracked,
+//! which takes linear time per skeleton, since each skeleton on the path is a cycle or a multi-edge.
+//! There is no such recurrence for the skeleton of an R-node, so the computation fails if one is needed.
+//! In that case, the probability can be estimated by sampling instead.
+
+use std::borrow::Borrow;
+
+use rustc_hash::FxHashSet;
+use thiserror::Error;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The error returned by [`SPQRDecomposition::two_terminal_reliability`] if the probability depends on the skeleton of an R-node.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("the reliability depends on the skeleton of R-node {r_node}")]
+pub struct RNodeReliabilityError<IndexType> {
+    pub r_node: SPQRNodeIndex<IndexType>,
+}
+
+/// The probabilities of the ways in which some nodes are connected.
+///
+/// Each way is given by a label for each node, with two nodes being connected if they have the same label.
+/// Labels are numbered in the order of their first occurrence, such that each way has a unique representation.
+struct ConnectionDistribution<NodeIndex> {
+    nodes: Vec<NodeIndex>,
+    probabilities: Vec<(Vec<usize>, f64)>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the probability that `u` and `v` are connected, if each edge works independently with the given probability.
+    ///
+    /// See the [module documentation](self) for how the decomposition is used.
+    /// Self-loops do not influence the result, and a node is always connected to itself.
+    ///
+    /// Returns an error if a block between `u` and `v` has an R-node whose skeleton influences the result.
+    /// Then [`estimate_two_terminal_reliability`](Self::estimate_two_terminal_reliability) can be used instead.
+    pub fn two_terminal_reliability(
+        &self,
+        u: Graph::NodeIndex,
+        v: Graph::NodeIndex,
+        edge_probabilities: impl Fn(Graph::EdgeIndex) -> f64,
+    ) -> Result<f64, RNodeReliabilityError<Graph::IndexType>> {
+        if u == v {
+            return Ok(1.0);
+        }
+        if self.node_component_index(u) != self.node_component_index(v) {
+            return Ok(0.0);
+        }
+
+        let mut reliability = 1.0;
+        for (block_index, from, to) in self.block_cut_tree_path(u, v) {
+            reliability *= self.block_reliability(block_index, from, to, &edge_probabilities)?;
+        }
+        Ok(reliability)
+    }
+
+    /// Estimates the probability that `u` and `v` are connected, if each edge works independently with the given probability.
+    ///
+    /// The estimate is the fraction of `samples` random choices of working edges that connect the nodes,
+    /// using a pseudorandom number generator seeded with `seed`.
+    /// Unlike [`two_terminal_reliability`](Self::two_terminal_reliability), this works for every graph,
+    /// but each sample takes time linear in the size of the component of `u`.
+    pub fn estimate_two_terminal_reliability(
+        &self,
+        u: Graph::NodeIndex,
+        v: Graph::NodeIndex,
+        edge_probabilities: impl Fn(Graph::EdgeIndex) -> f64,
+        samples: usize,
+        seed: u64,
+    ) -> f64 {
+        if u == v {
+            return 1.0;
+        }
+        if self.node_component_index(u) != self.node_component_index(v) || samples == 0 {
+            return 0.0;
+        }
+
+        let edges = self
+            .iter_edges_in_component(self.node_component_index(u))
+            .map(|edge_index| (edge_index, edge_probabilities(edge_index)))
+            .collect::<Vec<_>>();
+        let mut state = seed;
+        let mut connected_samples = 0;
+        for _ in 0..samples {
+            let working_edges = edges
+                .iter()
+                .filter(|&&(_, probability)| random_unit(&mut state) < probability)
+                .map(|&(edge_index, _)| edge_index)
+                .collect::<FxHashSet<_>>();
+
+            let mut reached = FxHashSet::from_iter([u]);
+            let mut stack = vec![u];
+            while let Some(node_index) = stack.pop() {
+                for edge_index in self.graph_ref().incident_edges(node_index) {
+                    if working_edges.contains(&edge_index) {
+                        let (a, b) = self.graph_ref().edge_endpoints(edge_index);
+                        let neighbor = if a == node_index { b } else { a };
+                        if reached.insert(neighbor) {
+                            stack.push(neighbor);
+                        }
+                    }
+                }
+            }
+            if reached.contains(&v) {
+                connected_samples += 1;
+            }
+        }
+        connected_samples as f64 / samples as f64
+    }
+
+    /// Returns the probability that the distinct nodes `s` and `t` are connected within the given block.
+    fn block_reliability(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        s: Graph::NodeIndex,
+        t: Graph::NodeIndex,
+        edge_probabilities: &impl Fn(Graph::EdgeIndex) -> f64,
+    ) -> Result<f64, RNodeReliabilityError<Graph::IndexType>> {
+        let block = &self.blocks[block_index];
+        if block.spqr_nodes.is_empty() {
+            let failure = block
+                .iter_edges()
+                .filter(|&edge_index| {
+                    let (a, b) = self.graph_ref().edge_endpoints(edge_index);
+                    a != b
+                })
+                .map(|edge_index| 1.0 - edge_probabilities(edge_index))
+                .product::<f64>();
+            return Ok(1.0 - failure);
+        }
+
+        let in_block = |node_index| {
+            self.node_spqr_node_indices(node_index)
+                .filter(|&spqr_node_index| self.spqr_nodes[spqr_node_index].block == block_index)
+                .collect::<Vec<_>>()
+        };
+        let (s_spqr_nodes, t_spqr_nodes) = (in_block(s), in_block(t));
+        let mut path = self.spqr_tree_path(s_spqr_nodes[0], t_spqr_nodes[0]);
+        // The SPQR nodes containing a node form a subtree, so the path enters and leaves each of them only once.
+        let start = path
+            .iter()
+            .rposition(|(spqr_node_index, _)| s_spqr_nodes.contains(spqr_node_index))
+            .unwrap();
+        let end = path
+            .iter()
+            .position(|(spqr_node_index, _)| t_spqr_nodes.contains(spqr_node_index))
+            .unwrap();
+        if start >= end {
+            // Some SPQR node contains both nodes.
+            let &(spqr_node_index, _) = path[end..=start]
+                .iter()
+                .find(|(spqr_node_index, _)| {
+                    s_spqr_nodes.contains(spqr_node_index) && t_spqr_nodes.contains(spqr_node_index)
+                })
+                .unwrap();
+            path = vec![(spqr_node_index, None)];
+        } else {
+            path.truncate(end + 1);
+            path.drain(..start);
+            path[end - start].1 = None;
+        }
+
+        let mut distribution = ConnectionDistribution {
+            nodes: vec![s],
+            probabilities: vec![(vec![0], 1.0)],
+        };
+        for position in 0..path.len() {
+            let (spqr_node_index, next_edge) = path[position];
+            let previous_edge = (position > 0).then(|| path[position - 1].1.unwrap());
+            let excluded = previous_edge
+                .into_iter()
+                .chain(next_edge)
+                .collect::<Vec<_>>();
+            let next_nodes = match next_edge {
+                Some(spqr_edge_index) => {
+                    let (a, b) = self.spqr_edges[spqr_edge_index].virtual_edge;
+                    vec![s, a, b]
+                }
+                None => vec![s, t],
+            };
+            let mut terminals = distribution.nodes.clone();
+            terminals.extend_from_slice(&next_nodes);
+            let edges = self.reduced_skeleton_edges(
+                spqr_node_index,
+                &excluded,
+                &terminals,
+                edge_probabilities,
+            )?;
+            distribution = distribution.extend(&edges, next_nodes);
+        }
+
+        Ok(distribution
+            .probabilities
+            .iter()
+            .filter(|(labels, _)| labels[0] == labels[1])
+            .map(|(_, probability)| probability)
+            .sum())
+    }
+
+    /// Returns the skeleton edges of the given SPQR node without the excluded virtual edges and without self-loops,
+    /// with the series of edges between the given terminals and the poles of the excluded virtual edges merged into single edges,
+    /// and with parallel edges merged into single edges.
+    /// Each edge is returned with the probability that it works.
+    fn reduced_skeleton_edges(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        excluded: &[SPQREdgeIndex<Graph::IndexType>],
+        terminals: &[Graph::NodeIndex],
+        edge_probabilities: &impl Fn(Graph::EdgeIndex) -> f64,
+    ) -> Result<
+        Vec<(Graph::NodeIndex, Graph::NodeIndex, f64)>,
+        RNodeReliabilityError<Graph::IndexType>,
+    > {
+        let probability = |skeleton_edge| match skeleton_edge {
+            SkeletonEdge::Real(edge_index) => Ok(edge_probabilities(edge_index)),
+            SkeletonEdge::Virtual(spqr_edge_index) => {
+                self.pole_reliability(spqr_node_index, spqr_edge_index, edge_probabilities)
+            }
+        };
+        let is_excluded = |skeleton_edge| matches!(skeleton_edge, SkeletonEdge::Virtual(spqr_edge_index) if excluded.contains(&spqr_edge_index));
+
+        match self.spqr_nodes[spqr_node_index].spqr_node_type {
+            SPQRNodeType::SNode => {
+                let mut cycle = self.s_node_cycle(spqr_node_index);
+                let cut = (0..cycle.len())
+                    .find(|&position| {
+                        let previous = (position + cycle.len() - 1) % cycle.len();
+                        terminals.contains(&cycle[position].1) || is_excluded(cycle[previous].0)
+                    })
+                    .unwrap();
+                cycle.rotate_left(cut);
+
+                let mut edges = Vec::new();
+                let mut segment = None;
+                for &(skeleton_edge, node_index) in &cycle {
+                    if terminals.contains(&node_index)
+                        && let Some((segment_start, reliability)) = segment.take()
+                    {
+                        edges.push((segment_start, node_index, reliability));
+                    }
+                    if !is_excluded(skeleton_edge) {
+                        let (segment_start, reliability) = segment.unwrap_or((node_index, 1.0));
+                        segment = Some((segment_start, reliability * probability(skeleton_edge)?));
+                    } else if let Some((segment_start, reliability)) = segment.take() {
+                        edges.push((segment_start, node_index, reliability));
+                    }
+                }
+                if let Some((segment_start, reliability)) = segment {
+                    edges.push((segment_start, cycle[0].1, reliability));
+                }
+                Ok(edges)
+            }
+            SPQRNodeType::PNode => {
+                let mut poles = None;
+                let mut failure = 1.0;
+                for (skeleton_edge, (a, b)) in self.iter_skeleton_edges(spqr_node_index) {
+                    if a != b && !is_excluded(skeleton_edge) {
+                        poles = Some((a, b));
+                        failure *= 1.0 - probability(skeleton_edge)?;
+                    }
+                }
+                Ok(poles
+                    .map(|(a, b)| (a, b, 1.0 - failure))
+                    .into_iter()
+                    .collect())
+            }
+            SPQRNodeType::RNode => Err(RNodeReliabilityError {
+                r_node: spqr_node_index,
+            }),
+        }
+    }
+
+    /// Returns the probability that the side of the given virtual edge opposite to the given SPQR node connects its poles.
+    fn pole_reliability(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+        edge_probabilities: &impl Fn(Graph::EdgeIndex) -> f64,
+    ) -> Result<f64, RNodeReliabilityError<Graph::IndexType>> {
+        self.reduce_pole_side(
+            spqr_node_index,
+            spqr_edge_index,
+            &mut |edge_index| edge_probabilities(edge_index),
+            &mut |a, b| a * b,
+            &mut |a, b| 1.0 - (1.0 - a) * (1.0 - b),
+        )
+        .map_err(|r_node| RNodeReliabilityError { r_node })
+    }
+}
+
+impl<NodeIndex: Copy + Eq> ConnectionDistribution<NodeIndex> {
+    /// Adds the given edges, each working with the given probability, and returns the distribution over the ways in which the given nodes are connected.
+    fn extend(&self, edges: &[(NodeIndex, NodeIndex, f64)], nodes: Vec<NodeIndex>) -> Self {
+        let mut probabilities: Vec<(Vec<usize>, f64)> = Vec::new();
+        for (labels, probability) in &self.probabilities {
+            for working_edges in 0..1usize << edges.len() {
+                let mut groups: Vec<Vec<NodeIndex>> = Vec::new();
+                let mut connect = |u, v| {
+                    let position = |node_index| {
+                        groups
+                            .iter()
+                            .position(|group: &Vec<_>| group.contains(&node_index))
+                    };
+                    match (position(u), position(v)) {
+                        (None, None) => groups.push(if u == v { vec![u] } else { vec![u, v] }),
+                        (Some(group), None) => groups[group].push(v),
+                        (None, Some(group)) => groups[group].push(u),
+                        (Some(group_u), Some(group_v)) if group_u != group_v => {
+                            let merged = groups.swap_remove(group_u.max(group_v));
+                            groups[group_u.min(group_v)].extend(merged);
+                        }
+                        (Some(_), Some(_)) => {}
+                    }
+                };
+
+                let mut sample_probability = *probability;
+                for (position, &node_index) in self.nodes.iter().enumerate() {
+                    let first = labels.iter().position(|&label| label == labels[position]);
+                    connect(self.nodes[first.unwrap()], node_index);
+                }
+                for (position, &(u, v, edge_probability)) in edges.iter().enumerate() {
+                    if working_edges & 1 << position != 0 {
+                        connect(u, v);
+                        sample_probability *= edge_probability;
+                    } else {
+                        sample_probability *= 1.0 - edge_probability;
+                    }
+                }
+
+                let mut group_labels = Vec::new();
+                let labels = nodes
+                    .iter()
+                    .map(|&node_index| {
+                        let group = groups.iter().position(|group| group.contains(&node_index));
+                        match group_labels.iter().position(|&other| other == group) {
+                            Some(label) if group.is_some() => label,
+                            _ => {
+                                group_labels.push(group);
+                                group_labels.len() - 1
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                match probabilities.iter_mut().find(|(other, _)| *other == labels) {
+                    Some((_, probability)) => *probability += sample_probability,
+                    None => probabilities.push((labels, sample_probability)),
+                }
+            }
+        }
+        Self {
+            nodes,
+            probabilities,
+        }
+    }
+}
+
+/// Returns a pseudorandom number in `[0, 1)` and advances the state of a SplitMix64 generator.
+fn random_unit(state: &mut u64) -> f64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,112 @@
+use std::io::BufReader;
+
+use bidirected_adjacency_array::graph::BidirectedAdjacencyArray;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        tests::{TestGraph, test_inputs},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_two_terminal_reliability() {
+    /// Sums the probabilities of all sets of working edges that connect the nodes.
+    fn brute_force_reliabilities(
+        graph: &TestGraph,
+        edge_probability: impl Fn(usize) -> f64,
+    ) -> Vec<Vec<f64>> {
+        let node_count = graph.node_count();
+        let edges = graph
+            .edge_indices()
+            .map(|edge_index| {
+                let (u, v) = graph.edge_endpoints(edge_index);
+                (usize::from(u), usize::from(v))
+            })
+            .collect::<Vec<_>>();
+        let mut reliabilities = vec![vec![0.0; node_count]; node_count];
+        for working_edges in 0..1usize << edges.len() {
+            let mut probability = 1.0;
+            let mut component = (0..node_count).collect::<Vec<_>>();
+            for (position, &(u, v)) in edges.iter().enumerate() {
+                if working_edges & 1 << position != 0 {
+                    probability *= edge_probability(position);
+                    let (from, to) = (component[u], component[v]);
+                    for label in &mut component {
+                        if *label == from {
+                            *label = to;
+                        }
+                    }
+                } else {
+                    probability *= 1.0 - edge_probability(position);
+                }
+            }
+            for u in 0..node_count {
+                for v in 0..node_count {
+                    if component[u] == component[v] {
+                        reliabilities[u][v] += probability;
+                    }
+                }
+            }
+        }
+        reliabilities
+    }
+
+    // Two Wheatstone bridges in series, whose middle edges make the reliability depend on the P-nodes between the S-nodes.
+    let bridges_gfa = "H\tVN:Z:1.0\nS\ts\tA\nS\ta\tA\nS\tb\tA\nS\tc\tA\nS\td\tA\nS\tt\tA\n\
+        L\ts\t+\ta\t+\t0M\nL\ts\t+\tb\t+\t0M\nL\ta\t+\tb\t+\t0M\nL\ta\t+\tc\t+\t0M\n\
+        L\tb\t+\td\t+\t0M\nL\tc\t+\td\t+\t0M\nL\tc\t+\tt\t+\t0M\nL\td\t+\tt\t+\t0M\n";
+    let bridges_spqr = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 s a b c d t\nB B0 G0 s a b c d t\n\
+        S S0 B0 s a b\nP P1 B0 a b\nS S2 B0 a c d b\nP P3 B0 c d\nS S4 B0 c d t\n\
+        V V0 S0 P1 a b\nV V1 P1 S2 a b\nV V2 S2 P3 c d\nV V3 P3 S4 c d\n\
+        E E0 S0 s a\nE E1 S0 s b\nE E2 P1 a b\nE E3 S2 a c\nE E4 S2 b d\nE E5 P3 c d\n\
+        E E6 S4 c t\nE E7 S4 d t\n";
+    let mut graphs = test_inputs(&[
+        "tiny1",
+        "multiedge",
+        "random3_5_False_0",
+        "random5_10_False_0",
+    ]);
+    graphs.push((
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(bridges_gfa.as_bytes())).unwrap(),
+        bridges_spqr.to_string(),
+    ));
+
+    let edge_probability = |position: usize| 0.3 + 0.07 * (position % 9) as f64;
+    let mut r_node_errors = 0;
+    for (graph, spqr_decomposition_file) in &graphs {
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(graph, spqr_decomposition_file.as_bytes()).unwrap();
+        let expected = brute_force_reliabilities(graph, edge_probability);
+        for u in graph.node_indices() {
+            for v in graph.node_indices() {
+                let expected = expected[usize::from(u)][usize::from(v)];
+                match spqr_decomposition.two_terminal_reliability(u, v, |edge_index| {
+                    edge_probability(usize::from(edge_index))
+                }) {
+                    Ok(reliability) => assert!((reliability - expected).abs() < 1e-9),
+                    Err(error) => {
+                        assert_eq!(
+                            spqr_decomposition.spqr_node(error.r_node).spqr_node_type(),
+                            SPQRNodeType::RNode
+                        );
+                        r_node_errors += 1;
+                        if r_node_errors == 1 {
+                            let estimate = spqr_decomposition.estimate_two_terminal_reliability(
+                                u,
+                                v,
+                                |edge_index| edge_probability(usize::from(edge_index)),
+                                20_000,
+                                42,
+                            );
+                            assert!((estimate - expected).abs() < 0.03);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    assert!(r_node_errors > 0);
+}
@@ -0,0 +1,108 @@
+use std::{borrow::Borrow, fmt::Write};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        indices::{BlockIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Renders the SPQR tree of the given block as an indented tree for debugging.
+    ///
+    /// Each SPQR node is printed with its nodes, its real edges and its virtual edges.
+    /// Each child is prefixed by the SPQR edge connecting it to its parent.
+    /// The first SPQR node of the block is used as root.
+    pub fn render_tree(&self, block_index: BlockIndex<Graph::IndexType>) -> String {
+        let block = &self.blocks[block_index];
+        let mut result = format!("B{block_index}");
+        if block.spqr_nodes.is_empty() {
+            result.push_str(" real {");
+            for (index, edge_index) in block.iter_edges().enumerate() {
+                if index > 0 {
+                    result.push(' ');
+                }
+                self.render_edge(&mut result, self.graph_ref().edge_endpoints(edge_index));
+            }
+            result.push_str("}\n");
+            return result;
+        }
+        result.push('\n');
+
+        let mut stack: Vec<(
+            SPQRNodeIndex<Graph::IndexType>,
+            Option<SPQREdgeIndex<Graph::IndexType>>,
+            String,
+            bool,
+        )> = vec![(block.spqr_nodes[0], None, String::new(), true)];
+
+        while let Some((spqr_node_index, parent_spqr_edge, prefix, is_last)) = stack.pop() {
+            let spqr_node = &self.spqr_nodes[spqr_node_index];
+            result.push_str(&prefix);
+            result.push_str(if is_last { "└── " } else { "├── " });
+            if let Some(spqr_edge_index) = parent_spqr_edge {
+                write!(result, "V{spqr_edge_index} ").unwrap();
+                self.render_edge(&mut result, self.spqr_edges[spqr_edge_index].virtual_edge());
+                result.push_str(" ── ");
+            }
+
+            result.push_str(&self.spqr_node_name(spqr_node_index));
+            result.push_str(" [");
+            for (index, node_index) in spqr_node.iter_nodes().enumerate() {
+                if index > 0 {
+                    result.push(' ');
+                }
+                result.push_str(&self.graph_ref().node_name(node_index));
+            }
+            result.push_str("] real {");
+            for (index, edge_index) in spqr_node.iter_edges().enumerate() {
+                if index > 0 {
+                    result.push(' ');
+                }
+                self.render_edge(&mut result, self.graph_ref().edge_endpoints(edge_index));
+            }
+            result.push_str("} virtual {");
+            for (index, spqr_edge_index) in spqr_node.iter_incident_spqr_edges().enumerate() {
+                if index > 0 {
+                    result.push(' ');
+                }
+                self.render_edge(&mut result, self.spqr_edges[spqr_edge_index].virtual_edge());
+            }
+            result.push_str("}\n");
+
+            let child_prefix = format!("{prefix}{}", if is_last { "    " } else { "│   " });
+            let children = spqr_node
+                .iter_incident_spqr_edges()
+                .filter(|spqr_edge_index| Some(*spqr_edge_index) != parent_spqr_edge)
+                .collect::<Vec<_>>();
+            for (index, spqr_edge_index) in children.iter().copied().enumerate().rev() {
+                let (u, v) = self.spqr_edges[spqr_edge_index].endpoints();
+                let child = if u == spqr_node_index { v } else { u };
+                stack.push((
+                    child,
+                    Some(spqr_edge_index),
+                    child_prefix.clone(),
+                    index + 1 == children.len(),
+                ));
+            }
+        }
+
+        result
+    }
+
+    fn render_edge(&self, result: &mut String, (u, v): (Graph::NodeIndex, Graph::NodeIndex)) {
+        write!(
+            result,
+            "{}-{}",
+            self.graph_ref().node_name(u),
+            self.graph_ref().node_name(v)
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,21 @@
+use crate::decomposition::tests::{read_decomposition, read_graph};
+
+#[test]
+fn test_render_tree() {
+    let graph = read_graph("multiedge");
+    let spqr_decomposition = read_decomposition(&graph, "multiedge");
+    let (block_index, _) = spqr_decomposition.iter_blocks().next().unwrap();
+    assert_eq!(
+        spqr_decomposition.render_tree(block_index),
+        "B0\n\
+         └── P0 [2 1] real {2-1 1-2} virtual {2-1}\n\
+         \x20   └── V0 2-1 ── S1 [1 3 2] real {2-3 3-1} virtual {2-1}\n",
+    );
+
+    let graph = read_graph("Ultrabubble_dataset_chr_22_G182870");
+    let spqr_decomposition = read_decomposition(&graph, "Ultrabubble_dataset_chr_22_G182870");
+    for (block_index, block) in spqr_decomposition.iter_blocks() {
+        let rendered = spqr_decomposition.render_tree(block_index);
+        assert_eq!(rendered.lines().count(), block.spqr_node_count() + 1);
+    }
+}
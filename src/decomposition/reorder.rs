@@ -0,0 +1,212 @@
+use std::{borrow::Borrow, collections::VecDeque};
+
+use tagged_vec::TaggedVec;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        indices::{BlockIndex, GraphIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Renumbers blocks, SPQR nodes and SPQR edges such that entities that are close in the hierarchy get close indices.
+    ///
+    /// Blocks are numbered component by component, in breadth-first order of the block-cut tree of each component.
+    /// SPQR nodes and SPQR edges are numbered block by block in the new order of the blocks,
+    /// in breadth-first order of the SPQR tree of each block.
+    /// All references between entities are rewritten accordingly, and the lists of blocks, SPQR nodes and SPQR edges
+    /// of the entities are sorted by their new indices.
+    ///
+    /// This speeds up traversals of the whole decomposition, since they access memory mostly sequentially afterwards.
+    pub fn reorder_for_locality(&mut self) {
+        // The cached node sets, SPQR tree neighbours and rooted SPQR trees are indexed by block and SPQR node, so they become invalid.
+        self.cache.invalidate();
+
+        let block_order = self.block_locality_order();
+        let (spqr_node_order, spqr_edge_order) = self.spqr_locality_order(&block_order);
+        let block_map = inverse_permutation(&block_order);
+        let spqr_node_map = inverse_permutation(&spqr_node_order);
+        let spqr_edge_map = inverse_permutation(&spqr_edge_order);
+        let map_block = |block_index: &mut BlockIndex<Graph::IndexType>| {
+            *block_index = block_map[(*block_index).into_usize()]
+        };
+        let map_spqr_node = |spqr_node_index: &mut SPQRNodeIndex<Graph::IndexType>| {
+            *spqr_node_index = spqr_node_map[(*spqr_node_index).into_usize()]
+        };
+        let map_spqr_edge = |spqr_edge_index: &mut SPQREdgeIndex<Graph::IndexType>| {
+            *spqr_edge_index = spqr_edge_map[(*spqr_edge_index).into_usize()]
+        };
+
+        permute(&mut self.blocks, &block_order);
+        permute(&mut self.spqr_nodes, &spqr_node_order);
+        permute(&mut self.spqr_edges, &spqr_edge_order);
+
+        for component in self.components.iter_values_mut() {
+            component.blocks.iter_mut().for_each(map_block);
+            component.blocks.sort_unstable();
+        }
+
+        for block in self.blocks.iter_values_mut() {
+            block.spqr_nodes.iter_mut().for_each(map_spqr_node);
+            block.spqr_nodes.sort_unstable();
+            block.spqr_edges.iter_mut().for_each(map_spqr_edge);
+            block.spqr_edges.sort_unstable();
+        }
+
+        for cut_node in self.cut_nodes.iter_values_mut() {
+            cut_node.adjacent_blocks.iter_mut().for_each(map_block);
+            cut_node.adjacent_blocks.sort_unstable();
+        }
+
+        for spqr_node in self.spqr_nodes.iter_values_mut() {
+            map_block(&mut spqr_node.block);
+            spqr_node.spqr_edges.iter_mut().for_each(map_spqr_edge);
+            spqr_node.spqr_edges.sort_unstable();
+        }
+
+        for spqr_edge in self.spqr_edges.iter_values_mut() {
+            map_spqr_node(&mut spqr_edge.endpoints.0);
+            map_spqr_node(&mut spqr_edge.endpoints.1);
+        }
+
+        for node_data in self.node_data.iter_values_mut() {
+            node_data.block_indices.iter_mut().for_each(map_block);
+            node_data.block_indices.sort_unstable();
+            node_data
+                .spqr_node_indices
+                .iter_mut()
+                .for_each(map_spqr_node);
+            node_data.spqr_node_indices.sort_unstable();
+        }
+
+        for edge_data in self.edge_data.iter_values_mut() {
+            if let Some(mut block_index) = edge_data.block_index.into_option() {
+                map_block(&mut block_index);
+                edge_data.block_index = block_index.into();
+            }
+            if let Some(mut spqr_node_index) = edge_data.spqr_node_index.into_option() {
+                map_spqr_node(&mut spqr_node_index);
+                edge_data.spqr_node_index = spqr_node_index.into();
+            }
+        }
+    }
+
+    /// Returns the blocks in breadth-first order of the block-cut trees of the components.
+    fn block_locality_order(&self) -> Vec<BlockIndex<Graph::IndexType>> {
+        let mut visited = vec![false; self.blocks.len()];
+        let mut order = Vec::with_capacity(self.blocks.len());
+        let mut queue = VecDeque::new();
+
+        // Blocks are visited in the order of the components, so blocks of the same component stay together.
+        let roots = self
+            .components
+            .iter_values()
+            .flat_map(|component| component.blocks.iter().copied())
+            .chain(self.blocks.iter_indices(..));
+        for root in roots {
+            if visited[root.into_usize()] {
+                continue;
+            }
+            visited[root.into_usize()] = true;
+            queue.push_back(root);
+
+            while let Some(block_index) = queue.pop_front() {
+                order.push(block_index);
+
+                for &cut_node_index in &self.blocks[block_index].cut_nodes {
+                    for &adjacent_block_index in &self.cut_nodes[cut_node_index].adjacent_blocks {
+                        if !visited[adjacent_block_index.into_usize()] {
+                            visited[adjacent_block_index.into_usize()] = true;
+                            queue.push_back(adjacent_block_index);
+                        }
+                    }
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Returns the SPQR nodes and SPQR edges in breadth-first order of the SPQR trees of the blocks in the given order.
+    fn spqr_locality_order(
+        &self,
+        block_order: &[BlockIndex<Graph::IndexType>],
+    ) -> (
+        Vec<SPQRNodeIndex<Graph::IndexType>>,
+        Vec<SPQREdgeIndex<Graph::IndexType>>,
+    ) {
+        let mut visited_spqr_nodes = vec![false; self.spqr_nodes.len()];
+        let mut visited_spqr_edges = vec![false; self.spqr_edges.len()];
+        let mut spqr_node_order = Vec::with_capacity(self.spqr_nodes.len());
+        let mut spqr_edge_order = Vec::with_capacity(self.spqr_edges.len());
+        let mut queue = VecDeque::new();
+
+        let roots = block_order
+            .iter()
+            .flat_map(|&block_index| self.blocks[block_index].spqr_nodes.iter().copied())
+            .chain(self.spqr_nodes.iter_indices(..));
+        for root in roots {
+            if visited_spqr_nodes[root.into_usize()] {
+                continue;
+            }
+            visited_spqr_nodes[root.into_usize()] = true;
+            queue.push_back(root);
+
+            while let Some(spqr_node_index) = queue.pop_front() {
+                spqr_node_order.push(spqr_node_index);
+
+                for &spqr_edge_index in &self.spqr_nodes[spqr_node_index].spqr_edges {
+                    if visited_spqr_edges[spqr_edge_index.into_usize()] {
+                        continue;
+                    }
+                    visited_spqr_edges[spqr_edge_index.into_usize()] = true;
+                    spqr_edge_order.push(spqr_edge_index);
+
+                    let (u, v) = self.spqr_edges[spqr_edge_index].endpoints;
+                    let other = if u == spqr_node_index { v } else { u };
+                    if !visited_spqr_nodes[other.into_usize()] {
+                        visited_spqr_nodes[other.into_usize()] = true;
+                        queue.push_back(other);
+                    }
+                }
+            }
+        }
+
+        // SPQR edges that are not referenced by any SPQR node keep their relative order at the end.
+        spqr_edge_order.extend(
+            self.spqr_edges
+                .iter_indices(..)
+                .filter(|spqr_edge_index| !visited_spqr_edges[(*spqr_edge_index).into_usize()]),
+        );
+
+        (spqr_node_order, spqr_edge_order)
+    }
+}
+
+/// Maps each index to its position in the given permutation.
+fn inverse_permutation<Index: GraphIndex>(order: &[Index]) -> Vec<Index> {
+    let mut inverse = vec![None; order.len()];
+    for (position, &index) in order.iter().enumerate() {
+        inverse[index.into()] = Some(position.into());
+    }
+    inverse.into_iter().map(Option::unwrap).collect()
+}
+
+/// Reorders the values such that the value at index `order[i]` moves to index `i`.
+fn permute<Index: GraphIndex, Value>(tagged_vec: &mut TaggedVec<Index, Value>, order: &[Index]) {
+    let mut values = std::mem::take(tagged_vec)
+        .into_values_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    *tagged_vec = order
+        .iter()
+        .map(|&index| values[index.into()].take().unwrap())
+        .collect();
+}
@@ -0,0 +1,51 @@
+use crate::decomposition::{
+    SPQRDecomposition,
+    tests::{assert_spqr_tables_consistent, read_concatenated},
+};
+
+#[test]
+fn test_reorder_for_locality() {
+    let (graph, spqr_decomposition_file) =
+        read_concatenated(&["Ultrabubble_dataset_chr_22_G182870", "random5_10_False_0"]);
+    // Interleave the blocks of the components by declaring them in reverse order.
+    let lines = spqr_decomposition_file.lines().collect::<Vec<_>>();
+    let mut reordered = lines
+        .iter()
+        .filter(|line| line.starts_with(['H', 'G']))
+        .copied()
+        .collect::<Vec<_>>();
+    reordered.extend(lines.iter().rev().filter(|line| line.starts_with('B')));
+    reordered.extend(
+        lines
+            .iter()
+            .filter(|line| !line.starts_with(['H', 'G', 'B'])),
+    );
+    let reordered = reordered.join("\n") + "\n";
+    let original = SPQRDecomposition::read_plain_spqr(&graph, reordered.as_bytes()).unwrap();
+
+    let mut spqr_decomposition = original.clone();
+    spqr_decomposition.reorder_for_locality();
+    assert_ne!(spqr_decomposition, original);
+    assert!(spqr_decomposition.is_equivalent_to(&original));
+    assert_spqr_tables_consistent(&spqr_decomposition);
+
+    // The blocks of each component and the SPQR nodes of each block are numbered consecutively.
+    let mut next_block_index = 0;
+    for (component_index, _) in spqr_decomposition.iter_components() {
+        for (block_index, _) in spqr_decomposition.iter_blocks_in_component(component_index) {
+            assert_eq!(block_index.into_usize(), next_block_index);
+            next_block_index += 1;
+        }
+    }
+    let mut next_spqr_node_index = 0;
+    for (block_index, _) in spqr_decomposition.iter_blocks() {
+        for (spqr_node_index, _) in spqr_decomposition.iter_spqr_nodes_in_block(block_index) {
+            assert_eq!(spqr_node_index.into_usize(), next_spqr_node_index);
+            next_spqr_node_index += 1;
+        }
+    }
+
+    let mut reordered_twice = spqr_decomposition.clone();
+    reordered_twice.reorder_for_locality();
+    assert_eq!(reordered_twice, spqr_decomposition);
+}
@@ -0,0 +1,205 @@
+use std::{borrow::Borrow, collections::VecDeque, sync::OnceLock};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        indices::{
+            BlockIndex, GraphIndexInteger, OptionalSPQREdgeIndex, SPQREdgeIndex, SPQRNodeIndex,
+        },
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The SPQR trees of all blocks, each rooted at the first SPQR node of its block.
+#[derive(Debug, Clone)]
+pub(crate) struct RootedSPQRTrees<IndexType: GraphIndexInteger> {
+    /// The parent of each SPQR node, or the SPQR node itself if it is a root.
+    parents: Box<[SPQRNodeIndex<IndexType>]>,
+    /// The SPQR edge to the parent of each SPQR node, or none if it is a root.
+    parent_edges: Box<[OptionalSPQREdgeIndex<IndexType>]>,
+    depths: Box<[usize]>,
+    /// For answering lowest common ancestor queries by binary lifting, `ancestors[k][i]` is the `2^(k + 1)`-th ancestor of SPQR node `i`.
+    ///
+    /// Ancestors beyond the root are clamped to the root.
+    /// The table is built on the first query, since the other queries do not need it.
+    ancestors: OnceLock<Box<[Box<[SPQRNodeIndex<IndexType>]>]>>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the root of the SPQR tree of the given block, which is its first SPQR node.
+    ///
+    /// Returns `None` if the block has no SPQR tree.
+    pub fn spqr_tree_root(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> Option<SPQRNodeIndex<Graph::IndexType>> {
+        self.blocks[block_index].spqr_nodes.first().copied()
+    }
+
+    /// Returns the parent of the given SPQR node in its SPQR tree rooted at [`spqr_tree_root`](Self::spqr_tree_root),
+    /// together with the SPQR edge connecting them.
+    ///
+    /// Returns `None` for roots.
+    /// The rooted SPQR trees of all blocks are computed on first access and cached afterwards.
+    pub fn spqr_tree_parent(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+    ) -> Option<(
+        SPQRNodeIndex<Graph::IndexType>,
+        SPQREdgeIndex<Graph::IndexType>,
+    )> {
+        let rooted = self.rooted_spqr_trees();
+        let index = spqr_node_index.into_usize();
+        rooted.parent_edges[index]
+            .into_option()
+            .map(|spqr_edge_index| (rooted.parents[index], spqr_edge_index))
+    }
+
+    /// Returns the amount of SPQR edges between the given SPQR node and the root of its SPQR tree.
+    pub fn spqr_tree_depth(&self, spqr_node_index: SPQRNodeIndex<Graph::IndexType>) -> usize {
+        self.rooted_spqr_trees().depths[spqr_node_index.into_usize()]
+    }
+
+    /// Returns the lowest common ancestor of the given SPQR nodes in their rooted SPQR tree.
+    ///
+    /// The first query builds a table of ancestors in `O(n log n)` time, after which each query takes `O(log n)` time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the SPQR nodes are in different blocks.
+    pub fn spqr_tree_lowest_common_ancestor(
+        &self,
+        a: SPQRNodeIndex<Graph::IndexType>,
+        b: SPQRNodeIndex<Graph::IndexType>,
+    ) -> SPQRNodeIndex<Graph::IndexType> {
+        assert_eq!(
+            self.spqr_nodes[a].block, self.spqr_nodes[b].block,
+            "the SPQR nodes are in different SPQR trees"
+        );
+        self.rooted_spqr_trees().lowest_common_ancestor(a, b)
+    }
+
+    fn rooted_spqr_trees(&self) -> &RootedSPQRTrees<Graph::IndexType> {
+        self.cache
+            .rooted_spqr_trees
+            .get_or_init(|| self.build_rooted_spqr_trees())
+    }
+
+    fn build_rooted_spqr_trees(&self) -> RootedSPQRTrees<Graph::IndexType> {
+        let mut parents: Vec<_> = self.spqr_nodes.iter_indices(..).collect();
+        let mut parent_edges = vec![OptionalSPQREdgeIndex::new_none(); self.spqr_nodes.len()];
+        let mut depths = vec![0; self.spqr_nodes.len()];
+        let mut visited = vec![false; self.spqr_nodes.len()];
+        let mut queue = VecDeque::new();
+
+        // SPQR nodes that are not reachable from the root of their block become roots themselves.
+        let roots = self
+            .blocks
+            .iter_values()
+            .filter_map(|block| block.spqr_nodes.first().copied())
+            .chain(self.spqr_nodes.iter_indices(..));
+        for root in roots {
+            if visited[root.into_usize()] {
+                continue;
+            }
+            visited[root.into_usize()] = true;
+            queue.push_back(root);
+
+            while let Some(spqr_node_index) = queue.pop_front() {
+                for neighbor in self.spqr_tree_neighbors(spqr_node_index) {
+                    let index = neighbor.spqr_node.into_usize();
+                    if !visited[index] {
+                        visited[index] = true;
+                        parents[index] = spqr_node_index;
+                        parent_edges[index] = neighbor.spqr_edge.into();
+                        depths[index] = depths[spqr_node_index.into_usize()] + 1;
+                        queue.push_back(neighbor.spqr_node);
+                    }
+                }
+            }
+        }
+
+        RootedSPQRTrees {
+            parents: parents.into(),
+            parent_edges: parent_edges.into(),
+            depths: depths.into(),
+            ancestors: OnceLock::new(),
+        }
+    }
+}
+
+impl<IndexType: GraphIndexInteger> RootedSPQRTrees<IndexType> {
+    fn lowest_common_ancestor(
+        &self,
+        mut a: SPQRNodeIndex<IndexType>,
+        mut b: SPQRNodeIndex<IndexType>,
+    ) -> SPQRNodeIndex<IndexType> {
+        let ancestors = self.ancestors.get_or_init(|| self.build_ancestors());
+        let ancestor = |level: usize, node: SPQRNodeIndex<IndexType>| {
+            if level == 0 {
+                self.parents[node.into_usize()]
+            } else {
+                ancestors[level - 1][node.into_usize()]
+            }
+        };
+
+        if self.depths[a.into_usize()] < self.depths[b.into_usize()] {
+            (a, b) = (b, a);
+        }
+        let mut difference = self.depths[a.into_usize()] - self.depths[b.into_usize()];
+        let mut level = 0;
+        while difference > 0 {
+            if difference & 1 == 1 {
+                a = ancestor(level, a);
+            }
+            difference >>= 1;
+            level += 1;
+        }
+        if a == b {
+            return a;
+        }
+
+        for level in (0..=ancestors.len()).rev() {
+            let (parent_a, parent_b) = (ancestor(level, a), ancestor(level, b));
+            if parent_a != parent_b {
+                (a, b) = (parent_a, parent_b);
+            }
+        }
+        self.parents[a.into_usize()]
+    }
+
+    fn build_ancestors(&self) -> Box<[Box<[SPQRNodeIndex<IndexType>]>]> {
+        let max_depth = self.depths.iter().copied().max().unwrap_or(0);
+        let level_count = (usize::BITS - max_depth.leading_zeros()) as usize;
+
+        let mut ancestors: Vec<Box<[SPQRNodeIndex<IndexType>]>> = Vec::new();
+        for _ in 1..level_count {
+            let previous = ancestors.last().unwrap_or(&self.parents);
+            let level = previous
+                .iter()
+                .map(|ancestor| previous[ancestor.into_usize()])
+                .collect();
+            ancestors.push(level);
+        }
+        ancestors.into()
+    }
+
+    /// Returns the amount of heap memory used by the rooted SPQR trees in bytes.
+    pub(crate) fn heap_size(&self) -> usize {
+        size_of_val(&*self.parents)
+            + size_of_val(&*self.parent_edges)
+            + size_of_val(&*self.depths)
+            + self.ancestors.get().map_or(0, |ancestors| {
+                size_of_val(&**ancestors)
+                    + ancestors
+                        .iter()
+                        .map(|level| size_of_val(&**level))
+                        .sum::<usize>()
+            })
+    }
+}
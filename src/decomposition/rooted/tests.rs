@@ -0,0 +1,46 @@
+use crate::decomposition::tests::{read_decomposition, read_graph};
+
+#[test]
+fn test_rooted_spqr_trees() {
+    let graph = read_graph("Ultrabubble_dataset_chr_22_G182870");
+    let spqr_decomposition = read_decomposition(&graph, "Ultrabubble_dataset_chr_22_G182870");
+    assert_eq!(spqr_decomposition.memory_usage().rooted_spqr_tree_cache, 0);
+
+    for (block_index, _) in spqr_decomposition.iter_blocks() {
+        let spqr_nodes = spqr_decomposition
+            .iter_spqr_nodes_in_block(block_index)
+            .map(|(spqr_node_index, _)| spqr_node_index)
+            .collect::<Vec<_>>();
+        let root = spqr_decomposition.spqr_tree_root(block_index);
+        assert_eq!(root, spqr_nodes.first().copied());
+
+        let path_to_root = |mut spqr_node_index| {
+            let mut path = vec![spqr_node_index];
+            while let Some((parent, spqr_edge_index)) =
+                spqr_decomposition.spqr_tree_parent(spqr_node_index)
+            {
+                let (u, v) = spqr_decomposition.spqr_edge(spqr_edge_index).endpoints();
+                assert!((u, v) == (parent, spqr_node_index) || (v, u) == (parent, spqr_node_index));
+                spqr_node_index = parent;
+                path.push(spqr_node_index);
+            }
+            path
+        };
+
+        for &a in &spqr_nodes {
+            let path_a = path_to_root(a);
+            assert_eq!(path_a.last().copied(), root);
+            assert_eq!(path_a.len() - 1, spqr_decomposition.spqr_tree_depth(a));
+
+            for &b in &spqr_nodes {
+                let path_b = path_to_root(b);
+                let expected = *path_a.iter().find(|a| path_b.contains(a)).unwrap();
+                assert_eq!(
+                    spqr_decomposition.spqr_tree_lowest_common_ancestor(a, b),
+                    expected
+                );
+            }
+        }
+    }
+    assert!(spqr_decomposition.memory_usage().rooted_spqr_tree_cache > 0);
+}
@@ -0,0 +1,286 @@
+//! Sampling random graphs with the same decomposition structure as a given graph.
+//!
+//! The sampled graph is assembled from a new skeleton for each SPQR node, glued along the virtual edges as in the given SPQR trees,
+//! and from new blocks glued at cut nodes as in the given block-cut trees.
+//! The skeletons of S-nodes and P-nodes are determined by their amount of edges and are kept,
+//! but the virtual edges are placed on random skeleton edges.
+//! The skeletons of R-nodes are replaced by random triconnected planar graphs with the same amount of nodes:
+//! a wheel with random non-crossing chords on its rim, which stays planar since the chords are drawn in the outer face,
+//! and stays triconnected since wheels are triconnected and adding edges keeps it so.
+//! Self-loops are moved to random nodes of their blocks, or of their components if they are not part of a block.
+
+use std::borrow::Borrow;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        indices::{BlockIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A graph given by its amount of nodes and its edges between nodes numbered from zero, see [`SPQRDecomposition::sample_graph_with_same_spqr_structure`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SampledGraph {
+    pub node_count: usize,
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns a random graph whose block-cut trees and SPQR trees are isomorphic to those of this decomposition,
+    /// including the types of the SPQR nodes, for null-model comparisons.
+    ///
+    /// See the [module documentation](self) for how the graph is built.
+    /// The skeleton of an R-node keeps its amount of edges, unless it has more than a planar triconnected graph can have,
+    /// and gets more nodes if it has more virtual edges than such a graph can have.
+    /// The nodes and edges of the sampled graph are in random order.
+    ///
+    /// Each call to `rng` must return a uniformly random 64-bit number.
+    pub fn sample_graph_with_same_spqr_structure(
+        &self,
+        mut rng: impl FnMut() -> u64,
+    ) -> SampledGraph {
+        let mut graph = SampledGraph::default();
+        for (_, component) in self.iter_components() {
+            let first_node = graph.node_count;
+            let mut cut_node_ids = FxHashMap::default();
+            let mut stack = component
+                .blocks
+                .first()
+                .map(|&root| (root, None))
+                .into_iter()
+                .collect::<Vec<_>>();
+            while let Some((block_index, parent_cut_node)) = stack.pop() {
+                let (block_node_count, block_edges) = self.sample_block(block_index, &mut rng);
+                let mut local_nodes = (0..block_node_count).collect::<Vec<_>>();
+                shuffle(&mut local_nodes, &mut rng);
+                let mut ids = vec![None; block_node_count];
+
+                // Each cut node of the block is placed on a different random node of the sampled block.
+                for (&cut_node_index, &local) in
+                    self.blocks[block_index].cut_nodes.iter().zip(&local_nodes)
+                {
+                    if Some(cut_node_index) == parent_cut_node {
+                        ids[local] = Some(cut_node_ids[&cut_node_index]);
+                    } else {
+                        ids[local] = Some(graph.node_count);
+                        cut_node_ids.insert(cut_node_index, graph.node_count);
+                        graph.node_count += 1;
+                        for &child in &self.cut_nodes[cut_node_index].adjacent_blocks {
+                            if child != block_index {
+                                stack.push((child, Some(cut_node_index)));
+                            }
+                        }
+                    }
+                }
+                let ids = ids
+                    .into_iter()
+                    .map(|id| {
+                        id.unwrap_or_else(|| {
+                            graph.node_count += 1;
+                            graph.node_count - 1
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                graph
+                    .edges
+                    .extend(block_edges.into_iter().map(|(u, v)| (ids[u], ids[v])));
+            }
+
+            // Components without blocks consist of a single node, possibly with self-loops that are not part of any block.
+            if component.blocks.is_empty() {
+                graph.node_count += 1;
+            }
+            for _ in component.iter_edges() {
+                let node = first_node + random_below(graph.node_count - first_node, &mut rng);
+                graph.edges.push((node, node));
+            }
+        }
+
+        let mut permutation = (0..graph.node_count).collect::<Vec<_>>();
+        shuffle(&mut permutation, &mut rng);
+        for (u, v) in &mut graph.edges {
+            (*u, *v) = (permutation[*u], permutation[*v]);
+        }
+        shuffle(&mut graph.edges, &mut rng);
+        graph
+    }
+
+    /// Samples a block with the same SPQR tree as the given block, returning its amount of nodes and its edges.
+    fn sample_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        rng: &mut impl FnMut() -> u64,
+    ) -> (usize, Vec<(usize, usize)>) {
+        let block = &self.blocks[block_index];
+        let mut edges = Vec::new();
+        let mut loop_count = 0;
+        for edge_index in self.iter_edges_in_block(block_index) {
+            let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+            if u == v {
+                loop_count += 1;
+            } else if block.spqr_nodes.is_empty() {
+                edges.push((0, 1));
+            }
+        }
+
+        let mut node_count = block.nodes.len();
+        if let Some(&root) = block.spqr_nodes.first() {
+            node_count = 0;
+            let mut stack: Vec<(
+                SPQRNodeIndex<Graph::IndexType>,
+                Option<(SPQREdgeIndex<Graph::IndexType>, (usize, usize))>,
+            )> = vec![(root, None)];
+            while let Some((spqr_node_index, parent)) = stack.pop() {
+                let (skeleton_node_count, skeleton_edges) =
+                    self.sample_skeleton(spqr_node_index, rng);
+
+                // Assign the virtual edges to random skeleton edges, the parent edge first.
+                let mut positions = (0..skeleton_edges.len()).collect::<Vec<_>>();
+                shuffle(&mut positions, rng);
+                let mut virtual_edges = FxHashMap::default();
+                let incident_edges = parent
+                    .map(|(spqr_edge_index, _)| spqr_edge_index)
+                    .into_iter()
+                    .chain(
+                        self.spqr_nodes[spqr_node_index]
+                            .iter_incident_spqr_edges()
+                            .filter(|&spqr_edge_index| {
+                                Some(spqr_edge_index) != parent.map(|(parent_edge, _)| parent_edge)
+                            }),
+                    );
+                for (spqr_edge_index, &position) in incident_edges.zip(&positions) {
+                    virtual_edges.insert(position, spqr_edge_index);
+                }
+
+                let mut ids = vec![None; skeleton_node_count];
+                if let Some((spqr_edge_index, (x, y))) = parent {
+                    let position = positions[0];
+                    debug_assert_eq!(virtual_edges[&position], spqr_edge_index);
+                    let (a, b) = skeleton_edges[position];
+                    ids[a] = Some(x);
+                    ids[b] = Some(y);
+                }
+                let ids = ids
+                    .into_iter()
+                    .map(|id| {
+                        id.unwrap_or_else(|| {
+                            node_count += 1;
+                            node_count - 1
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                for (position, &(a, b)) in skeleton_edges.iter().enumerate() {
+                    match virtual_edges.get(&position) {
+                        None => edges.push((ids[a], ids[b])),
+                        Some(&spqr_edge_index)
+                            if Some(spqr_edge_index)
+                                != parent.map(|(parent_edge, _)| parent_edge) =>
+                        {
+                            let child = self.opposite_spqr_node(spqr_node_index, spqr_edge_index);
+                            let poles = if rng().is_multiple_of(2) {
+                                (ids[a], ids[b])
+                            } else {
+                                (ids[b], ids[a])
+                            };
+                            stack.push((child, Some((spqr_edge_index, poles))));
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        for _ in 0..loop_count {
+            let node = random_below(node_count, rng);
+            edges.push((node, node));
+        }
+        (node_count, edges)
+    }
+
+    /// Samples a skeleton for the given SPQR node, returning its amount of nodes and its edges.
+    fn sample_skeleton(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        rng: &mut impl FnMut() -> u64,
+    ) -> (usize, Vec<(usize, usize)>) {
+        let spqr_node = &self.spqr_nodes[spqr_node_index];
+        let edge_count = self
+            .iter_skeleton_edges(spqr_node_index)
+            .filter(|(_, (u, v))| u != v)
+            .count();
+        match spqr_node.spqr_node_type {
+            SPQRNodeType::SNode => (
+                edge_count,
+                (0..edge_count)
+                    .map(|node| (node, (node + 1) % edge_count))
+                    .collect(),
+            ),
+            SPQRNodeType::PNode => (2, vec![(0, 1); edge_count]),
+            SPQRNodeType::RNode => {
+                let virtual_edge_count = spqr_node.spqr_edges.len();
+                let mut node_count = spqr_node.nodes.len();
+                while 3 * node_count - 6 < virtual_edge_count {
+                    node_count += 1;
+                }
+                let edge_count = edge_count
+                    .min(3 * node_count - 6)
+                    .max(2 * (node_count - 1))
+                    .max(virtual_edge_count);
+                (
+                    node_count,
+                    random_triconnected_planar_graph(node_count, edge_count, rng),
+                )
+            }
+        }
+    }
+}
+
+/// Returns the edges of a random triconnected planar graph with the given amounts of nodes and edges,
+/// built from a wheel with node zero as hub by adding random non-crossing chords on the rim.
+///
+/// The amount of nodes must be at least four, and the amount of edges between `2 * (node_count - 1)` and `3 * node_count - 6`.
+fn random_triconnected_planar_graph(
+    node_count: usize,
+    edge_count: usize,
+    rng: &mut impl FnMut() -> u64,
+) -> Vec<(usize, usize)> {
+    let rim_length = node_count - 1;
+    let mut edges = (1..node_count)
+        .flat_map(|node| [(0, node), (node, node % rim_length + 1)])
+        .collect::<Vec<_>>();
+
+    // Clipping random ears of the rim polygon yields the chords of a random triangulation of it.
+    let mut polygon = (1..node_count).collect::<Vec<_>>();
+    let mut chords = Vec::new();
+    while polygon.len() > 3 {
+        let position = random_below(polygon.len(), rng);
+        let previous = polygon[(position + polygon.len() - 1) % polygon.len()];
+        let next = polygon[(position + 1) % polygon.len()];
+        chords.push((previous, next));
+        polygon.remove(position);
+    }
+    shuffle(&mut chords, rng);
+    edges.extend(chords.into_iter().take(edge_count - edges.len()));
+    edges
+}
+
+/// Returns a random number below `bound`, which must be positive.
+fn random_below(bound: usize, rng: &mut impl FnMut() -> u64) -> usize {
+    (rng() % bound as u64) as usize
+}
+
+/// Shuffles the given slice with the Fisher-Yates algorithm.
+fn shuffle<T>(slice: &mut [T], rng: &mut impl FnMut() -> u64) {
+    for position in (1..slice.len()).rev() {
+        slice.swap(position, random_below(position + 1, rng));
+    }
+}
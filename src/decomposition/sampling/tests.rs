@@ -0,0 +1,79 @@
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{all_test_inputs, is_biconnected, is_triconnected, read_subdivided_wheel},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_sample_graph_with_same_spqr_structure() {
+    /// Counts the nodes whose removal disconnects their component.
+    fn cut_node_count(node_count: usize, edges: &[(usize, usize)]) -> usize {
+        let component_count = |removed: Option<usize>| {
+            let mut labels = (0..node_count).collect::<Vec<_>>();
+            for &(u, v) in edges {
+                if Some(u) != removed && Some(v) != removed {
+                    let (from, to) = (labels[u], labels[v]);
+                    labels
+                        .iter_mut()
+                        .filter(|label| **label == from)
+                        .for_each(|label| *label = to);
+                }
+            }
+            (0..node_count)
+                .filter(|&node| Some(node) != removed && labels[node] == node)
+                .count()
+        };
+        let base = component_count(None);
+        (0..node_count)
+            .filter(|&node| {
+                let isolated = edges.iter().all(|&(u, v)| u != node && v != node);
+                !isolated && component_count(Some(node)) > base
+            })
+            .count()
+    }
+
+    let graphs = all_test_inputs();
+
+    let mut state = 0x2545_F491_4F6C_DD1D_u64;
+    let mut rng = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    for (graph, spqr_decomposition_file) in &graphs {
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(graph, spqr_decomposition_file.as_bytes()).unwrap();
+        let edges = graph
+            .edge_indices()
+            .map(|edge_index| {
+                let (u, v) = graph.edge_endpoints(edge_index);
+                (usize::from(u), usize::from(v))
+            })
+            .collect::<Vec<_>>();
+        for _ in 0..5 {
+            let sampled = spqr_decomposition.sample_graph_with_same_spqr_structure(&mut rng);
+            // The skeleton of the only R-node in the test graphs already has as many edges as a wheel.
+            assert_eq!(sampled.node_count, graph.node_count());
+            assert_eq!(sampled.edges.len(), graph.edge_count());
+            assert_eq!(
+                sampled.edges.iter().filter(|(u, v)| u == v).count(),
+                edges.iter().filter(|(u, v)| u == v).count()
+            );
+            assert_eq!(
+                cut_node_count(sampled.node_count, &sampled.edges),
+                cut_node_count(graph.node_count(), &edges)
+            );
+        }
+    }
+
+    let (graph, spqr_decomposition_file) = read_subdivided_wheel();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let sampled = spqr_decomposition.sample_graph_with_same_spqr_structure(&mut rng);
+    let nodes = (0..sampled.node_count).collect::<Vec<_>>();
+    assert!(is_biconnected(&nodes, &sampled.edges));
+    assert!(!is_triconnected(&nodes, &sampled.edges));
+}
@@ -0,0 +1,152 @@
+use std::borrow::Borrow;
+
+use smallvec::{SmallVec, smallvec};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        indices::{ComponentIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// An inclusion-minimal set of at most two nodes whose removal disconnects its component,
+/// see [`SPQRDecomposition::iter_minimal_separators_le2`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MinimalSeparator<NodeIndex, IndexType> {
+    pub component: ComponentIndex<IndexType>,
+    /// A cut node, or a separation pair in which neither node is a cut node.
+    pub nodes: SmallVec<[NodeIndex; 2]>,
+    /// One node of each part that the component splits into when removing the separator.
+    pub parts: Vec<NodeIndex>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns an iterator over all inclusion-minimal separators of size one and two, i.e. the cut nodes and the separation pairs that contain no cut node.
+    ///
+    /// The cut nodes come first, followed by the separation pairs of each block.
+    /// Separation pairs are read from the SPQR trees: they are the poles of virtual edges, and the non-adjacent nodes of S-node cycles.
+    /// The poles of a virtual edge are only reported if they split off at least two parts that contain further nodes.
+    pub fn iter_minimal_separators_le2(
+        &self,
+    ) -> impl Iterator<Item = MinimalSeparator<Graph::NodeIndex, Graph::IndexType>> {
+        let mut separators = Vec::new();
+
+        for cut_node in self.cut_nodes.iter_values() {
+            separators.push(MinimalSeparator {
+                component: cut_node.component,
+                nodes: smallvec![cut_node.node],
+                parts: cut_node
+                    .adjacent_blocks
+                    .iter()
+                    .map(|&block_index| {
+                        self.blocks[block_index]
+                            .iter_nodes()
+                            .find(|&node_index| node_index != cut_node.node)
+                            .unwrap()
+                    })
+                    .collect(),
+            });
+        }
+
+        let is_cut_node = |node_index| self.node_data[node_index].cut_node_index.is_some();
+        for block in self.blocks.iter_values() {
+            for &spqr_node_index in &block.spqr_nodes {
+                let spqr_node = &self.spqr_nodes[spqr_node_index];
+                let mut push = |u, v, parts: Vec<_>| {
+                    if parts.len() >= 2 && !is_cut_node(u) && !is_cut_node(v) {
+                        separators.push(MinimalSeparator {
+                            component: block.component,
+                            nodes: smallvec![u, v],
+                            parts,
+                        });
+                    }
+                };
+
+                match spqr_node.spqr_node_type {
+                    SPQRNodeType::PNode => {
+                        let (u, v) = spqr_node.p_node_poles().unwrap();
+                        let parts = spqr_node
+                            .iter_incident_spqr_edges()
+                            .map(|spqr_edge_index| {
+                                self.part_representative(spqr_node_index, spqr_edge_index)
+                            })
+                            .collect();
+                        push(u, v, parts);
+                    }
+                    SPQRNodeType::SNode => {
+                        let cycle = self.s_node_cycle(spqr_node_index);
+                        for i in 0..cycle.len() {
+                            for j in i + 2..cycle.len() - usize::from(i == 0) {
+                                push(
+                                    cycle[i].1,
+                                    cycle[j].1,
+                                    vec![cycle[i + 1].1, cycle[(j + 1) % cycle.len()].1],
+                                );
+                            }
+                        }
+                    }
+                    SPQRNodeType::RNode => {}
+                }
+
+                // Virtual edges at P-nodes are covered by the poles of the P-node.
+                for spqr_edge_index in spqr_node.iter_incident_spqr_edges() {
+                    let spqr_edge = &self.spqr_edges[spqr_edge_index];
+                    let other = if spqr_edge.endpoints.0 == spqr_node_index {
+                        spqr_edge.endpoints.1
+                    } else {
+                        spqr_edge.endpoints.0
+                    };
+                    if spqr_node_index < other
+                        && spqr_node.spqr_node_type != SPQRNodeType::PNode
+                        && self.spqr_nodes[other].spqr_node_type != SPQRNodeType::PNode
+                    {
+                        let (u, v) = spqr_edge.virtual_edge;
+                        push(
+                            u,
+                            v,
+                            vec![
+                                self.part_representative(other, spqr_edge_index),
+                                self.part_representative(spqr_node_index, spqr_edge_index),
+                            ],
+                        );
+                    }
+                }
+            }
+        }
+
+        separators.into_iter()
+    }
+
+    /// Returns a node on the far side of the given SPQR edge, as seen from the given SPQR node, that is not a pole of its virtual edge.
+    fn part_representative(
+        &self,
+        from: SPQRNodeIndex<Graph::IndexType>,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+    ) -> Graph::NodeIndex {
+        let spqr_edge = &self.spqr_edges[spqr_edge_index];
+        let (u, v) = spqr_edge.virtual_edge;
+        let to = if spqr_edge.endpoints.0 == from {
+            spqr_edge.endpoints.1
+        } else {
+            spqr_edge.endpoints.0
+        };
+
+        // Only P-nodes contain no further nodes, and their other branches all contain further nodes.
+        self.spqr_nodes[to]
+            .iter_nodes()
+            .find(|&node_index| node_index != u && node_index != v)
+            .unwrap_or_else(|| {
+                let next = self.spqr_nodes[to]
+                    .iter_incident_spqr_edges()
+                    .find(|&next| next != spqr_edge_index)
+                    .expect("P-node has only one virtual edge");
+                self.part_representative(to, next)
+            })
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{TestGraph, read_decomposition, read_graph, read_subdivided_wheel},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_iter_minimal_separators_le2() {
+    type NodeIndex = <TestGraph as StaticGraph>::NodeIndex;
+
+    /// Counts the connected parts of the graph without the removed nodes by brute force.
+    fn count_parts(graph: &TestGraph, removed: &[NodeIndex]) -> usize {
+        let mut visited = vec![false; graph.node_count()];
+        let mut parts = 0;
+        for start in graph.node_indices() {
+            if removed.contains(&start) || visited[start.into_usize()] {
+                continue;
+            }
+            parts += 1;
+            visited[start.into_usize()] = true;
+            let mut stack = vec![start];
+            while let Some(node_index) = stack.pop() {
+                for edge_index in graph.incident_edges(node_index) {
+                    let (u, v) = graph.edge_endpoints(edge_index);
+                    let other = if u == node_index { v } else { u };
+                    if !removed.contains(&other) && !visited[other.into_usize()] {
+                        visited[other.into_usize()] = true;
+                        stack.push(other);
+                    }
+                }
+            }
+        }
+        parts
+    }
+
+    let (wheel_graph, wheel_decomposition_file) = read_subdivided_wheel();
+    let wheel_decomposition =
+        SPQRDecomposition::read_plain_spqr(&wheel_graph, wheel_decomposition_file.as_bytes())
+            .unwrap();
+    let mut cases = vec![("wheel", &wheel_graph, wheel_decomposition)];
+    let graphs = [
+        "tiny1",
+        "multiedge",
+        "random3_5_False_0",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ]
+    .map(|name| (name, read_graph(name)));
+    for (name, graph) in &graphs {
+        cases.push((name, graph, read_decomposition(graph, name)));
+    }
+
+    for (name, graph, spqr_decomposition) in cases {
+        let separators = spqr_decomposition
+            .iter_minimal_separators_le2()
+            .collect::<Vec<_>>();
+        let mut found = separators
+            .iter()
+            .map(|separator| {
+                assert_eq!(
+                    separator.parts.len(),
+                    count_parts(graph, &separator.nodes),
+                    "{name}"
+                );
+                for part in &separator.parts {
+                    assert!(!separator.nodes.contains(part));
+                }
+                let mut nodes = separator.nodes.to_vec();
+                nodes.sort();
+                nodes
+            })
+            .collect::<Vec<_>>();
+        found.sort();
+        let found_len = found.len();
+        found.dedup();
+        assert_eq!(found.len(), found_len, "{name}: separator reported twice");
+
+        let nodes = graph.node_indices().collect::<Vec<_>>();
+        let separates = |removed: &[NodeIndex]| count_parts(graph, removed) > 1;
+        let mut expected = Vec::new();
+        for (i, &u) in nodes.iter().enumerate() {
+            if separates(&[u]) {
+                expected.push(vec![u]);
+                continue;
+            }
+            for &v in &nodes[i + 1..] {
+                if !separates(&[v]) && separates(&[u, v]) {
+                    expected.push(vec![u, v]);
+                }
+            }
+        }
+        expected.sort();
+        assert_eq!(found, expected, "{name}");
+    }
+}
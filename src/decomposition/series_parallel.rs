@@ -0,0 +1,209 @@
+//! Reducing series-parallel networks along the SPQR trees.
+//!
+//! Many quantities of two-terminal networks, such as resistances, capacities or connection probabilities,
+//! can be computed by repeatedly replacing two edges in series or in parallel by a single edge with a combined value.
+//! In a block without R-nodes, the SPQR tree describes such a sequence of replacements:
+//! each virtual edge stands for its side, which is a series composition of the other skeleton edges of an S-node,
+//! or a parallel composition of the other skeleton edges of a P-node.
+//! The skeletons of R-nodes cannot be reduced this way.
+
+use std::borrow::Borrow;
+
+use thiserror::Error;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The error returned by [`SPQRDecomposition::reduce_series_parallel`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SeriesParallelReductionError<IndexType> {
+    #[error("the block contains R-node {0}, which cannot be reduced")]
+    RNode(SPQRNodeIndex<IndexType>),
+
+    #[error(
+        "no SPQR node contains both terminals, so the block is not a series-parallel network between them"
+    )]
+    NotTwoTerminal,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Reduces the given block to a single value between `source` and `sink`, see the [module documentation](self).
+    ///
+    /// Each edge starts with its `edge_value`, and two values in series or in parallel are combined with `series` or `parallel`.
+    /// Values in series are combined in the order from `source` to `sink`, but the order of values in parallel is arbitrary.
+    /// Self-loops are not part of any path between the terminals and are ignored.
+    ///
+    /// The block is a series-parallel network between `source` and `sink` if it has no R-nodes and some SPQR node contains both terminals.
+    /// Otherwise, an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` and `sink` are equal, or if one of them is not in the given block.
+    pub fn reduce_series_parallel<Value>(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        source: Graph::NodeIndex,
+        sink: Graph::NodeIndex,
+        mut edge_value: impl FnMut(Graph::EdgeIndex) -> Value,
+        mut series: impl FnMut(Value, Value) -> Value,
+        mut parallel: impl FnMut(Value, Value) -> Value,
+    ) -> Result<Value, SeriesParallelReductionError<Graph::IndexType>> {
+        assert_ne!(source, sink, "source and sink are equal");
+        let block = &self.blocks[block_index];
+        assert!(
+            block.nodes.contains(&source) && block.nodes.contains(&sink),
+            "source or sink is not in the block"
+        );
+        if block.spqr_nodes.is_empty() {
+            return Ok(block
+                .iter_edges()
+                .filter(|&edge_index| {
+                    let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+                    u != v
+                })
+                .map(&mut edge_value)
+                .reduce(&mut parallel)
+                .expect("a block with two nodes has an edge"));
+        }
+
+        let spqr_node_index = self
+            .node_spqr_node_indices(source)
+            .find(|&spqr_node_index| {
+                self.spqr_nodes[spqr_node_index].block == block_index
+                    && self.spqr_nodes[spqr_node_index].nodes.contains(&sink)
+            })
+            .ok_or(SeriesParallelReductionError::NotTwoTerminal)?;
+        // The paths from source to sink in the skeleton, which are combined in parallel.
+        let paths: Vec<Vec<_>> = match self.spqr_nodes[spqr_node_index].spqr_node_type {
+            SPQRNodeType::SNode => {
+                let mut cycle = self.s_node_cycle(spqr_node_index);
+                let source_position = cycle
+                    .iter()
+                    .position(|&(_, node_index)| node_index == source)
+                    .unwrap();
+                cycle.rotate_left(source_position);
+                let sink_position = cycle
+                    .iter()
+                    .position(|&(_, node_index)| node_index == sink)
+                    .unwrap();
+                let (first, second) = cycle.split_at(sink_position);
+                vec![
+                    first
+                        .iter()
+                        .map(|&(skeleton_edge, _)| skeleton_edge)
+                        .collect(),
+                    second
+                        .iter()
+                        .rev()
+                        .map(|&(skeleton_edge, _)| skeleton_edge)
+                        .collect(),
+                ]
+            }
+            SPQRNodeType::PNode => self
+                .iter_skeleton_edges(spqr_node_index)
+                .filter(|&(_, (u, v))| u != v)
+                .map(|(skeleton_edge, _)| vec![skeleton_edge])
+                .collect(),
+            SPQRNodeType::RNode => {
+                return Err(SeriesParallelReductionError::RNode(spqr_node_index));
+            }
+        };
+
+        let mut result = None;
+        for path in paths {
+            let mut path_value = None;
+            for skeleton_edge in path {
+                let value = match skeleton_edge {
+                    SkeletonEdge::Real(edge_index) => edge_value(edge_index),
+                    SkeletonEdge::Virtual(spqr_edge_index) => self
+                        .reduce_pole_side(
+                            spqr_node_index,
+                            spqr_edge_index,
+                            &mut edge_value,
+                            &mut series,
+                            &mut parallel,
+                        )
+                        .map_err(SeriesParallelReductionError::RNode)?,
+                };
+                path_value = Some(match path_value {
+                    Some(path_value) => series(path_value, value),
+                    None => value,
+                });
+            }
+            let path_value = path_value.unwrap();
+            result = Some(match result {
+                Some(result) => parallel(result, path_value),
+                None => path_value,
+            });
+        }
+        Ok(result.unwrap())
+    }
+
+    /// Reduces the side of the given virtual edge opposite to the given SPQR node to a single value between the poles of the virtual edge,
+    /// see [`reduce_series_parallel`](Self::reduce_series_parallel).
+    ///
+    /// Values in series are combined in the order of the skeleton cycles of S-nodes, which may lead from either pole to the other.
+    /// Returns the first R-node on the side as error, if there is one.
+    pub(crate) fn reduce_pole_side<Value>(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        spqr_edge_index: SPQREdgeIndex<Graph::IndexType>,
+        edge_value: &mut impl FnMut(Graph::EdgeIndex) -> Value,
+        series: &mut impl FnMut(Value, Value) -> Value,
+        parallel: &mut impl FnMut(Value, Value) -> Value,
+    ) -> Result<Value, SPQRNodeIndex<Graph::IndexType>> {
+        let other = self.opposite_spqr_node(spqr_node_index, spqr_edge_index);
+        let skeleton_edges: Vec<_> = match self.spqr_nodes[other].spqr_node_type {
+            SPQRNodeType::SNode => {
+                let mut cycle = self.s_node_cycle(other);
+                let position = cycle
+                    .iter()
+                    .position(|&(skeleton_edge, _)| {
+                        skeleton_edge == SkeletonEdge::Virtual(spqr_edge_index)
+                    })
+                    .unwrap();
+                cycle.rotate_left(position);
+                cycle[1..]
+                    .iter()
+                    .map(|&(skeleton_edge, _)| skeleton_edge)
+                    .collect()
+            }
+            SPQRNodeType::PNode => self
+                .iter_skeleton_edges(other)
+                .filter(|&(skeleton_edge, (u, v))| {
+                    u != v && skeleton_edge != SkeletonEdge::Virtual(spqr_edge_index)
+                })
+                .map(|(skeleton_edge, _)| skeleton_edge)
+                .collect(),
+            SPQRNodeType::RNode => return Err(other),
+        };
+
+        let mut result = None;
+        for skeleton_edge in skeleton_edges {
+            let value = match skeleton_edge {
+                SkeletonEdge::Real(edge_index) => edge_value(edge_index),
+                SkeletonEdge::Virtual(other_edge_index) => {
+                    self.reduce_pole_side(other, other_edge_index, edge_value, series, parallel)?
+                }
+            };
+            result = Some(match result {
+                Some(result) if self.spqr_nodes[other].spqr_node_type == SPQRNodeType::SNode => {
+                    series(result, value)
+                }
+                Some(result) => parallel(result, value),
+                None => value,
+            });
+        }
+        Ok(result.unwrap())
+    }
+}
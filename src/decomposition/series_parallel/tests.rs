@@ -0,0 +1,127 @@
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, series_parallel::SeriesParallelReductionError,
+        tests::all_test_inputs,
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_reduce_series_parallel() {
+    /// Computes the effective resistance between the nodes by solving the grounded Laplacian system of the given edges.
+    fn effective_resistance(
+        nodes: &[usize],
+        edges: &[(usize, usize, f64)],
+        source: usize,
+        sink: usize,
+    ) -> f64 {
+        let free = nodes
+            .iter()
+            .copied()
+            .filter(|&node| node != sink)
+            .collect::<Vec<_>>();
+        let n = free.len();
+        let mut matrix = vec![vec![0.0; n + 1]; n];
+        for &(u, v, resistance) in edges {
+            let (u, v) = (
+                free.iter().position(|&node| node == u),
+                free.iter().position(|&node| node == v),
+            );
+            for (a, b) in [(u, v), (v, u)] {
+                if let Some(a) = a {
+                    matrix[a][a] += 1.0 / resistance;
+                    if let Some(b) = b {
+                        matrix[a][b] -= 1.0 / resistance;
+                    }
+                }
+            }
+        }
+        let source_row = free.iter().position(|&node| node == source).unwrap();
+        matrix[source_row][n] = 1.0;
+
+        for column in 0..n {
+            let pivot = (column..n)
+                .max_by(|&a, &b| matrix[a][column].abs().total_cmp(&matrix[b][column].abs()))
+                .unwrap();
+            matrix.swap(column, pivot);
+            let pivot_row = matrix[column].clone();
+            for (row, entries) in matrix.iter_mut().enumerate() {
+                if row != column {
+                    let factor = entries[column] / pivot_row[column];
+                    for (entry, pivot_entry) in entries.iter_mut().zip(&pivot_row).skip(column) {
+                        *entry -= factor * pivot_entry;
+                    }
+                }
+            }
+        }
+        matrix[source_row][n] / matrix[source_row][source_row]
+    }
+
+    let graphs = all_test_inputs();
+
+    let resistance = |edge_index: usize| 1.0 + (edge_index % 3) as f64;
+    let (mut reductions, mut not_two_terminal, mut r_nodes) = (0, 0, 0);
+    for (graph, spqr_decomposition_file) in &graphs {
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(graph, spqr_decomposition_file.as_bytes()).unwrap();
+        for (block_index, block) in spqr_decomposition.iter_blocks() {
+            let nodes = block.iter_nodes().map(usize::from).collect::<Vec<_>>();
+            let edges = spqr_decomposition
+                .iter_edges_in_block(block_index)
+                .map(|edge_index| {
+                    let (u, v) = graph.edge_endpoints(edge_index);
+                    (
+                        usize::from(u),
+                        usize::from(v),
+                        resistance(usize::from(edge_index)),
+                    )
+                })
+                .filter(|&(u, v, _)| u != v)
+                .collect::<Vec<_>>();
+            for source in block.iter_nodes() {
+                for sink in block.iter_nodes().filter(|&sink| sink != source) {
+                    match spqr_decomposition.reduce_series_parallel(
+                        block_index,
+                        source,
+                        sink,
+                        |edge_index| resistance(usize::from(edge_index)),
+                        |a, b| a + b,
+                        |a, b| a * b / (a + b),
+                    ) {
+                        Ok(value) => {
+                            let expected = effective_resistance(
+                                &nodes,
+                                &edges,
+                                usize::from(source),
+                                usize::from(sink),
+                            );
+                            assert!((value - expected).abs() < 1e-9);
+                            reductions += 1;
+                        }
+                        Err(SeriesParallelReductionError::NotTwoTerminal) => {
+                            assert!(spqr_decomposition.node_spqr_node_indices(source).all(
+                                |spqr_node_index| {
+                                    !spqr_decomposition
+                                        .spqr_node(spqr_node_index)
+                                        .iter_nodes()
+                                        .any(|node_index| node_index == sink)
+                                }
+                            ));
+                            not_two_terminal += 1;
+                        }
+                        Err(SeriesParallelReductionError::RNode(spqr_node_index)) => {
+                            assert_eq!(
+                                spqr_decomposition
+                                    .spqr_node(spqr_node_index)
+                                    .spqr_node_type(),
+                                SPQRNodeType::RNode
+                            );
+                            r_nodes += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    assert!(reductions > 0 && not_two_terminal > 0 && r_nodes > 0);
+}
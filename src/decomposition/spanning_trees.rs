@@ -0,0 +1,460 @@
+//! Counting spanning trees along the block-cut tree and the SPQR trees.
+//!
+//! The spanning trees of a connected graph are the combinations of spanning trees of its blocks.
+//! Within a block, each SPQR node is evaluated bottom-up together with the subtrees below it:
+//! for the pertinent graph `H` with poles `s` and `t`, we count its spanning trees `T(H)`,
+//! and its spanning forests with two trees that separate `s` from `t`, `F(H)`.
+//! A real edge has `T = F = 1`.
+//! The skeletons of S-nodes and P-nodes are series and parallel compositions, which have closed forms,
+//! and the skeletons of R-nodes are evaluated with Kirchhoff's matrix tree theorem,
+//! weighting each edge `e` with `T(e) / F(e)`.
+//!
+//! The counts grow exponentially, so they are computed modulo enough large primes to determine them, and combined by the Chinese remainder theorem.
+
+use std::{
+    borrow::Borrow,
+    fmt::{self, Display},
+    ops::Mul,
+};
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, SPQREdgeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// An unsigned integer of arbitrary size, with just the arithmetic needed to count spanning trees.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub(crate) struct BigUint {
+    /// The digits in base `2^64`, least significant first, without leading zeros.
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn mul_small(&self, factor: u64) -> Self {
+        let mut carry = 0;
+        let mut limbs = self
+            .limbs
+            .iter()
+            .map(|&limb| {
+                let product = u128::from(limb) * u128::from(factor) + carry;
+                carry = product >> 64;
+                product as u64
+            })
+            .collect::<Vec<_>>();
+        limbs.push(carry as u64);
+        Self::normalised(limbs)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut carry = false;
+        let limbs = (0..self.limbs.len().max(other.limbs.len()) + 1)
+            .map(|i| {
+                let (sum, first_carry) = self
+                    .limbs
+                    .get(i)
+                    .copied()
+                    .unwrap_or(0)
+                    .overflowing_add(other.limbs.get(i).copied().unwrap_or(0));
+                let (sum, second_carry) = sum.overflowing_add(carry.into());
+                carry = first_carry || second_carry;
+                sum
+            })
+            .collect();
+        Self::normalised(limbs)
+    }
+
+    /// Divides in place by the given divisor, and returns the remainder.
+    fn div_rem_small(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let dividend = u128::from(remainder) << 64 | u128::from(*limb);
+            *limb = (dividend / u128::from(divisor)) as u64;
+            remainder = (dividend % u128::from(divisor)) as u64;
+        }
+        *self = Self::normalised(std::mem::take(&mut self.limbs));
+        remainder
+    }
+
+    fn rem_small(&self, divisor: u64) -> u64 {
+        self.clone().div_rem_small(divisor)
+    }
+
+    fn normalised(mut limbs: Vec<u64>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Self { limbs }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::normalised(vec![value])
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = u128::from(a) * u128::from(b) + u128::from(limbs[i + j]) + carry;
+                limbs[i + j] = product as u64;
+                carry = product >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+        BigUint::normalised(limbs)
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let mut remaining = self.clone();
+        let mut chunks = Vec::new();
+        while !remaining.is_zero() {
+            chunks.push(remaining.div_rem_small(CHUNK));
+        }
+        let Some((first, rest)) = chunks.split_last() else {
+            return f.pad("0");
+        };
+        let mut digits = first.to_string();
+        for chunk in rest.iter().rev() {
+            digits.push_str(&format!("{chunk:019}"));
+        }
+        f.pad(&digits)
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the number of spanning trees of the graph in decimal, see the [module documentation](self).
+    ///
+    /// The count may exceed any fixed-size integer type, so it is returned as a string that can be parsed into one where it fits,
+    /// or into an arbitrary-precision integer.
+    /// If the graph is disconnected, this is the number of its maximal spanning forests, i.e. the product over its components.
+    /// Self-loops are never part of a spanning tree, and parallel edges are distinguished.
+    ///
+    /// Each block takes time cubic in the size of its largest R-node skeleton and linear in the size of the rest of its SPQR tree,
+    /// per 60 bits of its count.
+    pub fn count_spanning_trees(&self) -> String {
+        let mut primes = Vec::new();
+        self.blocks
+            .iter_indices(..)
+            .fold(BigUint::from(1), |count, block_index| {
+                &count * &self.count_block_spanning_trees(block_index, &mut primes)
+            })
+            .to_string()
+    }
+
+    /// Returns the number of spanning trees of the given block.
+    ///
+    /// The primes used so far are reused from `primes`, and further ones are appended.
+    fn count_block_spanning_trees(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        primes: &mut Vec<u64>,
+    ) -> BigUint {
+        let block = &self.blocks[block_index];
+        let is_loop = |edge_index| {
+            let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+            u == v
+        };
+        if block.spqr_nodes.is_empty() {
+            let edge_count = block
+                .iter_edges()
+                .filter(|&edge_index| !is_loop(edge_index))
+                .count();
+            return BigUint::from(if block.nodes.len() == 1 {
+                1
+            } else {
+                edge_count as u64
+            });
+        }
+
+        // Rooting at any node, each spanning tree is determined by the edge from each other node towards the root.
+        let mut degrees = FxHashMap::<Graph::NodeIndex, usize>::default();
+        for edge_index in self.iter_edges_in_block(block_index) {
+            let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+            if u != v {
+                *degrees.entry(u).or_default() += 1;
+                *degrees.entry(v).or_default() += 1;
+            }
+        }
+        let bits: f64 = block.nodes[1..]
+            .iter()
+            .map(|node_index| (degrees[node_index] as f64).log2())
+            .sum();
+        let prime_count = (bits / 60.0).ceil() as usize + 1;
+
+        let mut residues = Vec::with_capacity(prime_count);
+        let mut prime_position = 0;
+        while residues.len() < prime_count {
+            if prime_position == primes.len() {
+                primes.push(previous_prime(primes.last().copied().unwrap_or(1 << 61)));
+            }
+            let prime = primes[prime_position];
+            prime_position += 1;
+            residues.extend(
+                self.count_block_spanning_trees_modulo(block_index, prime)
+                    .map(|residue| (residue, prime)),
+            );
+        }
+
+        let mut count = BigUint::default();
+        let mut modulus = BigUint::from(1);
+        for (residue, prime) in residues {
+            let correction = mul_mod(
+                (residue + prime - count.rem_small(prime)) % prime,
+                inverse_mod(modulus.rem_small(prime), prime),
+                prime,
+            );
+            count = count.add(&modulus.mul_small(correction));
+            modulus = modulus.mul_small(prime);
+        }
+        count
+    }
+
+    /// Returns the number of spanning trees of the given block modulo the given prime,
+    /// or `None` if some `F` is divisible by the prime, such that its edge weight is undefined.
+    fn count_block_spanning_trees_modulo(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        prime: u64,
+    ) -> Option<u64> {
+        let spqr_nodes = &self.blocks[block_index].spqr_nodes;
+        let mut order = vec![(spqr_nodes[0], None)];
+        let mut position = 0;
+        while let Some(&(spqr_node_index, parent_edge)) = order.get(position) {
+            position += 1;
+            for spqr_edge_index in self.spqr_nodes[spqr_node_index].iter_incident_spqr_edges() {
+                if Some(spqr_edge_index) != parent_edge {
+                    let spqr_edge = &self.spqr_edges[spqr_edge_index];
+                    let child = if spqr_edge.endpoints.0 == spqr_node_index {
+                        spqr_edge.endpoints.1
+                    } else {
+                        spqr_edge.endpoints.0
+                    };
+                    order.push((child, Some(spqr_edge_index)));
+                }
+            }
+        }
+
+        // The counts `(T, F)` of the pertinent graph behind each virtual edge, as seen from its parent.
+        let mut pertinent_counts =
+            FxHashMap::<SPQREdgeIndex<Graph::IndexType>, (u64, u64)>::default();
+        for &(spqr_node_index, parent_edge) in order.iter().rev() {
+            let spqr_node = &self.spqr_nodes[spqr_node_index];
+            let mut edges = Vec::with_capacity(spqr_node.skeleton_edge_count());
+            for (skeleton_edge, (u, v)) in self.iter_skeleton_edges(spqr_node_index) {
+                match skeleton_edge {
+                    SkeletonEdge::Real(_) if u == v => {}
+                    SkeletonEdge::Real(_) => edges.push((u, v, (1, 1))),
+                    SkeletonEdge::Virtual(spqr_edge_index)
+                        if Some(spqr_edge_index) == parent_edge => {}
+                    SkeletonEdge::Virtual(spqr_edge_index) => {
+                        edges.push((u, v, pertinent_counts[&spqr_edge_index]))
+                    }
+                }
+            }
+
+            let (connected, separated) = match spqr_node.spqr_node_type {
+                SPQRNodeType::SNode => edges.iter().fold((1, 0), |(t, f), &(_, _, (te, fe))| {
+                    (
+                        mul_mod(t, te, prime),
+                        (mul_mod(f, te, prime) + mul_mod(t, fe, prime)) % prime,
+                    )
+                }),
+                SPQRNodeType::PNode => edges.iter().fold((0, 1), |(t, f), &(_, _, (te, fe))| {
+                    (
+                        (mul_mod(t, fe, prime) + mul_mod(f, te, prime)) % prime,
+                        mul_mod(f, fe, prime),
+                    )
+                }),
+                SPQRNodeType::RNode => {
+                    let nodes = spqr_node.iter_nodes().collect::<Vec<_>>();
+                    let connected = weighted_spanning_tree_count(&nodes, &edges, None, prime)?;
+                    match parent_edge {
+                        Some(parent_edge) => (
+                            connected,
+                            weighted_spanning_tree_count(
+                                &nodes,
+                                &edges,
+                                Some(self.spqr_edges[parent_edge].virtual_edge),
+                                prime,
+                            )?,
+                        ),
+                        None => (connected, 0),
+                    }
+                }
+            };
+
+            match parent_edge {
+                Some(parent_edge) => {
+                    pertinent_counts.insert(parent_edge, (connected, separated));
+                }
+                None => {
+                    // At the root, a cycle loses one edge and a bond keeps one edge.
+                    return Some(match spqr_node.spqr_node_type {
+                        SPQRNodeType::SNode => separated,
+                        SPQRNodeType::PNode | SPQRNodeType::RNode => connected,
+                    });
+                }
+            }
+        }
+        unreachable!("the root is the first SPQR node in the order")
+    }
+}
+
+/// Returns the sum over all spanning trees of the given graph, of the product of `T` over their edges and `F` over the other edges,
+/// modulo the given prime, after merging the given pair of nodes.
+///
+/// Returns `None` if some `F` is divisible by the prime.
+fn weighted_spanning_tree_count<NodeIndex: Copy + Eq>(
+    nodes: &[NodeIndex],
+    edges: &[(NodeIndex, NodeIndex, (u64, u64))],
+    merged: Option<(NodeIndex, NodeIndex)>,
+    prime: u64,
+) -> Option<u64> {
+    // Merging the nodes maps the second one to the index of the first one.
+    let mut ids = Vec::with_capacity(nodes.len());
+    let mut id_count = 0;
+    for &node_index in nodes {
+        ids.push(id_count);
+        if merged.is_none_or(|(_, t)| node_index != t) {
+            id_count += 1;
+        }
+    }
+    if let Some((s, t)) = merged {
+        let position = |node_index| nodes.iter().position(|&node| node == node_index).unwrap();
+        let (s, t) = (position(s), position(t));
+        ids[t] = ids[s];
+    }
+    let id = |node_index| ids[nodes.iter().position(|&node| node == node_index).unwrap()];
+
+    // The Laplacian without the row and column of the last node.
+    let size = id_count - 1;
+    let mut laplacian = vec![vec![0; size]; size];
+    let mut f_product = 1;
+    for &(u, v, (t, f)) in edges {
+        f_product = mul_mod(f_product, f, prime);
+        let (u, v) = (id(u), id(v));
+        if u == v {
+            continue;
+        }
+        if f % prime == 0 {
+            return None;
+        }
+        let weight = mul_mod(t, inverse_mod(f, prime), prime);
+        let (u, v) = ((u < size).then_some(u), (v < size).then_some(v));
+        for (a, b) in [(u, v), (v, u)] {
+            if let Some(a) = a {
+                laplacian[a][a] = (laplacian[a][a] + weight) % prime;
+                if let Some(b) = b {
+                    laplacian[a][b] = (laplacian[a][b] + prime - weight) % prime;
+                }
+            }
+        }
+    }
+
+    Some(mul_mod(f_product, determinant_mod(laplacian, prime), prime))
+}
+
+/// Returns the determinant of the given square matrix modulo the given prime, by Gaussian elimination.
+fn determinant_mod(mut matrix: Vec<Vec<u64>>, prime: u64) -> u64 {
+    let mut determinant = 1;
+    for column in 0..matrix.len() {
+        let Some(pivot) = (column..matrix.len()).find(|&row| matrix[row][column] != 0) else {
+            return 0;
+        };
+        if pivot != column {
+            matrix.swap(pivot, column);
+            determinant = prime - determinant;
+        }
+        determinant = mul_mod(determinant, matrix[column][column], prime);
+        let inverse = inverse_mod(matrix[column][column], prime);
+        let (pivot_rows, rows) = matrix.split_at_mut(column + 1);
+        let pivot_row = &pivot_rows[column];
+        for row in rows {
+            let factor = mul_mod(row[column], inverse, prime);
+            for (entry, &pivot_entry) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                *entry = (*entry + prime - mul_mod(factor, pivot_entry, prime)) % prime;
+            }
+        }
+    }
+    determinant % prime
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(modulus)) as u64
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Returns the multiplicative inverse modulo a prime, by Fermat's little theorem.
+fn inverse_mod(value: u64, prime: u64) -> u64 {
+    pow_mod(value, prime - 2, prime)
+}
+
+/// Returns the largest prime below the given number, using the Miller-Rabin test with a set of bases that is deterministic for 64 bits.
+fn previous_prime(below: u64) -> u64 {
+    let is_prime = |candidate: u64| {
+        let (mut odd, mut twos) = (candidate - 1, 0);
+        while odd % 2 == 0 {
+            odd /= 2;
+            twos += 1;
+        }
+        [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37]
+            .into_iter()
+            .all(|base| {
+                let mut x = pow_mod(base, odd, candidate);
+                if x == 1 || x == candidate - 1 {
+                    return true;
+                }
+                for _ in 1..twos {
+                    x = mul_mod(x, x, candidate);
+                    if x == candidate - 1 {
+                        return true;
+                    }
+                }
+                false
+            })
+    };
+
+    let mut candidate = (below - 1) | 1;
+    if candidate >= below {
+        candidate -= 2;
+    }
+    while !is_prime(candidate) {
+        candidate -= 2;
+    }
+    candidate
+}
@@ -0,0 +1,94 @@
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        spanning_trees::BigUint,
+        tests::{TEST_FILES, TestGraph, read_decomposition, read_graph, read_subdivided_wheel},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_count_spanning_trees() {
+    /// Counts the maximal spanning forests by Kirchhoff's theorem applied to each component.
+    fn kirchhoff(spqr_decomposition: &SPQRDecomposition<'_, TestGraph>) -> f64 {
+        spqr_decomposition
+            .iter_components()
+            .map(|(component_index, component)| {
+                let nodes = component.iter_nodes().collect::<Vec<_>>();
+                let size = nodes.len() - 1;
+                let mut laplacian = vec![vec![0.0; size]; size];
+                for edge_index in spqr_decomposition.iter_edges_in_component(component_index) {
+                    let (u, v) = spqr_decomposition.graph().edge_endpoints(edge_index);
+                    let (u, v) = (
+                        nodes.iter().position(|&node| node == u).unwrap(),
+                        nodes.iter().position(|&node| node == v).unwrap(),
+                    );
+                    if u != v {
+                        for (a, b) in [(u, v), (v, u)] {
+                            if a < size {
+                                laplacian[a][a] += 1.0;
+                                if b < size {
+                                    laplacian[a][b] -= 1.0;
+                                }
+                            }
+                        }
+                    }
+                }
+
+                let mut determinant = 1.0;
+                for column in 0..size {
+                    let pivot = (column..size)
+                        .max_by(|&a, &b| {
+                            f64::abs(laplacian[a][column])
+                                .total_cmp(&f64::abs(laplacian[b][column]))
+                        })
+                        .unwrap();
+                    laplacian.swap(pivot, column);
+                    if pivot != column {
+                        determinant = -determinant;
+                    }
+                    determinant *= laplacian[column][column];
+                    let (pivot_rows, rows) = laplacian.split_at_mut(column + 1);
+                    let pivot_row = &pivot_rows[column];
+                    for row in rows {
+                        let factor = row[column] / pivot_row[column];
+                        for (entry, pivot_entry) in row.iter_mut().zip(pivot_row) {
+                            *entry -= factor * pivot_entry;
+                        }
+                    }
+                }
+                determinant
+            })
+            .product()
+    }
+
+    let (graph, spqr_decomposition_file) = read_subdivided_wheel();
+    let wheel =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let graphs = TEST_FILES.map(read_graph);
+    let decompositions = TEST_FILES
+        .into_iter()
+        .zip(&graphs)
+        .map(|(name, graph)| read_decomposition(graph, name))
+        .chain([wheel]);
+
+    for spqr_decomposition in decompositions {
+        let count = spqr_decomposition
+            .count_spanning_trees()
+            .parse::<u128>()
+            .unwrap();
+        assert_eq!(count, kirchhoff(&spqr_decomposition).round() as u128);
+    }
+
+    let large = &BigUint::from(u64::MAX) * &BigUint::from(u64::MAX);
+    assert_eq!(
+        large.to_string(),
+        (u128::from(u64::MAX) * u128::from(u64::MAX)).to_string()
+    );
+    let larger = &large * &large;
+    assert_eq!(
+        larger.to_string(),
+        "115792089237316195398462578067141184799968521174335529155754622898352762650625"
+    );
+    assert_eq!(BigUint::default().to_string(), "0");
+}
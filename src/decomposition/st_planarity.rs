@@ -0,0 +1,200 @@
+//! Deciding whether two nodes of a block can lie on a common face, as required for st-planar and upward planar drawings.
+//!
+//! A block with a designated source `s` and sink `t` has a planar embedding with `s` and `t` on the outer face
+//! if and only if it stays planar when adding the edge `st`.
+//! In that case, orienting the block along an st-numbering yields a planar st-graph, which has an upward planar drawing.
+//!
+//! The SPQR tree reduces this to the skeletons on the path between the SPQR nodes containing `s` and those containing `t`.
+//! The skeletons of S-nodes are cycles, and the branches of P-nodes can be permuted freely, so neither restricts the embedding.
+//! The skeletons of R-nodes are triconnected and have a unique planar embedding up to mirroring,
+//! so there the parts of the skeleton leading towards `s` and towards `t` must share a face.
+
+use std::borrow::Borrow;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        indices::{BlockIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A node or a virtual edge of the skeleton of an SPQR node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkeletonElement<NodeIndex, IndexType> {
+    Node(NodeIndex),
+    /// A virtual edge, represented by the SPQR edge that connects the two SPQR nodes containing it.
+    VirtualEdge(SPQREdgeIndex<IndexType>),
+}
+
+/// Two elements of the skeleton of an R-node that must lie on a common face of its embedding,
+/// see [`SPQRDecomposition::st_planarity_constraints`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FaceConstraint<NodeIndex, IndexType> {
+    pub r_node: SPQRNodeIndex<IndexType>,
+    /// The source, or the virtual edge leading towards it.
+    pub source_side: SkeletonElement<NodeIndex, IndexType>,
+    /// The sink, or the virtual edge leading towards it.
+    pub sink_side: SkeletonElement<NodeIndex, IndexType>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the constraints that the embeddings of R-node skeletons must satisfy, such that `source` and `sink` lie on a common face of the given block.
+    ///
+    /// If the block is planar, then it has an embedding with `source` and `sink` on the outer face if and only if each returned constraint holds
+    /// in the embedding of the skeleton of its R-node, see the [module documentation](self).
+    /// Hence, if no constraints are returned, every planar embedding of the block can be rearranged to put `source` and `sink` on the outer face.
+    /// Constraints whose elements are incident or adjacent in the skeleton always hold, and are not returned.
+    ///
+    /// The path between the SPQR nodes containing `source` and `sink` is found by walking up the rooted SPQR tree, see [`spqr_tree_parent`](Self::spqr_tree_parent).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` and `sink` are equal, or if one of them is not in the given block.
+    pub fn st_planarity_constraints(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        source: Graph::NodeIndex,
+        sink: Graph::NodeIndex,
+    ) -> Vec<FaceConstraint<Graph::NodeIndex, Graph::IndexType>> {
+        assert_ne!(source, sink, "source and sink are equal");
+        let in_block = |node_index| {
+            self.node_spqr_node_indices(node_index)
+                .filter(|&spqr_node_index| self.spqr_nodes[spqr_node_index].block == block_index)
+                .collect::<Vec<_>>()
+        };
+        let (source_spqr_nodes, sink_spqr_nodes) = (in_block(source), in_block(sink));
+        if self.blocks[block_index].spqr_nodes.is_empty() {
+            assert!(
+                self.blocks[block_index].nodes.contains(&source)
+                    && self.blocks[block_index].nodes.contains(&sink),
+                "source or sink is not in the block"
+            );
+            return Vec::new();
+        }
+        assert!(
+            !source_spqr_nodes.is_empty() && !sink_spqr_nodes.is_empty(),
+            "source or sink is not in the block"
+        );
+
+        let path = self.spqr_tree_path(source_spqr_nodes[0], sink_spqr_nodes[0]);
+        // The SPQR nodes containing a node form a subtree, so the path enters and leaves each of them only once.
+        let start = path
+            .iter()
+            .rposition(|(spqr_node_index, _)| source_spqr_nodes.contains(spqr_node_index))
+            .unwrap();
+        let end = path
+            .iter()
+            .position(|(spqr_node_index, _)| sink_spqr_nodes.contains(spqr_node_index))
+            .unwrap();
+        if start > end {
+            // Some SPQR node contains both source and sink.
+            return path[end..=start]
+                .iter()
+                .find(|(spqr_node_index, _)| {
+                    source_spqr_nodes.contains(spqr_node_index)
+                        && sink_spqr_nodes.contains(spqr_node_index)
+                })
+                .and_then(|&(spqr_node_index, _)| {
+                    self.face_constraint(
+                        spqr_node_index,
+                        SkeletonElement::Node(source),
+                        SkeletonElement::Node(sink),
+                    )
+                })
+                .into_iter()
+                .collect();
+        }
+
+        let mut constraints = Vec::new();
+        for position in start..=end {
+            let (spqr_node_index, spqr_edge_to_next) = path[position];
+            let source_side = if position == start {
+                SkeletonElement::Node(source)
+            } else {
+                SkeletonElement::VirtualEdge(path[position - 1].1.unwrap())
+            };
+            let sink_side = if position == end {
+                SkeletonElement::Node(sink)
+            } else {
+                SkeletonElement::VirtualEdge(spqr_edge_to_next.unwrap())
+            };
+            constraints.extend(self.face_constraint(spqr_node_index, source_side, sink_side));
+        }
+        constraints
+    }
+
+    /// Returns the path between the given SPQR nodes of the same block,
+    /// each SPQR node together with the SPQR edge to the next one.
+    pub(crate) fn spqr_tree_path(
+        &self,
+        from: SPQRNodeIndex<Graph::IndexType>,
+        to: SPQRNodeIndex<Graph::IndexType>,
+    ) -> Vec<(
+        SPQRNodeIndex<Graph::IndexType>,
+        Option<SPQREdgeIndex<Graph::IndexType>>,
+    )> {
+        let ancestor = self.spqr_tree_lowest_common_ancestor(from, to);
+        let walk_up = |mut spqr_node_index| {
+            let mut walk = Vec::new();
+            while spqr_node_index != ancestor {
+                let (parent, spqr_edge_index) = self.spqr_tree_parent(spqr_node_index).unwrap();
+                walk.push((spqr_node_index, spqr_edge_index));
+                spqr_node_index = parent;
+            }
+            walk
+        };
+
+        let mut path: Vec<_> = walk_up(from)
+            .into_iter()
+            .map(|(spqr_node_index, spqr_edge_index)| (spqr_node_index, Some(spqr_edge_index)))
+            .collect();
+        let mut previous_edge = None;
+        let mut descent = Vec::new();
+        for (spqr_node_index, spqr_edge_index) in walk_up(to) {
+            descent.push((spqr_node_index, previous_edge));
+            previous_edge = Some(spqr_edge_index);
+        }
+        path.push((ancestor, previous_edge));
+        path.extend(descent.into_iter().rev());
+        path
+    }
+
+    /// Returns a constraint if the given SPQR node is an R-node and the given elements of its skeleton are neither equal, incident nor adjacent.
+    fn face_constraint(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        source_side: SkeletonElement<Graph::NodeIndex, Graph::IndexType>,
+        sink_side: SkeletonElement<Graph::NodeIndex, Graph::IndexType>,
+    ) -> Option<FaceConstraint<Graph::NodeIndex, Graph::IndexType>> {
+        if self.spqr_nodes[spqr_node_index].spqr_node_type != SPQRNodeType::RNode {
+            return None;
+        }
+
+        let is_incident = |node_index, spqr_edge_index: SPQREdgeIndex<_>| {
+            let (u, v) = self.spqr_edges[spqr_edge_index].virtual_edge;
+            node_index == u || node_index == v
+        };
+        let always_holds = match (source_side, sink_side) {
+            (SkeletonElement::Node(u), SkeletonElement::Node(v)) => self
+                .iter_skeleton_edges(spqr_node_index)
+                .any(|(_, endpoints)| endpoints == (u, v) || endpoints == (v, u)),
+            (SkeletonElement::Node(node_index), SkeletonElement::VirtualEdge(spqr_edge_index))
+            | (SkeletonElement::VirtualEdge(spqr_edge_index), SkeletonElement::Node(node_index)) => {
+                is_incident(node_index, spqr_edge_index)
+            }
+            (SkeletonElement::VirtualEdge(a), SkeletonElement::VirtualEdge(b)) => a == b,
+        };
+
+        (!always_holds).then_some(FaceConstraint {
+            r_node: spqr_node_index,
+            source_side,
+            sink_side,
+        })
+    }
+}
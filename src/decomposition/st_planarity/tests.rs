@@ -0,0 +1,48 @@
+use crate::decomposition::{
+    SPQRDecomposition,
+    st_planarity::{FaceConstraint, SkeletonElement},
+    tests::{read_decomposition, read_graph, read_subdivided_wheel},
+};
+
+#[test]
+fn test_st_planarity_constraints() {
+    let graph = read_graph("tiny1");
+    let spqr_decomposition = read_decomposition(&graph, "tiny1");
+    let node = |name| spqr_decomposition.node_index_by_name(name).unwrap();
+    assert!(
+        spqr_decomposition
+            .st_planarity_constraints(0.into(), node("j"), node("i"))
+            .is_empty()
+    );
+
+    let (graph, spqr_decomposition_file) = read_subdivided_wheel();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let node = |name| spqr_decomposition.node_index_by_name(name).unwrap();
+
+    for (source, sink) in [("1", "2"), ("h", "3"), ("x", "1"), ("2", "x")] {
+        assert!(
+            spqr_decomposition
+                .st_planarity_constraints(0.into(), node(source), node(sink))
+                .is_empty(),
+            "{source} {sink}"
+        );
+    }
+
+    assert_eq!(
+        spqr_decomposition.st_planarity_constraints(0.into(), node("1"), node("3")),
+        [FaceConstraint {
+            r_node: 0.into(),
+            source_side: SkeletonElement::Node(node("1")),
+            sink_side: SkeletonElement::Node(node("3")),
+        }]
+    );
+    assert_eq!(
+        spqr_decomposition.st_planarity_constraints(0.into(), node("3"), node("x")),
+        [FaceConstraint {
+            r_node: 0.into(),
+            source_side: SkeletonElement::Node(node("3")),
+            sink_side: SkeletonElement::VirtualEdge(0.into()),
+        }]
+    );
+}
@@ -0,0 +1,149 @@
+use std::{borrow::Borrow, collections::BTreeMap, fmt::Display};
+
+use crate::{
+    decomposition::{SPQRDecomposition, SPQRNodeType},
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Summary statistics of an SPQR decomposition, see [`SPQRDecomposition::statistics`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SPQRDecompositionStatistics {
+    pub node_count: usize,
+    pub edge_count: usize,
+    pub component_count: usize,
+    pub block_count: usize,
+    pub cut_node_count: usize,
+    pub spqr_node_count: usize,
+    pub spqr_edge_count: usize,
+    pub s_node_count: usize,
+    pub p_node_count: usize,
+    pub r_node_count: usize,
+    /// The amount of blocks that consist of a single edge.
+    pub bridge_count: usize,
+    /// The amount of nodes in the largest component.
+    pub largest_component_node_count: usize,
+    /// Maps a block count to the amount of components with that many blocks.
+    pub blocks_per_component_histogram: BTreeMap<usize, usize>,
+    /// Maps a node count to the amount of blocks with that many nodes.
+    pub block_size_histogram: BTreeMap<usize, usize>,
+    /// Maps a node count to the amount of S-nodes with that many nodes.
+    pub s_node_size_histogram: BTreeMap<usize, usize>,
+    /// Maps a node count to the amount of R-nodes with that many nodes.
+    pub r_node_size_histogram: BTreeMap<usize, usize>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Computes summary statistics of this decomposition.
+    pub fn statistics(&self) -> SPQRDecompositionStatistics {
+        let mut statistics = SPQRDecompositionStatistics {
+            node_count: self.graph_ref().node_count(),
+            edge_count: self.graph_ref().edge_count(),
+            component_count: self.component_count(),
+            block_count: self.block_count(),
+            cut_node_count: self.cut_node_count(),
+            spqr_node_count: self.spqr_node_count(),
+            spqr_edge_count: self.spqr_edge_count(),
+            ..Default::default()
+        };
+
+        for component in self.components.iter_values() {
+            statistics.largest_component_node_count = statistics
+                .largest_component_node_count
+                .max(component.node_count());
+            *statistics
+                .blocks_per_component_histogram
+                .entry(component.blocks.len())
+                .or_default() += 1;
+        }
+
+        for block in self.blocks.iter_values() {
+            *statistics
+                .block_size_histogram
+                .entry(block.node_count())
+                .or_default() += 1;
+            if block.node_count() == 2 && block.edge_count() == 1 {
+                statistics.bridge_count += 1;
+            }
+        }
+
+        for spqr_node in self.spqr_nodes.iter_values() {
+            match spqr_node.spqr_node_type() {
+                SPQRNodeType::SNode => {
+                    statistics.s_node_count += 1;
+                    *statistics
+                        .s_node_size_histogram
+                        .entry(spqr_node.node_count())
+                        .or_default() += 1;
+                }
+                SPQRNodeType::PNode => statistics.p_node_count += 1,
+                SPQRNodeType::RNode => {
+                    statistics.r_node_count += 1;
+                    *statistics
+                        .r_node_size_histogram
+                        .entry(spqr_node.node_count())
+                        .or_default() += 1;
+                }
+            }
+        }
+
+        statistics
+    }
+}
+
+impl Display for SPQRDecompositionStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Graph with {} nodes and {} edges",
+            self.node_count, self.edge_count
+        )?;
+        writeln!(
+            f,
+            "{} components (largest has {} nodes), {} blocks ({} bridges), {} cut nodes",
+            self.component_count,
+            self.largest_component_node_count,
+            self.block_count,
+            self.bridge_count,
+            self.cut_node_count,
+        )?;
+        writeln!(
+            f,
+            "{} SPQR nodes ({} S-nodes, {} P-nodes, {} R-nodes), {} SPQR edges",
+            self.spqr_node_count,
+            self.s_node_count,
+            self.p_node_count,
+            self.r_node_count,
+            self.spqr_edge_count,
+        )?;
+        write!(f, "Blocks per component:")?;
+        write_histogram(f, &self.blocks_per_component_histogram)?;
+        writeln!(f)?;
+        write!(f, "Block sizes:")?;
+        write_histogram(f, &self.block_size_histogram)?;
+        writeln!(f)?;
+        write!(f, "S-node sizes:")?;
+        write_histogram(f, &self.s_node_size_histogram)?;
+        writeln!(f)?;
+        write!(f, "R-node sizes:")?;
+        write_histogram(f, &self.r_node_size_histogram)
+    }
+}
+
+fn write_histogram(
+    f: &mut std::fmt::Formatter<'_>,
+    histogram: &BTreeMap<usize, usize>,
+) -> std::fmt::Result {
+    if histogram.is_empty() {
+        return write!(f, " none");
+    }
+
+    for (size, count) in histogram {
+        write!(f, " {size}x{count}")?;
+    }
+    Ok(())
+}
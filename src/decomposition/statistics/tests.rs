@@ -0,0 +1,18 @@
+use crate::decomposition::tests::{read_decomposition, read_graph};
+
+#[test]
+fn test_statistics() {
+    let graph = read_graph("tiny1");
+    let statistics = read_decomposition(&graph, "tiny1").statistics();
+
+    assert_eq!(statistics.node_count, 11);
+    assert_eq!(statistics.component_count, 1);
+    assert_eq!(statistics.largest_component_node_count, 11);
+    assert_eq!(statistics.block_count, 5);
+    assert_eq!(statistics.cut_node_count, 4);
+    assert_eq!(statistics.s_node_count, 5);
+    assert_eq!(statistics.r_node_count, 0);
+    assert_eq!(statistics.bridge_count, 0);
+    assert_eq!(statistics.block_size_histogram, [(3, 5)].into());
+    assert!(statistics.to_string().contains("Block sizes: 3x5"));
+}
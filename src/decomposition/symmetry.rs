@@ -0,0 +1,176 @@
+use std::{borrow::Borrow, collections::VecDeque};
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        indices::{BlockIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The orbits of the SPQR nodes and SPQR edges of a block under the automorphisms of its SPQR tree,
+/// see [`SPQRDecomposition::spqr_tree_orbits`].
+///
+/// Each orbit is sorted, and the orbits are sorted by their first element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SPQRTreeOrbits<IndexType> {
+    pub spqr_node_orbits: Vec<Vec<SPQRNodeIndex<IndexType>>>,
+    pub spqr_edge_orbits: Vec<Vec<SPQREdgeIndex<IndexType>>>,
+}
+
+/// The label of an SPQR node that automorphisms must preserve.
+type SkeletonLabel = (SPQRNodeType, usize, usize);
+
+impl<IndexType> SPQRTreeOrbits<IndexType> {
+    /// Returns true if the SPQR tree has no automorphism other than the identity.
+    pub fn is_asymmetric(&self) -> bool {
+        self.spqr_node_orbits.iter().all(|orbit| orbit.len() == 1)
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Computes the orbits of the SPQR nodes and SPQR edges of the given block under the automorphisms of its SPQR tree.
+    ///
+    /// An automorphism must map each SPQR node to one with the same type, the same amount of skeleton nodes and the same amount of real edges.
+    /// Every automorphism of the block induces such an automorphism of its SPQR tree,
+    /// so SPQR nodes in different orbits are never exchanged by a symmetry of the block.
+    /// The converse does not hold, since the order of the edges in the skeletons is not taken into account.
+    ///
+    /// The orbits are found by rooting the tree at its center and comparing the canonical codes of its subtrees, in `O(n log n)` time.
+    pub fn spqr_tree_orbits(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+    ) -> SPQRTreeOrbits<Graph::IndexType> {
+        let spqr_nodes = &self.blocks[block_index].spqr_nodes;
+        let local_index = spqr_nodes
+            .iter()
+            .enumerate()
+            .map(|(local, &spqr_node_index)| (spqr_node_index, local))
+            .collect::<FxHashMap<_, _>>();
+        let neighbors = |local: usize| {
+            self.spqr_tree_neighbors(spqr_nodes[local])
+                .iter()
+                .map(|neighbor| (local_index[&neighbor.spqr_node], neighbor.spqr_edge))
+        };
+
+        let centers = tree_centers(spqr_nodes.len(), neighbors);
+        let mut parents = vec![None; spqr_nodes.len()];
+        let mut order = Vec::with_capacity(spqr_nodes.len());
+        let mut queue = VecDeque::from_iter(centers.iter().copied());
+        let mut visited = vec![false; spqr_nodes.len()];
+        for &center in &centers {
+            visited[center] = true;
+        }
+        while let Some(local) = queue.pop_front() {
+            order.push(local);
+            for (neighbor, spqr_edge_index) in neighbors(local) {
+                if !visited[neighbor] {
+                    visited[neighbor] = true;
+                    parents[neighbor] = Some((local, spqr_edge_index));
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        // Bottom-up, identify each rooted subtree by its label and the sorted codes of its children.
+        let mut children_codes = vec![Vec::new(); spqr_nodes.len()];
+        let mut codes = vec![0; spqr_nodes.len()];
+        let mut code_ids = FxHashMap::<(SkeletonLabel, Vec<usize>), usize>::default();
+        for &local in order.iter().rev() {
+            let spqr_node = &self.spqr_nodes[spqr_nodes[local]];
+            let label = (
+                spqr_node.spqr_node_type,
+                spqr_node.node_count(),
+                spqr_node.real_edge_count(),
+            );
+            let mut key_children = std::mem::take(&mut children_codes[local]);
+            key_children.sort_unstable();
+            let next_id = code_ids.len();
+            codes[local] = *code_ids.entry((label, key_children)).or_insert(next_id);
+            if let Some((parent, _)) = parents[local] {
+                children_codes[parent].push(codes[local]);
+            }
+        }
+
+        // Top-down, nodes are in the same orbit if their parents are, and their subtrees have the same code.
+        let mut orbits = vec![0; spqr_nodes.len()];
+        let mut orbit_ids = FxHashMap::<(Option<usize>, usize), usize>::default();
+        for &local in &order {
+            let parent_orbit = parents[local].map(|(parent, _)| orbits[parent]);
+            let next_id = orbit_ids.len();
+            orbits[local] = *orbit_ids
+                .entry((parent_orbit, codes[local]))
+                .or_insert(next_id);
+        }
+
+        let mut spqr_node_orbits = vec![Vec::new(); orbit_ids.len()];
+        let mut spqr_edge_orbits = vec![Vec::new(); orbit_ids.len()];
+        for local in 0..spqr_nodes.len() {
+            spqr_node_orbits[orbits[local]].push(spqr_nodes[local]);
+            if let Some((_, spqr_edge_index)) = parents[local] {
+                spqr_edge_orbits[orbits[local]].push(spqr_edge_index);
+            }
+        }
+        if let [first, second] = centers[..] {
+            let central_edge = neighbors(first)
+                .find(|&(neighbor, _)| neighbor == second)
+                .unwrap()
+                .1;
+            spqr_edge_orbits.push(vec![central_edge]);
+        }
+
+        SPQRTreeOrbits {
+            spqr_node_orbits: sorted_orbits(spqr_node_orbits),
+            spqr_edge_orbits: sorted_orbits(spqr_edge_orbits),
+        }
+    }
+}
+
+/// Returns the one or two centers of a tree with the given amount of nodes, by repeatedly removing all leaves.
+pub(crate) fn tree_centers<Edge, Neighbors: Iterator<Item = (usize, Edge)>>(
+    node_count: usize,
+    neighbors: impl Fn(usize) -> Neighbors,
+) -> Vec<usize> {
+    let mut degrees = (0..node_count)
+        .map(|local| neighbors(local).count())
+        .collect::<Vec<_>>();
+    let mut leaves = (0..node_count)
+        .filter(|&local| degrees[local] <= 1)
+        .collect::<Vec<_>>();
+    let mut remaining = node_count;
+
+    while remaining > 2 {
+        remaining -= leaves.len();
+        let mut next_leaves = Vec::new();
+        for &leaf in &leaves {
+            for (neighbor, _) in neighbors(leaf) {
+                degrees[neighbor] -= 1;
+                if degrees[neighbor] == 1 {
+                    next_leaves.push(neighbor);
+                }
+            }
+        }
+        leaves = next_leaves;
+    }
+    leaves
+}
+
+fn sorted_orbits<T: Ord>(orbits: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let mut orbits = orbits
+        .into_iter()
+        .filter(|orbit| !orbit.is_empty())
+        .map(|mut orbit| {
+            orbit.sort_unstable();
+            orbit
+        })
+        .collect::<Vec<_>>();
+    orbits.sort_unstable();
+    orbits
+}
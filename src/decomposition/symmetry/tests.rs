@@ -0,0 +1,57 @@
+use std::io::BufReader;
+
+use bidirected_adjacency_array::graph::BidirectedAdjacencyArray;
+
+use crate::decomposition::{
+    SPQRDecomposition,
+    tests::{TestGraph, read_decomposition, read_graph},
+};
+
+#[test]
+fn test_spqr_tree_orbits() {
+    let graph = read_graph("multiedge");
+    let spqr_decomposition = read_decomposition(&graph, "multiedge");
+    assert!(
+        spqr_decomposition
+            .spqr_tree_orbits(0.into())
+            .is_asymmetric()
+    );
+
+    // Three parallel paths of length two between u and v, and an edge between u and v.
+    let gfa = "H\tVN:Z:1.0\nS\tu\tA\nS\tv\tA\nS\ta\tA\nS\tb\tA\nS\tc\tA\n\
+        L\tu\t+\tv\t+\t0M\nL\tu\t+\ta\t+\t0M\nL\ta\t+\tv\t+\t0M\n\
+        L\tu\t+\tb\t+\t0M\nL\tb\t+\tv\t+\t0M\nL\tu\t+\tc\t+\t0M\nL\tc\t+\tv\t+\t0M\n";
+    let graph: TestGraph =
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+    let spqr_decomposition_file = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 u v a b c\nB B0 G0 u v a b c\n\
+        P P0 B0 u v\nS S1 B0 u a v\nS S2 B0 u b v\nS S3 B0 u c v\n\
+        V V0 P0 S1 u v\nV V1 P0 S2 u v\nV V2 P0 S3 u v\n\
+        E E0 P0 u v\nE E1 S1 u a\nE E2 S1 a v\nE E3 S2 u b\nE E4 S2 b v\nE E5 S3 u c\nE E6 S3 c v\n";
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let orbits = spqr_decomposition.spqr_tree_orbits(0.into());
+    assert!(!orbits.is_asymmetric());
+    assert_eq!(
+        orbits.spqr_node_orbits,
+        [vec![0.into()], vec![1.into(), 2.into(), 3.into()]]
+    );
+    assert_eq!(
+        orbits.spqr_edge_orbits,
+        [vec![0.into(), 1.into(), 2.into()]]
+    );
+
+    // Two S-nodes that share their only virtual edge, so the tree has two centers.
+    let gfa = "H\tVN:Z:1.0\nS\tu\tA\nS\tv\tA\nS\ta\tA\nS\tb\tA\n\
+        L\tu\t+\ta\t+\t0M\nL\ta\t+\tv\t+\t0M\nL\tu\t+\tb\t+\t0M\nL\tb\t+\tv\t+\t0M\n";
+    let graph: TestGraph =
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap();
+    let spqr_decomposition_file = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 u v a b\nB B0 G0 u v a b\nS S0 B0 u a v\nS S1 B0 u b v\nV V0 S0 S1 u v\n\
+        E E0 S0 u a\nE E1 S0 a v\nE E2 S1 u b\nE E3 S1 b v\n";
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let orbits = spqr_decomposition.spqr_tree_orbits(0.into());
+    assert_eq!(orbits.spqr_node_orbits, [vec![0.into(), 1.into()]]);
+    assert_eq!(orbits.spqr_edge_orbits, [vec![0.into()]]);
+}
@@ -0,0 +1,498 @@
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    fs::{self, File},
+    io::BufReader,
+    sync::Arc,
+    thread,
+};
+
+use bidirected_adjacency_array::{
+    graph::BidirectedAdjacencyArray,
+    io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
+};
+
+use crate::{
+    decomposition::{OwnedSPQRDecomposition, SPQRDecomposition, SPQRNodeType, SkeletonEdge},
+    graph::StaticGraph,
+    profile::Phase,
+    progress::ProgressSink,
+};
+
+pub(crate) type TestGraph = BidirectedAdjacencyArray<u8, PlainGfaNodeData, PlainGfaEdgeData>;
+
+pub(crate) fn read_graph(name: &str) -> TestGraph {
+    BidirectedAdjacencyArray::read_gfa1(BufReader::new(
+        File::open(format!("test_files/{name}.gfa")).unwrap(),
+    ))
+    .unwrap()
+}
+
+pub(crate) fn read_decomposition<'graph>(
+    graph: &'graph TestGraph,
+    name: &str,
+) -> SPQRDecomposition<'graph, TestGraph> {
+    let spqr_decomposition_file = fs::read_to_string(format!("test_files/{name}.spqr")).unwrap();
+    SPQRDecomposition::read_plain_spqr(graph, spqr_decomposition_file.as_bytes()).unwrap()
+}
+
+/// The test files that come with a precomputed decomposition.
+pub(crate) const TEST_FILES: [&str; 6] = [
+    "tiny1",
+    "multiedge",
+    "random1_1_False_0",
+    "random3_5_False_0",
+    "random5_10_False_0",
+    "Ultrabubble_dataset_chr_22_G182870",
+];
+
+/// Reads the given test files and the subdivided wheel, each with its plain decomposition file.
+pub(crate) fn test_inputs(names: &[&str]) -> Vec<(TestGraph, String)> {
+    let (wheel, wheel_file) = read_subdivided_wheel();
+    names
+        .iter()
+        .map(|name| {
+            (
+                read_graph(name),
+                fs::read_to_string(format!("test_files/{name}.spqr")).unwrap(),
+            )
+        })
+        .chain([(wheel, wheel_file.to_string())])
+        .collect()
+}
+
+/// Reads all test files and the subdivided wheel, each with its plain decomposition file.
+pub(crate) fn all_test_inputs() -> Vec<(TestGraph, String)> {
+    test_inputs(&TEST_FILES)
+}
+
+/// A call to a [`ProgressSink`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProgressEvent {
+    Start(Phase, Option<usize>),
+    Advance(usize),
+    Finish(Phase),
+}
+
+/// A progress sink that records all calls.
+#[derive(Debug, Default)]
+pub(crate) struct RecordingProgress {
+    pub(crate) events: Vec<ProgressEvent>,
+}
+
+impl ProgressSink for RecordingProgress {
+    fn start_phase(&mut self, phase: Phase, total: Option<usize>) {
+        self.events.push(ProgressEvent::Start(phase, total));
+    }
+
+    fn advance(&mut self, processed: usize) {
+        self.events.push(ProgressEvent::Advance(processed));
+    }
+
+    fn finish_phase(&mut self, phase: Phase) {
+        self.events.push(ProgressEvent::Finish(phase));
+    }
+}
+
+/// Concatenates the given test graphs and their decompositions, prefixing all names with the name of the test file.
+pub(crate) fn read_concatenated(names: &[&str]) -> (TestGraph, String) {
+    let mut gfa = String::from("H\tVN:Z:1.0\n");
+    let mut spqr_decomposition_file =
+        String::from("H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n");
+
+    for name in names {
+        for line in fs::read_to_string(format!("test_files/{name}.gfa"))
+            .unwrap()
+            .lines()
+        {
+            let mut columns = line.split('\t').map(str::to_owned).collect::<Vec<_>>();
+            match columns[0].as_str() {
+                "S" => columns[1] = format!("{name}_{}", columns[1]),
+                "L" => {
+                    columns[1] = format!("{name}_{}", columns[1]);
+                    columns[3] = format!("{name}_{}", columns[3]);
+                }
+                _ => continue,
+            }
+            gfa.push_str(&columns.join("\t"));
+            gfa.push('\n');
+        }
+
+        for line in fs::read_to_string(format!("test_files/{name}.spqr"))
+            .unwrap()
+            .lines()
+            .filter(|line| !line.starts_with('H'))
+        {
+            let mut columns = line.split(' ');
+            spqr_decomposition_file.push_str(columns.next().unwrap());
+            for column in columns {
+                spqr_decomposition_file.push_str(&format!(" {name}_{column}"));
+            }
+            spqr_decomposition_file.push('\n');
+        }
+    }
+
+    (
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap(),
+        spqr_decomposition_file,
+    )
+}
+
+pub(crate) fn assert_spqr_tables_consistent(spqr_decomposition: &SPQRDecomposition<'_, TestGraph>) {
+    let mut spqr_node_count = 0;
+    for (block_index, block) in spqr_decomposition.iter_blocks() {
+        spqr_node_count += block.spqr_node_count();
+        for (spqr_node_index, spqr_node) in spqr_decomposition.iter_spqr_nodes_in_block(block_index)
+        {
+            assert_eq!(spqr_node.block(), block_index);
+            for spqr_edge_index in spqr_node.iter_incident_spqr_edges() {
+                let (u, v) = spqr_decomposition.spqr_edge(spqr_edge_index).endpoints();
+                assert!(u == spqr_node_index || v == spqr_node_index);
+                assert!(block.spqr_edges.contains(&spqr_edge_index));
+            }
+            for node_index in spqr_node.iter_nodes() {
+                assert!(
+                    spqr_decomposition
+                        .node_spqr_node_indices(node_index)
+                        .any(|i| i == spqr_node_index)
+                );
+            }
+            for edge_index in spqr_node.iter_edges() {
+                assert_eq!(
+                    spqr_decomposition.edge_data[edge_index]
+                        .spqr_node_index
+                        .into_option(),
+                    Some(spqr_node_index)
+                );
+            }
+        }
+    }
+    assert_eq!(spqr_node_count, spqr_decomposition.spqr_node_count());
+
+    for node_index in graph_nodes(spqr_decomposition) {
+        for spqr_node_index in spqr_decomposition.node_spqr_node_indices(node_index) {
+            assert!(
+                spqr_decomposition
+                    .spqr_node(spqr_node_index)
+                    .iter_nodes()
+                    .any(|n| n == node_index)
+            );
+        }
+    }
+
+    for (spqr_edge_index, spqr_edge) in spqr_decomposition.iter_spqr_edges() {
+        let (u, v) = spqr_edge.endpoints();
+        for spqr_node_index in [u, v] {
+            let spqr_node = spqr_decomposition.spqr_node(spqr_node_index);
+            assert!(
+                spqr_node
+                    .iter_incident_spqr_edges()
+                    .any(|i| i == spqr_edge_index)
+            );
+            let (a, b) = spqr_edge.virtual_edge();
+            assert!(spqr_node.iter_nodes().any(|n| n == a));
+            assert!(spqr_node.iter_nodes().any(|n| n == b));
+        }
+    }
+}
+
+fn graph_nodes(
+    spqr_decomposition: &SPQRDecomposition<'_, TestGraph>,
+) -> Vec<<TestGraph as StaticGraph>::NodeIndex> {
+    spqr_decomposition.graph().node_indices().collect()
+}
+
+/// A wheel with hub h and rim 1-2-3-4, where the rim edge 1-2 is subdivided by x.
+pub(crate) fn read_subdivided_wheel() -> (TestGraph, &'static str) {
+    let gfa = "H\tVN:Z:1.0\nS\th\tA\nS\t1\tA\nS\t2\tA\nS\t3\tA\nS\t4\tA\nS\tx\tA\n\
+        L\th\t+\t1\t+\t0M\nL\th\t+\t2\t+\t0M\nL\th\t+\t3\t+\t0M\nL\th\t+\t4\t+\t0M\n\
+        L\t2\t+\t3\t+\t0M\nL\t3\t+\t4\t+\t0M\nL\t4\t+\t1\t+\t0M\n\
+        L\t1\t+\tx\t+\t0M\nL\tx\t+\t2\t+\t0M\n";
+    let spqr_decomposition_file = "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format\n\
+        G G0 h 1 2 3 4 x\nB B0 G0 h 1 2 3 4 x\n\
+        R R0 B0 h 1 2 3 4\nS S1 B0 1 x 2\nV V0 R0 S1 1 2\n\
+        E E0 R0 h 1\nE E1 R0 h 2\nE E2 R0 h 3\nE E3 R0 h 4\nE E4 R0 2 3\nE E5 R0 3 4\nE E6 R0 4 1\n\
+        E E7 S1 1 x\nE E8 S1 x 2\n";
+    (
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(gfa.as_bytes())).unwrap(),
+        spqr_decomposition_file,
+    )
+}
+
+fn is_connected_without<Node: Copy + Eq + std::hash::Hash>(
+    nodes: &[Node],
+    edges: &[(Node, Node)],
+    removed: &[Node],
+) -> bool {
+    let remaining = nodes
+        .iter()
+        .copied()
+        .filter(|node| !removed.contains(node))
+        .collect::<Vec<_>>();
+    let mut visited = HashSet::from([remaining[0]]);
+    let mut stack = vec![remaining[0]];
+    while let Some(node) = stack.pop() {
+        for &(u, v) in edges {
+            for (from, to) in [(u, v), (v, u)] {
+                if from == node && !removed.contains(&to) && visited.insert(to) {
+                    stack.push(to);
+                }
+            }
+        }
+    }
+    visited.len() == remaining.len()
+}
+
+/// Returns true if the graph on the given nodes and edges is connected and has no cut node.
+pub(crate) fn is_biconnected<Node: Copy + Eq + std::hash::Hash>(
+    nodes: &[Node],
+    edges: &[(Node, Node)],
+) -> bool {
+    is_connected_without(nodes, edges, &[])
+        && (nodes.len() <= 2
+            || nodes
+                .iter()
+                .all(|&u| is_connected_without(nodes, edges, &[u])))
+}
+
+/// Returns true if the graph on the given nodes and edges has at least four nodes and no separator of size at most two.
+pub(crate) fn is_triconnected<Node: Copy + Eq + std::hash::Hash>(
+    nodes: &[Node],
+    edges: &[(Node, Node)],
+) -> bool {
+    nodes.len() >= 4
+        && is_connected_without(nodes, edges, &[])
+        && nodes.iter().all(|&u| {
+            nodes
+                .iter()
+                .all(|&v| u == v || is_connected_without(nodes, edges, &[u, v]))
+        })
+}
+
+#[test]
+fn test_owned_decomposition() {
+    fn load(name: &str) -> OwnedSPQRDecomposition<TestGraph> {
+        OwnedSPQRDecomposition::new_owned(read_graph(name), |graph| {
+            Ok::<_, ()>(read_decomposition(graph, name))
+        })
+        .unwrap()
+    }
+
+    let owned = load("random3_5_False_0");
+    let graph = read_graph("random3_5_False_0");
+    let borrowed = read_decomposition(&graph, "random3_5_False_0");
+    let borrowed_graph = borrowed.clone().graph();
+    assert!(std::ptr::eq(borrowed_graph, &graph));
+
+    assert_eq!(owned.graph_ref().node_count(), graph.node_count());
+    assert!(owned == borrowed);
+
+    let mut owned_output = Vec::new();
+    owned.write_plain_spqr(&mut owned_output).unwrap();
+    let mut borrowed_output = Vec::new();
+    borrowed.write_plain_spqr(&mut borrowed_output).unwrap();
+    assert_eq!(owned_output, borrowed_output);
+}
+
+#[test]
+fn test_into_owned() {
+    let graph = Arc::new(read_graph("tiny1"));
+    let expected_block_count = read_decomposition(&graph, "tiny1").block_count();
+
+    let shared = read_decomposition(&graph, "tiny1").into_owned(graph.clone());
+    let block_count = thread::spawn(move || shared.block_count()).join().unwrap();
+    assert_eq!(block_count, expected_block_count);
+
+    // A structurally equal graph is accepted as well.
+    let copy = read_graph("tiny1");
+    let shared = read_decomposition(&copy, "tiny1").into_owned(graph);
+    assert_eq!(shared.block_count(), expected_block_count);
+}
+
+#[test]
+#[should_panic]
+fn test_into_owned_different_graph() {
+    let graph = read_graph("tiny1");
+    let other = Arc::new(read_graph("random1_1_False_0"));
+    read_decomposition(&graph, "tiny1").into_owned(other);
+}
+
+#[test]
+fn test_set_extra_data() {
+    let graph = read_graph("tiny1");
+    let mut spqr_decomposition = read_decomposition(&graph, "tiny1");
+
+    let node_index = spqr_decomposition.iter_nodes().next().unwrap();
+    let (block_index, _) = spqr_decomposition.iter_blocks().next().unwrap();
+    let (spqr_node_index, _) = spqr_decomposition.iter_spqr_nodes().next().unwrap();
+    spqr_decomposition.set_node_extra_data(node_index, "node".to_string());
+    spqr_decomposition.set_block_extra_data(block_index, "block".to_string());
+    spqr_decomposition.set_spqr_node_extra_data(spqr_node_index, "spqr node".to_string());
+
+    assert_eq!(spqr_decomposition.node_extra_data(node_index), "node");
+    assert_eq!(spqr_decomposition.block_extra_data(block_index), "block");
+    assert_eq!(
+        spqr_decomposition.spqr_node_extra_data(spqr_node_index),
+        "spqr node"
+    );
+}
+
+#[test]
+fn test_global_iterators() {
+    let graph = read_graph("random5_10_False_0");
+    let spqr_decomposition = read_decomposition(&graph, "random5_10_False_0");
+
+    assert_eq!(
+        spqr_decomposition.iter_cut_nodes().count(),
+        spqr_decomposition.cut_node_count()
+    );
+    assert_eq!(
+        spqr_decomposition.iter_spqr_edges().count(),
+        spqr_decomposition.spqr_edge_count()
+    );
+    for (cut_node_index, cut_node) in spqr_decomposition.iter_cut_nodes() {
+        assert_eq!(
+            spqr_decomposition.cut_node_index_to_node_index(cut_node_index),
+            cut_node.node()
+        );
+    }
+    for (spqr_edge_index, spqr_edge) in spqr_decomposition.iter_spqr_edges() {
+        let (u, v) = spqr_edge.endpoints();
+        assert!(
+            spqr_decomposition
+                .spqr_node(u)
+                .iter_incident_spqr_edges()
+                .any(|index| index == spqr_edge_index)
+        );
+        assert!(
+            spqr_decomposition
+                .spqr_node(v)
+                .iter_incident_spqr_edges()
+                .any(|index| index == spqr_edge_index)
+        );
+    }
+}
+
+#[test]
+fn test_iter_edges_in_block_and_component() {
+    for name in ["tiny1", "multiedge", "random5_10_False_0"] {
+        let graph = read_graph(name);
+        let spqr_decomposition = read_decomposition(&graph, name);
+
+        let mut component_edges = spqr_decomposition
+            .iter_component_indices()
+            .flat_map(|component_index| spqr_decomposition.iter_edges_in_component(component_index))
+            .collect::<Vec<_>>();
+        component_edges.sort_unstable();
+        assert_eq!(component_edges, graph.edge_indices().collect::<Vec<_>>());
+
+        for (block_index, _) in spqr_decomposition.iter_blocks() {
+            for edge_index in spqr_decomposition.iter_edges_in_block(block_index) {
+                assert_eq!(
+                    spqr_decomposition.edge_data[edge_index].block_index,
+                    block_index.into()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_iter_skeleton_edges() {
+    let graph = read_graph("multiedge");
+    let spqr_decomposition = read_decomposition(&graph, "multiedge");
+
+    for (spqr_node_index, spqr_node) in spqr_decomposition.iter_spqr_nodes() {
+        let skeleton_edges = spqr_decomposition
+            .iter_skeleton_edges(spqr_node_index)
+            .collect::<Vec<_>>();
+        assert_eq!(skeleton_edges.len(), spqr_node.skeleton_edge_count());
+
+        for (skeleton_edge, (u, v)) in skeleton_edges {
+            assert!(spqr_node.iter_nodes().any(|node| node == u));
+            assert!(spqr_node.iter_nodes().any(|node| node == v));
+            if let SkeletonEdge::Virtual(spqr_edge_index) = skeleton_edge {
+                assert_eq!(
+                    spqr_decomposition.spqr_edge(spqr_edge_index).virtual_edge(),
+                    (u, v)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_structural_equality() {
+    let graph = read_graph("random3_5_False_0");
+    let copy = read_graph("random3_5_False_0");
+    let spqr_decomposition = read_decomposition(&graph, "random3_5_False_0");
+
+    assert_eq!(
+        spqr_decomposition,
+        read_decomposition(&copy, "random3_5_False_0")
+    );
+
+    let mut modified = spqr_decomposition.clone();
+    let node_index = modified.iter_nodes().next().unwrap();
+    modified.set_node_extra_data(node_index, "modified".to_string());
+    assert_ne!(spqr_decomposition, modified);
+
+    // The ordering is consistent with equality.
+    assert_eq!(
+        spqr_decomposition.cmp(&spqr_decomposition.clone()),
+        Ordering::Equal
+    );
+    assert_eq!(
+        spqr_decomposition.partial_cmp(&read_decomposition(&copy, "random3_5_False_0")),
+        Some(Ordering::Equal)
+    );
+    assert_ne!(spqr_decomposition.cmp(&modified), Ordering::Equal);
+    assert_eq!(
+        spqr_decomposition.cmp(&modified),
+        modified.cmp(&spqr_decomposition).reverse()
+    );
+}
+
+#[test]
+fn test_spqr_node_type_display_and_from_str() {
+    for spqr_node_type in [
+        SPQRNodeType::SNode,
+        SPQRNodeType::PNode,
+        SPQRNodeType::RNode,
+    ] {
+        assert_eq!(
+            spqr_node_type.to_string().parse::<SPQRNodeType>(),
+            Ok(spqr_node_type)
+        );
+    }
+    assert_eq!(SPQRNodeType::PNode.to_string(), "P");
+    assert!("Q".parse::<SPQRNodeType>().is_err());
+
+    let graph = read_graph("multiedge");
+    let spqr_decomposition = read_decomposition(&graph, "multiedge");
+    let (block_index, _) = spqr_decomposition.iter_blocks().next().unwrap();
+    let p_nodes = spqr_decomposition
+        .iter_spqr_nodes_of_type(block_index, SPQRNodeType::PNode)
+        .collect::<Vec<_>>();
+    assert_eq!(p_nodes.len(), 1);
+    assert!(p_nodes[0].1.is_p_node());
+    assert_eq!(
+        spqr_decomposition
+            .iter_spqr_nodes_of_type(block_index, SPQRNodeType::SNode)
+            .count(),
+        1
+    );
+    assert_eq!(
+        spqr_decomposition
+            .iter_spqr_nodes_of_type(block_index, SPQRNodeType::RNode)
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn test_decomposition_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SPQRDecomposition<'_, TestGraph>>();
+    assert_send_sync::<OwnedSPQRDecomposition<TestGraph>>();
+}
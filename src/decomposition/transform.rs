@@ -0,0 +1,433 @@
+use std::{borrow::Borrow, hash::Hash, marker::PhantomData};
+
+use rustc_hash::FxHashMap;
+use tagged_vec::TaggedVec;
+
+use crate::{
+    decomposition::{
+        Block, Component, CutNode, SPQRDecomposition, SPQRDecompositionEdgeData,
+        SPQRDecompositionNodeData, SPQREdge, SPQRNode,
+        indices::{
+            BlockIndex, ComponentIndex, CutNodeIndex, GraphIndexInteger, OptionalBlockIndex,
+            OptionalCutNodeIndex, OptionalSPQRNodeIndex, SPQREdgeIndex, SPQRNodeIndex,
+        },
+    },
+    graph::{StaticGraph, subgraph::Subgraph},
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Transfers this decomposition onto a graph that is isomorphic to the decomposed graph.
+    ///
+    /// The node and edge maps map each node and edge of the decomposed graph to the corresponding node and edge of `other_graph`.
+    /// This allows e.g. to compute a decomposition on a compacted copy of a graph and then transfer it back to the original graph object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the maps are not bijections, or if a mapped edge does not connect the mapped endpoints of the original edge.
+    pub fn remap<'other_graph, OtherGraph: StaticGraph<IndexType = Graph::IndexType>>(
+        &self,
+        other_graph: &'other_graph OtherGraph,
+        node_map: impl Fn(Graph::NodeIndex) -> OtherGraph::NodeIndex,
+        edge_map: impl Fn(Graph::EdgeIndex) -> OtherGraph::EdgeIndex,
+    ) -> SPQRDecomposition<'other_graph, OtherGraph> {
+        let graph = self.graph_ref();
+        assert_eq!(graph.node_count(), other_graph.node_count());
+        assert_eq!(graph.edge_count(), other_graph.edge_count());
+
+        let mut node_data = vec![None; other_graph.node_count()];
+        for node_index in graph.node_indices() {
+            let other_node_index = node_map(node_index).into();
+            assert!(
+                node_data[other_node_index]
+                    .replace(self.node_data[node_index].clone())
+                    .is_none(),
+                "the node map is not injective",
+            );
+        }
+
+        let mut edge_data = vec![None; other_graph.edge_count()];
+        for edge_index in graph.edge_indices() {
+            let other_edge_index = edge_map(edge_index);
+            let (u, v) = graph.edge_endpoints(edge_index);
+            let (u, v) = (node_map(u), node_map(v));
+            let (other_u, other_v) = other_graph.edge_endpoints(other_edge_index);
+            assert!(
+                (u, v) == (other_u, other_v) || (u, v) == (other_v, other_u),
+                "the edge map does not match the node map",
+            );
+            assert!(
+                edge_data[other_edge_index.into()]
+                    .replace(self.edge_data[edge_index].clone())
+                    .is_none(),
+                "the edge map is not injective",
+            );
+        }
+
+        let map_nodes = |nodes: &[Graph::NodeIndex]| nodes.iter().copied().map(&node_map).collect();
+        let map_edges = |edges: &[Graph::EdgeIndex]| edges.iter().copied().map(&edge_map).collect();
+
+        SPQRDecomposition {
+            graph: other_graph,
+            graph_lifetime: PhantomData,
+            components: self
+                .components
+                .iter_values()
+                .map(|component| Component {
+                    nodes: map_nodes(&component.nodes),
+                    edges: map_edges(&component.edges),
+                    blocks: component.blocks.clone(),
+                    cut_nodes: component.cut_nodes.clone(),
+                    extra_data: component.extra_data.clone(),
+                })
+                .collect(),
+            blocks: self
+                .blocks
+                .iter_values()
+                .map(|block| Block {
+                    component: block.component,
+                    nodes: map_nodes(&block.nodes),
+                    cut_nodes: block.cut_nodes.clone(),
+                    edges: map_edges(&block.edges),
+                    spqr_nodes: block.spqr_nodes.clone(),
+                    spqr_edges: block.spqr_edges.clone(),
+                    extra_data: block.extra_data.clone(),
+                })
+                .collect(),
+            cut_nodes: self
+                .cut_nodes
+                .iter_values()
+                .map(|cut_node| CutNode {
+                    component: cut_node.component,
+                    node: node_map(cut_node.node),
+                    adjacent_blocks: cut_node.adjacent_blocks.clone(),
+                    extra_data: cut_node.extra_data.clone(),
+                })
+                .collect(),
+            spqr_nodes: self
+                .spqr_nodes
+                .iter_values()
+                .map(|spqr_node| SPQRNode {
+                    block: spqr_node.block,
+                    nodes: map_nodes(&spqr_node.nodes),
+                    edges: map_edges(&spqr_node.edges),
+                    spqr_node_type: spqr_node.spqr_node_type,
+                    spqr_edges: spqr_node.spqr_edges.clone(),
+                    extra_data: spqr_node.extra_data.clone(),
+                })
+                .collect(),
+            spqr_edges: self
+                .spqr_edges
+                .iter_values()
+                .map(|spqr_edge| SPQREdge {
+                    endpoints: spqr_edge.endpoints,
+                    virtual_edge: (
+                        node_map(spqr_edge.virtual_edge.0),
+                        node_map(spqr_edge.virtual_edge.1),
+                    ),
+                })
+                .collect(),
+            node_data: node_data
+                .into_iter()
+                .map(|data| data.expect("the node map is not surjective"))
+                .collect::<TaggedVec<_, _>>(),
+            edge_data: edge_data
+                .into_iter()
+                .map(|data| data.expect("the edge map is not surjective"))
+                .collect::<TaggedVec<_, _>>(),
+            cache: Default::default(),
+        }
+    }
+
+    /// Returns the decomposition of the subgraph induced by the given components.
+    ///
+    /// The resulting decomposition owns a [`Subgraph`] view of the decomposed graph, which is accessible via [`SPQRDecomposition::graph`].
+    /// Components, blocks, cut nodes and SPQR nodes are renumbered consecutively, keeping their relative order.
+    /// The components are ordered as given.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a component is given twice.
+    pub fn restrict_to_components<'this>(
+        &'this self,
+        component_indices: impl IntoIterator<Item = ComponentIndex<Graph::IndexType>>,
+    ) -> SPQRDecomposition<'this, Subgraph<'this, Graph>, Subgraph<'this, Graph>> {
+        let component_indices = component_indices.into_iter().collect::<Vec<_>>();
+        let subgraph = Subgraph::new(
+            self.graph_ref(),
+            component_indices
+                .iter()
+                .flat_map(|&component_index| self.components[component_index].iter_nodes()),
+            component_indices
+                .iter()
+                .flat_map(|&component_index| self.iter_edges_in_component(component_index)),
+        );
+        let RestrictedEntities {
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            maps,
+        } = self.restrict_entities(
+            &component_indices,
+            |node| subgraph.subgraph_node(node).unwrap(),
+            |edge| subgraph.subgraph_edge(edge).unwrap(),
+        );
+        let node_data = subgraph
+            .node_indices()
+            .map(|node_index| maps.node_data(&self.node_data[subgraph.parent_node(node_index)]))
+            .collect();
+        let edge_data = subgraph
+            .edge_indices()
+            .map(|edge_index| maps.edge_data(&self.edge_data[subgraph.parent_edge(edge_index)]))
+            .collect();
+
+        SPQRDecomposition {
+            graph: subgraph,
+            graph_lifetime: PhantomData,
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+            cache: Default::default(),
+        }
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns the components, blocks, cut nodes, SPQR nodes and SPQR edges of the given components,
+    /// renumbered consecutively while keeping their relative order.
+    ///
+    /// Nodes and edges are translated with the given maps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a component is given twice.
+    pub(crate) fn restrict_entities<NodeIndex, EdgeIndex>(
+        &self,
+        component_indices: &[ComponentIndex<Graph::IndexType>],
+        map_node: impl Fn(Graph::NodeIndex) -> NodeIndex,
+        map_edge: impl Fn(Graph::EdgeIndex) -> EdgeIndex,
+    ) -> RestrictedEntities<NodeIndex, EdgeIndex, Graph::IndexType> {
+        let block_indices = component_indices
+            .iter()
+            .flat_map(|&component_index| self.components[component_index].blocks.iter().copied())
+            .collect::<Vec<_>>();
+        let cut_node_indices = component_indices
+            .iter()
+            .flat_map(|&component_index| self.components[component_index].iter_cut_nodes())
+            .collect::<Vec<_>>();
+        let spqr_node_indices = block_indices
+            .iter()
+            .flat_map(|&block_index| self.blocks[block_index].spqr_nodes.iter().copied())
+            .collect::<Vec<_>>();
+        let spqr_edge_indices = block_indices
+            .iter()
+            .flat_map(|&block_index| self.blocks[block_index].spqr_edges.iter().copied())
+            .collect::<Vec<_>>();
+
+        let maps = RestrictionMaps {
+            components: positions(component_indices),
+            blocks: positions(&block_indices),
+            cut_nodes: positions(&cut_node_indices),
+            spqr_nodes: positions(&spqr_node_indices),
+        };
+        assert_eq!(
+            maps.components.len(),
+            component_indices.len(),
+            "a component is given twice",
+        );
+        let spqr_edge_map = positions(&spqr_edge_indices);
+
+        let map_nodes =
+            |nodes: &[Graph::NodeIndex]| nodes.iter().map(|&node| map_node(node)).collect();
+        let map_edges =
+            |edges: &[Graph::EdgeIndex]| edges.iter().map(|&edge| map_edge(edge)).collect();
+
+        let components = component_indices
+            .iter()
+            .map(|&component_index| {
+                let component = &self.components[component_index];
+                Component {
+                    nodes: map_nodes(&component.nodes),
+                    edges: map_edges(&component.edges),
+                    blocks: component.blocks.iter().map(|b| maps.blocks[b]).collect(),
+                    cut_nodes: component
+                        .cut_nodes
+                        .iter()
+                        .map(|c| maps.cut_nodes[c])
+                        .collect(),
+                    extra_data: component.extra_data.clone(),
+                }
+            })
+            .collect();
+        let blocks = block_indices
+            .iter()
+            .map(|&block_index| {
+                let block = &self.blocks[block_index];
+                Block {
+                    component: maps.components[&block.component],
+                    nodes: map_nodes(&block.nodes),
+                    cut_nodes: block.cut_nodes.iter().map(|c| maps.cut_nodes[c]).collect(),
+                    edges: map_edges(&block.edges),
+                    spqr_nodes: block
+                        .spqr_nodes
+                        .iter()
+                        .map(|n| maps.spqr_nodes[n])
+                        .collect(),
+                    spqr_edges: block.spqr_edges.iter().map(|e| spqr_edge_map[e]).collect(),
+                    extra_data: block.extra_data.clone(),
+                }
+            })
+            .collect();
+        let cut_nodes = cut_node_indices
+            .iter()
+            .map(|&cut_node_index| {
+                let cut_node = &self.cut_nodes[cut_node_index];
+                CutNode {
+                    component: maps.components[&cut_node.component],
+                    node: map_node(cut_node.node),
+                    adjacent_blocks: cut_node
+                        .adjacent_blocks
+                        .iter()
+                        .map(|b| maps.blocks[b])
+                        .collect(),
+                    extra_data: cut_node.extra_data.clone(),
+                }
+            })
+            .collect();
+        let spqr_nodes = spqr_node_indices
+            .iter()
+            .map(|&spqr_node_index| {
+                let spqr_node = &self.spqr_nodes[spqr_node_index];
+                SPQRNode {
+                    block: maps.blocks[&spqr_node.block],
+                    nodes: map_nodes(&spqr_node.nodes),
+                    edges: map_edges(&spqr_node.edges),
+                    spqr_node_type: spqr_node.spqr_node_type,
+                    spqr_edges: spqr_node
+                        .spqr_edges
+                        .iter()
+                        .map(|e| spqr_edge_map[e])
+                        .collect(),
+                    extra_data: spqr_node.extra_data.clone(),
+                }
+            })
+            .collect();
+        let spqr_edges = spqr_edge_indices
+            .iter()
+            .map(|&spqr_edge_index| {
+                let spqr_edge = &self.spqr_edges[spqr_edge_index];
+                SPQREdge {
+                    endpoints: (
+                        maps.spqr_nodes[&spqr_edge.endpoints.0],
+                        maps.spqr_nodes[&spqr_edge.endpoints.1],
+                    ),
+                    virtual_edge: (
+                        map_node(spqr_edge.virtual_edge.0),
+                        map_node(spqr_edge.virtual_edge.1),
+                    ),
+                }
+            })
+            .collect();
+
+        RestrictedEntities {
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            maps,
+        }
+    }
+}
+
+/// The entities of some components of a decomposition, see [`SPQRDecomposition::restrict_entities`].
+pub(crate) struct RestrictedEntities<NodeIndex, EdgeIndex, IndexType> {
+    pub(crate) components:
+        TaggedVec<ComponentIndex<IndexType>, Component<NodeIndex, EdgeIndex, IndexType>>,
+    pub(crate) blocks: TaggedVec<BlockIndex<IndexType>, Block<NodeIndex, EdgeIndex, IndexType>>,
+    pub(crate) cut_nodes: TaggedVec<CutNodeIndex<IndexType>, CutNode<NodeIndex, IndexType>>,
+    pub(crate) spqr_nodes:
+        TaggedVec<SPQRNodeIndex<IndexType>, SPQRNode<NodeIndex, EdgeIndex, IndexType>>,
+    pub(crate) spqr_edges: TaggedVec<SPQREdgeIndex<IndexType>, SPQREdge<NodeIndex, IndexType>>,
+    pub(crate) maps: RestrictionMaps<IndexType>,
+}
+
+/// Maps the components, blocks, cut nodes and SPQR nodes of a decomposition to their restricted indices.
+pub(crate) struct RestrictionMaps<IndexType> {
+    components: FxHashMap<ComponentIndex<IndexType>, ComponentIndex<IndexType>>,
+    blocks: FxHashMap<BlockIndex<IndexType>, BlockIndex<IndexType>>,
+    cut_nodes: FxHashMap<CutNodeIndex<IndexType>, CutNodeIndex<IndexType>>,
+    spqr_nodes: FxHashMap<SPQRNodeIndex<IndexType>, SPQRNodeIndex<IndexType>>,
+}
+
+impl<IndexType: GraphIndexInteger> RestrictionMaps<IndexType> {
+    /// Translates the data of a node of one of the restricted components.
+    pub(crate) fn node_data(
+        &self,
+        node_data: &SPQRDecompositionNodeData<IndexType>,
+    ) -> SPQRDecompositionNodeData<IndexType> {
+        SPQRDecompositionNodeData {
+            component_index: self.components[&node_data.component_index],
+            block_indices: node_data
+                .block_indices
+                .iter()
+                .map(|b| self.blocks[b])
+                .collect(),
+            cut_node_index: OptionalCutNodeIndex::from_option(
+                node_data
+                    .cut_node_index
+                    .into_option()
+                    .map(|c| self.cut_nodes[&c]),
+            ),
+            spqr_node_indices: node_data
+                .spqr_node_indices
+                .iter()
+                .map(|n| self.spqr_nodes[n])
+                .collect(),
+            extra_data: node_data.extra_data.clone(),
+        }
+    }
+
+    /// Translates the data of an edge of one of the restricted components.
+    pub(crate) fn edge_data(
+        &self,
+        edge_data: &SPQRDecompositionEdgeData<IndexType>,
+    ) -> SPQRDecompositionEdgeData<IndexType> {
+        SPQRDecompositionEdgeData {
+            component_index: self.components[&edge_data.component_index],
+            block_index: OptionalBlockIndex::from_option(
+                edge_data.block_index.into_option().map(|b| self.blocks[&b]),
+            ),
+            spqr_node_index: OptionalSPQRNodeIndex::from_option(
+                edge_data
+                    .spqr_node_index
+                    .into_option()
+                    .map(|n| self.spqr_nodes[&n]),
+            ),
+            extra_data: edge_data.extra_data.clone(),
+        }
+    }
+}
+
+/// Maps each index to its position in the given list.
+pub(super) fn positions<Index: Copy + Eq + Hash + From<usize>>(
+    indices: &[Index],
+) -> FxHashMap<Index, Index> {
+    indices
+        .iter()
+        .enumerate()
+        .map(|(position, &index)| (index, position.into()))
+        .collect()
+}
@@ -0,0 +1,103 @@
+use std::{fs, io::BufReader};
+
+use bidirected_adjacency_array::graph::BidirectedAdjacencyArray;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{TestGraph, read_concatenated, read_decomposition, read_graph},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_remap() {
+    let graph = read_graph("tiny1");
+    let spqr_decomposition = read_decomposition(&graph, "tiny1");
+
+    // Declare the nodes and edges in reverse order, such that all indices change.
+    let gfa = fs::read_to_string("test_files/tiny1.gfa").unwrap();
+    let mut lines = gfa.lines().collect::<Vec<_>>();
+    lines[1..].reverse();
+    lines.sort_by_key(|line| !line.starts_with(['H', 'S']));
+    let reversed_gfa = lines.join("\n") + "\n";
+    let reversed_graph: TestGraph =
+        BidirectedAdjacencyArray::read_gfa1(BufReader::new(reversed_gfa.as_bytes())).unwrap();
+    assert_ne!(
+        graph.node_name(0.into()),
+        reversed_graph.node_name(0.into())
+    );
+
+    let node_map = |graph: &TestGraph, other_graph: &TestGraph, node_index| {
+        other_graph
+            .node_indices()
+            .find(|&other_node_index| {
+                other_graph.node_name(other_node_index) == graph.node_name(node_index)
+            })
+            .unwrap()
+    };
+    let edge_map = |graph: &TestGraph, other_graph: &TestGraph, edge_index| {
+        let (u, v) = graph.edge_endpoints(edge_index);
+        other_graph
+            .edges_between(
+                node_map(graph, other_graph, u),
+                node_map(graph, other_graph, v),
+            )
+            .next()
+            .unwrap()
+    };
+
+    let remapped = spqr_decomposition.remap(
+        &reversed_graph,
+        |node_index| node_map(&graph, &reversed_graph, node_index),
+        |edge_index| edge_map(&graph, &reversed_graph, edge_index),
+    );
+    assert_eq!(remapped, read_decomposition(&reversed_graph, "tiny1"));
+
+    let remapped_back = remapped.remap(
+        &graph,
+        |node_index| node_map(&reversed_graph, &graph, node_index),
+        |edge_index| edge_map(&reversed_graph, &graph, edge_index),
+    );
+    assert_eq!(remapped_back, spqr_decomposition);
+}
+
+#[test]
+fn test_restrict_to_components() {
+    let (graph, spqr_decomposition_file) = read_concatenated(&["tiny1", "multiedge"]);
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    assert_eq!(spqr_decomposition.component_count(), 2);
+
+    for (component_index, name) in spqr_decomposition
+        .iter_component_indices()
+        .zip(["tiny1", "multiedge"])
+    {
+        let expected_graph = read_graph(name);
+        let expected = read_decomposition(&expected_graph, name);
+        let restricted = spqr_decomposition.restrict_to_components([component_index]);
+        assert_eq!(restricted.statistics(), expected.statistics());
+
+        let subgraph = restricted.graph_ref();
+        for node_index in subgraph.node_indices() {
+            assert_eq!(
+                subgraph.node_name(node_index),
+                graph.node_name(subgraph.parent_node(node_index)),
+            );
+            assert_eq!(
+                restricted.node_spqr_node_indices(node_index).count(),
+                spqr_decomposition
+                    .node_spqr_node_indices(subgraph.parent_node(node_index))
+                    .count(),
+            );
+        }
+    }
+
+    let mut component_indices = spqr_decomposition
+        .iter_component_indices()
+        .collect::<Vec<_>>();
+    component_indices.reverse();
+    let restricted = spqr_decomposition.restrict_to_components(component_indices);
+    assert_eq!(restricted.statistics(), spqr_decomposition.statistics());
+    assert_eq!(restricted.graph_ref().node_name(0.into()), "multiedge_1");
+}
@@ -0,0 +1,75 @@
+//! Bounding the treewidth of the graph with the SPQR trees.
+//!
+//! The treewidth of a graph is the largest treewidth of its blocks,
+//! and the treewidth of a block is the largest treewidth of the skeletons of its SPQR nodes.
+//! The skeletons of S-nodes are cycles and those of P-nodes are multi-edges, both with treewidth at most two.
+//! The skeleton of an R-node with `n` nodes has treewidth at most `n - 1`.
+
+use std::borrow::Borrow;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        indices::{ComponentIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// An upper bound on the treewidth of a component, see [`SPQRDecomposition::treewidth_upper_bound`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TreewidthBound<IndexType> {
+    /// The blocks of the component have no R-nodes, so it is series-parallel and has treewidth at most two.
+    SeriesParallel,
+    /// The R-nodes of the component together with the number of nodes of their skeletons.
+    /// The treewidth of the component is at most two or one less than the largest of these numbers, whichever is larger.
+    RNodes(Vec<(SPQRNodeIndex<IndexType>, usize)>),
+}
+
+impl<IndexType> TreewidthBound<IndexType> {
+    /// Returns the upper bound on the treewidth.
+    pub fn bound(&self) -> usize {
+        match self {
+            Self::SeriesParallel => 2,
+            Self::RNodes(r_nodes) => r_nodes
+                .iter()
+                .map(|&(_, node_count)| node_count - 1)
+                .fold(2, usize::max),
+        }
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns an upper bound on the treewidth of each component.
+    ///
+    /// See the [module documentation](self) for why the bound holds.
+    /// For components with R-nodes, the sizes of their skeletons certify the bound,
+    /// and computing the treewidth exactly only requires running a solver on these skeletons, including their virtual edges.
+    pub fn treewidth_upper_bound(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            ComponentIndex<Graph::IndexType>,
+            TreewidthBound<Graph::IndexType>,
+        ),
+    > {
+        self.iter_components().map(|(component_index, _)| {
+            let r_nodes = self
+                .iter_blocks_in_component(component_index)
+                .flat_map(|(block_index, _)| self.iter_spqr_nodes_in_block(block_index))
+                .filter(|(_, spqr_node)| spqr_node.spqr_node_type == SPQRNodeType::RNode)
+                .map(|(spqr_node_index, spqr_node)| (spqr_node_index, spqr_node.nodes.len()))
+                .collect::<Vec<_>>();
+            let bound = if r_nodes.is_empty() {
+                TreewidthBound::SeriesParallel
+            } else {
+                TreewidthBound::RNodes(r_nodes)
+            };
+            (component_index, bound)
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use crate::decomposition::{
+    SPQRDecomposition, SPQRNodeType,
+    tests::{TEST_FILES, read_decomposition, read_graph, read_subdivided_wheel},
+    treewidth::TreewidthBound,
+};
+
+#[test]
+fn test_treewidth_upper_bound() {
+    for name in TEST_FILES {
+        let graph = read_graph(name);
+        let spqr_decomposition = read_decomposition(&graph, name);
+        for (_, bound) in spqr_decomposition.treewidth_upper_bound() {
+            assert_eq!(bound, TreewidthBound::SeriesParallel);
+            assert_eq!(bound.bound(), 2);
+        }
+    }
+
+    let (graph, spqr_decomposition_file) = read_subdivided_wheel();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let (r_node, _) = spqr_decomposition
+        .iter_spqr_nodes()
+        .find(|(_, spqr_node)| spqr_node.spqr_node_type() == SPQRNodeType::RNode)
+        .unwrap();
+    let bounds = spqr_decomposition
+        .treewidth_upper_bound()
+        .map(|(_, bound)| bound)
+        .collect::<Vec<_>>();
+    assert_eq!(bounds, [TreewidthBound::RNodes(vec![(r_node, 5)])]);
+    assert_eq!(bounds[0].bound(), 4);
+}
@@ -0,0 +1,62 @@
+use std::borrow::Borrow;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, indices::SPQRNodeIndex, node_set::NodeBitSet,
+    },
+    graph::{StaticGraph, subgraph::Subgraph},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The part of the graph that corresponds to an R-node, see [`SPQRDecomposition::iter_triconnected_subgraphs`].
+#[derive(Debug, Clone)]
+pub struct TriconnectedSubgraph<'a, Graph: StaticGraph> {
+    pub r_node: SPQRNodeIndex<Graph::IndexType>,
+    /// The subgraph of the graph induced by the nodes of the skeleton.
+    pub subgraph: Subgraph<'a, Graph>,
+    /// The nodes of the skeleton, as nodes of the graph.
+    pub nodes: &'a NodeBitSet<Graph::NodeIndex>,
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Returns an iterator over the subgraphs of the graph that correspond to the R-nodes.
+    ///
+    /// Each subgraph is induced by the nodes of the skeleton of its R-node within their block.
+    /// Besides the real edges of the skeleton, it contains the edges of the graph that are parallel to its virtual edges,
+    /// which are stored in neighbouring P-nodes.
+    /// A virtual edge without such a parallel edge stands for a path through other SPQR nodes, and has no counterpart in the subgraph.
+    ///
+    /// The node sets are the cached sets of [`spqr_node_node_set`](Self::spqr_node_node_set).
+    pub fn iter_triconnected_subgraphs(
+        &self,
+    ) -> impl Iterator<Item = TriconnectedSubgraph<'_, Graph>> {
+        self.iter_spqr_nodes()
+            .filter(|(_, spqr_node)| spqr_node.spqr_node_type() == SPQRNodeType::RNode)
+            .map(|(r_node_index, r_node)| {
+                let nodes = self.spqr_node_node_set(r_node_index);
+                let mut edges = r_node
+                    .iter_nodes()
+                    .flat_map(|node_index| self.graph_ref().incident_edges(node_index))
+                    .filter(|&edge_index| {
+                        let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+                        nodes.contains(u)
+                            && nodes.contains(v)
+                            && self.edge_data[edge_index].block_index.into_option()
+                                == Some(r_node.block())
+                    })
+                    .collect::<Vec<_>>();
+                edges.sort_unstable();
+                edges.dedup();
+
+                TriconnectedSubgraph {
+                    r_node: r_node_index,
+                    subgraph: Subgraph::new(self.graph_ref(), r_node.iter_nodes(), edges),
+                    nodes,
+                }
+            })
+    }
+}
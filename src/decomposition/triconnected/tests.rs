@@ -0,0 +1,35 @@
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{read_decomposition, read_graph, read_subdivided_wheel},
+    },
+    graph::StaticGraph,
+};
+
+#[test]
+fn test_iter_triconnected_subgraphs() {
+    let graph = read_graph("random5_10_False_0");
+    let spqr_decomposition = read_decomposition(&graph, "random5_10_False_0");
+    assert_eq!(spqr_decomposition.iter_triconnected_subgraphs().count(), 0);
+
+    let (graph, spqr_decomposition_file) = read_subdivided_wheel();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let node = |name| spqr_decomposition.node_index_by_name(name).unwrap();
+    let subgraphs = spqr_decomposition
+        .iter_triconnected_subgraphs()
+        .collect::<Vec<_>>();
+    assert_eq!(subgraphs.len(), 1);
+
+    let triconnected = &subgraphs[0];
+    assert_eq!(triconnected.r_node, 0.into());
+    assert_eq!(triconnected.nodes.len(), 5);
+    assert!(!triconnected.nodes.contains(node("x")));
+    assert_eq!(triconnected.subgraph.node_count(), 5);
+    // The rim edge 1-2 is subdivided, so only the seven real edges of the skeleton remain.
+    assert_eq!(triconnected.subgraph.edge_count(), 7);
+    for edge_index in triconnected.subgraph.edge_indices() {
+        let (u, v) = graph.edge_endpoints(triconnected.subgraph.parent_edge(edge_index));
+        assert!(triconnected.nodes.contains(u) && triconnected.nodes.contains(v));
+    }
+}
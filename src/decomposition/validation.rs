@@ -0,0 +1,661 @@
+//! Structural validation of complete SPQR decompositions.
+//!
+//! Unlike the sanity checks of the [builder](crate::decomposition::builder), which only ensure that every node and edge was assigned somewhere,
+//! [`SPQRDecomposition::validate`] checks that all references between entities are mutual and that the SPQR trees and skeletons have the expected shape.
+
+use std::{
+    borrow::Borrow,
+    fmt::{Debug, Display},
+};
+
+use thiserror::Error;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType,
+        indices::{BlockIndex, CutNodeIndex, SPQREdgeIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The error returned by [`SPQRDecomposition::validate`], listing all violated invariants.
+#[derive(Debug)]
+pub struct ValidationError<NodeIndex, EdgeIndex, IndexType> {
+    pub problems: Vec<ValidationProblem<NodeIndex, EdgeIndex, IndexType>>,
+}
+
+/// A single violated invariant of an SPQR decomposition.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationProblem<NodeIndex, EdgeIndex, IndexType> {
+    #[error("block {0} is not listed by its component")]
+    BlockNotInComponent(BlockIndex<IndexType>),
+
+    #[error("node {node} of block {block} does not list the block")]
+    NodeNotInBlock {
+        node: NodeIndex,
+        block: BlockIndex<IndexType>,
+    },
+
+    #[error("cut node {cut_node} of block {block} does not list the block as adjacent")]
+    CutNodeNotAdjacentToBlock {
+        cut_node: CutNodeIndex<IndexType>,
+        block: BlockIndex<IndexType>,
+    },
+
+    #[error("edge {edge} of block {block} is assigned to a different block")]
+    EdgeNotInBlock {
+        edge: EdgeIndex,
+        block: BlockIndex<IndexType>,
+    },
+
+    #[error("SPQR node {spqr_node} of block {block} is assigned to a different block")]
+    SPQRNodeNotInBlock {
+        spqr_node: SPQRNodeIndex<IndexType>,
+        block: BlockIndex<IndexType>,
+    },
+
+    #[error("SPQR edge {spqr_edge} of block {block} connects SPQR nodes outside of the block")]
+    SPQREdgeNotInBlock {
+        spqr_edge: SPQREdgeIndex<IndexType>,
+        block: BlockIndex<IndexType>,
+    },
+
+    #[error("the SPQR nodes and SPQR edges of block {0} do not form a tree")]
+    SPQRTreeNotATree(BlockIndex<IndexType>),
+
+    #[error("node {node} of SPQR node {spqr_node} does not list the SPQR node")]
+    NodeNotInSPQRNode {
+        node: NodeIndex,
+        spqr_node: SPQRNodeIndex<IndexType>,
+    },
+
+    #[error("edge {edge} of SPQR node {spqr_node} is assigned to a different SPQR node")]
+    EdgeNotInSPQRNode {
+        edge: EdgeIndex,
+        spqr_node: SPQRNodeIndex<IndexType>,
+    },
+
+    #[error("SPQR edge {spqr_edge} listed by SPQR node {spqr_node} is not incident to it")]
+    SPQREdgeNotIncident {
+        spqr_edge: SPQREdgeIndex<IndexType>,
+        spqr_node: SPQRNodeIndex<IndexType>,
+    },
+
+    #[error("SPQR node {0} has a skeleton edge with an endpoint outside of the SPQR node")]
+    SkeletonEdgeOutsideSPQRNode(SPQRNodeIndex<IndexType>),
+
+    #[error(
+        "the skeleton of SPQR node {spqr_node} does not have the shape of an {spqr_node_type}-node"
+    )]
+    InvalidSkeleton {
+        spqr_node: SPQRNodeIndex<IndexType>,
+        spqr_node_type: SPQRNodeType,
+    },
+
+    #[error(
+        "the decomposition has data for {nodes} nodes and {edges} edges, which does not match the graph"
+    )]
+    GraphSizeMismatch { nodes: usize, edges: usize },
+
+    #[error("a reference to {kind} {index} is out of bounds")]
+    IndexOutOfBounds { kind: &'static str, index: usize },
+}
+
+type Problems<Graph> = Vec<
+    ValidationProblem<
+        <Graph as StaticGraph>::NodeIndex,
+        <Graph as StaticGraph>::EdgeIndex,
+        <Graph as StaticGraph>::IndexType,
+    >,
+>;
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Checks the structural invariants of the decomposition, returning all violated invariants as an error.
+    ///
+    /// First, all references between entities are checked to be in bounds, and if any is not, only these are reported.
+    /// Hence, this does not panic even for decompositions read from corrupt binary files.
+    /// Then, the invariants are checked block by block, covering the block itself, its nodes, edges and cut nodes,
+    /// as well as its SPQR tree and the skeletons of its SPQR nodes.
+    pub fn validate(
+        &self,
+    ) -> Result<(), ValidationError<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>> {
+        let mut problems = Vec::new();
+        self.validate_indices(&mut problems);
+        if !problems.is_empty() {
+            return into_result(problems);
+        }
+        for block_index in self.blocks.iter_indices(..) {
+            self.validate_block(block_index, &mut problems);
+        }
+        into_result(problems)
+    }
+
+    /// Like [`validate`](Self::validate), but only checks the invariants of `sample_size` blocks chosen pseudo-randomly from `seed`.
+    ///
+    /// This allows a quick smoke test of enormous decompositions.
+    /// The same seed always selects the same blocks.
+    pub fn validate_sample(
+        &self,
+        sample_size: usize,
+        seed: u64,
+    ) -> Result<(), ValidationError<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>> {
+        let mut block_indices: Vec<BlockIndex<Graph::IndexType>> =
+            self.blocks.iter_indices(..).collect();
+        let sample_size = sample_size.min(block_indices.len());
+
+        // Partial Fisher-Yates shuffle.
+        let mut random = SplitMix64(seed);
+        for index in 0..sample_size {
+            let remaining = (block_indices.len() - index) as u64;
+            let chosen = index + (random.next() % remaining) as usize;
+            block_indices.swap(index, chosen);
+        }
+        block_indices.truncate(sample_size);
+        block_indices.sort_unstable();
+
+        let mut problems = Vec::new();
+        for block_index in block_indices {
+            self.validate_block(block_index, &mut problems);
+        }
+        into_result(problems)
+    }
+
+    /// Checks that the decomposition matches the size of the graph and that all stored indices are in bounds.
+    ///
+    /// The other checks, as well as most other methods, rely on this.
+    fn validate_indices(&self, problems: &mut Problems<Graph>) {
+        let (node_count, edge_count) =
+            (self.graph_ref().node_count(), self.graph_ref().edge_count());
+        if self.node_data.len() != node_count || self.edge_data.len() != edge_count {
+            problems.push(ValidationProblem::GraphSizeMismatch {
+                nodes: self.node_data.len(),
+                edges: self.edge_data.len(),
+            });
+            return;
+        }
+
+        let (components, blocks, cut_nodes, spqr_nodes, spqr_edges) = (
+            self.components.len(),
+            self.blocks.len(),
+            self.cut_nodes.len(),
+            self.spqr_nodes.len(),
+            self.spqr_edges.len(),
+        );
+
+        for component in self.components.iter_values() {
+            check_bounds(
+                problems,
+                "node",
+                component.nodes.iter().map(|&node| node.into()),
+                node_count,
+            );
+            check_bounds(
+                problems,
+                "edge",
+                component.edges.iter().map(|&edge| edge.into()),
+                edge_count,
+            );
+            check_bounds(
+                problems,
+                "block",
+                component.blocks.iter().map(|block| block.into_usize()),
+                blocks,
+            );
+            check_bounds(
+                problems,
+                "cut node",
+                component
+                    .cut_nodes
+                    .iter()
+                    .map(|cut_node| cut_node.into_usize()),
+                cut_nodes,
+            );
+        }
+        for block in self.blocks.iter_values() {
+            check_bounds(
+                problems,
+                "component",
+                [block.component.into_usize()],
+                components,
+            );
+            check_bounds(
+                problems,
+                "node",
+                block.nodes.iter().map(|&node| node.into()),
+                node_count,
+            );
+            check_bounds(
+                problems,
+                "edge",
+                block.edges.iter().map(|&edge| edge.into()),
+                edge_count,
+            );
+            check_bounds(
+                problems,
+                "cut node",
+                block.cut_nodes.iter().map(|cut_node| cut_node.into_usize()),
+                cut_nodes,
+            );
+            check_bounds(
+                problems,
+                "SPQR node",
+                block
+                    .spqr_nodes
+                    .iter()
+                    .map(|spqr_node| spqr_node.into_usize()),
+                spqr_nodes,
+            );
+            check_bounds(
+                problems,
+                "SPQR edge",
+                block
+                    .spqr_edges
+                    .iter()
+                    .map(|spqr_edge| spqr_edge.into_usize()),
+                spqr_edges,
+            );
+        }
+        for cut_node in self.cut_nodes.iter_values() {
+            check_bounds(
+                problems,
+                "component",
+                [cut_node.component.into_usize()],
+                components,
+            );
+            check_bounds(problems, "node", [cut_node.node.into()], node_count);
+            check_bounds(
+                problems,
+                "block",
+                cut_node
+                    .adjacent_blocks
+                    .iter()
+                    .map(|block| block.into_usize()),
+                blocks,
+            );
+        }
+        for spqr_node in self.spqr_nodes.iter_values() {
+            check_bounds(problems, "block", [spqr_node.block.into_usize()], blocks);
+            check_bounds(
+                problems,
+                "node",
+                spqr_node.nodes.iter().map(|&node| node.into()),
+                node_count,
+            );
+            check_bounds(
+                problems,
+                "edge",
+                spqr_node.edges.iter().map(|&edge| edge.into()),
+                edge_count,
+            );
+            check_bounds(
+                problems,
+                "SPQR edge",
+                spqr_node
+                    .spqr_edges
+                    .iter()
+                    .map(|spqr_edge| spqr_edge.into_usize()),
+                spqr_edges,
+            );
+        }
+        for spqr_edge in self.spqr_edges.iter_values() {
+            let (u, v) = spqr_edge.endpoints;
+            check_bounds(
+                problems,
+                "SPQR node",
+                [u.into_usize(), v.into_usize()],
+                spqr_nodes,
+            );
+            let (a, b) = spqr_edge.virtual_edge;
+            check_bounds(problems, "node", [a.into(), b.into()], node_count);
+        }
+        for node_data in self.node_data.iter_values() {
+            check_bounds(
+                problems,
+                "component",
+                [node_data.component_index.into_usize()],
+                components,
+            );
+            check_bounds(
+                problems,
+                "block",
+                node_data
+                    .block_indices
+                    .iter()
+                    .map(|block| block.into_usize()),
+                blocks,
+            );
+            check_bounds(
+                problems,
+                "cut node",
+                node_data.cut_node_index.into_usize(),
+                cut_nodes,
+            );
+            check_bounds(
+                problems,
+                "SPQR node",
+                node_data
+                    .spqr_node_indices
+                    .iter()
+                    .map(|spqr_node| spqr_node.into_usize()),
+                spqr_nodes,
+            );
+        }
+        for edge_data in self.edge_data.iter_values() {
+            check_bounds(
+                problems,
+                "component",
+                [edge_data.component_index.into_usize()],
+                components,
+            );
+            check_bounds(
+                problems,
+                "block",
+                edge_data.block_index.into_usize(),
+                blocks,
+            );
+            check_bounds(
+                problems,
+                "SPQR node",
+                edge_data.spqr_node_index.into_usize(),
+                spqr_nodes,
+            );
+        }
+    }
+
+    fn validate_block(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        problems: &mut Problems<Graph>,
+    ) {
+        let block = &self.blocks[block_index];
+
+        if !self.components[block.component]
+            .blocks
+            .contains(&block_index)
+        {
+            problems.push(ValidationProblem::BlockNotInComponent(block_index));
+        }
+
+        for &node in &block.nodes {
+            if !self.node_data[node].block_indices.contains(&block_index) {
+                problems.push(ValidationProblem::NodeNotInBlock {
+                    node,
+                    block: block_index,
+                });
+            }
+        }
+
+        for &cut_node in &block.cut_nodes {
+            if !self.cut_nodes[cut_node]
+                .adjacent_blocks
+                .contains(&block_index)
+            {
+                problems.push(ValidationProblem::CutNodeNotAdjacentToBlock {
+                    cut_node,
+                    block: block_index,
+                });
+            }
+        }
+
+        let spqr_node_edges = block
+            .spqr_nodes
+            .iter()
+            .flat_map(|&spqr_node_index| &self.spqr_nodes[spqr_node_index].edges);
+        for &edge in block.edges.iter().chain(spqr_node_edges) {
+            if self.edge_data[edge].block_index.into_option() != Some(block_index) {
+                problems.push(ValidationProblem::EdgeNotInBlock {
+                    edge,
+                    block: block_index,
+                });
+            }
+        }
+
+        for &spqr_node_index in &block.spqr_nodes {
+            if self.spqr_nodes[spqr_node_index].block != block_index {
+                problems.push(ValidationProblem::SPQRNodeNotInBlock {
+                    spqr_node: spqr_node_index,
+                    block: block_index,
+                });
+            }
+            self.validate_spqr_node(spqr_node_index, problems);
+        }
+
+        for &spqr_edge_index in &block.spqr_edges {
+            let (u, v) = self.spqr_edges[spqr_edge_index].endpoints;
+            if self.spqr_nodes[u].block != block_index || self.spqr_nodes[v].block != block_index {
+                problems.push(ValidationProblem::SPQREdgeNotInBlock {
+                    spqr_edge: spqr_edge_index,
+                    block: block_index,
+                });
+            }
+        }
+
+        if !self.is_spqr_tree(block_index) {
+            problems.push(ValidationProblem::SPQRTreeNotATree(block_index));
+        }
+    }
+
+    fn validate_spqr_node(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        problems: &mut Problems<Graph>,
+    ) {
+        let spqr_node = &self.spqr_nodes[spqr_node_index];
+
+        for &node in &spqr_node.nodes {
+            if !self.node_data[node]
+                .spqr_node_indices
+                .contains(&spqr_node_index)
+            {
+                problems.push(ValidationProblem::NodeNotInSPQRNode {
+                    node,
+                    spqr_node: spqr_node_index,
+                });
+            }
+        }
+
+        for &edge in &spqr_node.edges {
+            if self.edge_data[edge].spqr_node_index.into_option() != Some(spqr_node_index) {
+                problems.push(ValidationProblem::EdgeNotInSPQRNode {
+                    edge,
+                    spqr_node: spqr_node_index,
+                });
+            }
+        }
+
+        for &spqr_edge_index in &spqr_node.spqr_edges {
+            let (u, v) = self.spqr_edges[spqr_edge_index].endpoints;
+            if u != spqr_node_index && v != spqr_node_index {
+                problems.push(ValidationProblem::SPQREdgeNotIncident {
+                    spqr_edge: spqr_edge_index,
+                    spqr_node: spqr_node_index,
+                });
+            }
+        }
+
+        // Count the skeleton degree of each node, which also reveals skeleton edges leaving the SPQR node.
+        // Self-loops are attached to an arbitrary SPQR node containing their node and do not affect its shape, so they are not counted.
+        let mut nodes = spqr_node.nodes.clone();
+        nodes.sort_unstable();
+        let mut degrees = vec![0usize; nodes.len()];
+        let mut edge_count = 0;
+        let mut outside = false;
+        for skeleton_edge in spqr_node.iter_skeleton_edges() {
+            let (a, b) = self.skeleton_edge_endpoints(skeleton_edge);
+            if a != b {
+                edge_count += 1;
+            }
+            for endpoint in [a, b] {
+                match nodes.binary_search(&endpoint) {
+                    Ok(position) if a != b => degrees[position] += 1,
+                    Ok(_) => {}
+                    Err(_) => outside = true,
+                }
+            }
+        }
+        if outside {
+            problems.push(ValidationProblem::SkeletonEdgeOutsideSPQRNode(
+                spqr_node_index,
+            ));
+        }
+
+        let valid_skeleton = match spqr_node.spqr_node_type {
+            // A cycle.
+            SPQRNodeType::SNode => {
+                nodes.len() >= 3
+                    && edge_count == nodes.len()
+                    && degrees.iter().all(|&degree| degree == 2)
+            }
+            // Two poles with at least three parallel edges.
+            SPQRNodeType::PNode => nodes.len() == 2 && edge_count >= 3,
+            // A triconnected graph.
+            SPQRNodeType::RNode => nodes.len() >= 4 && degrees.iter().all(|&degree| degree >= 3),
+        };
+        if !valid_skeleton {
+            problems.push(ValidationProblem::InvalidSkeleton {
+                spqr_node: spqr_node_index,
+                spqr_node_type: spqr_node.spqr_node_type,
+            });
+        }
+    }
+
+    /// Returns true if the SPQR nodes and SPQR edges of the given block form a tree.
+    fn is_spqr_tree(&self, block_index: BlockIndex<Graph::IndexType>) -> bool {
+        let block = &self.blocks[block_index];
+        if block.spqr_nodes.is_empty() {
+            return block.spqr_edges.is_empty();
+        }
+        if block.spqr_edges.len() != block.spqr_nodes.len() - 1 {
+            return false;
+        }
+        // Fast path for blocks with a single SPQR node, such as cycles, which form a tree if they have no SPQR edges.
+        if block.spqr_nodes.len() == 1 {
+            return true;
+        }
+
+        // With one edge less than nodes, the SPQR nodes form a tree if and only if they are connected.
+        let mut spqr_nodes = block.spqr_nodes.clone();
+        spqr_nodes.sort_unstable();
+        let mut visited = vec![false; spqr_nodes.len()];
+        let mut stack = vec![0];
+        visited[0] = true;
+        while let Some(position) = stack.pop() {
+            for &spqr_edge_index in &self.spqr_nodes[spqr_nodes[position]].spqr_edges {
+                let (u, v) = self.spqr_edges[spqr_edge_index].endpoints;
+                let other = if u == spqr_nodes[position] { v } else { u };
+                let Ok(other_position) = spqr_nodes.binary_search(&other) else {
+                    return false;
+                };
+                if !visited[other_position] {
+                    visited[other_position] = true;
+                    stack.push(other_position);
+                }
+            }
+        }
+
+        visited.into_iter().all(|visited| visited)
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+where
+    Graph::NodeIndex: Send + Sync,
+    Graph::EdgeIndex: Send + Sync,
+    Graph::IndexType: Send + Sync,
+    GraphStorage: Sync,
+    Graph: Sync,
+{
+    /// Like [`validate`](Self::validate), but checks the blocks in parallel.
+    ///
+    /// The problems are reported in the same order as by [`validate`](Self::validate).
+    ///
+    /// The blocks are split into one contiguous chunk per core, each checked on a scoped thread of the standard library,
+    /// see [`ParIter`](crate::parallel::ParIter). There is no thread pool or work stealing like in rayon,
+    /// so a single large block is checked by one thread and bounds the running time.
+    pub fn par_validate(
+        &self,
+    ) -> Result<(), ValidationError<Graph::NodeIndex, Graph::EdgeIndex, Graph::IndexType>> {
+        let problems = self.par_map_blocks(|block_index, _| {
+            let mut problems = Vec::new();
+            self.validate_block(block_index, &mut problems);
+            problems
+        });
+        into_result(problems.into_iter().flatten().collect())
+    }
+}
+
+/// Reports each of the given indices that is not below `len` as out of bounds.
+fn check_bounds<NodeIndex, EdgeIndex, IndexType>(
+    problems: &mut Vec<ValidationProblem<NodeIndex, EdgeIndex, IndexType>>,
+    kind: &'static str,
+    indices: impl IntoIterator<Item = usize>,
+    len: usize,
+) {
+    problems.extend(
+        indices
+            .into_iter()
+            .filter(|&index| index >= len)
+            .map(|index| ValidationProblem::IndexOutOfBounds { kind, index }),
+    );
+}
+
+fn into_result<NodeIndex, EdgeIndex, IndexType>(
+    problems: Vec<ValidationProblem<NodeIndex, EdgeIndex, IndexType>>,
+) -> Result<(), ValidationError<NodeIndex, EdgeIndex, IndexType>> {
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError { problems })
+    }
+}
+
+/// A small deterministic pseudo-random number generator for choosing samples.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl<NodeIndex: Display, EdgeIndex: Display, IndexType: Display> Display
+    for ValidationError<NodeIndex, EdgeIndex, IndexType>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        /// Listing millions of problems is not helpful, so only the first few are printed.
+        const MAX_PRINTED_PROBLEMS: usize = 10;
+
+        write!(
+            f,
+            "the SPQR decomposition is invalid ({} problems)",
+            self.problems.len()
+        )?;
+        for problem in self.problems.iter().take(MAX_PRINTED_PROBLEMS) {
+            write!(f, "; {problem}")?;
+        }
+        if self.problems.len() > MAX_PRINTED_PROBLEMS {
+            write!(
+                f,
+                "; and {} more",
+                self.problems.len() - MAX_PRINTED_PROBLEMS
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<NodeIndex: Debug + Display, EdgeIndex: Debug + Display, IndexType: Debug + Display>
+    std::error::Error for ValidationError<NodeIndex, EdgeIndex, IndexType>
+{
+}
@@ -0,0 +1,42 @@
+use crate::decomposition::{
+    tests::{TEST_FILES, read_decomposition, read_graph},
+    validation::ValidationProblem,
+};
+
+#[test]
+fn test_validate() {
+    for name in TEST_FILES {
+        let graph = read_graph(name);
+        let spqr_decomposition = read_decomposition(&graph, name);
+        if let Err(error) = spqr_decomposition.validate() {
+            panic!("{name}: {error}");
+        }
+        spqr_decomposition.validate_sample(3, 42).unwrap();
+        #[cfg(feature = "parallel")]
+        spqr_decomposition.par_validate().unwrap();
+    }
+
+    let graph = read_graph("Ultrabubble_dataset_chr_22_G182870");
+    let mut spqr_decomposition = read_decomposition(&graph, "Ultrabubble_dataset_chr_22_G182870");
+    let (spqr_node_index, _) = spqr_decomposition.iter_spqr_nodes().next().unwrap();
+    let block_index = spqr_decomposition.spqr_node(spqr_node_index).block();
+    spqr_decomposition.spqr_nodes[spqr_node_index].block =
+        if block_index.into_usize() == 0 { 1 } else { 0 }.into();
+
+    let problems = spqr_decomposition.validate().unwrap_err().problems;
+    assert!(problems.contains(&ValidationProblem::SPQRNodeNotInBlock {
+        spqr_node: spqr_node_index,
+        block: block_index,
+    }));
+    #[cfg(feature = "parallel")]
+    assert_eq!(
+        spqr_decomposition.par_validate().unwrap_err().problems,
+        problems
+    );
+
+    let sampled = spqr_decomposition
+        .validate_sample(spqr_decomposition.block_count(), 7)
+        .unwrap_err()
+        .problems;
+    assert_eq!(sampled, problems);
+}
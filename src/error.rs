@@ -0,0 +1,87 @@
+//! A crate-wide error type, so applications can propagate the errors of all modules with `?`.
+//!
+//! The errors of the builder and of validation are generic over the index types of the graph,
+//! so they are converted into their messages. Use the module-specific errors directly to inspect individual problems.
+
+use std::fmt::Display;
+
+use thiserror::Error;
+
+use crate::decomposition::{
+    builder::{AddEdgeError, AddError, BuildError},
+    indices::IndexOverflowError,
+    validation::ValidationError,
+};
+use crate::io::error::FileReadError;
+#[cfg(feature = "plain-io")]
+use crate::io::plain_spqr_file::error::ReadError;
+
+#[cfg(test)]
+mod tests;
+
+/// Any error returned by this crate.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Reading a file in the plain SPQR file format failed.
+    #[cfg(feature = "plain-io")]
+    #[error(transparent)]
+    Read(#[from] ReadError),
+
+    /// Reading a file in the plain SPQR file format from a path failed.
+    #[cfg(feature = "plain-io")]
+    #[error(transparent)]
+    ReadFile(#[from] FileReadError<ReadError>),
+
+    /// Reading a file in the binary format from a path failed.
+    #[error(transparent)]
+    ReadBinaryFile(#[from] FileReadError<std::io::Error>),
+
+    /// An IO operation failed, including decoding the binary format.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// An entity could not be added to a builder.
+    #[error("{0}")]
+    Add(String),
+
+    /// An edge could not be added to a builder.
+    #[error(transparent)]
+    AddEdge(#[from] AddEdgeError),
+
+    /// A builder failed its sanity checks.
+    #[error("{0}")]
+    Build(String),
+
+    /// A decomposition violates its structural invariants.
+    #[error("{0}")]
+    Validation(String),
+
+    /// The graph is too large for the index type.
+    #[error(transparent)]
+    IndexOverflow(#[from] IndexOverflowError),
+}
+
+impl<NodeIndex: Display, EdgeIndex: Display, IndexType: Display>
+    From<AddError<NodeIndex, EdgeIndex, IndexType>> for Error
+{
+    fn from(error: AddError<NodeIndex, EdgeIndex, IndexType>) -> Self {
+        Self::Add(error.to_string())
+    }
+}
+
+impl<NodeIndex: Display, EdgeIndex: Display, IndexType: Display>
+    From<BuildError<NodeIndex, EdgeIndex, IndexType>> for Error
+{
+    fn from(error: BuildError<NodeIndex, EdgeIndex, IndexType>) -> Self {
+        Self::Build(error.to_string())
+    }
+}
+
+impl<NodeIndex: Display, EdgeIndex: Display, IndexType: Display>
+    From<ValidationError<NodeIndex, EdgeIndex, IndexType>> for Error
+{
+    fn from(error: ValidationError<NodeIndex, EdgeIndex, IndexType>) -> Self {
+        Self::Validation(error.to_string())
+    }
+}
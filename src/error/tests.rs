@@ -0,0 +1,45 @@
+use std::{fs::File, io::BufReader};
+
+use bidirected_adjacency_array::{
+    graph::BidirectedAdjacencyArray,
+    io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
+};
+
+use crate::{
+    Error,
+    decomposition::{SPQRDecomposition, builder::SPQRDecompositionBuilder},
+};
+
+type TestGraph = BidirectedAdjacencyArray<u8, PlainGfaNodeData, PlainGfaEdgeData>;
+
+/// Reads and validates the decomposition of the given test file, propagating all errors with `?`.
+fn read_and_validate(graph: &TestGraph, name: &str) -> Result<usize, Error> {
+    let spqr_decomposition = SPQRDecomposition::read_plain_spqr(
+        graph,
+        BufReader::new(File::open(format!("test_files/{name}.spqr"))?),
+    )?;
+    spqr_decomposition.validate()?;
+    Ok(spqr_decomposition.iter_blocks().count())
+}
+
+#[test]
+fn test_propagation() {
+    let graph =
+        TestGraph::read_gfa1(BufReader::new(File::open("test_files/tiny1.gfa").unwrap())).unwrap();
+    assert_eq!(read_and_validate(&graph, "tiny1").unwrap(), 5);
+    assert!(matches!(
+        read_and_validate(&graph, "missing"),
+        Err(Error::Io(_))
+    ));
+    assert!(matches!(
+        read_and_validate(&graph, "multiedge"),
+        Err(Error::Read(_))
+    ));
+
+    let build =
+        || -> Result<_, Error> { Ok(SPQRDecompositionBuilder::try_new(&graph)?.try_build()?) };
+    let Err(Error::Build(message)) = build() else {
+        panic!("building an empty builder must fail");
+    };
+    assert!(message.contains("is not assigned to any component"));
+}
@@ -0,0 +1,156 @@
+//! Entry points for fuzzing the parsers, e.g. from the `fuzz_targets` of `cargo fuzz`.
+//!
+//! Each entry point takes arbitrary bytes and returns an error for any input it rejects, so a panic always indicates a bug.
+//! The first bytes of the input describe a small graph, see [`FuzzGraph::from_bytes`], and the remaining bytes are parsed against it.
+//! Decompositions that are read successfully are also [validated](SPQRDecomposition::validate),
+//! which exercises all references between their entities.
+
+use std::borrow::Cow;
+
+use thiserror::Error;
+
+use crate::{
+    decomposition::{SPQRDecomposition, indices::DefaultIndexType},
+    graph::StaticGraph,
+    io::plain_spqr_file::error::ReadError,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of nodes of a [`FuzzGraph`].
+pub const MAX_NODES: usize = 16;
+
+/// The maximum amount of edges of a [`FuzzGraph`].
+pub const MAX_EDGES: usize = 64;
+
+/// The error returned by the fuzzing entry points.
+#[derive(Error, Debug)]
+pub enum FuzzError {
+    #[error("the input is too short to describe a graph")]
+    MissingGraph,
+
+    #[error("plain SPQR file error: {0}")]
+    Plain(#[from] ReadError),
+
+    #[error("binary file error: {0}")]
+    Binary(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Invalid(String),
+}
+
+/// A small graph generated from fuzzer input, which may contain self-loops and multiedges.
+///
+/// The nodes are named by the lowercase letters starting from `a`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzGraph {
+    edges: Vec<(usize, usize)>,
+    incident_edges: Vec<Vec<usize>>,
+}
+
+impl FuzzGraph {
+    /// Generates a graph from the first bytes of `data`, returning the graph and the remaining bytes.
+    ///
+    /// The first byte is the amount of nodes and the second byte the amount of edges, both modulo their maximum.
+    /// They are followed by two bytes per edge, which are the indices of its endpoints modulo the amount of nodes.
+    /// If `data` ends early, the graph has fewer edges.
+    pub fn from_bytes(data: &[u8]) -> Result<(Self, &[u8]), FuzzError> {
+        let [node_count, edge_count, data @ ..] = data else {
+            return Err(FuzzError::MissingGraph);
+        };
+        let node_count = usize::from(*node_count) % MAX_NODES + 1;
+        let edge_count = (usize::from(*edge_count) % (MAX_EDGES + 1)).min(data.len() / 2);
+        let (endpoints, data) = data.split_at(2 * edge_count);
+
+        let mut graph = Self {
+            edges: Vec::with_capacity(edge_count),
+            incident_edges: vec![Vec::new(); node_count],
+        };
+        for (edge, endpoints) in endpoints.chunks_exact(2).enumerate() {
+            let (a, b) = (
+                usize::from(endpoints[0]) % node_count,
+                usize::from(endpoints[1]) % node_count,
+            );
+            graph.edges.push((a, b));
+            graph.incident_edges[a].push(edge);
+            if a != b {
+                graph.incident_edges[b].push(edge);
+            }
+        }
+        Ok((graph, data))
+    }
+}
+
+impl StaticGraph for FuzzGraph {
+    type IndexType = DefaultIndexType;
+
+    type NodeIndex = usize;
+
+    type EdgeIndex = usize;
+
+    fn node_indices(&self) -> impl Iterator<Item = Self::NodeIndex> {
+        0..self.incident_edges.len()
+    }
+
+    fn edge_indices(&self) -> impl Iterator<Item = Self::EdgeIndex> {
+        0..self.edges.len()
+    }
+
+    fn node_count(&self) -> usize {
+        self.incident_edges.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn node_name(&self, node_index: Self::NodeIndex) -> Cow<'_, str> {
+        char::from(b'a' + node_index as u8).to_string().into()
+    }
+
+    fn incident_edges(&self, node: Self::NodeIndex) -> impl Iterator<Item = Self::EdgeIndex> {
+        self.incident_edges[node].iter().copied()
+    }
+
+    fn edge_endpoints(&self, edge: Self::EdgeIndex) -> (Self::NodeIndex, Self::NodeIndex) {
+        self.edges[edge]
+    }
+
+    fn edges_between(
+        &self,
+        u: Self::NodeIndex,
+        v: Self::NodeIndex,
+    ) -> impl Iterator<Item = Self::EdgeIndex> {
+        self.incident_edges(u)
+            .filter(move |&edge| self.edges[edge] == (u, v) || self.edges[edge] == (v, u))
+    }
+}
+
+/// Reads the input after the graph as a file in the plain SPQR file format, and validates the decomposition.
+pub fn read_plain_spqr(data: &[u8]) -> Result<(), FuzzError> {
+    let (graph, data) = FuzzGraph::from_bytes(data)?;
+    let spqr_decomposition = SPQRDecomposition::read_plain_spqr(&graph, data)?;
+    validate(&spqr_decomposition)
+}
+
+/// Reads the input after the graph in the binary format, both from a reader and from a slice, and validates the decomposition.
+///
+/// # Panics
+///
+/// Panics if reading from a slice yields a different decomposition than reading from a reader.
+pub fn read_binary(data: &[u8]) -> Result<(), FuzzError> {
+    let (graph, data) = FuzzGraph::from_bytes(data)?;
+    let spqr_decomposition = SPQRDecomposition::read_binary(&graph, data)?;
+    assert!(
+        SPQRDecomposition::read_binary_from_slice(&graph, data)? == spqr_decomposition,
+        "reading from a slice differs from reading from a reader",
+    );
+    validate(&spqr_decomposition)
+}
+
+fn validate(spqr_decomposition: &SPQRDecomposition<'_, FuzzGraph>) -> Result<(), FuzzError> {
+    spqr_decomposition
+        .validate()
+        .map_err(|error| FuzzError::Invalid(error.to_string()))
+}
@@ -0,0 +1,141 @@
+use std::{collections::HashMap, fs};
+
+use crate::{
+    decomposition::SPQRDecomposition,
+    fuzz::{FuzzGraph, read_binary, read_plain_spqr},
+    graph::StaticGraph,
+};
+
+/// Returns the graph of the given test file in the input format of [`FuzzGraph`], followed by its decomposition in the plain SPQR file format.
+///
+/// The nodes are renamed to letters in the order of their S-lines.
+fn fuzz_input(name: &str) -> (Vec<u8>, Vec<String>) {
+    let gfa = fs::read_to_string(format!("test_files/{name}.gfa")).unwrap();
+    let mut node_indices = HashMap::new();
+    let mut endpoints = Vec::new();
+    for line in gfa.lines() {
+        let columns = line.split('\t').collect::<Vec<_>>();
+        match columns[0] {
+            "S" => {
+                node_indices.insert(columns[1], node_indices.len() as u8);
+            }
+            "L" => endpoints.extend([node_indices[columns[1]], node_indices[columns[3]]]),
+            _ => {}
+        }
+    }
+    let mut graph = vec![node_indices.len() as u8 - 1, (endpoints.len() / 2) as u8];
+    graph.extend(endpoints);
+
+    let lines = fs::read_to_string(format!("test_files/{name}.spqr"))
+        .unwrap()
+        .lines()
+        .map(|line| {
+            line.split(' ')
+                .map(|column| match node_indices.get(column) {
+                    Some(&node_index) => char::from(b'a' + node_index).to_string(),
+                    None => column.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    (graph, lines)
+}
+
+/// A deterministic xorshift generator for mutating the inputs.
+fn xorshift(state: &mut u64) -> usize {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state as usize
+}
+
+#[test]
+fn test_fuzz_graph() {
+    let (graph, rest) = FuzzGraph::from_bytes(&[2, 3, 0, 1, 1, 2, 2, 2, 7]).unwrap();
+    assert_eq!(rest, [7]);
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 3);
+    assert_eq!(graph.node_name(2), "c");
+    assert_eq!(graph.edges_between(2, 1).collect::<Vec<_>>(), [1]);
+    assert_eq!(graph.edges_between(2, 2).collect::<Vec<_>>(), [2]);
+
+    // Missing endpoints shorten the edge list.
+    let (graph, rest) = FuzzGraph::from_bytes(&[0, 5, 0]).unwrap();
+    assert_eq!((graph.node_count(), graph.edge_count()), (1, 0));
+    assert_eq!(rest, [0]);
+    assert!(FuzzGraph::from_bytes(&[0]).is_err());
+}
+
+#[test]
+fn test_valid_inputs() {
+    for name in ["tiny1", "multiedge", "random3_5_False_0"] {
+        let (graph, lines) = fuzz_input(name);
+        let mut plain = graph.clone();
+        plain.extend(lines.join("\n").bytes());
+        read_plain_spqr(&plain).unwrap();
+
+        let (fuzz_graph, data) = FuzzGraph::from_bytes(&plain).unwrap();
+        let spqr_decomposition = SPQRDecomposition::read_plain_spqr(&fuzz_graph, data).unwrap();
+        let mut binary = graph;
+        spqr_decomposition.write_binary(&mut binary).unwrap();
+        read_binary(&binary).unwrap();
+    }
+}
+
+/// Mutates valid inputs randomly, which must never cause a panic.
+#[test]
+fn test_mutated_inputs() {
+    let mut state = 0x5EED;
+    for name in ["tiny1", "multiedge"] {
+        let (graph, lines) = fuzz_input(name);
+        let tokens = lines
+            .iter()
+            .flat_map(|line| line.split(' '))
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        for _ in 0..2000 {
+            let mut lines = lines.clone();
+            for _ in 0..xorshift(&mut state) % 4 + 1 {
+                let line = xorshift(&mut state) % lines.len();
+                let token = tokens[xorshift(&mut state) % tokens.len()].clone();
+                let mut columns = lines[line]
+                    .split(' ')
+                    .map(str::to_string)
+                    .collect::<Vec<_>>();
+                match xorshift(&mut state) % 4 {
+                    0 => {
+                        let column = xorshift(&mut state) % columns.len();
+                        columns[column] = token;
+                    }
+                    1 => columns.push(token),
+                    2 => drop(columns.pop()),
+                    _ => columns = vec![lines[xorshift(&mut state) % lines.len()].clone()],
+                }
+                lines[line] = columns.join(" ");
+            }
+            let mut input = graph.clone();
+            input.extend(lines.join("\n").bytes());
+            let _ = read_plain_spqr(&input);
+        }
+
+        let mut plain = graph.clone();
+        plain.extend(lines.join("\n").bytes());
+        let (fuzz_graph, data) = FuzzGraph::from_bytes(&plain).unwrap();
+        let mut binary = Vec::new();
+        SPQRDecomposition::read_plain_spqr(&fuzz_graph, data)
+            .unwrap()
+            .write_binary(&mut binary)
+            .unwrap();
+        for _ in 0..2000 {
+            let mut binary = binary.clone();
+            for _ in 0..xorshift(&mut state) % 3 + 1 {
+                let position = xorshift(&mut state) % binary.len();
+                binary[position] = xorshift(&mut state) as u8;
+            }
+            let mut input = graph.clone();
+            input.extend(binary);
+            let _ = read_binary(&input);
+        }
+    }
+}
@@ -2,7 +2,9 @@ use std::borrow::Cow;
 
 use crate::decomposition::indices::{GraphIndex, GraphIndexInteger};
 
+pub mod condensed;
 pub mod implementations;
+pub mod subgraph;
 
 /// An undirected graph without multiedges or self-loops.
 pub trait StaticGraph {
@@ -51,3 +53,45 @@ pub trait NamedNodeData {
 pub trait NamedEdgeData {
     fn name(&'_ self) -> Cow<'_, str>;
 }
+
+/// The side of a node that an edge is attached to in a bidirected graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Orientation {
+    Forward,
+    Reverse,
+}
+
+/// A [`StaticGraph`] whose edges are attached to oriented nodes, such as a pangenome graph in GFA format.
+///
+/// The decomposition itself ignores orientations, but accessors that export parts of the graph use this trait to preserve them.
+pub trait BidirectedGraph: StaticGraph {
+    /// Returns the orientations of the endpoints of the given edge.
+    ///
+    /// The orientations are in the same order as the endpoints returned by [`StaticGraph::edge_endpoints`].
+    fn edge_orientations(&self, edge: Self::EdgeIndex) -> (Orientation, Orientation);
+}
+
+impl Orientation {
+    pub fn is_forward(self) -> bool {
+        self == Self::Forward
+    }
+
+    pub fn is_reverse(self) -> bool {
+        self == Self::Reverse
+    }
+
+    pub fn invert(self) -> Self {
+        match self {
+            Self::Forward => Self::Reverse,
+            Self::Reverse => Self::Forward,
+        }
+    }
+
+    /// Returns the sign used for this orientation in GFA files.
+    pub fn gfa_sign(self) -> char {
+        match self {
+            Self::Forward => '+',
+            Self::Reverse => '-',
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use std::borrow::Cow;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::graph::{BidirectedGraph, Orientation, StaticGraph};
+
+/// A view of a graph in which each maximal path through nodes of degree two is contracted into a single edge.
+///
+/// A node is contracted if it has exactly two incident edges and neither of them is a self-loop.
+/// Each edge of the condensed graph stands for a chain of edges of the parent graph, whose length is its [weight](CondensedGraph::edge_weight).
+/// A chain that would start and end at the same node is split at its middle node, which is kept,
+/// such that the condensed graph has the same blocks as the parent graph, except that chains become single edges.
+/// Likewise, two opposite nodes are kept in each cycle that consists only of nodes of degree two.
+///
+/// The nodes and edges of the condensed graph are numbered consecutively starting from zero.
+/// Use [`CondensedGraph::parent_node`] and [`CondensedGraph::chain_edges`] to translate them back to the parent graph,
+/// and [`CondensedGraph::condensed_node`] to find where a node of the parent graph went.
+#[derive(Debug, Clone)]
+pub struct CondensedGraph<'graph, Graph: StaticGraph> {
+    parent: &'graph Graph,
+    nodes: Vec<Graph::NodeIndex>,
+    node_map: FxHashMap<Graph::NodeIndex, CondensedNode<Graph::NodeIndex, Graph::EdgeIndex>>,
+    edges: Vec<Chain<Graph>>,
+    edge_map: FxHashMap<Graph::EdgeIndex, Graph::EdgeIndex>,
+    incident_edges: Vec<Vec<Graph::EdgeIndex>>,
+}
+
+/// The position of a node of the parent graph in a [`CondensedGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CondensedNode<NodeIndex, EdgeIndex> {
+    /// The node is kept as the given node of the condensed graph.
+    Node(NodeIndex),
+    /// The node was contracted into the given edge of the condensed graph,
+    /// and is at the given position in its [chain nodes](CondensedGraph::chain_nodes).
+    Chain { edge: EdgeIndex, position: usize },
+}
+
+#[derive(Debug, Clone)]
+struct Chain<Graph: StaticGraph> {
+    endpoints: (Graph::NodeIndex, Graph::NodeIndex),
+    edges: Vec<Graph::EdgeIndex>,
+    nodes: Vec<Graph::NodeIndex>,
+}
+
+impl<'graph, Graph: StaticGraph> CondensedGraph<'graph, Graph> {
+    /// Creates the condensed graph of `parent`, see the [type documentation](Self).
+    ///
+    /// This takes time linear in the size of the parent graph.
+    pub fn new(parent: &'graph Graph) -> Self {
+        let is_chain_node = |node_index| {
+            let mut incident_edges = parent.incident_edges(node_index);
+            match (
+                incident_edges.next(),
+                incident_edges.next(),
+                incident_edges.next(),
+            ) {
+                (Some(first), Some(second), None) => [first, second].into_iter().all(|edge| {
+                    let (u, v) = parent.edge_endpoints(edge);
+                    u != v
+                }),
+                _ => false,
+            }
+        };
+        let mut kept = FxHashMap::default();
+        for node_index in parent.node_indices() {
+            kept.insert(node_index, !is_chain_node(node_index));
+        }
+
+        // Keep the middle node of each chain that would become a self-loop.
+        let mut visited = FxHashSet::default();
+        for node_index in parent.node_indices() {
+            if !kept[&node_index] {
+                continue;
+            }
+            for edge_index in parent.incident_edges(node_index) {
+                let (_, nodes, end) = walk_chain(parent, &kept, node_index, edge_index);
+                if end == node_index && !nodes.is_empty() {
+                    kept.insert(nodes[nodes.len() / 2], true);
+                }
+                visited.extend(nodes);
+            }
+        }
+
+        // The remaining nodes form cycles without kept nodes, of which two opposite nodes are kept.
+        for node_index in parent.node_indices() {
+            if kept[&node_index] || visited.contains(&node_index) {
+                continue;
+            }
+            kept.insert(node_index, true);
+            let edge_index = parent.incident_edges(node_index).next().unwrap();
+            let (_, nodes, end) = walk_chain(parent, &kept, node_index, edge_index);
+            debug_assert_eq!(end, node_index);
+            kept.insert(nodes[nodes.len() / 2], true);
+            visited.extend(nodes);
+        }
+
+        let nodes = parent
+            .node_indices()
+            .filter(|node_index| kept[node_index])
+            .collect::<Vec<_>>();
+        let mut node_map: FxHashMap<_, CondensedNode<Graph::NodeIndex, _>> = FxHashMap::default();
+        for (condensed_node, &parent_node) in nodes.iter().enumerate() {
+            node_map.insert(parent_node, CondensedNode::Node(condensed_node.into()));
+        }
+
+        let mut edges = Vec::new();
+        let mut edge_map = FxHashMap::default();
+        let mut incident_edges = vec![Vec::new(); nodes.len()];
+        for (condensed_node, &parent_node) in nodes.iter().enumerate() {
+            for edge_index in parent.incident_edges(parent_node) {
+                if edge_map.contains_key(&edge_index) {
+                    continue;
+                }
+                let (chain_edges, chain_nodes, end) =
+                    walk_chain(parent, &kept, parent_node, edge_index);
+                let condensed_edge: Graph::EdgeIndex = edges.len().into();
+                for &chain_edge in &chain_edges {
+                    edge_map.insert(chain_edge, condensed_edge);
+                }
+                for (position, &chain_node) in chain_nodes.iter().enumerate() {
+                    node_map.insert(
+                        chain_node,
+                        CondensedNode::Chain {
+                            edge: condensed_edge,
+                            position,
+                        },
+                    );
+                }
+                let CondensedNode::Node(condensed_end) = node_map[&end] else {
+                    unreachable!("chains end at kept nodes");
+                };
+                incident_edges[condensed_node].push(condensed_edge);
+                if condensed_end != condensed_node.into() {
+                    incident_edges[condensed_end.into()].push(condensed_edge);
+                }
+                edges.push(Chain {
+                    endpoints: (condensed_node.into(), condensed_end),
+                    edges: chain_edges,
+                    nodes: chain_nodes,
+                });
+            }
+        }
+
+        Self {
+            parent,
+            nodes,
+            node_map,
+            edges,
+            edge_map,
+            incident_edges,
+        }
+    }
+
+    pub fn parent(&self) -> &'graph Graph {
+        self.parent
+    }
+
+    /// Returns the index of the given condensed node in the parent graph.
+    pub fn parent_node(&self, node_index: Graph::NodeIndex) -> Graph::NodeIndex {
+        self.nodes[node_index.into()]
+    }
+
+    /// Returns the edges of the parent graph that were contracted into the given condensed edge,
+    /// in order from the first to the second of its [endpoints](StaticGraph::edge_endpoints).
+    pub fn chain_edges(&self, edge_index: Graph::EdgeIndex) -> &[Graph::EdgeIndex] {
+        &self.edges[edge_index.into()].edges
+    }
+
+    /// Returns the nodes of the parent graph that were contracted into the given condensed edge,
+    /// in order from the first to the second of its [endpoints](StaticGraph::edge_endpoints).
+    pub fn chain_nodes(&self, edge_index: Graph::EdgeIndex) -> &[Graph::NodeIndex] {
+        &self.edges[edge_index.into()].nodes
+    }
+
+    /// Returns the amount of edges of the parent graph that were contracted into the given condensed edge.
+    pub fn edge_weight(&self, edge_index: Graph::EdgeIndex) -> usize {
+        self.edges[edge_index.into()].edges.len()
+    }
+
+    /// Returns the position of the given parent graph node in the condensed graph.
+    pub fn condensed_node(
+        &self,
+        parent_node_index: Graph::NodeIndex,
+    ) -> CondensedNode<Graph::NodeIndex, Graph::EdgeIndex> {
+        self.node_map[&parent_node_index]
+    }
+
+    /// Returns the condensed edge that the given parent graph edge was contracted into.
+    pub fn condensed_edge(&self, parent_edge_index: Graph::EdgeIndex) -> Graph::EdgeIndex {
+        self.edge_map[&parent_edge_index]
+    }
+}
+
+/// Follows the chain starting at `start` with `edge_index` until it reaches a kept node,
+/// returning the edges and the contracted nodes of the chain, and the kept node at its end.
+fn walk_chain<Graph: StaticGraph>(
+    parent: &Graph,
+    kept: &FxHashMap<Graph::NodeIndex, bool>,
+    start: Graph::NodeIndex,
+    edge_index: Graph::EdgeIndex,
+) -> (
+    Vec<Graph::EdgeIndex>,
+    Vec<Graph::NodeIndex>,
+    Graph::NodeIndex,
+) {
+    let opposite = |edge_index, node_index| {
+        let (u, v) = parent.edge_endpoints(edge_index);
+        if u == node_index { v } else { u }
+    };
+    let mut edges = vec![edge_index];
+    let mut nodes = Vec::new();
+    let mut current = opposite(edge_index, start);
+    while !kept[&current] {
+        nodes.push(current);
+        let previous = *edges.last().unwrap();
+        let next = parent
+            .incident_edges(current)
+            .find(|&edge_index| edge_index != previous)
+            .unwrap();
+        edges.push(next);
+        current = opposite(next, current);
+    }
+    (edges, nodes, current)
+}
+
+impl<'graph, Graph: StaticGraph> StaticGraph for CondensedGraph<'graph, Graph> {
+    type IndexType = Graph::IndexType;
+
+    type NodeIndex = Graph::NodeIndex;
+
+    type EdgeIndex = Graph::EdgeIndex;
+
+    fn node_indices(&self) -> impl Iterator<Item = Self::NodeIndex> {
+        (0..self.nodes.len()).map(Into::into)
+    }
+
+    fn edge_indices(&self) -> impl Iterator<Item = Self::EdgeIndex> {
+        (0..self.edges.len()).map(Into::into)
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn node_name(&self, node_index: Self::NodeIndex) -> Cow<'_, str> {
+        self.parent.node_name(self.parent_node(node_index))
+    }
+
+    fn incident_edges(&self, node: Self::NodeIndex) -> impl Iterator<Item = Self::EdgeIndex> {
+        self.incident_edges[node.into()].iter().copied()
+    }
+
+    fn edge_endpoints(&self, edge: Self::EdgeIndex) -> (Self::NodeIndex, Self::NodeIndex) {
+        self.edges[edge.into()].endpoints
+    }
+
+    fn edges_between(
+        &self,
+        u: Self::NodeIndex,
+        v: Self::NodeIndex,
+    ) -> impl Iterator<Item = Self::EdgeIndex> {
+        self.incident_edges(u).filter(move |&edge| {
+            let (a, b) = self.edge_endpoints(edge);
+            (a, b) == (u, v) || (a, b) == (v, u)
+        })
+    }
+}
+
+impl<'graph, Graph: BidirectedGraph> BidirectedGraph for CondensedGraph<'graph, Graph> {
+    /// Returns the orientations with which the first and the last edge of the chain attach to its endpoints.
+    ///
+    /// The orientations at the contracted nodes are not checked, so the chain may not be a valid walk in the bidirected graph.
+    fn edge_orientations(&self, edge: Self::EdgeIndex) -> (Orientation, Orientation) {
+        let chain = &self.edges[edge.into()];
+        let (u, v) = chain.endpoints;
+        let attachment = |parent_edge, node_index, first| {
+            let (a, b) = self.parent.edge_endpoints(parent_edge);
+            let (orientation_a, orientation_b) = self.parent.edge_orientations(parent_edge);
+            if a == b {
+                if first { orientation_a } else { orientation_b }
+            } else if a == self.parent_node(node_index) {
+                orientation_a
+            } else {
+                orientation_b
+            }
+        };
+        (
+            attachment(chain.edges[0], u, true),
+            attachment(*chain.edges.last().unwrap(), v, false),
+        )
+    }
+}
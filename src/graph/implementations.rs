@@ -1,2 +1,4 @@
 #[cfg(feature = "bidirected-adjacency-array")]
 pub mod bidirected_adjacency_array;
+#[cfg(feature = "binary-io")]
+pub mod compact;
@@ -4,7 +4,14 @@ use bidirected_adjacency_array::{
     io::gfa1::GfaNodeData,
 };
 
-use crate::graph::{NamedNodeData, StaticGraph};
+use crate::{
+    decomposition::indices::DefaultIndexType,
+    graph::{BidirectedGraph, NamedNodeData, Orientation, StaticGraph},
+};
+
+/// A bidirected adjacency array using the [`DefaultIndexType`] selected at compile time.
+pub type DefaultBidirectedAdjacencyArray<NodeData, EdgeData> =
+    BidirectedAdjacencyArray<DefaultIndexType, NodeData, EdgeData>;
 
 impl<IndexType: GraphIndexInteger, NodeData: NamedNodeData, EdgeData> StaticGraph
     for BidirectedAdjacencyArray<IndexType, NodeData, EdgeData>
@@ -63,6 +70,25 @@ impl<IndexType: GraphIndexInteger, NodeData: NamedNodeData, EdgeData> StaticGrap
     }
 }
 
+impl<IndexType: GraphIndexInteger, NodeData: NamedNodeData, EdgeData> BidirectedGraph
+    for BidirectedAdjacencyArray<IndexType, NodeData, EdgeData>
+{
+    fn edge_orientations(&self, edge: Self::EdgeIndex) -> (Orientation, Orientation) {
+        let edge_data = self.edge(edge);
+        let orientation = |forward| {
+            if forward {
+                Orientation::Forward
+            } else {
+                Orientation::Reverse
+            }
+        };
+        (
+            orientation(edge_data.from().is_forward()),
+            orientation(edge_data.to().is_forward()),
+        )
+    }
+}
+
 impl<T: GfaNodeData> NamedNodeData for T {
     fn name(&'_ self) -> std::borrow::Cow<'_, str> {
         GfaNodeData::name(self)
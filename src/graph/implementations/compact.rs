@@ -0,0 +1,246 @@
+//! A compact, read-only graph format that can be queried directly from its bytes, e.g. from a memory-mapped file.
+
+use std::{
+    borrow::Cow,
+    io::{BufWriter, Write},
+    marker::PhantomData,
+};
+
+use optional_numeric_index::implement_generic_index;
+
+use crate::{
+    decomposition::indices::{DefaultIndexType, GraphIndexInteger},
+    graph::StaticGraph,
+};
+
+#[cfg(all(test, feature = "bidirected-adjacency-array"))]
+mod tests;
+
+implement_generic_index!(pub CompactNodeIndex, pub OptionalCompactNodeIndex);
+implement_generic_index!(pub CompactEdgeIndex, pub OptionalCompactEdgeIndex);
+
+/// Marks the start of a compact graph file.
+const MAGIC: [u8; 8] = *b"SPQRCGR1";
+
+const WORD: usize = size_of::<usize>();
+
+/// A graph stored in the compact graph format, see [`write_compact_graph`].
+///
+/// The graph is a view of its bytes and does not copy them.
+/// Hence, if the bytes are a memory-mapped file, only the parts of the file that are actually queried are loaded into memory,
+/// which allows to work with graphs that do not fit into RAM.
+///
+/// The format is platform-dependent like the [binary format of decompositions](crate::io::binary).
+/// It consists of a magic number, the amounts of nodes and edges, followed by the incidence lists of all nodes,
+/// the endpoints of all edges and the names of all nodes.
+/// Only the lengths of these arrays are checked when the graph is opened, so queries on a corrupt file may panic.
+#[derive(Debug, Clone)]
+pub struct CompactGraph<Bytes, IndexType = DefaultIndexType> {
+    bytes: Bytes,
+    node_count: usize,
+    edge_count: usize,
+    incidence_offsets: usize,
+    incidences: usize,
+    endpoints: usize,
+    name_offsets: usize,
+    names: usize,
+    index_type: PhantomData<IndexType>,
+}
+
+impl<Bytes: AsRef<[u8]>, IndexType: GraphIndexInteger> CompactGraph<Bytes, IndexType> {
+    /// Opens a graph in the compact graph format.
+    ///
+    /// This only reads the header and the array lengths, and does not copy the bytes.
+    pub fn new(bytes: Bytes) -> std::io::Result<Self> {
+        let invalid = |message| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+        let data = bytes.as_ref();
+        if data.get(..MAGIC.len()) != Some(&MAGIC[..]) {
+            return Err(invalid("not a compact graph file"));
+        }
+
+        let word_at = |offset: usize| {
+            data.get(offset..offset + WORD)
+                .map(|word| usize::from_ne_bytes(word.try_into().unwrap()))
+                .ok_or_else(|| invalid("compact graph file is truncated"))
+        };
+        let array_end = |offset: usize, len: Option<usize>| {
+            len.and_then(|len| len.checked_mul(WORD))
+                .and_then(|len| offset.checked_add(len))
+                .ok_or_else(|| invalid("array length overflows"))
+        };
+
+        let node_count = word_at(MAGIC.len())?;
+        let edge_count = word_at(MAGIC.len() + WORD)?;
+        let incidence_offsets = MAGIC.len() + 2 * WORD;
+        let incidences = array_end(incidence_offsets, node_count.checked_add(1))?;
+        let incidence_count = word_at(incidences - WORD)?;
+        let endpoints = array_end(incidences, Some(incidence_count))?;
+        let name_offsets = array_end(endpoints, edge_count.checked_mul(2))?;
+        let names = array_end(name_offsets, node_count.checked_add(1))?;
+        let name_len = word_at(names - WORD)?;
+
+        if names.checked_add(name_len) != Some(data.len()) {
+            return Err(invalid("compact graph file has the wrong length"));
+        }
+        if IndexType::max_value().to_usize().unwrap_or(usize::MAX) < node_count.max(edge_count) {
+            return Err(invalid("compact graph is too large for its index type"));
+        }
+
+        Ok(Self {
+            bytes,
+            node_count,
+            edge_count,
+            incidence_offsets,
+            incidences,
+            endpoints,
+            name_offsets,
+            names,
+            index_type: PhantomData,
+        })
+    }
+
+    /// Returns the bytes of the graph.
+    pub fn into_bytes(self) -> Bytes {
+        self.bytes
+    }
+
+    fn word(&self, array: usize, index: usize) -> usize {
+        let offset = array + index * WORD;
+        usize::from_ne_bytes(
+            self.bytes.as_ref()[offset..offset + WORD]
+                .try_into()
+                .unwrap(),
+        )
+    }
+}
+
+impl<Bytes: AsRef<[u8]>, IndexType: GraphIndexInteger> StaticGraph
+    for CompactGraph<Bytes, IndexType>
+{
+    type IndexType = IndexType;
+
+    type NodeIndex = CompactNodeIndex<IndexType>;
+
+    type EdgeIndex = CompactEdgeIndex<IndexType>;
+
+    fn node_indices(&self) -> impl Iterator<Item = Self::NodeIndex> {
+        (0..self.node_count).map(Into::into)
+    }
+
+    fn edge_indices(&self) -> impl Iterator<Item = Self::EdgeIndex> {
+        (0..self.edge_count).map(Into::into)
+    }
+
+    fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn node_name(&self, node_index: Self::NodeIndex) -> Cow<'_, str> {
+        let node_index = node_index.into_usize();
+        let start = self.names + self.word(self.name_offsets, node_index);
+        let end = self.names + self.word(self.name_offsets, node_index + 1);
+        String::from_utf8_lossy(&self.bytes.as_ref()[start..end])
+    }
+
+    fn incident_edges(&self, node: Self::NodeIndex) -> impl Iterator<Item = Self::EdgeIndex> {
+        let node = node.into_usize();
+        let start = self.word(self.incidence_offsets, node);
+        let end = self.word(self.incidence_offsets, node + 1);
+        (start..end).map(|incidence| self.word(self.incidences, incidence).into())
+    }
+
+    fn edge_endpoints(&self, edge: Self::EdgeIndex) -> (Self::NodeIndex, Self::NodeIndex) {
+        let edge = edge.into_usize();
+        (
+            self.word(self.endpoints, 2 * edge).into(),
+            self.word(self.endpoints, 2 * edge + 1).into(),
+        )
+    }
+
+    fn edges_between(
+        &self,
+        u: Self::NodeIndex,
+        v: Self::NodeIndex,
+    ) -> impl Iterator<Item = Self::EdgeIndex> {
+        self.incident_edges(u).filter(move |edge| {
+            let endpoints = self.edge_endpoints(*edge);
+            if u == v {
+                endpoints.0 == v && endpoints.1 == v
+            } else {
+                endpoints.0 == v || endpoints.1 == v
+            }
+        })
+    }
+}
+
+/// Writes the given graph in the compact graph format, see [`CompactGraph`].
+///
+/// Nodes and edges keep their indices, so a decomposition of `graph` can be transferred to the compact graph
+/// with [`SPQRDecomposition::remap`](crate::decomposition::SPQRDecomposition::remap).
+///
+/// # Panics
+///
+/// Panics if the node or edge indices of `graph` are not consecutive and in ascending order starting from zero.
+pub fn write_compact_graph<Graph: StaticGraph>(
+    graph: &Graph,
+    writer: impl Write,
+) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+
+    writer.write_all(&MAGIC)?;
+    write_word(&mut writer, graph.node_count())?;
+    write_word(&mut writer, graph.edge_count())?;
+
+    assert!(
+        graph
+            .node_indices()
+            .enumerate()
+            .all(|(position, node_index)| node_index.into() == position),
+        "node indices are not consecutive",
+    );
+    assert!(
+        graph
+            .edge_indices()
+            .enumerate()
+            .all(|(position, edge_index)| edge_index.into() == position),
+        "edge indices are not consecutive",
+    );
+
+    let mut incidence_count = 0;
+    write_word(&mut writer, 0)?;
+    for node_index in graph.node_indices() {
+        incidence_count += graph.incident_edges(node_index).count();
+        write_word(&mut writer, incidence_count)?;
+    }
+    for node_index in graph.node_indices() {
+        for edge_index in graph.incident_edges(node_index) {
+            write_word(&mut writer, edge_index.into())?;
+        }
+    }
+
+    for edge_index in graph.edge_indices() {
+        let (u, v) = graph.edge_endpoints(edge_index);
+        write_word(&mut writer, u.into())?;
+        write_word(&mut writer, v.into())?;
+    }
+
+    let mut name_len = 0;
+    write_word(&mut writer, 0)?;
+    for node_index in graph.node_indices() {
+        name_len += graph.node_name(node_index).len();
+        write_word(&mut writer, name_len)?;
+    }
+    for node_index in graph.node_indices() {
+        writer.write_all(graph.node_name(node_index).as_bytes())?;
+    }
+
+    writer.flush()
+}
+
+fn write_word(writer: &mut impl Write, word: usize) -> std::io::Result<()> {
+    writer.write_all(&word.to_ne_bytes())
+}
@@ -0,0 +1,57 @@
+use std::{fs::File, io::BufReader};
+
+use bidirected_adjacency_array::{
+    graph::BidirectedAdjacencyArray,
+    io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
+};
+use itertools::Itertools;
+
+use crate::graph::{
+    StaticGraph,
+    implementations::compact::{CompactGraph, write_compact_graph},
+};
+
+#[test]
+fn test_compact_graph() {
+    let graph = BidirectedAdjacencyArray::<u32, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/random5_10_False_0.gfa").unwrap()),
+    )
+    .unwrap();
+    let mut bytes = Vec::new();
+    write_compact_graph(&graph, &mut bytes).unwrap();
+    let compact = CompactGraph::<_, u32>::new(&bytes[..]).unwrap();
+
+    assert_eq!(compact.node_count(), graph.node_count());
+    assert_eq!(compact.edge_count(), graph.edge_count());
+    for node_index in graph.node_indices() {
+        let compact_node_index = node_index.into_usize().into();
+        assert_eq!(
+            compact.node_name(compact_node_index),
+            graph.node_name(node_index)
+        );
+        assert_eq!(
+            compact
+                .incident_edges(compact_node_index)
+                .map(usize::from)
+                .sorted()
+                .collect::<Vec<_>>(),
+            graph
+                .incident_edges(node_index)
+                .map(usize::from)
+                .sorted()
+                .collect::<Vec<_>>(),
+        );
+    }
+    for edge_index in graph.edge_indices() {
+        let (u, v) = graph.edge_endpoints(edge_index);
+        let (compact_u, compact_v) = compact.edge_endpoints(edge_index.into_usize().into());
+        assert_eq!(
+            (usize::from(compact_u), usize::from(compact_v)),
+            (u.into_usize(), v.into_usize())
+        );
+    }
+
+    for len in [0, 8, bytes.len() - 1] {
+        assert!(CompactGraph::<_, u32>::new(&bytes[..len]).is_err());
+    }
+}
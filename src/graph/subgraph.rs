@@ -0,0 +1,142 @@
+use std::borrow::Cow;
+
+use rustc_hash::FxHashMap;
+
+use crate::graph::{BidirectedGraph, Orientation, StaticGraph};
+
+/// A view of a subset of the nodes and edges of a graph.
+///
+/// The nodes and edges of the subgraph are numbered consecutively starting from zero.
+/// Use [`Subgraph::parent_node`] and [`Subgraph::parent_edge`] to translate them back to the parent graph.
+#[derive(Debug, Clone)]
+pub struct Subgraph<'graph, Graph: StaticGraph> {
+    parent: &'graph Graph,
+    nodes: Vec<Graph::NodeIndex>,
+    edges: Vec<Graph::EdgeIndex>,
+    node_map: FxHashMap<Graph::NodeIndex, Graph::NodeIndex>,
+    edge_map: FxHashMap<Graph::EdgeIndex, Graph::EdgeIndex>,
+}
+
+impl<'graph, Graph: StaticGraph> Subgraph<'graph, Graph> {
+    /// Creates a subgraph of `parent` with the given nodes and edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node or edge is given twice, or if an edge has an endpoint that is not part of the subgraph.
+    pub fn new(
+        parent: &'graph Graph,
+        nodes: impl IntoIterator<Item = Graph::NodeIndex>,
+        edges: impl IntoIterator<Item = Graph::EdgeIndex>,
+    ) -> Self {
+        let nodes = nodes.into_iter().collect::<Vec<_>>();
+        let edges = edges.into_iter().collect::<Vec<_>>();
+
+        let mut node_map = FxHashMap::default();
+        for (subgraph_node, &parent_node) in nodes.iter().enumerate() {
+            assert!(
+                node_map.insert(parent_node, subgraph_node.into()).is_none(),
+                "node {parent_node} is given twice",
+            );
+        }
+
+        let mut edge_map = FxHashMap::default();
+        for (subgraph_edge, &parent_edge) in edges.iter().enumerate() {
+            let (u, v) = parent.edge_endpoints(parent_edge);
+            assert!(
+                node_map.contains_key(&u) && node_map.contains_key(&v),
+                "edge {parent_edge} has an endpoint outside of the subgraph",
+            );
+            assert!(
+                edge_map.insert(parent_edge, subgraph_edge.into()).is_none(),
+                "edge {parent_edge} is given twice",
+            );
+        }
+
+        Self {
+            parent,
+            nodes,
+            edges,
+            node_map,
+            edge_map,
+        }
+    }
+
+    pub fn parent(&self) -> &'graph Graph {
+        self.parent
+    }
+
+    /// Returns the index of the given subgraph node in the parent graph.
+    pub fn parent_node(&self, node_index: Graph::NodeIndex) -> Graph::NodeIndex {
+        self.nodes[node_index.into()]
+    }
+
+    /// Returns the index of the given subgraph edge in the parent graph.
+    pub fn parent_edge(&self, edge_index: Graph::EdgeIndex) -> Graph::EdgeIndex {
+        self.edges[edge_index.into()]
+    }
+
+    /// Returns the index of the given parent graph node in the subgraph, or `None` if it is not part of the subgraph.
+    pub fn subgraph_node(&self, parent_node_index: Graph::NodeIndex) -> Option<Graph::NodeIndex> {
+        self.node_map.get(&parent_node_index).copied()
+    }
+
+    /// Returns the index of the given parent graph edge in the subgraph, or `None` if it is not part of the subgraph.
+    pub fn subgraph_edge(&self, parent_edge_index: Graph::EdgeIndex) -> Option<Graph::EdgeIndex> {
+        self.edge_map.get(&parent_edge_index).copied()
+    }
+}
+
+impl<'graph, Graph: StaticGraph> StaticGraph for Subgraph<'graph, Graph> {
+    type IndexType = Graph::IndexType;
+
+    type NodeIndex = Graph::NodeIndex;
+
+    type EdgeIndex = Graph::EdgeIndex;
+
+    fn node_indices(&self) -> impl Iterator<Item = Self::NodeIndex> {
+        (0..self.nodes.len()).map(Into::into)
+    }
+
+    fn edge_indices(&self) -> impl Iterator<Item = Self::EdgeIndex> {
+        (0..self.edges.len()).map(Into::into)
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn node_name(&self, node_index: Self::NodeIndex) -> Cow<'_, str> {
+        self.parent.node_name(self.parent_node(node_index))
+    }
+
+    fn incident_edges(&self, node: Self::NodeIndex) -> impl Iterator<Item = Self::EdgeIndex> {
+        self.parent
+            .incident_edges(self.parent_node(node))
+            .filter_map(|edge| self.subgraph_edge(edge))
+    }
+
+    fn edge_endpoints(&self, edge: Self::EdgeIndex) -> (Self::NodeIndex, Self::NodeIndex) {
+        let (u, v) = self.parent.edge_endpoints(self.parent_edge(edge));
+        (self.node_map[&u], self.node_map[&v])
+    }
+
+    fn edges_between(
+        &self,
+        u: Self::NodeIndex,
+        v: Self::NodeIndex,
+    ) -> impl Iterator<Item = Self::EdgeIndex> {
+        self.parent
+            .edges_between(self.parent_node(u), self.parent_node(v))
+            .filter_map(|edge| self.subgraph_edge(edge))
+    }
+}
+
+impl<'graph, Graph: BidirectedGraph> BidirectedGraph for Subgraph<'graph, Graph> {
+    fn edge_orientations(&self, edge: Self::EdgeIndex) -> (Orientation, Orientation) {
+        self.parent.edge_orientations(self.parent_edge(edge))
+    }
+}
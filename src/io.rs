@@ -1,3 +1,15 @@
 #[cfg(feature = "binary-io")]
 pub mod binary;
+#[cfg(feature = "dot-io")]
+pub mod dot;
+pub mod error;
+#[cfg(feature = "gfa-io")]
+pub mod gfa;
+#[cfg(feature = "ogdf-compare")]
+pub mod ogdf;
+#[cfg(feature = "plain-io")]
 pub mod plain_spqr_file;
+#[cfg(feature = "svg-io")]
+pub mod svg;
+#[cfg(feature = "binary-io")]
+pub mod workspace;
@@ -1,58 +1,140 @@
-use std::io::Read;
+use std::{
+    borrow::Borrow,
+    fs::File,
+    io::{BufReader, IoSlice, Read, Write},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    path::Path,
+};
 
-use smallvec::SmallVec;
+use tagged_vec::TaggedVec;
 
 use crate::{
     decomposition::{
         Block, Component, CutNode, SPQRDecomposition, SPQRDecompositionEdgeData,
-        SPQRDecompositionNodeData, SPQREdge, SPQRNode, SPQRNodeType, indices::GraphIndexInteger,
+        SPQRDecompositionNodeData, SPQREdge, SPQRNode, SPQRNodeType,
+        extra_data::{ExtraData, ExtraDataPool},
+        indices::GraphIndexInteger,
     },
     graph::StaticGraph,
+    io::error::FileReadError,
+    profile::Phase,
+    progress::ProgressSink,
 };
 
+mod append;
+#[cfg(feature = "parallel")]
+mod parallel;
+pub mod portable;
 #[cfg(test)]
 mod tests;
 
 impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
     /// Reads a bidirected adjacency array from a platform-dependent binary format.
-    pub fn read_binary(graph: &'graph Graph, mut reader: impl Read) -> std::io::Result<Self> {
-        let component_amount = read_usize_binary(&mut reader)?;
-        let components = (0..component_amount)
-            .map(|_| Component::read_binary(&mut reader))
-            .collect::<std::io::Result<_>>()?;
-
-        let block_amount = read_usize_binary(&mut reader)?;
-        let blocks = (0..block_amount)
-            .map(|_| Block::read_binary(&mut reader))
-            .collect::<std::io::Result<_>>()?;
-
-        let cut_node_amount = read_usize_binary(&mut reader)?;
-        let cut_nodes = (0..cut_node_amount)
-            .map(|_| CutNode::read_binary(&mut reader))
-            .collect::<std::io::Result<_>>()?;
-
-        let spqr_node_amount = read_usize_binary(&mut reader)?;
-        let spqr_nodes = (0..spqr_node_amount)
-            .map(|_| SPQRNode::read_binary(&mut reader))
-            .collect::<std::io::Result<_>>()?;
-
-        let spqr_edge_amount = read_usize_binary(&mut reader)?;
-        let spqr_edges = (0..spqr_edge_amount)
-            .map(|_| SPQREdge::read_binary(&mut reader))
-            .collect::<std::io::Result<_>>()?;
-
-        let node_data_amount = read_usize_binary(&mut reader)?;
-        let node_data = (0..node_data_amount)
-            .map(|_| SPQRDecompositionNodeData::read_binary(&mut reader))
-            .collect::<std::io::Result<_>>()?;
-
-        let edge_data_amount = read_usize_binary(&mut reader)?;
-        let edge_data = (0..edge_data_amount)
-            .map(|_| SPQRDecompositionEdgeData::read_binary(&mut reader))
-            .collect::<std::io::Result<_>>()?;
+    ///
+    /// The format starts with a [header](NATIVE_BINARY_MAGIC), followed by independent length-prefixed sections, one for each kind of entity.
+    /// Files without the header were written before the format was versioned, and are decoded in their old layout.
+    pub fn read_binary(graph: &'graph Graph, reader: impl Read) -> std::io::Result<Self> {
+        Self::read_binary_with_progress(graph, reader, &mut ())
+    }
 
-        Ok(Self {
+    /// Like [`read_binary`](Self::read_binary), but reports the amount of sections read and decoded to `progress`.
+    ///
+    /// Reading the sections is reported as [`Phase::Parse`] and decoding them as [`Phase::Build`].
+    /// Files in the old layout have no sections, so their decoding is reported as [`Phase::Parse`] without amounts.
+    pub fn read_binary_with_progress(
+        graph: &'graph Graph,
+        mut reader: impl Read,
+        progress: &mut impl ProgressSink,
+    ) -> std::io::Result<Self> {
+        if let Header::Legacy(prefix) = read_header(&mut reader)? {
+            progress.start_phase(Phase::Parse, None);
+            let spqr_decomposition = Self::read_legacy(graph, prefix, reader)?;
+            progress.finish_phase(Phase::Parse);
+            return Ok(spqr_decomposition);
+        }
+
+        progress.start_phase(Phase::Parse, Some(SECTION_COUNT));
+        let sections = read_sections(reader, progress)?;
+        progress.finish_phase(Phase::Parse);
+        Self::decode_sections(graph, sections, progress)
+    }
+
+    /// Reads an SPQR decomposition in the platform-dependent binary format from the file at `path`.
+    ///
+    /// Errors record the path, and errors while reading the sections also the amount of bytes read before the error occurred.
+    pub fn read_binary_from_path(
+        graph: &'graph Graph,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, FileReadError<std::io::Error>> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|error| FileReadError::new(path, error))?;
+        let mut reader = CountingReader {
+            reader: BufReader::new(file),
+            count: 0,
+        };
+        let sections = match read_header(&mut reader) {
+            Ok(Header::Current) => read_sections(&mut reader, &mut ()),
+            Ok(Header::Legacy(prefix)) => {
+                let result = Self::read_legacy(graph, prefix, &mut reader);
+                return result.map_err(|error| FileReadError {
+                    path: path.to_owned(),
+                    line: None,
+                    offset: Some(reader.count),
+                    source: error,
+                });
+            }
+            Err(error) => Err(error),
+        }
+        .map_err(|error| FileReadError {
+            path: path.to_owned(),
+            line: None,
+            offset: Some(reader.count),
+            source: error,
+        })?;
+        Self::decode_sections(graph, sections, &mut ())
+            .map_err(|error| FileReadError::new(path, error))
+    }
+
+    /// Decodes the sections read by [`read_sections`], reporting each decoded section as [`Phase::Build`].
+    fn decode_sections(
+        graph: &'graph Graph,
+        sections: [Vec<u8>; SECTION_COUNT],
+        progress: &mut impl ProgressSink,
+    ) -> std::io::Result<Self> {
+        let [
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+        ] = sections;
+        progress.start_phase(Phase::Build, Some(SECTION_COUNT));
+        let pool = &mut ExtraDataPool::new();
+        let mut decoded = 0;
+        let mut advance = || {
+            decoded += 1;
+            progress.advance(decoded);
+        };
+        let components = decode_section(&components, pool)?;
+        advance();
+        let blocks = decode_section(&blocks, pool)?;
+        advance();
+        let cut_nodes = decode_section(&cut_nodes, pool)?;
+        advance();
+        let spqr_nodes = decode_section(&spqr_nodes, pool)?;
+        advance();
+        let spqr_edges = decode_section(&spqr_edges, pool)?;
+        advance();
+        let node_data = decode_section(&node_data, pool)?;
+        advance();
+        let edge_data = decode_section(&edge_data, pool)?;
+        advance();
+        let spqr_decomposition = Self {
             graph,
+            graph_lifetime: PhantomData,
             components,
             blocks,
             cut_nodes,
@@ -60,58 +142,403 @@ impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
             spqr_edges,
             node_data,
             edge_data,
-        })
+            cache: Default::default(),
+        };
+        progress.finish_phase(Phase::Build);
+        Ok(spqr_decomposition)
     }
 
-    /// Writes the bidirected adjacency array into a platform-dependent binary format.
-    pub fn write_binary(&self, mut writer: impl std::io::Write) -> std::io::Result<()> {
-        write_usize_binary(self.components.len(), &mut writer)?;
-        for component in self.components.iter_values() {
-            component.write_binary(&mut writer)?;
-        }
+    /// Reads an SPQR decomposition from either the [portable](Self::read_portable_binary) or the platform-dependent binary format.
+    ///
+    /// Files starting with the [header](NATIVE_BINARY_MAGIC) of the platform-dependent format are decoded as such.
+    /// Files starting with the [version byte](portable::PORTABLE_BINARY_VERSION) of the portable format are decoded as portable files,
+    /// and all other files, including those that fail to decode as portable files,
+    /// in the layout that the platform-dependent format had before it was versioned.
+    /// This keeps files written by older versions of [`write_binary`](SPQRDecomposition::write_binary) loadable.
+    pub fn read_any_binary(graph: &'graph Graph, mut reader: impl Read) -> std::io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::read_any_binary_from_slice(graph, &bytes)
+    }
 
-        write_usize_binary(self.blocks.len(), &mut writer)?;
-        for block in self.blocks.iter_values() {
-            block.write_binary(&mut writer)?;
+    /// Like [`read_any_binary`](Self::read_any_binary), but decodes the decomposition from bytes in memory.
+    ///
+    /// If a file starts with the version byte of the portable format, but decodes as neither format, the error of the portable format is returned.
+    pub fn read_any_binary_from_slice(graph: &'graph Graph, bytes: &[u8]) -> std::io::Result<Self> {
+        if has_header(bytes)? {
+            return Self::read_binary_from_slice(graph, bytes);
+        }
+        if bytes.first() != Some(&portable::PORTABLE_BINARY_VERSION) {
+            return Self::decode_legacy(graph, bytes);
         }
 
-        write_usize_binary(self.cut_nodes.len(), &mut writer)?;
-        for cut_node in self.cut_nodes.iter_values() {
-            cut_node.write_binary(&mut writer)?;
+        // The first byte of a file without header is part of the amount of components, so it may equal the version byte by chance.
+        match Self::read_portable_binary_from_slice(graph, bytes) {
+            Ok(spqr_decomposition) => Ok(spqr_decomposition),
+            Err(error) => Self::decode_legacy(graph, bytes).map_err(|_| error),
         }
+    }
 
-        write_usize_binary(self.spqr_nodes.len(), &mut writer)?;
-        for spqr_node in self.spqr_nodes.iter_values() {
-            spqr_node.write_binary(&mut writer)?;
+    /// Reads an SPQR decomposition from a platform-dependent binary format stored in memory, e.g. in a memory-mapped file.
+    ///
+    /// The format is the same as for [`read_binary`](Self::read_binary),
+    /// but the sections are decoded directly from `bytes` instead of being copied into buffers first.
+    pub fn read_binary_from_slice(graph: &'graph Graph, bytes: &[u8]) -> std::io::Result<Self> {
+        if !has_header(bytes)? {
+            return Self::decode_legacy(graph, bytes);
         }
 
-        write_usize_binary(self.spqr_edges.len(), &mut writer)?;
-        for spqr_edge in self.spqr_edges.iter_values() {
-            spqr_edge.write_binary(&mut writer)?;
+        let [
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+        ] = split_sections(&bytes[HEADER_LEN..])?;
+        let pool = &mut ExtraDataPool::new();
+
+        Ok(Self {
+            graph,
+            graph_lifetime: PhantomData,
+            components: decode_section(components, pool)?,
+            blocks: decode_section(blocks, pool)?,
+            cut_nodes: decode_section(cut_nodes, pool)?,
+            spqr_nodes: decode_section(spqr_nodes, pool)?,
+            spqr_edges: decode_section(spqr_edges, pool)?,
+            node_data: decode_section(node_data, pool)?,
+            edge_data: decode_section(edge_data, pool)?,
+            cache: Default::default(),
+        })
+    }
+
+    /// Reads the rest of a file without header into memory and decodes it with [`decode_legacy`](Self::decode_legacy).
+    ///
+    /// The `prefix` are the bytes already read while looking for the header.
+    fn read_legacy(
+        graph: &'graph Graph,
+        mut prefix: Vec<u8>,
+        mut reader: impl Read,
+    ) -> std::io::Result<Self> {
+        reader.read_to_end(&mut prefix)?;
+        Self::decode_legacy(graph, &prefix)
+    }
+
+    /// Decodes the layout written before the format was versioned.
+    ///
+    /// It has neither a header nor sections, the entities of each kind are only prefixed by their amount,
+    /// and only nodes and edges carry extra data.
+    /// Since it has no sections, it is decoded from memory, such that corrupt lengths are detected before allocating.
+    fn decode_legacy(graph: &'graph Graph, mut bytes: &[u8]) -> std::io::Result<Self> {
+        let source = &mut bytes;
+        let pool = &mut ExtraDataPool::new();
+
+        let spqr_decomposition = Self {
+            graph,
+            graph_lifetime: PhantomData,
+            components: decode_legacy_entities(source, |source| {
+                Component::decode_from(source, pool, Layout::Legacy)
+            })?,
+            blocks: decode_legacy_entities(source, |source| {
+                Block::decode_from(source, pool, Layout::Legacy)
+            })?,
+            cut_nodes: decode_legacy_entities(source, |source| {
+                CutNode::decode_from(source, pool, Layout::Legacy)
+            })?,
+            spqr_nodes: decode_legacy_entities(source, |source| {
+                SPQRNode::decode_from(source, pool, Layout::Legacy)
+            })?,
+            spqr_edges: decode_legacy_entities(source, SPQREdge::decode_from)?,
+            node_data: decode_legacy_entities(source, |source| {
+                SPQRDecompositionNodeData::decode_from(source, pool)
+            })?,
+            edge_data: decode_legacy_entities(source, |source| {
+                SPQRDecompositionEdgeData::decode_from(source, pool)
+            })?,
+            cache: Default::default(),
+        };
+
+        if source.is_empty() {
+            Ok(spqr_decomposition)
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "file contains trailing bytes",
+            ))
         }
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Writes the bidirected adjacency array into a platform-dependent binary format.
+    ///
+    /// The format starts with a [header](NATIVE_BINARY_MAGIC), followed by independent length-prefixed sections, one for each kind of entity.
+    pub fn write_binary(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let sections = [
+            encode_section(self.components.iter_values())?,
+            encode_section(self.blocks.iter_values())?,
+            encode_section(self.cut_nodes.iter_values())?,
+            encode_section(self.spqr_nodes.iter_values())?,
+            encode_section(self.spqr_edges.iter_values())?,
+            encode_section(self.node_data.iter_values())?,
+            encode_section(self.edge_data.iter_values())?,
+        ];
+
+        write_header(&mut writer)?;
+        write_sections(&sections, writer)
+    }
+}
+
+/// The magic bytes at the start of a file in the platform-dependent binary format, followed by the [`NATIVE_BINARY_VERSION`].
+pub const NATIVE_BINARY_MAGIC: [u8; 7] = *b"SPQRBIN";
+
+/// The version of the platform-dependent binary format.
+///
+/// Version 1 consists of length-prefixed sections, and stores extra data for all entities.
+/// Files without the [`NATIVE_BINARY_MAGIC`] were written before the format was versioned,
+/// and are still read by [`read_binary`](SPQRDecomposition::read_binary).
+pub const NATIVE_BINARY_VERSION: u8 = 1;
+
+/// The length in bytes of the magic and the version.
+const HEADER_LEN: usize = NATIVE_BINARY_MAGIC.len() + 1;
+
+/// The amount of sections in the binary format.
+const SECTION_COUNT: usize = 7;
+
+/// The layouts of the entities in the platform-dependent binary format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// The layout before the format was versioned, where only nodes and edges carry extra data.
+    Legacy,
+    /// The layout of the [`NATIVE_BINARY_VERSION`].
+    Current,
+}
+
+/// The start of a file in the platform-dependent binary format.
+enum Header {
+    /// The file has a header with the current version.
+    Current,
+    /// The file has no header, and the given bytes were read while looking for it.
+    Legacy(Vec<u8>),
+}
+
+/// Reads the header, or up to as many bytes as it has if the file has no header.
+fn read_header(reader: &mut impl Read) -> std::io::Result<Header> {
+    let mut prefix = Vec::with_capacity(HEADER_LEN);
+    reader.take(HEADER_LEN as u64).read_to_end(&mut prefix)?;
+    Ok(if has_header(&prefix)? {
+        Header::Current
+    } else {
+        Header::Legacy(prefix)
+    })
+}
+
+fn write_header(writer: &mut impl Write) -> std::io::Result<()> {
+    writer.write_all(&NATIVE_BINARY_MAGIC)?;
+    writer.write_all(&[NATIVE_BINARY_VERSION])
+}
+
+/// Returns true if `bytes` start with a header with the current version, and false if they have no header.
+fn has_header(bytes: &[u8]) -> std::io::Result<bool> {
+    let Some(version) = bytes.strip_prefix(&NATIVE_BINARY_MAGIC) else {
+        return Ok(false);
+    };
+    match version.first() {
+        Some(&NATIVE_BINARY_VERSION) => Ok(true),
+        Some(version) => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("unsupported binary version {version}"),
+        )),
+        None => Err(std::io::ErrorKind::UnexpectedEof.into()),
+    }
+}
 
-        write_usize_binary(self.node_data.len(), &mut writer)?;
-        for node_data in self.node_data.iter_values() {
-            node_data.write_binary(&mut writer)?;
+/// Decodes the entities of one kind in the [legacy layout](Layout::Legacy), prefixed by their amount.
+fn decode_legacy_entities<Index, Item, Source: BinarySource>(
+    source: &mut Source,
+    mut decode: impl FnMut(&mut Source) -> std::io::Result<Item>,
+) -> std::io::Result<TaggedVec<Index, Item>> {
+    let amount: usize = read_value(source)?;
+    // Every item takes at least one byte, so larger amounts are corrupt and must not be preallocated.
+    source.ensure_available(amount)?;
+    let mut items = Vec::new();
+    for _ in 0..amount {
+        items.push(decode(source)?);
+    }
+    Ok(items.into())
+}
+
+/// An item that is stored in a section of the binary format.
+trait SectionItem: Sized {
+    fn decode(reader: &mut &[u8], pool: &mut ExtraDataPool) -> std::io::Result<Self>;
+
+    fn encode(&self, section: &mut Vec<u8>) -> std::io::Result<()>;
+}
+
+macro_rules! impl_section_item {
+    ($type:ty, ($($layout:expr)?), $($generics:tt)*) => {
+        impl<$($generics)*> SectionItem for $type {
+            fn decode(reader: &mut &[u8], pool: &mut ExtraDataPool) -> std::io::Result<Self> {
+                Self::decode_from(reader, pool $(, $layout)?)
+            }
+
+            fn encode(&self, section: &mut Vec<u8>) -> std::io::Result<()> {
+                self.write_binary(section)
+            }
         }
+    };
+}
 
-        write_usize_binary(self.edge_data.len(), &mut writer)?;
-        for edge_data in self.edge_data.iter_values() {
-            edge_data.write_binary(&mut writer)?;
+impl_section_item!(Component<NodeIndex, EdgeIndex, IndexType>, (Layout::Current), NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy);
+impl_section_item!(Block<NodeIndex, EdgeIndex, IndexType>, (Layout::Current), NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy);
+impl_section_item!(CutNode<NodeIndex, IndexType>, (Layout::Current), NodeIndex: Copy, IndexType: Copy);
+impl_section_item!(SPQRNode<NodeIndex, EdgeIndex, IndexType>, (Layout::Current), NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy);
+impl_section_item!(SPQRDecompositionNodeData<IndexType>, (), IndexType: GraphIndexInteger);
+impl_section_item!(SPQRDecompositionEdgeData<IndexType>, (), IndexType: GraphIndexInteger);
+
+impl<NodeIndex: Copy, IndexType: Copy> SectionItem for SPQREdge<NodeIndex, IndexType> {
+    fn decode(reader: &mut &[u8], _pool: &mut ExtraDataPool) -> std::io::Result<Self> {
+        Self::decode_from(reader)
+    }
+
+    fn encode(&self, section: &mut Vec<u8>) -> std::io::Result<()> {
+        self.write_binary(section)
+    }
+}
+
+/// Encodes the amount of items followed by the items themselves into a section.
+fn encode_section<'item, Item: SectionItem + 'item>(
+    items: impl ExactSizeIterator<Item = &'item Item>,
+) -> std::io::Result<Vec<u8>> {
+    let mut section = Vec::new();
+    write_usize_binary(items.len(), &mut section)?;
+    for item in items {
+        item.encode(&mut section)?;
+    }
+    Ok(section)
+}
+
+/// Decodes a section created by [`encode_section`].
+///
+/// Identical extra data strings share their allocation via the given pool.
+fn decode_section<Index, Item: SectionItem>(
+    mut section: &[u8],
+    pool: &mut ExtraDataPool,
+) -> std::io::Result<TaggedVec<Index, Item>> {
+    let amount: usize = read_value(&mut section)?;
+    // Every item takes at least one byte, so larger amounts are corrupt and must not be preallocated.
+    if amount > section.len() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "section contains more items than bytes",
+        ));
+    }
+    let collection = (0..amount)
+        .map(|_| Item::decode(&mut section, pool))
+        .collect::<std::io::Result<_>>()?;
+
+    if section.is_empty() {
+        Ok(collection)
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "section contains trailing bytes",
+        ))
+    }
+}
+
+/// Reads all sections, each prefixed by its length in bytes.
+fn read_sections(
+    mut reader: impl Read,
+    progress: &mut impl ProgressSink,
+) -> std::io::Result<[Vec<u8>; SECTION_COUNT]> {
+    let mut sections: [Vec<u8>; SECTION_COUNT] = Default::default();
+    for (section_index, section) in sections.iter_mut().enumerate() {
+        let len: usize = read_value(&mut ReaderSource(&mut reader))?;
+        (&mut reader).take(len as u64).read_to_end(section)?;
+        if section.len() != len {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
         }
+        progress.advance(section_index + 1);
+    }
+    Ok(sections)
+}
 
-        Ok(())
+/// A reader that counts the bytes read from it.
+struct CountingReader<Reader> {
+    reader: Reader,
+    count: u64,
+}
+
+impl<Reader: Read> Read for CountingReader<Reader> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.count += read as u64;
+        Ok(read)
     }
 }
 
+/// Splits in-memory bytes into all sections, each prefixed by its length in bytes.
+fn split_sections(mut bytes: &[u8]) -> std::io::Result<[&[u8]; SECTION_COUNT]> {
+    let mut sections: [&[u8]; SECTION_COUNT] = Default::default();
+    for section in &mut sections {
+        let len: usize = read_value(&mut bytes)?;
+        (*section, bytes) = bytes
+            .split_at_checked(len)
+            .ok_or(std::io::ErrorKind::UnexpectedEof)?;
+    }
+    Ok(sections)
+}
+
+/// Writes all sections, each prefixed by its length in bytes, using vectored IO.
+fn write_sections(sections: &[Vec<u8>], mut writer: impl Write) -> std::io::Result<()> {
+    let lens = sections
+        .iter()
+        .map(|section| section.len().to_ne_bytes())
+        .collect::<Vec<_>>();
+    let mut slices = lens
+        .iter()
+        .zip(sections)
+        .flat_map(|(len, section)| [IoSlice::new(len), IoSlice::new(section)])
+        .collect::<Vec<_>>();
+    let mut slices = &mut slices[..];
+
+    while !slices.is_empty() {
+        match writer.write_vectored(slices) {
+            Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+            Ok(written) => IoSlice::advance_slices(&mut slices, written),
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(())
+}
+
 impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> Component<NodeIndex, EdgeIndex, IndexType> {
     /// Reads from a platform-dependent binary format.
-    pub fn read_binary(mut reader: impl Read) -> std::io::Result<Self> {
+    pub fn read_binary(reader: impl Read) -> std::io::Result<Self> {
+        Self::decode_from(
+            &mut ReaderSource(reader),
+            &mut ExtraDataPool::new(),
+            Layout::Current,
+        )
+    }
+
+    fn decode_from(
+        source: &mut impl BinarySource,
+        pool: &mut ExtraDataPool,
+        layout: Layout,
+    ) -> std::io::Result<Self> {
         Ok(Self {
-            nodes: read_vec_binary(&mut reader)?,
-            edges: read_vec_binary(&mut reader)?,
-            blocks: read_vec_binary(&mut reader)?,
-            cut_nodes: read_vec_binary(&mut reader)?,
+            nodes: read_vec(source)?,
+            edges: read_vec(source)?,
+            blocks: read_vec(source)?,
+            cut_nodes: read_vec(source)?,
+            extra_data: read_entity_extra_data(source, pool, layout)?,
         })
     }
 
@@ -121,20 +548,34 @@ impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> Component<NodeIndex, Edg
         write_slice_binary(&self.edges, &mut writer)?;
         write_slice_binary(&self.blocks, &mut writer)?;
         write_slice_binary(&self.cut_nodes, &mut writer)?;
+        write_str_binary(&self.extra_data, &mut writer)?;
         Ok(())
     }
 }
 
 impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> Block<NodeIndex, EdgeIndex, IndexType> {
     /// Reads from a platform-dependent binary format.
-    pub fn read_binary(mut reader: impl Read) -> std::io::Result<Self> {
+    pub fn read_binary(reader: impl Read) -> std::io::Result<Self> {
+        Self::decode_from(
+            &mut ReaderSource(reader),
+            &mut ExtraDataPool::new(),
+            Layout::Current,
+        )
+    }
+
+    fn decode_from(
+        source: &mut impl BinarySource,
+        pool: &mut ExtraDataPool,
+        layout: Layout,
+    ) -> std::io::Result<Self> {
         Ok(Self {
-            component: read_binary(&mut reader)?,
-            nodes: read_vec_binary(&mut reader)?,
-            cut_nodes: read_vec_binary(&mut reader)?,
-            edges: read_vec_binary(&mut reader)?,
-            spqr_nodes: read_vec_binary(&mut reader)?,
-            spqr_edges: read_vec_binary(&mut reader)?,
+            component: read_value(source)?,
+            nodes: read_vec(source)?,
+            cut_nodes: read_vec(source)?,
+            edges: read_vec(source)?,
+            spqr_nodes: read_vec(source)?,
+            spqr_edges: read_vec(source)?,
+            extra_data: read_entity_extra_data(source, pool, layout)?,
         })
     }
 
@@ -146,17 +587,31 @@ impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> Block<NodeIndex, EdgeInd
         write_slice_binary(&self.edges, &mut writer)?;
         write_slice_binary(&self.spqr_nodes, &mut writer)?;
         write_slice_binary(&self.spqr_edges, &mut writer)?;
+        write_str_binary(&self.extra_data, &mut writer)?;
         Ok(())
     }
 }
 
 impl<NodeIndex: Copy, IndexType: Copy> CutNode<NodeIndex, IndexType> {
     /// Reads from a platform-dependent binary format.
-    pub fn read_binary(mut reader: impl Read) -> std::io::Result<Self> {
+    pub fn read_binary(reader: impl Read) -> std::io::Result<Self> {
+        Self::decode_from(
+            &mut ReaderSource(reader),
+            &mut ExtraDataPool::new(),
+            Layout::Current,
+        )
+    }
+
+    fn decode_from(
+        source: &mut impl BinarySource,
+        pool: &mut ExtraDataPool,
+        layout: Layout,
+    ) -> std::io::Result<Self> {
         Ok(Self {
-            component: read_binary(&mut reader)?,
-            node: read_binary(&mut reader)?,
-            adjacent_blocks: read_vec_binary(&mut reader)?.into(),
+            component: read_value(source)?,
+            node: read_value(source)?,
+            adjacent_blocks: read_vec(source)?.into(),
+            extra_data: read_entity_extra_data(source, pool, layout)?,
         })
     }
 
@@ -165,19 +620,33 @@ impl<NodeIndex: Copy, IndexType: Copy> CutNode<NodeIndex, IndexType> {
         write_binary(&self.component, &mut writer)?;
         write_binary(&self.node, &mut writer)?;
         write_slice_binary(&self.adjacent_blocks, &mut writer)?;
+        write_str_binary(&self.extra_data, &mut writer)?;
         Ok(())
     }
 }
 
 impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> SPQRNode<NodeIndex, EdgeIndex, IndexType> {
     /// Reads from a platform-dependent binary format.
-    pub fn read_binary(mut reader: impl Read) -> std::io::Result<Self> {
+    pub fn read_binary(reader: impl Read) -> std::io::Result<Self> {
+        Self::decode_from(
+            &mut ReaderSource(reader),
+            &mut ExtraDataPool::new(),
+            Layout::Current,
+        )
+    }
+
+    fn decode_from(
+        source: &mut impl BinarySource,
+        pool: &mut ExtraDataPool,
+        layout: Layout,
+    ) -> std::io::Result<Self> {
         Ok(Self {
-            block: read_binary(&mut reader)?,
-            nodes: read_vec_binary(&mut reader)?,
-            edges: read_vec_binary(&mut reader)?,
-            spqr_node_type: SPQRNodeType::read_binary(&mut reader)?,
-            spqr_edges: read_vec_binary(&mut reader)?.into(),
+            block: read_value(source)?,
+            nodes: read_vec(source)?,
+            edges: read_vec(source)?,
+            spqr_node_type: SPQRNodeType::decode_from(source)?,
+            spqr_edges: read_vec(source)?.into(),
+            extra_data: read_entity_extra_data(source, pool, layout)?,
         })
     }
 
@@ -188,20 +657,26 @@ impl<NodeIndex: Copy, EdgeIndex: Copy, IndexType: Copy> SPQRNode<NodeIndex, Edge
         write_slice_binary(&self.edges, &mut writer)?;
         self.spqr_node_type.write_binary(&mut writer)?;
         write_slice_binary(&self.spqr_edges, &mut writer)?;
+        write_str_binary(&self.extra_data, &mut writer)?;
         Ok(())
     }
 }
 
 impl SPQRNodeType {
     /// Reads from a platform-dependent binary format.
-    pub fn read_binary(mut reader: impl Read) -> std::io::Result<Self> {
-        let mut byte = [0u8; 1];
-        reader.read_exact(&mut byte)?;
-        match byte[0] {
+    pub fn read_binary(reader: impl Read) -> std::io::Result<Self> {
+        Self::decode_from(&mut ReaderSource(reader))
+    }
+
+    fn decode_from(source: &mut impl BinarySource) -> std::io::Result<Self> {
+        match read_value::<u8>(source)? {
             0 => Ok(Self::SNode),
             1 => Ok(Self::PNode),
             2 => Ok(Self::RNode),
-            _ => panic!(),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "invalid SPQR node type",
+            )),
         }
     }
 
@@ -219,10 +694,14 @@ impl SPQRNodeType {
 
 impl<NodeIndex: Copy, IndexType: Copy> SPQREdge<NodeIndex, IndexType> {
     /// Reads from a platform-dependent binary format.
-    pub fn read_binary(mut reader: impl Read) -> std::io::Result<Self> {
+    pub fn read_binary(reader: impl Read) -> std::io::Result<Self> {
+        Self::decode_from(&mut ReaderSource(reader))
+    }
+
+    fn decode_from(source: &mut impl BinarySource) -> std::io::Result<Self> {
         Ok(Self {
-            endpoints: read_binary(&mut reader)?,
-            virtual_edge: read_binary(&mut reader)?,
+            endpoints: read_value(source)?,
+            virtual_edge: read_value(source)?,
         })
     }
 
@@ -236,13 +715,16 @@ impl<NodeIndex: Copy, IndexType: Copy> SPQREdge<NodeIndex, IndexType> {
 
 impl<IndexType: GraphIndexInteger> SPQRDecompositionNodeData<IndexType> {
     /// Reads from a platform-dependent binary format.
-    pub fn read_binary(mut reader: impl Read) -> std::io::Result<Self> {
+    fn decode_from(
+        source: &mut impl BinarySource,
+        pool: &mut ExtraDataPool,
+    ) -> std::io::Result<Self> {
         Ok(Self {
-            component_index: read_binary(&mut reader)?,
-            block_indices: read_vec_binary(&mut reader)?.into(),
-            cut_node_index: read_binary(&mut reader)?,
-            spqr_node_indices: read_vec_binary(&mut reader)?.into(),
-            extra_data: read_string_binary(&mut reader)?,
+            component_index: read_value(source)?,
+            block_indices: read_vec(source)?.into(),
+            cut_node_index: read_value(source)?,
+            spqr_node_indices: read_vec(source)?.into(),
+            extra_data: read_extra_data(source, pool)?,
         })
     }
 
@@ -259,12 +741,15 @@ impl<IndexType: GraphIndexInteger> SPQRDecompositionNodeData<IndexType> {
 
 impl<IndexType: GraphIndexInteger> SPQRDecompositionEdgeData<IndexType> {
     /// Reads from a platform-dependent binary format.
-    pub fn read_binary(mut reader: impl Read) -> std::io::Result<Self> {
+    fn decode_from(
+        source: &mut impl BinarySource,
+        pool: &mut ExtraDataPool,
+    ) -> std::io::Result<Self> {
         Ok(Self {
-            component_index: read_binary(&mut reader)?,
-            block_index: read_binary(&mut reader)?,
-            spqr_node_index: read_binary(&mut reader)?,
-            extra_data: read_string_binary(&mut reader)?,
+            component_index: read_value(source)?,
+            block_index: read_value(source)?,
+            spqr_node_index: read_value(source)?,
+            extra_data: read_extra_data(source, pool)?,
         })
     }
 
@@ -278,18 +763,73 @@ impl<IndexType: GraphIndexInteger> SPQRDecompositionEdgeData<IndexType> {
     }
 }
 
-fn read_vec_binary<T: Copy>(mut reader: impl Read) -> std::io::Result<Vec<T>> {
-    let mut len_bytes = [0u8; std::mem::size_of::<usize>()];
-    reader.read_exact(&mut len_bytes)?;
-    let len = usize::from_ne_bytes(len_bytes);
+/// A source of bytes to decode the binary format from.
+///
+/// In-memory sections are decoded directly from their slices,
+/// such that each scalar or slice costs a bounds check and a copy rather than a call into a reader.
+trait BinarySource {
+    /// Fills `bytes` completely from the source.
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> std::io::Result<()>;
+
+    /// Returns an error if it is known that fewer than `len` bytes are left.
+    ///
+    /// This rejects corrupt lengths before memory is allocated for them.
+    fn ensure_available(&self, len: usize) -> std::io::Result<()>;
+}
+
+/// Decodes from an arbitrary reader, whose remaining length is unknown.
+struct ReaderSource<Reader>(Reader);
+
+impl<Reader: Read> BinarySource for ReaderSource<Reader> {
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> std::io::Result<()> {
+        self.0.read_exact(bytes)
+    }
+
+    fn ensure_available(&self, _len: usize) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl BinarySource for &[u8] {
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> std::io::Result<()> {
+        let (head, tail) = self
+            .split_at_checked(bytes.len())
+            .ok_or(std::io::ErrorKind::UnexpectedEof)?;
+        bytes.copy_from_slice(head);
+        *self = tail;
+        Ok(())
+    }
+
+    fn ensure_available(&self, len: usize) -> std::io::Result<()> {
+        if self.len() < len {
+            Err(std::io::ErrorKind::UnexpectedEof.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn read_value<T: Copy>(source: &mut impl BinarySource) -> std::io::Result<T> {
+    let mut value = MaybeUninit::<T>::uninit();
+    unsafe {
+        let value_bytes =
+            std::slice::from_raw_parts_mut(value.as_mut_ptr() as *mut u8, std::mem::size_of::<T>());
+        source.read_bytes(value_bytes)?;
+        Ok(value.assume_init())
+    }
+}
+
+fn read_vec<T: Copy>(source: &mut impl BinarySource) -> std::io::Result<Vec<T>> {
+    let len: usize = read_value(source)?;
+    let byte_len = len.checked_mul(std::mem::size_of::<T>()).ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "slice length overflows")
+    })?;
+    source.ensure_available(byte_len)?;
 
     let mut vec = Vec::with_capacity(len);
     unsafe {
-        let vec_bytes = std::slice::from_raw_parts_mut(
-            vec.as_mut_ptr() as *mut u8,
-            len * std::mem::size_of::<T>(),
-        );
-        reader.read_exact(vec_bytes)?;
+        let vec_bytes = std::slice::from_raw_parts_mut(vec.as_mut_ptr() as *mut u8, byte_len);
+        source.read_bytes(vec_bytes)?;
         vec.set_len(len);
     }
 
@@ -310,19 +850,30 @@ fn write_slice_binary<T: Copy>(
     Ok(())
 }
 
-fn read_string_binary(mut reader: impl Read) -> std::io::Result<String> {
-    let bytes = read_vec_binary(&mut reader)?;
-    Ok(String::from_utf8(bytes).unwrap())
+fn read_extra_data(
+    source: &mut impl BinarySource,
+    pool: &mut ExtraDataPool,
+) -> std::io::Result<ExtraData> {
+    let bytes = read_vec(source)?;
+    let string = std::str::from_utf8(&bytes)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+    Ok(pool.intern(string))
 }
 
-fn write_str_binary(s: &str, mut writer: impl std::io::Write) -> std::io::Result<()> {
-    write_slice_binary(s.as_bytes(), &mut writer)
+/// Reads the extra data of a component, block, cut node or SPQR node, which only exists in the [current layout](Layout::Current).
+fn read_entity_extra_data(
+    source: &mut impl BinarySource,
+    pool: &mut ExtraDataPool,
+    layout: Layout,
+) -> std::io::Result<ExtraData> {
+    match layout {
+        Layout::Legacy => Ok(ExtraData::default()),
+        Layout::Current => read_extra_data(source, pool),
+    }
 }
 
-fn read_usize_binary(mut reader: impl Read) -> std::io::Result<usize> {
-    let mut bytes = [0u8; std::mem::size_of::<usize>()];
-    reader.read_exact(&mut bytes)?;
-    Ok(usize::from_ne_bytes(bytes))
+fn write_str_binary(s: &str, mut writer: impl std::io::Write) -> std::io::Result<()> {
+    write_slice_binary(s.as_bytes(), &mut writer)
 }
 
 fn write_usize_binary(value: usize, mut writer: impl std::io::Write) -> std::io::Result<()> {
@@ -330,17 +881,6 @@ fn write_usize_binary(value: usize, mut writer: impl std::io::Write) -> std::io:
     Ok(())
 }
 
-fn read_binary<T: Copy>(mut reader: impl Read) -> std::io::Result<T> {
-    let mut vec = SmallVec::<[T; 1]>::new();
-    unsafe {
-        vec.set_len(1);
-        let vec_bytes =
-            std::slice::from_raw_parts_mut(vec.as_mut_ptr() as *mut u8, std::mem::size_of::<T>());
-        reader.read_exact(vec_bytes)?;
-    }
-    Ok(vec.into_iter().next().unwrap())
-}
-
 fn write_binary<T: Copy>(value: &T, mut writer: impl std::io::Write) -> std::io::Result<()> {
     let value_bytes = unsafe {
         std::slice::from_raw_parts(value as *const T as *const u8, std::mem::size_of::<T>())
@@ -0,0 +1,399 @@
+use std::{
+    borrow::Borrow,
+    io::{Read, Seek, SeekFrom, Write},
+    marker::PhantomData,
+};
+
+use tagged_vec::TaggedVec;
+
+use crate::{
+    decomposition::{
+        Block, Component, CutNode, SPQRDecomposition, SPQRDecompositionEdgeData,
+        SPQRDecompositionNodeData, SPQREdge, SPQRNode,
+        extra_data::ExtraDataPool,
+        indices::{
+            ComponentIndex, GraphIndex, OptionalBlockIndex, OptionalCutNodeIndex,
+            OptionalSPQRNodeIndex,
+        },
+    },
+    graph::StaticGraph,
+    io::binary::{
+        SectionItem, decode_section, encode_section, read_sections, read_value, write_binary,
+        write_sections, write_usize_binary,
+    },
+};
+
+/// Marks the end of an appendable binary file, followed by the [`APPENDABLE_BINARY_VERSION`].
+const MAGIC: [u8; 7] = *b"SPQRAPN";
+
+/// The version of the appendable binary format, which changes with the layout of the segments or of the table of contents.
+const APPENDABLE_BINARY_VERSION: u8 = 1;
+
+/// The length in bytes of the trailer of an appendable binary file,
+/// consisting of the offset of the table of contents, the [`MAGIC`] and the [`APPENDABLE_BINARY_VERSION`].
+const TRAILER_LEN: usize = size_of::<usize>() + MAGIC.len() + 1;
+
+impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
+    /// Reads a decomposition from the appendable binary format written by [`append_binary`](Self::append_binary).
+    ///
+    /// The segments of the file are concatenated in the order they were appended.
+    /// Together, they need to cover every node and edge of the graph exactly once.
+    pub fn read_appendable_binary(
+        graph: &'graph Graph,
+        mut reader: impl Read + Seek,
+    ) -> std::io::Result<Self> {
+        let table_of_contents = read_table_of_contents(&mut reader)?.ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "file has no segments")
+        })?;
+
+        let mut decomposition = Self {
+            graph,
+            graph_lifetime: PhantomData,
+            components: Default::default(),
+            blocks: Default::default(),
+            cut_nodes: Default::default(),
+            spqr_nodes: Default::default(),
+            spqr_edges: Default::default(),
+            node_data: Default::default(),
+            edge_data: Default::default(),
+            cache: Default::default(),
+        };
+        let mut node_data = vec![None; graph.node_count()];
+        let mut edge_data = vec![None; graph.edge_count()];
+
+        let pool = &mut ExtraDataPool::new();
+
+        for &(offset, len) in &table_of_contents.segments {
+            reader.seek(SeekFrom::Start(offset as u64))?;
+            let [
+                components,
+                blocks,
+                cut_nodes,
+                spqr_nodes,
+                spqr_edges,
+                segment_node_data,
+                segment_edge_data,
+            ] = read_sections((&mut reader).take(len as u64), &mut ())?;
+
+            let offsets = IndexOffsets {
+                components: decomposition.components.len(),
+                blocks: decomposition.blocks.len(),
+                cut_nodes: decomposition.cut_nodes.len(),
+                spqr_nodes: decomposition.spqr_nodes.len(),
+                spqr_edges: decomposition.spqr_edges.len(),
+            };
+
+            for mut component in
+                decode_section::<usize, Component<_, _, _>>(&components, pool)?.into_values_iter()
+            {
+                shift_all(&mut component.blocks, offsets.blocks);
+                shift_all(&mut component.cut_nodes, offsets.cut_nodes);
+                decomposition.components.push(component);
+            }
+            for mut block in
+                decode_section::<usize, Block<_, _, _>>(&blocks, pool)?.into_values_iter()
+            {
+                block.component = shifted(block.component, offsets.components);
+                shift_all(&mut block.cut_nodes, offsets.cut_nodes);
+                shift_all(&mut block.spqr_nodes, offsets.spqr_nodes);
+                shift_all(&mut block.spqr_edges, offsets.spqr_edges);
+                decomposition.blocks.push(block);
+            }
+            for mut cut_node in
+                decode_section::<usize, CutNode<_, _>>(&cut_nodes, pool)?.into_values_iter()
+            {
+                cut_node.component = shifted(cut_node.component, offsets.components);
+                shift_all(&mut cut_node.adjacent_blocks, offsets.blocks);
+                decomposition.cut_nodes.push(cut_node);
+            }
+            for mut spqr_node in
+                decode_section::<usize, SPQRNode<_, _, _>>(&spqr_nodes, pool)?.into_values_iter()
+            {
+                spqr_node.block = shifted(spqr_node.block, offsets.blocks);
+                shift_all(&mut spqr_node.spqr_edges, offsets.spqr_edges);
+                decomposition.spqr_nodes.push(spqr_node);
+            }
+            for mut spqr_edge in
+                decode_section::<usize, SPQREdge<_, _>>(&spqr_edges, pool)?.into_values_iter()
+            {
+                spqr_edge.endpoints.0 = shifted(spqr_edge.endpoints.0, offsets.spqr_nodes);
+                spqr_edge.endpoints.1 = shifted(spqr_edge.endpoints.1, offsets.spqr_nodes);
+                decomposition.spqr_edges.push(spqr_edge);
+            }
+
+            for Keyed(node_index, mut data) in decode_section::<
+                usize,
+                Keyed<Graph::NodeIndex, SPQRDecompositionNodeData<_>>,
+            >(&segment_node_data, pool)?
+            .into_values_iter()
+            {
+                data.component_index = shifted(data.component_index, offsets.components);
+                shift_all(&mut data.block_indices, offsets.blocks);
+                data.cut_node_index = OptionalCutNodeIndex::from_option(
+                    data.cut_node_index
+                        .into_option()
+                        .map(|c| shifted(c, offsets.cut_nodes)),
+                );
+                shift_all(&mut data.spqr_node_indices, offsets.spqr_nodes);
+                assign_once(&mut node_data, node_index.into(), data, "node")?;
+            }
+            for Keyed(edge_index, mut data) in decode_section::<
+                usize,
+                Keyed<Graph::EdgeIndex, SPQRDecompositionEdgeData<_>>,
+            >(&segment_edge_data, pool)?
+            .into_values_iter()
+            {
+                data.component_index = shifted(data.component_index, offsets.components);
+                data.block_index = OptionalBlockIndex::from_option(
+                    data.block_index
+                        .into_option()
+                        .map(|b| shifted(b, offsets.blocks)),
+                );
+                data.spqr_node_index = OptionalSPQRNodeIndex::from_option(
+                    data.spqr_node_index
+                        .into_option()
+                        .map(|n| shifted(n, offsets.spqr_nodes)),
+                );
+                assign_once(&mut edge_data, edge_index.into(), data, "edge")?;
+            }
+        }
+
+        decomposition.node_data = collect_complete(node_data, "node")?;
+        decomposition.edge_data = collect_complete(edge_data, "edge")?;
+        Ok(decomposition)
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Appends the given components as a new segment to a file in the appendable binary format.
+    ///
+    /// If the file is empty, a new appendable binary file is started.
+    /// Otherwise, the new segment overwrites the old table of contents, and an updated table of contents is written after it.
+    /// Existing segments are never rewritten, so incremental pipelines can store each batch of newly computed components
+    /// without rewriting what they have stored before.
+    ///
+    /// Components, blocks, cut nodes, SPQR nodes and SPQR edges are numbered relative to their segment,
+    /// while nodes and edges keep their indices in the graph.
+    /// Hence, all appended decompositions need to be of the same graph, or of graphs that share the indices of their common nodes and edges,
+    /// like the growing prefixes of a graph that is constructed incrementally.
+    /// Use [`read_appendable_binary`](SPQRDecomposition::read_appendable_binary) to read the whole file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a component is given twice.
+    pub fn append_binary(
+        &self,
+        component_indices: impl IntoIterator<Item = ComponentIndex<Graph::IndexType>>,
+        mut file: impl Read + Write + Seek,
+    ) -> std::io::Result<()> {
+        let component_indices = component_indices.into_iter().collect::<Vec<_>>();
+        let restricted = self.restrict_entities(&component_indices, |node| node, |edge| edge);
+        let node_data = component_indices
+            .iter()
+            .flat_map(|&component_index| self.components[component_index].iter_nodes())
+            .map(|node_index| {
+                Keyed(
+                    node_index,
+                    restricted.maps.node_data(&self.node_data[node_index]),
+                )
+            })
+            .collect::<Vec<_>>();
+        let edge_data = component_indices
+            .iter()
+            .flat_map(|&component_index| self.iter_edges_in_component(component_index))
+            .map(|edge_index| {
+                Keyed(
+                    edge_index,
+                    restricted.maps.edge_data(&self.edge_data[edge_index]),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let sections = [
+            encode_section(restricted.components.iter_values())?,
+            encode_section(restricted.blocks.iter_values())?,
+            encode_section(restricted.cut_nodes.iter_values())?,
+            encode_section(restricted.spqr_nodes.iter_values())?,
+            encode_section(restricted.spqr_edges.iter_values())?,
+            encode_section(node_data.iter())?,
+            encode_section(edge_data.iter())?,
+        ];
+
+        let mut table_of_contents = read_table_of_contents(&mut file)?.unwrap_or_default();
+        let offset = table_of_contents.end;
+        file.seek(SeekFrom::Start(offset as u64))?;
+        let mut segment = Vec::new();
+        write_sections(&sections, &mut segment)?;
+        file.write_all(&segment)?;
+
+        table_of_contents.segments.push((offset, segment.len()));
+        table_of_contents.end = offset + segment.len();
+        table_of_contents.write(&mut file)?;
+        file.flush()
+    }
+}
+
+/// The segments of an appendable binary file, stored at its end.
+#[derive(Default)]
+struct TableOfContents {
+    /// The offset and length in bytes of each segment.
+    segments: Vec<(usize, usize)>,
+    /// The offset of the table of contents, which is the end of the last segment.
+    end: usize,
+}
+
+impl TableOfContents {
+    /// Writes the table of contents followed by the trailer.
+    fn write(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let mut bytes = Vec::new();
+        write_usize_binary(self.segments.len(), &mut bytes)?;
+        for (offset, len) in &self.segments {
+            write_usize_binary(*offset, &mut bytes)?;
+            write_usize_binary(*len, &mut bytes)?;
+        }
+        write_usize_binary(self.end, &mut bytes)?;
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(APPENDABLE_BINARY_VERSION);
+        writer.write_all(&bytes)
+    }
+}
+
+/// Reads the table of contents of an appendable binary file, or returns `None` if the file is empty.
+fn read_table_of_contents(
+    mut reader: impl Read + Seek,
+) -> std::io::Result<Option<TableOfContents>> {
+    let file_len = reader.seek(SeekFrom::End(0))?;
+    if file_len == 0 {
+        return Ok(None);
+    }
+    let invalid = |message| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+    let trailer_offset = file_len
+        .checked_sub(TRAILER_LEN as u64)
+        .ok_or_else(|| invalid("file is too short to be an appendable binary file"))?;
+
+    reader.seek(SeekFrom::Start(trailer_offset))?;
+    let mut trailer = [0; TRAILER_LEN];
+    reader.read_exact(&mut trailer)?;
+    let mut trailer = &trailer[..];
+    let end: usize = read_value(&mut trailer)?;
+    let (magic, version) = trailer.split_at(MAGIC.len());
+    if magic != MAGIC {
+        return Err(invalid("file is not an appendable binary file"));
+    }
+    if version != [APPENDABLE_BINARY_VERSION] {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("unsupported appendable binary version {}", version[0]),
+        ));
+    }
+    if end as u64 > trailer_offset {
+        return Err(invalid(
+            "table of contents starts after the end of the file",
+        ));
+    }
+
+    reader.seek(SeekFrom::Start(end as u64))?;
+    let mut bytes = vec![0; (trailer_offset - end as u64) as usize];
+    reader.read_exact(&mut bytes)?;
+    let mut bytes = &bytes[..];
+    let segment_count: usize = read_value(&mut bytes)?;
+    if segment_count > bytes.len() {
+        return Err(invalid(
+            "table of contents contains more segments than bytes",
+        ));
+    }
+    let segments = (0..segment_count)
+        .map(|_| Ok((read_value(&mut bytes)?, read_value(&mut bytes)?)))
+        .collect::<std::io::Result<Vec<(usize, usize)>>>()?;
+    if !bytes.is_empty() {
+        return Err(invalid("table of contents contains trailing bytes"));
+    }
+    if segments.iter().any(|&(offset, len)| {
+        offset
+            .checked_add(len)
+            .is_none_or(|segment_end| segment_end > end)
+    }) {
+        return Err(invalid("segment extends past the table of contents"));
+    }
+
+    Ok(Some(TableOfContents { segments, end }))
+}
+
+/// An item of a section together with the index of the node or edge it belongs to.
+struct Keyed<Index, Item>(Index, Item);
+
+impl<Index: Copy, Item: SectionItem> SectionItem for Keyed<Index, Item> {
+    fn decode(reader: &mut &[u8], pool: &mut ExtraDataPool) -> std::io::Result<Self> {
+        Ok(Self(read_value(reader)?, Item::decode(reader, pool)?))
+    }
+
+    fn encode(&self, section: &mut Vec<u8>) -> std::io::Result<()> {
+        write_binary(&self.0, &mut *section)?;
+        self.1.encode(section)
+    }
+}
+
+/// The amount of each kind of entity in the segments before the current one.
+struct IndexOffsets {
+    components: usize,
+    blocks: usize,
+    cut_nodes: usize,
+    spqr_nodes: usize,
+    spqr_edges: usize,
+}
+
+fn shifted<Index: GraphIndex>(index: Index, offset: usize) -> Index {
+    (index.into() + offset).into()
+}
+
+fn shift_all<'index, Index: GraphIndex + 'index>(
+    indices: impl IntoIterator<Item = &'index mut Index>,
+    offset: usize,
+) {
+    for index in indices {
+        *index = shifted(*index, offset);
+    }
+}
+
+/// Stores `data` at `index`, failing if the index is out of range or already taken by an earlier segment.
+fn assign_once<Data>(
+    slots: &mut [Option<Data>],
+    index: usize,
+    data: Data,
+    kind: &str,
+) -> std::io::Result<()> {
+    let slot = slots.get_mut(index).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{kind} {index} is not in the graph"),
+        )
+    })?;
+    if slot.replace(data).is_some() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{kind} {index} is contained in more than one segment"),
+        ));
+    }
+    Ok(())
+}
+
+/// Collects the data of all nodes or edges, failing if one of them was not contained in any segment.
+fn collect_complete<Index, Data>(
+    slots: Vec<Option<Data>>,
+    kind: &str,
+) -> std::io::Result<TaggedVec<Index, Data>> {
+    slots
+        .into_iter()
+        .enumerate()
+        .map(|(index, data)| {
+            data.ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{kind} {index} is not contained in any segment"),
+                )
+            })
+        })
+        .collect()
+}
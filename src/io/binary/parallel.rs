@@ -0,0 +1,117 @@
+use std::{
+    borrow::Borrow,
+    io::{Read, Write},
+    marker::PhantomData,
+    panic,
+    thread::{self, ScopedJoinHandle},
+};
+
+use crate::{
+    decomposition::{SPQRDecomposition, extra_data::ExtraDataPool},
+    graph::StaticGraph,
+    io::binary::{
+        Header, decode_section, encode_section, read_header, read_sections, write_header,
+        write_sections,
+    },
+};
+
+impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph>
+where
+    Graph::NodeIndex: Send,
+    Graph::EdgeIndex: Send,
+    Graph::IndexType: Send,
+{
+    /// Reads a bidirected adjacency array from a platform-dependent binary format, decoding each section on its own thread.
+    ///
+    /// The format is the same as for [`read_binary`](Self::read_binary).
+    /// Files in the old layout without sections are decoded on the calling thread.
+    pub fn read_binary_parallel(
+        graph: &'graph Graph,
+        mut reader: impl Read,
+    ) -> std::io::Result<Self> {
+        if let Header::Legacy(prefix) = read_header(&mut reader)? {
+            return Self::read_legacy(graph, prefix, reader);
+        }
+
+        let [
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+        ] = read_sections(reader, &mut ())?;
+
+        thread::scope(|scope| {
+            let components = scope.spawn(|| decode_section(&components, &mut ExtraDataPool::new()));
+            let blocks = scope.spawn(|| decode_section(&blocks, &mut ExtraDataPool::new()));
+            let cut_nodes = scope.spawn(|| decode_section(&cut_nodes, &mut ExtraDataPool::new()));
+            let spqr_nodes = scope.spawn(|| decode_section(&spqr_nodes, &mut ExtraDataPool::new()));
+            let spqr_edges = scope.spawn(|| decode_section(&spqr_edges, &mut ExtraDataPool::new()));
+            let node_data = scope.spawn(|| decode_section(&node_data, &mut ExtraDataPool::new()));
+            let edge_data = scope.spawn(|| decode_section(&edge_data, &mut ExtraDataPool::new()));
+
+            Ok(Self {
+                graph,
+                graph_lifetime: PhantomData,
+                components: join(components)?,
+                blocks: join(blocks)?,
+                cut_nodes: join(cut_nodes)?,
+                spqr_nodes: join(spqr_nodes)?,
+                spqr_edges: join(spqr_edges)?,
+                node_data: join(node_data)?,
+                edge_data: join(edge_data)?,
+                cache: Default::default(),
+            })
+        })
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+where
+    Graph::NodeIndex: Sync,
+    Graph::EdgeIndex: Sync,
+    Graph::IndexType: Sync,
+{
+    /// Writes the bidirected adjacency array into a platform-dependent binary format, encoding each section on its own thread.
+    ///
+    /// The format is the same as for [`write_binary`](Self::write_binary).
+    pub fn write_binary_parallel(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let Self {
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+            ..
+        } = self;
+
+        let sections = thread::scope(|scope| {
+            [
+                scope.spawn(|| encode_section(components.iter_values())),
+                scope.spawn(|| encode_section(blocks.iter_values())),
+                scope.spawn(|| encode_section(cut_nodes.iter_values())),
+                scope.spawn(|| encode_section(spqr_nodes.iter_values())),
+                scope.spawn(|| encode_section(spqr_edges.iter_values())),
+                scope.spawn(|| encode_section(node_data.iter_values())),
+                scope.spawn(|| encode_section(edge_data.iter_values())),
+            ]
+            .map(join)
+        });
+        let sections = sections.into_iter().collect::<std::io::Result<Vec<_>>>()?;
+
+        write_header(&mut writer)?;
+        write_sections(&sections, writer)
+    }
+}
+
+/// Joins a thread, propagating its panic if it panicked.
+fn join<T>(handle: ScopedJoinHandle<'_, T>) -> T {
+    handle
+        .join()
+        .unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
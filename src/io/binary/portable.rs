@@ -0,0 +1,449 @@
+//! A platform-independent binary format for SPQR decompositions.
+//!
+//! The format starts with a [version byte](PORTABLE_BINARY_VERSION), which distinguishes it from the [header](super::NATIVE_BINARY_MAGIC) of the platform-dependent format.
+//! All integers are stored as unsigned [LEB128](https://en.wikipedia.org/wiki/LEB128) varints, independently of the index type and the endianness of the platform.
+//! Optional indices are stored as zero if they are absent and as the index plus one otherwise,
+//! and lists and strings are prefixed by their length.
+//! The entities follow each other in the order of the sections of the platform-dependent format, each kind prefixed by its amount.
+//!
+//! Equal decompositions are always written as identical bytes, so the output can be checksummed.
+
+use std::{
+    borrow::Borrow,
+    io::{BufWriter, Read, Write},
+    marker::PhantomData,
+};
+
+use tagged_vec::TaggedVec;
+
+use crate::{
+    decomposition::{
+        Block, Component, CutNode, SPQRDecomposition, SPQRDecompositionEdgeData,
+        SPQRDecompositionNodeData, SPQREdge, SPQRNode, SPQRNodeType,
+        extra_data::{ExtraData, ExtraDataPool},
+        indices::{
+            BlockIndex, ComponentIndex, CutNodeIndex, GraphIndexInteger, OptionalBlockIndex,
+            OptionalCutNodeIndex, OptionalSPQRNodeIndex, SPQREdgeIndex, SPQRNodeIndex,
+        },
+    },
+    graph::StaticGraph,
+};
+
+/// The version of the portable binary format, written as its first byte.
+pub const PORTABLE_BINARY_VERSION: u8 = 1;
+
+impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
+    /// Reads an SPQR decomposition from the platform-independent binary format written by [`write_portable_binary`](SPQRDecomposition::write_portable_binary).
+    ///
+    /// The file can be read with any index type that can index all of its entities.
+    pub fn read_portable_binary(
+        graph: &'graph Graph,
+        mut reader: impl Read,
+    ) -> std::io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::read_portable_binary_from_slice(graph, &bytes)
+    }
+
+    /// Like [`read_portable_binary`](Self::read_portable_binary), but decodes the decomposition from bytes in memory.
+    pub fn read_portable_binary_from_slice(
+        graph: &'graph Graph,
+        bytes: &[u8],
+    ) -> std::io::Result<Self> {
+        let bytes = match bytes.split_first() {
+            Some((&PORTABLE_BINARY_VERSION, bytes)) => bytes,
+            Some((version, _)) => {
+                return Err(invalid_data(format!(
+                    "unsupported portable binary version {version}"
+                )));
+            }
+            None => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+        };
+        let decoder = &mut Decoder {
+            bytes,
+            graph,
+            pool: ExtraDataPool::new(),
+        };
+
+        let components = decoder.entities(|decoder| {
+            Ok(Component {
+                nodes: decoder.list(Decoder::node)?,
+                edges: decoder.list(Decoder::edge)?,
+                blocks: decoder.list(|decoder| decoder.index().map(BlockIndex::from_raw))?,
+                cut_nodes: decoder.list(|decoder| decoder.index().map(CutNodeIndex::from_raw))?,
+                extra_data: decoder.extra_data()?,
+            })
+        })?;
+        let blocks = decoder.entities(|decoder| {
+            Ok(Block {
+                component: ComponentIndex::from_raw(decoder.index()?),
+                nodes: decoder.list(Decoder::node)?,
+                cut_nodes: decoder.list(|decoder| decoder.index().map(CutNodeIndex::from_raw))?,
+                edges: decoder.list(Decoder::edge)?,
+                spqr_nodes: decoder.list(|decoder| decoder.index().map(SPQRNodeIndex::from_raw))?,
+                spqr_edges: decoder.list(|decoder| decoder.index().map(SPQREdgeIndex::from_raw))?,
+                extra_data: decoder.extra_data()?,
+            })
+        })?;
+        let cut_nodes = decoder.entities(|decoder| {
+            Ok(CutNode {
+                component: ComponentIndex::from_raw(decoder.index()?),
+                node: decoder.node()?,
+                adjacent_blocks: decoder
+                    .list(|decoder| decoder.index().map(BlockIndex::from_raw))?
+                    .into(),
+                extra_data: decoder.extra_data()?,
+            })
+        })?;
+        let spqr_nodes = decoder.entities(|decoder| {
+            Ok(SPQRNode {
+                block: BlockIndex::from_raw(decoder.index()?),
+                nodes: decoder.list(Decoder::node)?,
+                edges: decoder.list(Decoder::edge)?,
+                spqr_node_type: match decoder.usize()? {
+                    0 => SPQRNodeType::SNode,
+                    1 => SPQRNodeType::PNode,
+                    2 => SPQRNodeType::RNode,
+                    _ => return Err(invalid_data("invalid SPQR node type")),
+                },
+                spqr_edges: decoder
+                    .list(|decoder| decoder.index().map(SPQREdgeIndex::from_raw))?
+                    .into(),
+                extra_data: decoder.extra_data()?,
+            })
+        })?;
+        let spqr_edges = decoder.entities(|decoder| {
+            Ok(SPQREdge {
+                endpoints: (
+                    SPQRNodeIndex::from_raw(decoder.index()?),
+                    SPQRNodeIndex::from_raw(decoder.index()?),
+                ),
+                virtual_edge: (decoder.node()?, decoder.node()?),
+            })
+        })?;
+        let node_data = decoder.entities(|decoder| {
+            Ok(SPQRDecompositionNodeData {
+                component_index: ComponentIndex::from_raw(decoder.index()?),
+                block_indices: decoder
+                    .list(|decoder| decoder.index().map(BlockIndex::from_raw))?
+                    .into(),
+                cut_node_index: OptionalCutNodeIndex::from_raw(decoder.optional_index()?),
+                spqr_node_indices: decoder
+                    .list(|decoder| decoder.index().map(SPQRNodeIndex::from_raw))?
+                    .into(),
+                extra_data: decoder.extra_data()?,
+            })
+        })?;
+        let edge_data = decoder.entities(|decoder| {
+            Ok(SPQRDecompositionEdgeData {
+                component_index: ComponentIndex::from_raw(decoder.index()?),
+                block_index: OptionalBlockIndex::from_raw(decoder.optional_index()?),
+                spqr_node_index: OptionalSPQRNodeIndex::from_raw(decoder.optional_index()?),
+                extra_data: decoder.extra_data()?,
+            })
+        })?;
+
+        if node_data.len() != graph.node_count() || edge_data.len() != graph.edge_count() {
+            return Err(invalid_data(
+                "the amounts of nodes and edges do not match the graph",
+            ));
+        }
+        if !decoder.bytes.is_empty() {
+            return Err(invalid_data("trailing bytes after the decomposition"));
+        }
+
+        Ok(Self {
+            graph,
+            graph_lifetime: PhantomData,
+            components,
+            blocks,
+            cut_nodes,
+            spqr_nodes,
+            spqr_edges,
+            node_data,
+            edge_data,
+            cache: Default::default(),
+        })
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Writes the decomposition into a platform-independent binary format.
+    ///
+    /// Unlike [`write_binary`](Self::write_binary), the output does not depend on the index type or the platform,
+    /// so equal decompositions are written as identical bytes everywhere.
+    pub fn write_portable_binary(&self, writer: impl Write) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        let writer = &mut writer;
+
+        writer.write_all(&[PORTABLE_BINARY_VERSION])?;
+        write_usize(writer, self.components.len())?;
+        for component in self.components.iter_values() {
+            write_list(writer, component.nodes.iter().map(|&node| node.into()))?;
+            write_list(writer, component.edges.iter().map(|&edge| edge.into()))?;
+            write_list(
+                writer,
+                component.blocks.iter().map(|block| block.into_usize()),
+            )?;
+            write_list(
+                writer,
+                component
+                    .cut_nodes
+                    .iter()
+                    .map(|cut_node| cut_node.into_usize()),
+            )?;
+            write_str(writer, &component.extra_data)?;
+        }
+
+        write_usize(writer, self.blocks.len())?;
+        for block in self.blocks.iter_values() {
+            write_usize(writer, block.component.into_usize())?;
+            write_list(writer, block.nodes.iter().map(|&node| node.into()))?;
+            write_list(
+                writer,
+                block.cut_nodes.iter().map(|cut_node| cut_node.into_usize()),
+            )?;
+            write_list(writer, block.edges.iter().map(|&edge| edge.into()))?;
+            write_list(
+                writer,
+                block
+                    .spqr_nodes
+                    .iter()
+                    .map(|spqr_node| spqr_node.into_usize()),
+            )?;
+            write_list(
+                writer,
+                block
+                    .spqr_edges
+                    .iter()
+                    .map(|spqr_edge| spqr_edge.into_usize()),
+            )?;
+            write_str(writer, &block.extra_data)?;
+        }
+
+        write_usize(writer, self.cut_nodes.len())?;
+        for cut_node in self.cut_nodes.iter_values() {
+            write_usize(writer, cut_node.component.into_usize())?;
+            write_usize(writer, cut_node.node.into())?;
+            write_list(
+                writer,
+                cut_node
+                    .adjacent_blocks
+                    .iter()
+                    .map(|block| block.into_usize()),
+            )?;
+            write_str(writer, &cut_node.extra_data)?;
+        }
+
+        write_usize(writer, self.spqr_nodes.len())?;
+        for spqr_node in self.spqr_nodes.iter_values() {
+            write_usize(writer, spqr_node.block.into_usize())?;
+            write_list(writer, spqr_node.nodes.iter().map(|&node| node.into()))?;
+            write_list(writer, spqr_node.edges.iter().map(|&edge| edge.into()))?;
+            write_usize(
+                writer,
+                match spqr_node.spqr_node_type {
+                    SPQRNodeType::SNode => 0,
+                    SPQRNodeType::PNode => 1,
+                    SPQRNodeType::RNode => 2,
+                },
+            )?;
+            write_list(
+                writer,
+                spqr_node
+                    .spqr_edges
+                    .iter()
+                    .map(|spqr_edge| spqr_edge.into_usize()),
+            )?;
+            write_str(writer, &spqr_node.extra_data)?;
+        }
+
+        write_usize(writer, self.spqr_edges.len())?;
+        for spqr_edge in self.spqr_edges.iter_values() {
+            write_usize(writer, spqr_edge.endpoints.0.into_usize())?;
+            write_usize(writer, spqr_edge.endpoints.1.into_usize())?;
+            write_usize(writer, spqr_edge.virtual_edge.0.into())?;
+            write_usize(writer, spqr_edge.virtual_edge.1.into())?;
+        }
+
+        write_usize(writer, self.node_data.len())?;
+        for node_data in self.node_data.iter_values() {
+            write_usize(writer, node_data.component_index.into_usize())?;
+            write_list(
+                writer,
+                node_data
+                    .block_indices
+                    .iter()
+                    .map(|block| block.into_usize()),
+            )?;
+            write_optional(writer, node_data.cut_node_index.into_usize())?;
+            write_list(
+                writer,
+                node_data
+                    .spqr_node_indices
+                    .iter()
+                    .map(|spqr_node| spqr_node.into_usize()),
+            )?;
+            write_str(writer, &node_data.extra_data)?;
+        }
+
+        write_usize(writer, self.edge_data.len())?;
+        for edge_data in self.edge_data.iter_values() {
+            write_usize(writer, edge_data.component_index.into_usize())?;
+            write_optional(writer, edge_data.block_index.into_usize())?;
+            write_optional(writer, edge_data.spqr_node_index.into_usize())?;
+            write_str(writer, &edge_data.extra_data)?;
+        }
+
+        writer.flush()
+    }
+}
+
+/// Decodes the portable binary format from bytes in memory.
+struct Decoder<'bytes, 'graph, Graph> {
+    bytes: &'bytes [u8],
+    graph: &'graph Graph,
+    pool: ExtraDataPool,
+}
+
+impl<Graph: StaticGraph> Decoder<'_, '_, Graph> {
+    fn usize(&mut self) -> std::io::Result<usize> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = self
+                .bytes
+                .split_first()
+                .ok_or(std::io::ErrorKind::UnexpectedEof)?;
+            self.bytes = rest;
+            let bits = u64::from(byte & 0x7f);
+            if shift == 63 && bits > 1 {
+                break;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return value
+                    .try_into()
+                    .map_err(|_| invalid_data("integer does not fit into usize"));
+            }
+        }
+        Err(invalid_data("integer does not fit into 64 bits"))
+    }
+
+    /// Reads an amount of items, each of which takes at least one byte.
+    fn amount(&mut self) -> std::io::Result<usize> {
+        let amount = self.usize()?;
+        // Larger amounts are corrupt and must not be preallocated.
+        if amount > self.bytes.len() {
+            Err(std::io::ErrorKind::UnexpectedEof.into())
+        } else {
+            Ok(amount)
+        }
+    }
+
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> std::io::Result<T>,
+    ) -> std::io::Result<Vec<T>> {
+        let amount = self.amount()?;
+        (0..amount).map(|_| item(self)).collect()
+    }
+
+    fn entities<Index, T>(
+        &mut self,
+        item: impl FnMut(&mut Self) -> std::io::Result<T>,
+    ) -> std::io::Result<TaggedVec<Index, T>> {
+        Ok(self.list(item)?.into())
+    }
+
+    fn index<IndexType: GraphIndexInteger>(&mut self) -> std::io::Result<IndexType> {
+        let value = self.usize()?;
+        // The maximum value is reserved for optional indices.
+        IndexType::try_from(value)
+            .ok()
+            .filter(|index| *index != IndexType::max_value())
+            .ok_or_else(|| invalid_data("index does not fit into the index type"))
+    }
+
+    fn optional_index<IndexType: GraphIndexInteger>(
+        &mut self,
+    ) -> std::io::Result<Option<IndexType>> {
+        match self.usize()? {
+            0 => Ok(None),
+            value => IndexType::try_from(value - 1)
+                .ok()
+                .filter(|index| *index != IndexType::max_value())
+                .map(Some)
+                .ok_or_else(|| invalid_data("index does not fit into the index type")),
+        }
+    }
+
+    fn node(&mut self) -> std::io::Result<Graph::NodeIndex> {
+        let value = self.usize()?;
+        if value < self.graph.node_count() {
+            Ok(value.into())
+        } else {
+            Err(invalid_data("node index out of bounds"))
+        }
+    }
+
+    fn edge(&mut self) -> std::io::Result<Graph::EdgeIndex> {
+        let value = self.usize()?;
+        if value < self.graph.edge_count() {
+            Ok(value.into())
+        } else {
+            Err(invalid_data("edge index out of bounds"))
+        }
+    }
+
+    fn extra_data(&mut self) -> std::io::Result<ExtraData> {
+        let len = self.amount()?;
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        let string = std::str::from_utf8(bytes).map_err(invalid_data)?;
+        Ok(self.pool.intern(string))
+    }
+}
+
+fn write_usize(writer: &mut impl Write, value: usize) -> std::io::Result<()> {
+    let mut value = value as u64;
+    let mut bytes = [0; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes[len] = byte;
+            len += 1;
+            break;
+        }
+        bytes[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&bytes[..len])
+}
+
+fn write_optional(writer: &mut impl Write, value: Option<usize>) -> std::io::Result<()> {
+    write_usize(writer, value.map_or(0, |value| value + 1))
+}
+
+fn write_list(
+    writer: &mut impl Write,
+    values: impl ExactSizeIterator<Item = usize>,
+) -> std::io::Result<()> {
+    write_usize(writer, values.len())?;
+    for value in values {
+        write_usize(writer, value)?;
+    }
+    Ok(())
+}
+
+fn write_str(writer: &mut impl Write, string: &str) -> std::io::Result<()> {
+    write_usize(writer, string.len())?;
+    writer.write_all(string.as_bytes())
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+}
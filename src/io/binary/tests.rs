@@ -1,6 +1,6 @@
 use std::{
     fs::{self, File},
-    io::BufReader,
+    io::{BufReader, Cursor},
 };
 
 use bidirected_adjacency_array::{
@@ -8,7 +8,14 @@ use bidirected_adjacency_array::{
     io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
 };
 
-use crate::decomposition::SPQRDecomposition;
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{ProgressEvent, RecordingProgress, read_concatenated},
+    },
+    io::binary::{NATIVE_BINARY_MAGIC, NATIVE_BINARY_VERSION, portable::PORTABLE_BINARY_VERSION},
+    profile::Phase,
+};
 
 #[test]
 fn test_tiny1() {
@@ -25,29 +32,379 @@ fn test_tiny1() {
     spqr_decomposition.write_binary(&mut buffer).unwrap();
     let read_spqr_decomposition = SPQRDecomposition::read_binary(&graph, &buffer[..]).unwrap();
 
+    assert_eq!(spqr_decomposition, read_spqr_decomposition);
+}
+
+#[test]
+fn test_truncated() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+    let mut buffer = Vec::new();
+    spqr_decomposition.write_binary(&mut buffer).unwrap();
+    for len in [0, 1, buffer.len() / 2, buffer.len() - 1] {
+        assert!(SPQRDecomposition::read_binary(&graph, &buffer[..len]).is_err());
+    }
+}
+
+#[test]
+fn test_corrupt_lengths() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+    let mut buffer = Vec::new();
+    spqr_decomposition.write_binary(&mut buffer).unwrap();
+    let word = size_of::<usize>();
+    let header_len = NATIVE_BINARY_MAGIC.len() + 1;
+
+    // The amount of components, followed by the amount of nodes of the first component.
+    for offset in [header_len + word, header_len + 2 * word] {
+        for corrupt_len in [usize::MAX, usize::MAX / 2 + 1, buffer.len()] {
+            let mut corrupt = buffer.clone();
+            corrupt[offset..offset + word].copy_from_slice(&corrupt_len.to_ne_bytes());
+            assert!(SPQRDecomposition::read_binary(&graph, &corrupt[..]).is_err());
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/Ultrabubble_dataset_chr_22_G182870.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file =
+        fs::read_to_string("test_files/Ultrabubble_dataset_chr_22_G182870.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+    let mut buffer = Vec::new();
+    spqr_decomposition.write_binary(&mut buffer).unwrap();
+    let mut parallel_buffer = Vec::new();
+    spqr_decomposition
+        .write_binary_parallel(&mut parallel_buffer)
+        .unwrap();
+    assert_eq!(buffer, parallel_buffer);
+
+    let read_spqr_decomposition =
+        SPQRDecomposition::read_binary_parallel(&graph, &buffer[..]).unwrap();
+    assert_eq!(spqr_decomposition, read_spqr_decomposition);
+}
+
+#[test]
+fn test_shared_extra_data() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let mut spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    for node_index in spqr_decomposition.iter_nodes().collect::<Vec<_>>() {
+        // Each call allocates a separate string.
+        spqr_decomposition.set_node_extra_data(node_index, String::from("annotated"));
+    }
+    let annotated_size = 2 * size_of::<usize>() + "annotated".len();
+    assert_eq!(
+        spqr_decomposition.memory_usage().extra_data,
+        graph.node_count() * annotated_size,
+    );
+
+    let mut buffer = Vec::new();
+    spqr_decomposition.write_binary(&mut buffer).unwrap();
+    let read_spqr_decomposition = SPQRDecomposition::read_binary(&graph, &buffer[..]).unwrap();
+    assert_eq!(spqr_decomposition, read_spqr_decomposition);
+    assert_eq!(
+        read_spqr_decomposition.memory_usage().extra_data,
+        annotated_size,
+    );
+}
+
+#[test]
+fn test_append() {
+    let (graph, spqr_decomposition_file) =
+        read_concatenated(&["tiny1", "multiedge", "random5_10_False_0"]);
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let component_indices = spqr_decomposition
+        .iter_component_indices()
+        .collect::<Vec<_>>();
+    assert_eq!(component_indices.len(), 3);
+
+    // Append the components in two batches, in a different order than in the decomposition.
+    let mut file = Cursor::new(Vec::new());
+    spqr_decomposition
+        .append_binary([component_indices[2]], &mut file)
+        .unwrap();
+    let first_len = file.get_ref().len();
+    assert!(SPQRDecomposition::read_appendable_binary(&graph, &mut file).is_err());
+
+    spqr_decomposition
+        .append_binary([component_indices[0], component_indices[1]], &mut file)
+        .unwrap();
+    assert!(file.get_ref().len() > first_len);
+    let read_spqr_decomposition =
+        SPQRDecomposition::read_appendable_binary(&graph, &mut file).unwrap();
+    assert!(read_spqr_decomposition.is_equivalent_to(&spqr_decomposition));
+
+    // Appending a component twice covers its nodes twice.
+    spqr_decomposition
+        .append_binary([component_indices[1]], &mut file)
+        .unwrap();
+    assert!(SPQRDecomposition::read_appendable_binary(&graph, &mut file).is_err());
+
+    let mut corrupt = file.into_inner();
+    *corrupt.last_mut().unwrap() += 1;
+    let error =
+        SPQRDecomposition::read_appendable_binary(&graph, Cursor::new(&corrupt)).unwrap_err();
+    assert_eq!(error.to_string(), "unsupported appendable binary version 2");
+    *corrupt.last_mut().unwrap() -= 1;
+    let version_offset = corrupt.len() - 1;
+    corrupt[version_offset - 1] ^= 1;
+    assert!(SPQRDecomposition::read_appendable_binary(&graph, Cursor::new(&corrupt)).is_err());
+    assert!(SPQRDecomposition::read_appendable_binary(&graph, Cursor::new(&corrupt[..5])).is_err());
+}
+
+#[test]
+fn test_progress() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let mut buffer = Vec::new();
+    spqr_decomposition.write_binary(&mut buffer).unwrap();
+
+    let mut progress = RecordingProgress::default();
+    let read_spqr_decomposition =
+        SPQRDecomposition::read_binary_with_progress(&graph, &buffer[..], &mut progress).unwrap();
+    assert_eq!(spqr_decomposition, read_spqr_decomposition);
+
+    let mut expected = Vec::new();
+    for phase in [Phase::Parse, Phase::Build] {
+        expected.push(ProgressEvent::Start(phase, Some(7)));
+        expected.extend((1..=7).map(ProgressEvent::Advance));
+        expected.push(ProgressEvent::Finish(phase));
+    }
+    assert_eq!(progress.events, expected);
+}
+
+#[test]
+fn test_read_from_path() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let mut buffer = Vec::new();
+    spqr_decomposition.write_binary(&mut buffer).unwrap();
+
+    let path = std::env::temp_dir().join(format!("spqr-tree-path-{}.spqr.bin", std::process::id()));
+    fs::write(&path, &buffer).unwrap();
+    let read_spqr_decomposition = SPQRDecomposition::read_binary_from_path(&graph, &path).unwrap();
+    assert_eq!(spqr_decomposition, read_spqr_decomposition);
+
+    fs::write(&path, &buffer[..buffer.len() - 3]).unwrap();
+    let error = SPQRDecomposition::read_binary_from_path(&graph, &path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(error.source.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(error.line, None);
+    assert_eq!(error.offset, Some(buffer.len() as u64 - 3));
+    assert_eq!(error.path, path);
+}
+
+/// Writes the given test file in the plain and in the portable binary format using indices of type `IndexType`,
+/// checking that the portable binary format reads back to the same decomposition.
+fn write_deterministic<IndexType: bidirected_adjacency_array::index::GraphIndexInteger>(
+    name: &str,
+) -> (Vec<u8>, Vec<u8>) {
+    let graph =
+        BidirectedAdjacencyArray::<IndexType, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+            BufReader::new(File::open(format!("test_files/{name}.gfa")).unwrap()),
+        )
+        .unwrap();
+    let spqr_decomposition = SPQRDecomposition::read_plain_spqr(
+        &graph,
+        BufReader::new(File::open(format!("test_files/{name}.spqr")).unwrap()),
+    )
+    .unwrap();
+
+    let mut plain = Vec::new();
+    spqr_decomposition.write_plain_spqr(&mut plain).unwrap();
+    let mut portable = Vec::new();
+    spqr_decomposition
+        .write_portable_binary(&mut portable)
+        .unwrap();
     assert_eq!(
-        spqr_decomposition.components,
-        read_spqr_decomposition.components,
+        SPQRDecomposition::read_portable_binary(&graph, &portable[..]).unwrap(),
+        spqr_decomposition
     );
-    assert_eq!(spqr_decomposition.blocks, read_spqr_decomposition.blocks);
+    (plain, portable)
+}
+
+#[test]
+fn test_portable_binary_is_deterministic() {
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let expected = write_deterministic::<u8>(name);
+        assert_eq!(write_deterministic::<u16>(name), expected, "{name}");
+        assert_eq!(write_deterministic::<u32>(name), expected, "{name}");
+        assert_eq!(write_deterministic::<u64>(name), expected, "{name}");
+        assert_eq!(write_deterministic::<usize>(name), expected, "{name}");
+    }
+}
+
+#[test]
+fn test_portable_binary_errors() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let (_, portable) = write_deterministic::<u32>("tiny1");
+
+    for len in 0..portable.len() {
+        assert!(
+            SPQRDecomposition::read_portable_binary_from_slice(&graph, &portable[..len]).is_err(),
+            "{len}"
+        );
+    }
+    let mut trailing = portable.clone();
+    trailing.push(0);
+    assert!(SPQRDecomposition::read_portable_binary_from_slice(&graph, &trailing).is_err());
+
+    // The first node of the first component is out of bounds.
+    let mut corrupt = portable.clone();
+    corrupt[3] = 100;
     assert_eq!(
-        spqr_decomposition.cut_nodes,
-        read_spqr_decomposition.cut_nodes,
+        SPQRDecomposition::read_portable_binary_from_slice(&graph, &corrupt)
+            .unwrap_err()
+            .kind(),
+        std::io::ErrorKind::InvalidData
     );
+
+    let mut unknown_version = portable;
+    unknown_version[0] = PORTABLE_BINARY_VERSION + 1;
+    assert!(
+        SPQRDecomposition::read_portable_binary_from_slice(&graph, &unknown_version)
+            .unwrap_err()
+            .to_string()
+            .contains("unsupported portable binary version")
+    );
+}
+
+#[test]
+fn test_read_any_binary() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition = SPQRDecomposition::read_plain_spqr(
+        &graph,
+        BufReader::new(File::open("test_files/tiny1.spqr").unwrap()),
+    )
+    .unwrap();
+    let mut native = Vec::new();
+    spqr_decomposition.write_binary(&mut native).unwrap();
+    let mut portable = Vec::new();
+    spqr_decomposition
+        .write_portable_binary(&mut portable)
+        .unwrap();
+    assert_eq!(portable[0], PORTABLE_BINARY_VERSION);
+    assert_ne!(native[0], PORTABLE_BINARY_VERSION);
+
+    for bytes in [&native, &portable] {
+        assert_eq!(
+            SPQRDecomposition::read_any_binary(&graph, &bytes[..]).unwrap(),
+            spqr_decomposition
+        );
+    }
+
+    // The file written before the binary format was versioned (see `test_read_legacy`) has one component,
+    // so its first byte equals the version byte of the portable format by chance.
+    #[cfg(all(target_pointer_width = "64", target_endian = "little"))]
+    {
+        let legacy = fs::read("test_files/tiny1.legacy.spqr.bin").unwrap();
+        assert_eq!(legacy[0], PORTABLE_BINARY_VERSION);
+        assert_eq!(
+            SPQRDecomposition::read_any_binary(&graph, &legacy[..]).unwrap(),
+            spqr_decomposition
+        );
+        assert_eq!(
+            SPQRDecomposition::read_any_binary_from_slice(&graph, &legacy).unwrap(),
+            spqr_decomposition
+        );
+    }
+}
+
+/// `test_files/tiny1.legacy.spqr.bin` was written by `write_binary` before the binary format was versioned,
+/// on a 64-bit little-endian platform with `u8` indices.
+#[cfg(all(target_pointer_width = "64", target_endian = "little"))]
+#[test]
+fn test_read_legacy() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition = SPQRDecomposition::read_plain_spqr(
+        &graph,
+        BufReader::new(File::open("test_files/tiny1.spqr").unwrap()),
+    )
+    .unwrap();
+    let path = "test_files/tiny1.legacy.spqr.bin";
+    let legacy = fs::read(path).unwrap();
+    assert!(!legacy.starts_with(&NATIVE_BINARY_MAGIC));
+
     assert_eq!(
-        spqr_decomposition.spqr_nodes,
-        read_spqr_decomposition.spqr_nodes,
+        SPQRDecomposition::read_binary(&graph, &legacy[..]).unwrap(),
+        spqr_decomposition
     );
     assert_eq!(
-        spqr_decomposition.spqr_edges,
-        read_spqr_decomposition.spqr_edges,
+        SPQRDecomposition::read_binary_from_slice(&graph, &legacy).unwrap(),
+        spqr_decomposition
     );
     assert_eq!(
-        spqr_decomposition.node_data,
-        read_spqr_decomposition.node_data,
+        SPQRDecomposition::read_binary_from_path(&graph, path).unwrap(),
+        spqr_decomposition
     );
+    #[cfg(feature = "parallel")]
     assert_eq!(
-        spqr_decomposition.edge_data,
-        read_spqr_decomposition.edge_data,
+        SPQRDecomposition::read_binary_parallel(&graph, &legacy[..]).unwrap(),
+        spqr_decomposition
     );
+    for len in [0, legacy.len() / 2, legacy.len() - 1] {
+        assert!(SPQRDecomposition::read_binary(&graph, &legacy[..len]).is_err());
+        assert!(SPQRDecomposition::read_binary_from_slice(&graph, &legacy[..len]).is_err());
+    }
+
+    // Files are written with the header, and other versions are rejected.
+    let mut current = Vec::new();
+    spqr_decomposition.write_binary(&mut current).unwrap();
+    assert!(current.starts_with(&NATIVE_BINARY_MAGIC));
+    assert_eq!(current[NATIVE_BINARY_MAGIC.len()], NATIVE_BINARY_VERSION);
+    current[NATIVE_BINARY_MAGIC.len()] += 1;
+    for error in [
+        SPQRDecomposition::read_binary(&graph, &current[..]).unwrap_err(),
+        SPQRDecomposition::read_binary_from_slice(&graph, &current).unwrap_err(),
+    ] {
+        assert!(error.to_string().contains("unsupported binary version"));
+    }
 }
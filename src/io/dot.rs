@@ -0,0 +1,291 @@
+//! Methods for exporting parts of an SPQR decomposition in the [DOT](https://graphviz.org/doc/info/lang.html) format of Graphviz.
+//!
+//! Nodes are identified by their names, which are quoted, so they may contain any characters.
+//!
+//! Besides single blocks and skeletons, the whole decomposition can be rendered as a forest of SPQR trees
+//! whose appearance is configured by a [`DotStyle`], such that figures need no post-processing of the DOT text.
+
+use std::{borrow::Borrow, io::Write};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, SPQRNodeIndex},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The appearance of the decomposition in [`SPQRDecomposition::write_decomposition_dot`].
+///
+/// Colors are given as Graphviz [color names or RGB values](https://graphviz.org/doc/info/colors.html).
+/// Labels are templates in which `{name}` is replaced by the name of the entity as in the plain SPQR file format,
+/// `{type}` by the letter of its SPQR node type, or `B` for blocks, `{size}` by its amount of nodes,
+/// and `{extra}` by its extra data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotStyle {
+    pub s_node_color: String,
+    pub p_node_color: String,
+    pub r_node_color: String,
+    /// The color of blocks without SPQR tree, which are drawn as a single box.
+    pub block_color: String,
+    pub cut_node_color: String,
+    pub spqr_node_label: String,
+    pub block_label: String,
+    /// Whether to draw a frame around the entities of each component.
+    pub cluster_components: bool,
+    /// Whether to draw a frame around the SPQR tree of each block.
+    pub cluster_blocks: bool,
+}
+
+impl Default for DotStyle {
+    fn default() -> Self {
+        Self {
+            s_node_color: "lightblue".to_string(),
+            p_node_color: "palegreen".to_string(),
+            r_node_color: "lightsalmon".to_string(),
+            block_color: "lightgrey".to_string(),
+            cut_node_color: "white".to_string(),
+            spqr_node_label: "{name}".to_string(),
+            block_label: "{name}".to_string(),
+            cluster_components: false,
+            cluster_blocks: true,
+        }
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Write the subgraph induced by the given block as an undirected DOT graph.
+    pub fn write_block_dot(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        writeln!(writer, "graph {} {{", quote(&format!("B{block_index}")))?;
+        for node_index in self.blocks[block_index].iter_nodes() {
+            writeln!(
+                writer,
+                "  {};",
+                quote(&self.graph_ref().node_name(node_index))
+            )?;
+        }
+        for edge_index in self.iter_edges_in_block(block_index) {
+            let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+            writeln!(
+                writer,
+                "  {} -- {};",
+                quote(&self.graph_ref().node_name(u)),
+                quote(&self.graph_ref().node_name(v)),
+            )?;
+        }
+        writeln!(writer, "}}")
+    }
+
+    /// Write the skeleton of the given SPQR node as an undirected DOT graph.
+    ///
+    /// Virtual edges are drawn dashed and labelled with the name of their SPQR edge.
+    pub fn write_spqr_node_skeleton_dot(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        writeln!(
+            writer,
+            "graph {} {{",
+            quote(&self.spqr_node_name(spqr_node_index))
+        )?;
+        for node_index in self.spqr_nodes[spqr_node_index].iter_nodes() {
+            writeln!(
+                writer,
+                "  {};",
+                quote(&self.graph_ref().node_name(node_index))
+            )?;
+        }
+        for (skeleton_edge, (u, v)) in self.iter_skeleton_edges(spqr_node_index) {
+            write!(
+                writer,
+                "  {} -- {}",
+                quote(&self.graph_ref().node_name(u)),
+                quote(&self.graph_ref().node_name(v)),
+            )?;
+            match skeleton_edge {
+                SkeletonEdge::Real(_) => writeln!(writer, ";")?,
+                SkeletonEdge::Virtual(spqr_edge_index) => writeln!(
+                    writer,
+                    " [style=dashed, label={}];",
+                    quote(&format!("V{spqr_edge_index}"))
+                )?,
+            }
+        }
+        writeln!(writer, "}}")
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Write the SPQR trees of all blocks as a single undirected DOT graph with the given style.
+    ///
+    /// SPQR nodes are filled with the color of their type, and blocks without SPQR tree are drawn as a single box.
+    /// Each cut node is drawn as a circle labelled with the name of its node,
+    /// connected to the first SPQR node containing it in each of its blocks, or to the box of the block.
+    /// Components with a single node are drawn like cut nodes.
+    pub fn write_decomposition_dot(
+        &self,
+        style: &DotStyle,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        writeln!(writer, "graph \"SPQR\" {{")?;
+        writeln!(writer, "  node [style=filled];")?;
+        let mut edges = Vec::new();
+
+        for (component_index, component) in self.iter_components() {
+            let indent = if style.cluster_components {
+                writeln!(
+                    writer,
+                    "  subgraph {} {{",
+                    quote(&format!("cluster_G{component_index}"))
+                )?;
+                writeln!(
+                    writer,
+                    "    label={};",
+                    quote(&format!("G{component_index}"))
+                )?;
+                "    "
+            } else {
+                "  "
+            };
+
+            if component.blocks.is_empty() {
+                writeln!(
+                    writer,
+                    "{indent}{} [shape=circle, fillcolor={}, label={}];",
+                    quote(&format!("G{component_index}")),
+                    quote(&style.cut_node_color),
+                    quote(&self.graph_ref().node_name(component.nodes[0])),
+                )?;
+            }
+
+            for &block_index in &component.blocks {
+                let block = &self.blocks[block_index];
+                let block_name = format!("B{block_index}");
+                let block_indent = if style.cluster_blocks && !block.spqr_nodes.is_empty() {
+                    writeln!(
+                        writer,
+                        "{indent}subgraph {} {{",
+                        quote(&format!("cluster_{block_name}"))
+                    )?;
+                    writeln!(writer, "{indent}  label={};", quote(&block_name))?;
+                    format!("{indent}  ")
+                } else {
+                    indent.to_string()
+                };
+
+                if block.spqr_nodes.is_empty() {
+                    let label = render_label(
+                        &style.block_label,
+                        &block_name,
+                        "B",
+                        block.node_count(),
+                        self.block_extra_data(block_index),
+                    );
+                    writeln!(
+                        writer,
+                        "{block_indent}{} [shape=box, fillcolor={}, label={}];",
+                        quote(&block_name),
+                        quote(&style.block_color),
+                        quote(&label),
+                    )?;
+                }
+                for (spqr_node_index, spqr_node) in self.iter_spqr_nodes_in_block(block_index) {
+                    let spqr_node_name = self.spqr_node_name(spqr_node_index);
+                    let spqr_node_type = spqr_node.spqr_node_type();
+                    let color = match spqr_node_type {
+                        SPQRNodeType::SNode => &style.s_node_color,
+                        SPQRNodeType::PNode => &style.p_node_color,
+                        SPQRNodeType::RNode => &style.r_node_color,
+                    };
+                    let label = render_label(
+                        &style.spqr_node_label,
+                        &spqr_node_name,
+                        &spqr_node_type.to_string(),
+                        spqr_node.node_count(),
+                        self.spqr_node_extra_data(spqr_node_index),
+                    );
+                    writeln!(
+                        writer,
+                        "{block_indent}{} [fillcolor={}, label={}];",
+                        quote(&spqr_node_name),
+                        quote(color),
+                        quote(&label),
+                    )?;
+                }
+                for (_, spqr_edge) in self.iter_spqr_edges_in_block(block_index) {
+                    let (u, v) = spqr_edge.endpoints();
+                    edges.push((self.spqr_node_name(u), self.spqr_node_name(v)));
+                }
+
+                if block_indent.len() > indent.len() {
+                    writeln!(writer, "{indent}}}")?;
+                }
+            }
+
+            for &cut_node_index in &component.cut_nodes {
+                let cut_node = &self.cut_nodes[cut_node_index];
+                let cut_node_name = format!("C{cut_node_index}");
+                writeln!(
+                    writer,
+                    "{indent}{} [shape=circle, fillcolor={}, label={}];",
+                    quote(&cut_node_name),
+                    quote(&style.cut_node_color),
+                    quote(&self.graph_ref().node_name(cut_node.node)),
+                )?;
+                for &block_index in &cut_node.adjacent_blocks {
+                    let target = self
+                        .node_spqr_node_indices(cut_node.node)
+                        .find(|&spqr_node_index| {
+                            self.spqr_nodes[spqr_node_index].block == block_index
+                        })
+                        .map_or_else(
+                            || format!("B{block_index}"),
+                            |spqr_node_index| self.spqr_node_name(spqr_node_index),
+                        );
+                    edges.push((cut_node_name.clone(), target));
+                }
+            }
+
+            if style.cluster_components {
+                writeln!(writer, "  }}")?;
+            }
+        }
+
+        for (u, v) in edges {
+            writeln!(writer, "  {} -- {};", quote(&u), quote(&v))?;
+        }
+        writeln!(writer, "}}")
+    }
+}
+
+/// Fills in the placeholders of a label template, see [`DotStyle`].
+fn render_label(
+    template: &str,
+    name: &str,
+    spqr_node_type: &str,
+    size: usize,
+    extra: &str,
+) -> String {
+    template
+        .replace("{name}", name)
+        .replace("{type}", spqr_node_type)
+        .replace("{size}", &size.to_string())
+        .replace("{extra}", extra)
+}
+
+/// Returns the given string as a quoted DOT identifier.
+fn quote(string: &str) -> String {
+    format!("\"{}\"", string.replace('\\', "\\\\").replace('"', "\\\""))
+}
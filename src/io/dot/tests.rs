@@ -0,0 +1,143 @@
+use std::fs;
+
+use bidirected_adjacency_array::{
+    graph::BidirectedAdjacencyArray,
+    io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
+};
+
+use crate::{decomposition::SPQRDecomposition, io::dot::DotStyle};
+
+#[test]
+fn test_tiny1_dot() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        fs::read_to_string("test_files/tiny1.gfa")
+            .unwrap()
+            .as_bytes(),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+    let mut buffer = Vec::new();
+    spqr_decomposition
+        .write_block_dot(0.into(), &mut buffer)
+        .unwrap();
+    let dot = String::from_utf8(buffer).unwrap();
+    assert!(dot.starts_with("graph \"B0\" {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert_eq!(
+        dot.lines()
+            .filter(|line| line.ends_with("\";") && !line.contains(" -- "))
+            .count(),
+        3
+    );
+    assert_eq!(dot.lines().filter(|line| line.contains(" -- ")).count(), 3);
+
+    let mut buffer = Vec::new();
+    spqr_decomposition
+        .write_spqr_node_skeleton_dot(0.into(), &mut buffer)
+        .unwrap();
+    let dot = String::from_utf8(buffer).unwrap();
+    assert!(dot.starts_with("graph \"S0\" {\n"));
+    let edge_count = spqr_decomposition.iter_skeleton_edges(0.into()).count();
+    assert_eq!(
+        dot.lines().filter(|line| line.contains(" -- ")).count(),
+        edge_count
+    );
+    assert_eq!(
+        dot.lines()
+            .filter(|line| line.contains("style=dashed"))
+            .count(),
+        spqr_decomposition
+            .spqr_node(0.into())
+            .iter_incident_spqr_edges()
+            .count()
+    );
+}
+
+#[test]
+fn test_multiedge_decomposition_dot() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        fs::read_to_string("test_files/multiedge.gfa")
+            .unwrap()
+            .as_bytes(),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/multiedge.spqr").unwrap();
+    let mut spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let statistics = spqr_decomposition.statistics();
+
+    let mut buffer = Vec::new();
+    spqr_decomposition
+        .write_decomposition_dot(&DotStyle::default(), &mut buffer)
+        .unwrap();
+    let dot = String::from_utf8(buffer).unwrap();
+    assert!(dot.starts_with("graph \"SPQR\" {\n  node [style=filled];\n"));
+    assert!(dot.ends_with("}\n"));
+    let blocks_with_spqr_tree = spqr_decomposition
+        .iter_blocks()
+        .filter(|(block_index, _)| {
+            spqr_decomposition
+                .iter_spqr_nodes_in_block(*block_index)
+                .next()
+                .is_some()
+        })
+        .count();
+    assert_eq!(
+        dot.matches("subgraph \"cluster_B").count(),
+        blocks_with_spqr_tree
+    );
+    assert_eq!(dot.matches("cluster_G").count(), 0);
+    assert_eq!(
+        dot.matches("fillcolor=\"lightblue\"").count(),
+        statistics.s_node_count
+    );
+    assert_eq!(
+        dot.matches("fillcolor=\"palegreen\"").count(),
+        statistics.p_node_count
+    );
+    assert_eq!(
+        dot.matches("shape=box").count(),
+        statistics.block_count - blocks_with_spqr_tree
+    );
+    let cut_node_edges = spqr_decomposition
+        .iter_cut_nodes()
+        .map(|(_, cut_node)| cut_node.iter_adjacent_blocks().count())
+        .sum::<usize>();
+    assert_eq!(
+        dot.matches(" -- ").count(),
+        statistics.spqr_edge_count + cut_node_edges
+    );
+
+    let (spqr_node_index, _) = spqr_decomposition.iter_spqr_nodes().next().unwrap();
+    spqr_decomposition.set_spqr_node_extra_data(spqr_node_index, "a \"quoted\" note");
+    let style = DotStyle {
+        p_node_color: "#00ff00".to_string(),
+        spqr_node_label: "{type}{size} {extra}".to_string(),
+        cluster_components: true,
+        cluster_blocks: false,
+        ..DotStyle::default()
+    };
+    let mut buffer = Vec::new();
+    spqr_decomposition
+        .write_decomposition_dot(&style, &mut buffer)
+        .unwrap();
+    let dot = String::from_utf8(buffer).unwrap();
+    assert_eq!(
+        dot.matches("subgraph \"cluster_G").count(),
+        statistics.component_count
+    );
+    assert_eq!(dot.matches("cluster_B").count(), 0);
+    assert_eq!(
+        dot.matches("fillcolor=\"#00ff00\"").count(),
+        statistics.p_node_count
+    );
+    let spqr_node = spqr_decomposition.spqr_node(spqr_node_index);
+    assert!(dot.contains(&format!(
+        "label=\"{}{} a \\\"quoted\\\" note\"",
+        spqr_node.spqr_node_type(),
+        spqr_node.node_count()
+    )));
+}
@@ -0,0 +1,50 @@
+//! Errors of the path-aware readers, which record where in which file reading failed.
+
+use std::{fmt, path::PathBuf};
+
+/// An error that occurred while reading the file at `path`.
+#[derive(Debug)]
+pub struct FileReadError<Source> {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The one-based number of the line at which the error occurred, if it is tied to a line of a text file.
+    pub line: Option<usize>,
+    /// The byte offset at which the error occurred, if known.
+    ///
+    /// For text files this is the start of [`line`](Self::line),
+    /// and for binary files the amount of bytes read before the error occurred.
+    pub offset: Option<u64>,
+    /// The error itself.
+    pub source: Source,
+}
+
+impl<Source> FileReadError<Source> {
+    /// Creates an error that is not tied to a location within the file.
+    pub fn new(path: impl Into<PathBuf>, source: impl Into<Source>) -> Self {
+        Self {
+            path: path.into(),
+            line: None,
+            offset: None,
+            source: source.into(),
+        }
+    }
+}
+
+impl<Source: fmt::Display> fmt::Display for FileReadError<Source> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, " (byte {offset})")?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+impl<Source: std::error::Error + 'static> std::error::Error for FileReadError<Source> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
@@ -0,0 +1,94 @@
+//! Methods for exporting parts of an SPQR decomposition in [GFA1](https://github.com/GFA-spec/GFA-spec/blob/master/GFA1.md) format.
+
+use std::{borrow::Borrow, io::Write};
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        indices::{BlockIndex, SPQRNodeIndex},
+    },
+    graph::BidirectedGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+impl<'graph, Graph: BidirectedGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Write the subgraph induced by the given block in GFA1 format.
+    ///
+    /// The edges keep the orientations they have in the graph.
+    pub fn write_block_gfa(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        writeln!(writer, "H\tVN:Z:1.0")?;
+
+        for node_index in self.blocks[block_index].iter_nodes() {
+            let node_name = self.graph_ref().node_name(node_index);
+            writeln!(writer, "S\t{node_name}\t*")?;
+        }
+
+        for edge_index in self.iter_edges_in_block(block_index) {
+            let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+            let (orientation_u, orientation_v) = self.graph_ref().edge_orientations(edge_index);
+            let node_name_u = self.graph_ref().node_name(u);
+            let node_name_v = self.graph_ref().node_name(v);
+            writeln!(
+                writer,
+                "L\t{node_name_u}\t{}\t{node_name_v}\t{}\t*",
+                orientation_u.gfa_sign(),
+                orientation_v.gfa_sign(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the skeleton of the given SPQR node in GFA1 format.
+    ///
+    /// Real edges keep the orientations they have in the graph.
+    /// Virtual edges have no orientation, so they are written as `+`/`+` links with a `VE:Z` tag containing the name of their SPQR edge.
+    pub fn write_spqr_node_skeleton_gfa(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        let spqr_node = &self.spqr_nodes[spqr_node_index];
+        writeln!(writer, "H\tVN:Z:1.0")?;
+
+        for node_index in spqr_node.iter_nodes() {
+            let node_name = self.graph_ref().node_name(node_index);
+            writeln!(writer, "S\t{node_name}\t*")?;
+        }
+
+        // Write real edges.
+        for edge_index in spqr_node.iter_edges() {
+            let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+            let (orientation_u, orientation_v) = self.graph_ref().edge_orientations(edge_index);
+            let node_name_u = self.graph_ref().node_name(u);
+            let node_name_v = self.graph_ref().node_name(v);
+            writeln!(
+                writer,
+                "L\t{node_name_u}\t{}\t{node_name_v}\t{}\t*",
+                orientation_u.gfa_sign(),
+                orientation_v.gfa_sign(),
+            )?;
+        }
+
+        // Write virtual edges.
+        for spqr_edge_index in spqr_node.iter_incident_spqr_edges() {
+            let (u, v) = self.spqr_edge(spqr_edge_index).virtual_edge();
+            let node_name_u = self.graph_ref().node_name(u);
+            let node_name_v = self.graph_ref().node_name(v);
+            writeln!(
+                writer,
+                "L\t{node_name_u}\t+\t{node_name_v}\t+\t*\tVE:Z:V{spqr_edge_index}",
+            )?;
+        }
+
+        Ok(())
+    }
+}
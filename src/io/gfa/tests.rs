@@ -0,0 +1,90 @@
+use std::{
+    fs::{self, File},
+    io::BufReader,
+};
+
+use bidirected_adjacency_array::{
+    graph::BidirectedAdjacencyArray,
+    io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
+};
+
+use crate::decomposition::SPQRDecomposition;
+
+#[test]
+fn test_tiny1_skeleton_orientations() {
+    let gfa_file = fs::read_to_string("test_files/tiny1.gfa").unwrap();
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, &mut spqr_decomposition_file.as_bytes())
+            .unwrap();
+
+    let mut expected_links = gfa_file
+        .lines()
+        .filter(|line| line.starts_with('L'))
+        .map(|line| line.split('\t').take(5).collect::<Vec<_>>().join("\t"))
+        .collect::<Vec<_>>();
+    expected_links.sort_unstable();
+
+    let mut links = Vec::new();
+    for (spqr_node_index, _) in spqr_decomposition.iter_spqr_nodes() {
+        let mut buffer = Vec::new();
+        spqr_decomposition
+            .write_spqr_node_skeleton_gfa(spqr_node_index, &mut buffer)
+            .unwrap();
+        let buffer = String::from_utf8(buffer).unwrap();
+        links.extend(
+            buffer
+                .lines()
+                .filter(|line| line.starts_with('L'))
+                .map(|line| line.split('\t').take(5).collect::<Vec<_>>().join("\t")),
+        );
+    }
+    links.sort_unstable();
+
+    assert_eq!(expected_links, links);
+}
+
+#[test]
+fn test_tiny1_block_links() {
+    let gfa_file = fs::read_to_string("test_files/tiny1.gfa").unwrap();
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        gfa_file.as_bytes(),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+    let mut expected_links = gfa_file
+        .lines()
+        .filter(|line| line.starts_with('L'))
+        .map(|line| line.split('\t').take(5).collect::<Vec<_>>().join("\t"))
+        .collect::<Vec<_>>();
+    expected_links.sort_unstable();
+
+    let mut links = Vec::new();
+    for (block_index, block) in spqr_decomposition.iter_blocks() {
+        let mut buffer = Vec::new();
+        spqr_decomposition
+            .write_block_gfa(block_index, &mut buffer)
+            .unwrap();
+        let buffer = String::from_utf8(buffer).unwrap();
+        assert_eq!(
+            buffer.lines().filter(|line| line.starts_with('S')).count(),
+            block.node_count()
+        );
+        links.extend(
+            buffer
+                .lines()
+                .filter(|line| line.starts_with('L'))
+                .map(|line| line.split('\t').take(5).collect::<Vec<_>>().join("\t")),
+        );
+    }
+    links.sort_unstable();
+
+    assert_eq!(expected_links, links);
+}
@@ -0,0 +1,110 @@
+//! A harness for cross-validating SPQR decompositions against an external tool based on [OGDF](https://ogdf.net).
+//!
+//! OGDF reads graphs in [GML](https://en.wikipedia.org/wiki/Graph_Modelling_Language), so the graph is written as GML with its node names as labels,
+//! and the tool is invoked as `<tool> <input.gml> <output.spqr>`.
+//! It is expected to compute the SPQR decomposition with OGDF and write it in the plain SPQR file format,
+//! using the labels as node names, after which the decomposition is imported
+//! and can be compared with [`SPQRDecomposition::is_equivalent_to`] or [`SPQRDecomposition::diff`].
+//!
+//! This module is only available with the `ogdf-compare` feature.
+//! Its tests read the path of the tool from the environment variable `SPQR_OGDF_TOOL`, and skip the comparison if it is not set.
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use thiserror::Error;
+
+use crate::{
+    decomposition::SPQRDecomposition, graph::StaticGraph, io::plain_spqr_file::error::ReadError,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The error returned by [`SPQRDecomposition::compute_with_ogdf`].
+#[derive(Error, Debug)]
+pub enum OgdfCompareError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("the OGDF tool exited with {status}: {stderr}")]
+    ToolFailed { status: ExitStatus, stderr: String },
+
+    #[error("cannot read the output of the OGDF tool: {0}")]
+    Read(#[from] ReadError),
+}
+
+/// Write the given graph in GML format, with the node indices as ids and the node names as labels.
+///
+/// Self-loops and parallel edges are written as they are.
+pub fn write_gml<Graph: StaticGraph>(graph: &Graph, mut writer: impl Write) -> std::io::Result<()> {
+    writeln!(writer, "graph [")?;
+    writeln!(writer, "  directed 0")?;
+    for node_index in graph.node_indices() {
+        // GML strings cannot contain double quotes, which are written as an HTML entity instead.
+        let label = graph
+            .node_name(node_index)
+            .replace('&', "&amp;")
+            .replace('"', "&quot;");
+        writeln!(writer, "  node [ id {node_index} label \"{label}\" ]")?;
+    }
+    for edge_index in graph.edge_indices() {
+        let (u, v) = graph.edge_endpoints(edge_index);
+        writeln!(writer, "  edge [ source {u} target {v} ]")?;
+    }
+    writeln!(writer, "]")
+}
+
+impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
+    /// Computes the SPQR decomposition of the given graph with an external OGDF-based tool, see the [module documentation](self).
+    ///
+    /// The input and output of the tool are stored in temporary files, which are removed afterwards.
+    pub fn compute_with_ogdf(
+        graph: &'graph Graph,
+        tool: impl AsRef<Path>,
+    ) -> Result<Self, OgdfCompareError> {
+        static INVOCATION: AtomicUsize = AtomicUsize::new(0);
+        let prefix = format!(
+            "spqr-tree-ogdf-{}-{}",
+            std::process::id(),
+            INVOCATION.fetch_add(1, Ordering::Relaxed)
+        );
+        let input = TemporaryFile(std::env::temp_dir().join(format!("{prefix}.gml")));
+        let output = TemporaryFile(std::env::temp_dir().join(format!("{prefix}.spqr")));
+
+        let mut writer = BufWriter::new(File::create(&input.0)?);
+        write_gml(graph, &mut writer)?;
+        writer.flush()?;
+        drop(writer);
+
+        let result = Command::new(tool.as_ref())
+            .arg(&input.0)
+            .arg(&output.0)
+            .output()?;
+        if !result.status.success() {
+            return Err(OgdfCompareError::ToolFailed {
+                status: result.status,
+                stderr: String::from_utf8_lossy(&result.stderr).into_owned(),
+            });
+        }
+
+        Ok(Self::read_plain_spqr(
+            graph,
+            BufReader::new(File::open(&output.0)?),
+        )?)
+    }
+}
+
+/// A file that is removed when dropped.
+struct TemporaryFile(PathBuf);
+
+impl Drop for TemporaryFile {
+    fn drop(&mut self) {
+        let _ignore = fs::remove_file(&self.0);
+    }
+}
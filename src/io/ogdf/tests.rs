@@ -0,0 +1,102 @@
+use std::fs;
+
+use bidirected_adjacency_array::{
+    graph::BidirectedAdjacencyArray,
+    io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
+};
+
+use crate::{decomposition::SPQRDecomposition, io::ogdf::write_gml};
+
+type TestGraph = BidirectedAdjacencyArray<u8, PlainGfaNodeData, PlainGfaEdgeData>;
+
+fn read_graph(name: &str) -> TestGraph {
+    BidirectedAdjacencyArray::read_gfa1(
+        fs::read_to_string(format!("test_files/{name}.gfa"))
+            .unwrap()
+            .as_bytes(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_write_gml() {
+    let graph = read_graph("tiny1");
+    let mut buffer = Vec::new();
+    write_gml(&graph, &mut buffer).unwrap();
+    let gml = String::from_utf8(buffer).unwrap();
+
+    assert!(gml.starts_with("graph [\n  directed 0\n"));
+    assert!(gml.contains("  node [ id 0 label \"a\" ]\n"));
+    assert_eq!(gml.matches("node [").count(), 11);
+    assert_eq!(gml.matches("edge [").count(), 15);
+    assert!(gml.ends_with("]\n"));
+}
+
+/// Runs the harness with a stand-in for the OGDF tool that outputs the expected decomposition.
+#[cfg(unix)]
+#[test]
+fn test_compute_with_ogdf_plumbing() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let graph = read_graph("tiny1");
+    let tool = std::env::temp_dir().join(format!("spqr-tree-fake-ogdf-{}.sh", std::process::id()));
+    fs::write(
+        &tool,
+        format!(
+            "#!/bin/sh\ngrep -q 'label \"a\"' \"$1\" || exit 1\ncp '{}' \"$2\"\n",
+            fs::canonicalize("test_files/tiny1.spqr").unwrap().display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&tool, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let expected = SPQRDecomposition::read_plain_spqr(
+        &graph,
+        fs::read_to_string("test_files/tiny1.spqr")
+            .unwrap()
+            .as_bytes(),
+    )
+    .unwrap();
+    let computed = SPQRDecomposition::compute_with_ogdf(&graph, &tool).unwrap();
+    assert!(computed.is_equivalent_to(&expected));
+
+    let failing = SPQRDecomposition::compute_with_ogdf(&graph, "/bin/false");
+    assert!(matches!(
+        failing,
+        Err(crate::io::ogdf::OgdfCompareError::ToolFailed { .. })
+    ));
+    fs::remove_file(tool).unwrap();
+}
+
+/// Compares the decompositions of the test files with those computed by the OGDF tool given in `SPQR_OGDF_TOOL`.
+#[test]
+fn test_ogdf_agrees_with_test_files() {
+    let Some(tool) = std::env::var_os("SPQR_OGDF_TOOL") else {
+        eprintln!("SPQR_OGDF_TOOL is not set, skipping the comparison with OGDF");
+        return;
+    };
+
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random1_1_False_0",
+        "random3_5_False_0",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = read_graph(name);
+        let expected = SPQRDecomposition::read_plain_spqr(
+            &graph,
+            fs::read_to_string(format!("test_files/{name}.spqr"))
+                .unwrap()
+                .as_bytes(),
+        )
+        .unwrap();
+        let computed = SPQRDecomposition::compute_with_ogdf(&graph, &tool).unwrap();
+        assert!(
+            computed.is_equivalent_to(&expected),
+            "{name}: {:?}",
+            expected.diff(&computed)
+        );
+    }
+}
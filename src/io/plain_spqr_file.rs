@@ -1,444 +1,1007 @@
 //! Methods for reading and writing an SPQR decomposition in the [`.spqr` file format](https://github.com/sebschmi/SPQR-tree-file-format).
 
 use std::{
-    io::{BufRead, Write},
+    borrow::Borrow,
+    fs::File,
+    io::{BufRead, BufReader, Seek, Write},
     iter,
+    path::Path,
 };
 
-use log::{debug, trace};
-use rustc_hash::FxHashMap;
+use log::{debug, trace, warn};
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
-    decomposition::{SPQRDecomposition, SPQRNodeType, builder::SPQRDecompositionBuilder},
+    decomposition::{
+        SPQRDecomposition,
+        builder::{SPQRDecompositionBuilder, SPQRDecompositionBuilderCapacities},
+        extra_data::ExtraDataPool,
+        indices::{BlockIndex, ComponentIndex, SPQREdgeIndex, SPQRNodeIndex},
+        statistics::SPQRDecompositionStatistics,
+    },
     graph::StaticGraph,
-    io::plain_spqr_file::{error::ReadError, line_reader::LineReader},
+    io::{
+        error::FileReadError,
+        plain_spqr_file::{
+            error::ReadError,
+            event::Event,
+            line_handler::{DeclaredNames, LineHandler},
+            line_reader::{Columns, LineReader},
+            line_writer::LineWriter,
+            version::FormatVersion,
+        },
+    },
+    profile::{Phase, Profile},
+    progress::{PROGRESS_INTERVAL, ProgressSink},
+    strictness::Strictness,
 };
 
 pub mod error;
+pub mod event;
+pub mod line_handler;
 mod line_reader;
+mod line_writer;
+#[cfg(feature = "parallel")]
+mod parallel;
 #[cfg(test)]
 pub mod tests;
+pub mod version;
+
+/// Count the entities declared in a file in the plain SPQR file format.
+///
+/// This is meant as a cheap first pass before reading the file with [`SPQRDecomposition::read_plain_spqr_with_capacities`].
+/// The file is not validated beyond splitting it into lines.
+pub fn scan_capacities(
+    reader: impl BufRead,
+) -> Result<SPQRDecompositionBuilderCapacities, ReadError> {
+    let mut line_reader = LineReader::new(reader);
+    let mut capacities = SPQRDecompositionBuilderCapacities::default();
+
+    while let Some(line) = line_reader.next()? {
+        match &line[0] {
+            "G" => capacities.components += 1,
+            "B" => capacities.blocks += 1,
+            "C" => capacities.cut_nodes += 1,
+            "S" | "P" | "R" => capacities.spqr_nodes += 1,
+            "V" => capacities.spqr_edges += 1,
+            _ => {}
+        }
+    }
+
+    Ok(capacities)
+}
+
+/// Computes summary statistics of a file in the plain SPQR file format in a single pass, without building the decomposition.
+///
+/// This allows to inspect huge files quickly, since only the names of blocks with two nodes and of their SPQR nodes are stored,
+/// which are needed to detect bridges.
+/// For a valid file, the result equals [`SPQRDecomposition::statistics`] of the decomposition read from it.
+/// The lines are not validated, and lines of unknown types are ignored.
+pub fn scan_statistics(reader: impl BufRead) -> Result<SPQRDecompositionStatistics, ReadError> {
+    let mut line_reader = LineReader::new(reader);
+    let mut statistics = SPQRDecompositionStatistics::default();
+    let mut component_block_counts = FxHashMap::<String, usize>::default();
+    // The amount of edges of each block with two nodes, which is a bridge if it has exactly one edge.
+    let mut two_node_block_edge_counts = FxHashMap::<String, usize>::default();
+    let mut two_node_block_spqr_nodes = FxHashMap::<String, String>::default();
+
+    while let Some(line) = line_reader.next()? {
+        // The amount of nodes listed from the given column on.
+        let node_count = |skip| line.iter().skip(skip).count();
+        match &line[0] {
+            "G" => {
+                statistics.component_count += 1;
+                statistics.node_count += node_count(2);
+                statistics.largest_component_node_count =
+                    statistics.largest_component_node_count.max(node_count(2));
+                if let Some(component_name) = line.column(1) {
+                    component_block_counts.insert(component_name.to_string(), 0);
+                }
+            }
+            "B" => {
+                statistics.block_count += 1;
+                *statistics
+                    .block_size_histogram
+                    .entry(node_count(3))
+                    .or_default() += 1;
+                if let Some(count) = line
+                    .column(2)
+                    .and_then(|component_name| component_block_counts.get_mut(component_name))
+                {
+                    *count += 1;
+                }
+                if node_count(3) == 2
+                    && let Some(block_name) = line.column(1)
+                {
+                    two_node_block_edge_counts.insert(block_name.to_string(), 0);
+                }
+            }
+            "C" => statistics.cut_node_count += 1,
+            spqr_node_type @ ("S" | "P" | "R") => {
+                statistics.spqr_node_count += 1;
+                match spqr_node_type {
+                    "S" => {
+                        statistics.s_node_count += 1;
+                        *statistics
+                            .s_node_size_histogram
+                            .entry(node_count(3))
+                            .or_default() += 1;
+                    }
+                    "P" => statistics.p_node_count += 1,
+                    _ => {
+                        statistics.r_node_count += 1;
+                        *statistics
+                            .r_node_size_histogram
+                            .entry(node_count(3))
+                            .or_default() += 1;
+                    }
+                }
+                if let (Some(spqr_node_name), Some(block_name)) = (line.column(1), line.column(2))
+                    && two_node_block_edge_counts.contains_key(block_name)
+                {
+                    two_node_block_spqr_nodes
+                        .insert(spqr_node_name.to_string(), block_name.to_string());
+                }
+            }
+            "V" => statistics.spqr_edge_count += 1,
+            "E" | "Q" => {
+                statistics.edge_count += 1;
+                if let Some(name) = line.column(2) {
+                    let block_name = two_node_block_spqr_nodes
+                        .get(name)
+                        .map_or(name, String::as_str);
+                    if let Some(count) = two_node_block_edge_counts.get_mut(block_name) {
+                        *count += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for block_count in component_block_counts.into_values() {
+        *statistics
+            .blocks_per_component_histogram
+            .entry(block_count)
+            .or_default() += 1;
+    }
+    statistics.bridge_count = two_node_block_edge_counts
+        .into_values()
+        .filter(|&edge_count| edge_count == 1)
+        .count();
+
+    Ok(statistics)
+}
+
+/// Parses a file in the plain SPQR file format line by line, passing each line to `handler` as an [`Event`].
+///
+/// No decomposition is built and no graph is needed, so this allows to filter or transform files that do not fit into memory.
+/// Lines are only checked for missing columns, and names are not resolved.
+/// Lines of unknown types are passed as [`Event::Other`].
+/// Parsing stops at the first error returned by `handler`.
+pub fn parse_events<Error: From<ReadError>>(
+    reader: impl BufRead,
+    mut handler: impl FnMut(Event<'_>) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let mut line_reader = LineReader::new(reader);
+    let version = check_header(line_reader.next().map_err(ReadError::from)?)?;
+    handler(Event::Header { version })?;
+
+    while let Some(line) = line_reader.next().map_err(ReadError::from)? {
+        handler(Event::from_columns(version, &line)?)?;
+    }
+
+    Ok(())
+}
 
 impl<'graph, Graph: StaticGraph> SPQRDecomposition<'graph, Graph> {
     /// Read an SPQR decomposition in the plain SPQR file format.
     pub fn read_plain_spqr(graph: &'graph Graph, reader: impl BufRead) -> Result<Self, ReadError> {
-        debug!("Reading SPQR decomposition in plain SPQR format...");
-        let mut line_reader = LineReader::new(reader);
-
-        // Parse header.
-        trace!("Parsing header");
-        let header = line_reader.next()?.ok_or(ReadError::MissingHeader)?;
-        if &header[0] != "H" {
-            return Err(ReadError::MissingHeader);
+        Self::read_plain_spqr_with_capacities(graph, reader, Default::default())
+    }
+
+    /// Read an SPQR decomposition in the plain SPQR file format, preallocating space for the given amounts of entities.
+    ///
+    /// This includes the maps from names to indices that are used during parsing.
+    /// The capacities can be obtained from a first pass over the file using [`scan_capacities`].
+    pub fn read_plain_spqr_with_capacities(
+        graph: &'graph Graph,
+        reader: impl BufRead,
+        capacities: SPQRDecompositionBuilderCapacities,
+    ) -> Result<Self, ReadError> {
+        Self::read_plain_spqr_with_strictness(graph, reader, capacities, Strictness::Error)
+    }
+
+    /// Read an SPQR decomposition in the plain SPQR file format, reacting to inconsistencies according to `strictness`.
+    ///
+    /// With [`Strictness::Warn`], lines of unknown types are logged and skipped,
+    /// and the decomposition is built as described in [`try_build_with_strictness`](SPQRDecompositionBuilder::try_build_with_strictness).
+    ///
+    /// Errors that are not [inconsistencies](ReadError::is_inconsistency), such as I/O errors, are always returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file is inconsistent and `strictness` is [`Strictness::Panic`].
+    pub fn read_plain_spqr_with_strictness(
+        graph: &'graph Graph,
+        reader: impl BufRead,
+        capacities: SPQRDecompositionBuilderCapacities,
+        strictness: Strictness,
+    ) -> Result<Self, ReadError> {
+        let result = parse_lines(
+            graph,
+            &mut LineReader::new(reader),
+            capacities,
+            strictness,
+            &mut (),
+            &mut (),
+        )
+        .and_then(LineApplier::finish);
+        match result {
+            Err(error) if strictness == Strictness::Panic && error.is_inconsistency() => {
+                panic!("{error}")
+            }
+            result => result,
         }
-        if header.column(1) != Some("v0.4") {
-            return Err(ReadError::UnsupportedVersion);
+    }
+
+    /// Read an SPQR decomposition in the plain SPQR file format, passing lines of unknown types to `line_handler`.
+    ///
+    /// Lines that the handler does not handle are rejected with [`ReadError::InvalidLineType`] as usual.
+    pub fn read_plain_spqr_with_line_handler(
+        graph: &'graph Graph,
+        reader: impl BufRead,
+        line_handler: &mut impl LineHandler<Graph>,
+    ) -> Result<Self, ReadError> {
+        parse_lines(
+            graph,
+            &mut LineReader::new(reader),
+            Default::default(),
+            Strictness::Error,
+            &mut (),
+            line_handler,
+        )?
+        .finish()
+    }
+
+    /// Read an SPQR decomposition in the plain SPQR file format from the file at `path`.
+    ///
+    /// Errors record the path, and errors caused by a specific line also its line number and byte offset.
+    pub fn read_plain_spqr_from_path(
+        graph: &'graph Graph,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, FileReadError<ReadError>> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|error| FileReadError::new(path, error))?;
+        let mut line_reader = LineReader::new(BufReader::new(file));
+        parse_lines(
+            graph,
+            &mut line_reader,
+            Default::default(),
+            Strictness::Error,
+            &mut (),
+            &mut (),
+        )
+        .map_err(|error| FileReadError {
+            path: path.to_owned(),
+            line: Some(line_reader.line_number()),
+            offset: Some(line_reader.line_offset()),
+            source: error,
+        })?
+        .finish()
+        .map_err(|error| FileReadError::new(path, error))
+    }
+
+    /// Read an SPQR decomposition in the plain SPQR file format in two passes.
+    ///
+    /// The first pass counts the entities with [`scan_capacities`], then the reader is rewound to its start
+    /// and the decomposition is read with [`read_plain_spqr_with_capacities`](Self::read_plain_spqr_with_capacities).
+    pub fn read_plain_spqr_presized(
+        graph: &'graph Graph,
+        mut reader: impl BufRead + Seek,
+    ) -> Result<Self, ReadError> {
+        let capacities = scan_capacities(&mut reader)?;
+        reader.rewind()?;
+        Self::read_plain_spqr_with_capacities(graph, reader, capacities)
+    }
+
+    /// Read an SPQR decomposition in the plain SPQR file format, recording the duration and allocations of each phase in `profile`.
+    pub fn read_plain_spqr_with_profile(
+        graph: &'graph Graph,
+        reader: impl BufRead,
+        profile: &mut Profile,
+    ) -> Result<Self, ReadError> {
+        profile
+            .record(Phase::Parse, || {
+                parse_lines(
+                    graph,
+                    &mut LineReader::new(reader),
+                    Default::default(),
+                    Strictness::Error,
+                    &mut (),
+                    &mut (),
+                )
+            })?
+            .finish_with_profile(profile)
+    }
+
+    /// Read an SPQR decomposition in the plain SPQR file format, reporting the amount of lines parsed and the phases of building it to `progress`.
+    pub fn read_plain_spqr_with_progress(
+        graph: &'graph Graph,
+        reader: impl BufRead,
+        progress: &mut impl ProgressSink,
+    ) -> Result<Self, ReadError> {
+        progress.start_phase(Phase::Parse, None);
+        let line_applier = parse_lines(
+            graph,
+            &mut LineReader::new(reader),
+            Default::default(),
+            Strictness::Error,
+            progress,
+            &mut (),
+        )?;
+        progress.finish_phase(Phase::Parse);
+        line_applier
+            .into_builder()?
+            .try_build_with_progress(progress)
+            .map_err(|error| ReadError::InconsistentDecomposition(error.to_string()))
+    }
+}
+
+/// Parses all lines of a file in the plain SPQR file format, without building the decomposition yet.
+///
+/// Lines of types unknown to the version of the file are passed to `line_handler` first.
+/// On error, the line reader points to the line that caused it.
+fn parse_lines<'graph, Graph: StaticGraph>(
+    graph: &'graph Graph,
+    line_reader: &mut LineReader<impl BufRead>,
+    capacities: SPQRDecompositionBuilderCapacities,
+    strictness: Strictness,
+    progress: &mut impl ProgressSink,
+    line_handler: &mut impl LineHandler<Graph>,
+) -> Result<LineApplier<'graph, Graph>, ReadError> {
+    debug!("Reading SPQR decomposition in plain SPQR format...");
+
+    let version = check_header(line_reader.next()?)?;
+
+    let name_to_node_index = node_name_map(graph);
+    let mut line_applier = LineApplier::new(graph, capacities, strictness);
+
+    // The header counts as a line, so the progress matches the line numbers of the file.
+    let mut line_count = 1;
+    while let Some(line) = line_reader.next()? {
+        let handled = !version.line_types().contains(&&line[0])
+            && line_handler
+                .handle_line(
+                    &line.iter().collect::<Vec<_>>(),
+                    &line_applier.declared_names(&name_to_node_index),
+                )
+                .map_err(ReadError::LineHandler)?;
+        if !handled {
+            line_applier.apply(Event::from_columns(version, &line)?, |_, node_name| {
+                name_to_node_index
+                    .get(node_name)
+                    .copied()
+                    .ok_or_else(|| ReadError::UnknownNodeName(node_name.to_string()))
+            })?;
         }
-        if header.column(2).is_none() {
-            return Err(ReadError::MissingHeaderUrl);
+        line_count += 1;
+        if line_count % PROGRESS_INTERVAL == 0 {
+            progress.advance(line_count);
         }
+    }
+    progress.advance(line_count);
+
+    Ok(line_applier)
+}
 
-        let mut builder = SPQRDecompositionBuilder::new(graph);
-        let name_to_node_index: FxHashMap<_, _> = graph
+/// Checks the header line of a file in the plain SPQR file format and returns the declared version.
+fn check_header(header: Option<Columns<'_>>) -> Result<FormatVersion, ReadError> {
+    trace!("Parsing header");
+    let header = header.ok_or(ReadError::MissingHeader)?;
+    if &header[0] != "H" {
+        return Err(ReadError::MissingHeader);
+    }
+    let version_name = header.column(1).unwrap_or_default();
+    let Some(version) = FormatVersion::from_name(version_name) else {
+        warn!("Unsupported file format version {version_name:?}");
+        return Err(ReadError::UnsupportedVersion);
+    };
+    if header.column(2).is_none() {
+        return Err(ReadError::MissingHeaderUrl);
+    }
+
+    debug!("File format version is {version}");
+    Ok(version)
+}
+
+fn node_name_map<Graph: StaticGraph>(graph: &Graph) -> FxHashMap<String, Graph::NodeIndex> {
+    let mut name_to_node_index =
+        FxHashMap::with_capacity_and_hasher(graph.node_count(), Default::default());
+    name_to_node_index.extend(
+        graph
             .node_indices()
-            .map(|node_index| (graph.node_name(node_index).into_owned(), node_index))
-            .collect();
-        let mut name_to_component_index = FxHashMap::default();
-        let mut name_to_block_index = FxHashMap::default();
-        let mut name_to_spqr_node_index = FxHashMap::default();
-        let mut name_to_spqr_edge_index = FxHashMap::default();
-
-        while let Some(line) = line_reader.next()? {
-            match &line[0] {
-                "G" => {
-                    trace!("Parsing G-line");
-                    let component_name = line
-                        .column(1)
-                        .ok_or(ReadError::MissingComponentNameInGLine)?;
-                    let nodes = line
-                        .iter()
-                        .skip(2)
-                        .map(|node| {
-                            name_to_node_index
-                                .get(node)
-                                .copied()
-                                .ok_or_else(|| ReadError::UnknownNodeName(node.to_string()))
-                        })
-                        .collect::<Result<Vec<_>, _>>()?;
-                    trace!(
-                        "Found component {component_name} with {} nodes",
-                        nodes.len()
-                    );
+            .map(|node_index| (graph.node_name(node_index).into_owned(), node_index)),
+    );
+    name_to_node_index
+}
 
-                    if nodes.is_empty() {
-                        return Err(ReadError::EmptyComponent);
-                    }
+/// Applies the lines following the header of a file in the plain SPQR file format to a builder.
+struct LineApplier<'graph, Graph: StaticGraph> {
+    graph: &'graph Graph,
+    builder: SPQRDecompositionBuilder<'graph, Graph>,
+    name_to_component_index: FxHashMap<String, ComponentIndex<Graph::IndexType>>,
+    name_to_block_index: FxHashMap<String, BlockIndex<Graph::IndexType>>,
+    name_to_spqr_node_index: FxHashMap<String, SPQRNodeIndex<Graph::IndexType>>,
+    name_to_spqr_edge_index: FxHashMap<String, SPQREdgeIndex<Graph::IndexType>>,
+    extra_data_pool: ExtraDataPool,
+    /// The nodes and block names of the C-lines, which are applied after all other lines,
+    /// because a C-line may refer to blocks that are declared after it.
+    cut_nodes: Vec<(Graph::NodeIndex, Vec<String>)>,
+    /// The edges of multiedges that E-lines were resolved to.
+    resolved_multiedges: FxHashSet<Graph::EdgeIndex>,
+    strictness: Strictness,
+}
 
-                    let component_index = builder.add_component(nodes);
-                    name_to_component_index.insert(component_name.to_string(), component_index);
-                }
-                "N" => {
-                    trace!("Parsing N-line");
-                    let node_name = line.column(1).ok_or(ReadError::MissingNodeNameInNLine)?;
-                    let extra_data = line.iter().skip(2).collect::<Vec<_>>();
-                    let extra_data = extra_data.join(" ");
-                    let node_index = name_to_node_index
-                        .get(node_name)
-                        .copied()
-                        .ok_or_else(|| ReadError::UnknownNodeName(node_name.to_string()))?;
-                    builder.add_extra_data_to_node(node_index, extra_data);
-                }
-                "B" => {
-                    trace!("Parsing B-line");
-                    let block_name = line.column(1).ok_or(ReadError::MissingBlockNameInBLine)?;
-                    let component_name = line
-                        .column(2)
-                        .ok_or(ReadError::MissingComponentNameInBLine)?;
-                    let component_index =
-                        *name_to_component_index.get(component_name).ok_or_else(|| {
-                            ReadError::UnknownComponentName(component_name.to_string())
-                        })?;
-                    let nodes = line
-                        .iter()
-                        .skip(3)
-                        .map(|node| {
-                            name_to_node_index
-                                .get(node)
-                                .copied()
-                                .ok_or_else(|| ReadError::UnknownNodeName(node.to_string()))
-                        })
-                        .collect::<Result<Vec<_>, _>>()?;
-
-                    if nodes.is_empty() {
-                        return Err(ReadError::EmptyBlock);
-                    }
+impl<'graph, Graph: StaticGraph> LineApplier<'graph, Graph> {
+    fn new(
+        graph: &'graph Graph,
+        capacities: SPQRDecompositionBuilderCapacities,
+        strictness: Strictness,
+    ) -> Self {
+        Self {
+            graph,
+            strictness,
+            builder: SPQRDecompositionBuilder::with_capacities(graph, capacities),
+            name_to_component_index: FxHashMap::with_capacity_and_hasher(
+                capacities.components,
+                Default::default(),
+            ),
+            name_to_block_index: FxHashMap::with_capacity_and_hasher(
+                capacities.blocks,
+                Default::default(),
+            ),
+            name_to_spqr_node_index: FxHashMap::with_capacity_and_hasher(
+                capacities.spqr_nodes,
+                Default::default(),
+            ),
+            name_to_spqr_edge_index: FxHashMap::with_capacity_and_hasher(
+                capacities.spqr_edges,
+                Default::default(),
+            ),
+            extra_data_pool: ExtraDataPool::new(),
+            cut_nodes: Vec::with_capacity(capacities.cut_nodes),
+            resolved_multiedges: FxHashSet::default(),
+        }
+    }
 
-                    let block_index = builder.add_block(component_index, nodes);
-                    trace!("Block {block_name} has index {block_index}");
-                    name_to_block_index.insert(block_name.to_string(), block_index);
-                }
-                "C" => {
-                    trace!("Parsing C-line");
-                    let cut_node_name = line.column(1).ok_or(ReadError::MissingNodeNameInCLine)?;
-                    let cut_node_index = name_to_node_index
-                        .get(cut_node_name)
-                        .copied()
-                        .ok_or_else(|| ReadError::UnknownNodeName(cut_node_name.to_string()))?;
-                    let block_indices =
-                        line.iter()
-                            .skip(2)
-                            .map(|block_name| {
-                                name_to_block_index.get(block_name).cloned().ok_or_else(|| {
-                                    ReadError::UnknownBlockName(block_name.to_string())
-                                })
-                            })
-                            .collect::<Result<Vec<_>, _>>()?;
-
-                    if block_indices.is_empty() {
-                        return Err(ReadError::EmptyCutNode);
-                    }
+    fn declared_names<'this>(
+        &'this self,
+        name_to_node_index: &'this FxHashMap<String, Graph::NodeIndex>,
+    ) -> DeclaredNames<'this, Graph> {
+        DeclaredNames {
+            nodes: name_to_node_index,
+            components: &self.name_to_component_index,
+            blocks: &self.name_to_block_index,
+            spqr_nodes: &self.name_to_spqr_node_index,
+            spqr_edges: &self.name_to_spqr_edge_index,
+        }
+    }
 
-                    builder.add_cut_node(cut_node_index, block_indices);
+    /// Applies a single line that was parsed into an [`Event`].
+    ///
+    /// Node names are resolved by `resolve_node`, which receives the column and the name of the node.
+    fn apply(
+        &mut self,
+        event: Event<'_>,
+        resolve_node: impl Fn(usize, &str) -> Result<Graph::NodeIndex, ReadError>,
+    ) -> Result<(), ReadError> {
+        match event {
+            Event::Component { name, nodes } => {
+                trace!("Parsing G-line");
+                let nodes = nodes
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, node)| resolve_node(2 + index, node))
+                    .collect::<Result<Vec<_>, _>>()?;
+                trace!("Found component {name} with {} nodes", nodes.len());
+
+                if nodes.is_empty() {
+                    return Err(ReadError::EmptyComponent);
                 }
-                "S" | "P" | "R" => {
-                    let spqr_node_type = match &line[0] {
-                        "S" => {
-                            trace!("Parsing S-line");
-                            SPQRNodeType::SNode
-                        }
-                        "P" => {
-                            trace!("Parsing P-line");
-                            SPQRNodeType::PNode
-                        }
-                        "R" => {
-                            trace!("Parsing R-line");
-                            SPQRNodeType::RNode
-                        }
-                        _ => unreachable!(),
-                    };
-                    let spqr_node_name = line
-                        .column(1)
-                        .ok_or(ReadError::MissingSPQRNodeNameInSPRLine)?;
-                    let block_name = line.column(2).ok_or(ReadError::MissingBlockNameInSPRLine)?;
-                    let block_index = *name_to_block_index
-                        .get(block_name)
-                        .ok_or_else(|| ReadError::UnknownBlockName(block_name.to_string()))?;
-                    let nodes = line
-                        .iter()
-                        .skip(3)
-                        .map(|node| {
-                            name_to_node_index
-                                .get(node)
-                                .copied()
-                                .ok_or_else(|| ReadError::UnknownNodeName(node.to_string()))
-                        })
-                        .collect::<Result<Vec<_>, _>>()?;
-
-                    if nodes.len() < 2 {
-                        return Err(ReadError::LessThanTwoNodesInSPQRNode);
-                    }
 
-                    let spqr_node_index = builder.add_spqr_node(block_index, nodes, spqr_node_type);
-                    name_to_spqr_node_index.insert(spqr_node_name.to_string(), spqr_node_index);
+                let component_index = self.builder.try_add_component(nodes)?;
+                self.name_to_component_index
+                    .insert(name.to_string(), component_index);
+            }
+            Event::NodeExtraData { node, extra_data } => {
+                trace!("Parsing N-line");
+                let node_index = resolve_node(1, node)?;
+                self.builder.try_add_extra_data_to_node(
+                    node_index,
+                    self.extra_data_pool.intern(extra_data),
+                )?;
+            }
+            Event::Block {
+                name,
+                component,
+                nodes,
+            } => {
+                trace!("Parsing B-line");
+                let component_index = *self
+                    .name_to_component_index
+                    .get(component)
+                    .ok_or_else(|| ReadError::UnknownComponentName(component.to_string()))?;
+                let nodes = nodes
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, node)| resolve_node(3 + index, node))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if nodes.is_empty() {
+                    return Err(ReadError::EmptyBlock);
                 }
-                "V" => {
-                    trace!("Parsing V-line");
-                    let spqr_edge_name = line
-                        .column(1)
-                        .ok_or(ReadError::MissingSPQREdgeNameInVLine)?;
-                    let spqr_node_name_u = line
-                        .column(2)
-                        .ok_or(ReadError::MissingSPQRNodeNameInVLine)?;
-                    let spqr_node_name_v = line
-                        .column(3)
-                        .ok_or(ReadError::MissingSPQRNodeNameInVLine)?;
-                    let node_name_u = line.column(4).ok_or(ReadError::MissingNodeNameInVLine)?;
-                    let node_name_v = line.column(5).ok_or(ReadError::MissingNodeNameInVLine)?;
-                    let spqr_node_index_u = *name_to_spqr_node_index
-                        .get(spqr_node_name_u)
-                        .ok_or_else(|| {
-                            ReadError::UnknownSPQRNodeName(spqr_node_name_u.to_string())
-                        })?;
-                    let spqr_node_index_v = *name_to_spqr_node_index
-                        .get(spqr_node_name_v)
-                        .ok_or_else(|| {
-                            ReadError::UnknownSPQRNodeName(spqr_node_name_v.to_string())
-                        })?;
-                    let node_index_u = name_to_node_index
-                        .get(node_name_u)
-                        .copied()
-                        .ok_or_else(|| ReadError::UnknownNodeName(node_name_u.to_string()))?;
-                    let node_index_v = name_to_node_index
-                        .get(node_name_v)
-                        .copied()
-                        .ok_or_else(|| ReadError::UnknownNodeName(node_name_v.to_string()))?;
 
-                    let block_index = builder.spqr_node_block_index(spqr_node_index_u);
-                    if block_index != builder.spqr_node_block_index(spqr_node_index_v) {
-                        return Err(ReadError::SPQREdgeBetweenDifferentBlocks(
-                            spqr_edge_name.to_string(),
-                        ));
-                    }
+                let block_index = self.builder.try_add_block(component_index, nodes)?;
+                trace!("Block {name} has index {block_index}");
+                self.name_to_block_index
+                    .insert(name.to_string(), block_index);
+            }
+            Event::CutNode { node, blocks } => {
+                trace!("Parsing C-line");
+                let cut_node_index = resolve_node(1, node)?;
 
-                    let spqr_edge_index = builder.add_spqr_edge(
-                        Some(block_index).into(),
-                        (spqr_node_index_u, spqr_node_index_v),
-                        (node_index_u, node_index_v),
-                    );
-                    name_to_spqr_edge_index.insert(spqr_edge_name.to_string(), spqr_edge_index);
+                if blocks.is_empty() {
+                    return Err(ReadError::EmptyCutNode);
                 }
-                "E" => {
-                    trace!(
-                        "Parsing E-line with name {}",
-                        line.column(1).unwrap_or("<missing>")
-                    );
-
-                    let edge_name = line.column(1).ok_or(ReadError::MissingEdgeNameInELine)?;
-                    let spqr_node_or_block_or_component_name = line
-                        .column(2)
-                        .ok_or(ReadError::MissingSPQRNodeOrBlockOrComponentNameInELine)?;
-                    let node_name_u = line.column(3).ok_or(ReadError::MissingNodeNameInELine)?;
-                    let node_name_v = line.column(4).ok_or(ReadError::MissingNodeNameInELine)?;
 
-                    let node_index_u = name_to_node_index
-                        .get(node_name_u)
-                        .copied()
-                        .ok_or_else(|| ReadError::UnknownNodeName(node_name_u.to_string()))?;
-                    let node_index_v = name_to_node_index
-                        .get(node_name_v)
-                        .copied()
-                        .ok_or_else(|| ReadError::UnknownNodeName(node_name_v.to_string()))?;
-
-                    trace!(
-                        "Edge {edge_name} has indices {:?}",
-                        graph
-                            .edges_between(node_index_u, node_index_v)
-                            .collect::<Vec<_>>(),
-                    );
+                self.cut_nodes.push((
+                    cut_node_index,
+                    blocks.into_iter().map(str::to_string).collect(),
+                ));
+            }
+            Event::SPQRNode {
+                spqr_node_type,
+                name,
+                block,
+                nodes,
+            } => {
+                trace!("Parsing {spqr_node_type}-line");
+                let block_index = *self
+                    .name_to_block_index
+                    .get(block)
+                    .ok_or_else(|| ReadError::UnknownBlockName(block.to_string()))?;
+                let nodes = nodes
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, node)| resolve_node(3 + index, node))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if nodes.len() < 2 {
+                    return Err(ReadError::LessThanTwoNodesInSPQRNode);
+                }
 
-                    let mut edges_between = graph.edges_between(node_index_u, node_index_v);
-                    let Some(first) = edges_between.next() else {
-                        return Err(ReadError::NoEdgeBetweenNodes(
-                            node_name_u.to_string(),
-                            node_name_v.to_string(),
-                        ));
-                    };
-                    let second = edges_between.next();
-                    let is_multiedge = second.is_some();
+                let spqr_node_index =
+                    self.builder
+                        .try_add_spqr_node(block_index, nodes, spqr_node_type)?;
+                self.name_to_spqr_node_index
+                    .insert(name.to_string(), spqr_node_index);
+            }
+            Event::SPQREdge {
+                name,
+                spqr_nodes: (spqr_node_name_u, spqr_node_name_v),
+                virtual_edge: (node_name_u, node_name_v),
+            } => {
+                trace!("Parsing V-line");
+                let spqr_node_index_u = *self
+                    .name_to_spqr_node_index
+                    .get(spqr_node_name_u)
+                    .ok_or_else(|| ReadError::UnknownSPQRNodeName(spqr_node_name_u.to_string()))?;
+                let spqr_node_index_v = *self
+                    .name_to_spqr_node_index
+                    .get(spqr_node_name_v)
+                    .ok_or_else(|| ReadError::UnknownSPQRNodeName(spqr_node_name_v.to_string()))?;
+                let node_index_u = resolve_node(4, node_name_u)?;
+                let node_index_v = resolve_node(5, node_name_v)?;
+
+                let block_index = self.builder.spqr_node_block_index(spqr_node_index_u);
+                if block_index != self.builder.spqr_node_block_index(spqr_node_index_v) {
+                    return Err(ReadError::SPQREdgeBetweenDifferentBlocks(name.to_string()));
+                }
 
-                    if let Some(spqr_node_index) = name_to_spqr_node_index
-                        .get(spqr_node_or_block_or_component_name)
-                        .copied()
-                    {
-                        for edge_index in iter::once(first).chain(second).chain(edges_between) {
-                            let result = builder.add_edge_to_spqr_node(edge_index, spqr_node_index);
-                            if !is_multiedge {
-                                result?;
-                            }
+                let spqr_edge_index = self.builder.try_add_spqr_edge(
+                    Some(block_index).into(),
+                    (spqr_node_index_u, spqr_node_index_v),
+                    (node_index_u, node_index_v),
+                )?;
+                self.name_to_spqr_edge_index
+                    .insert(name.to_string(), spqr_edge_index);
+            }
+            Event::Edge {
+                name: edge_name,
+                parent: spqr_node_or_block_or_component_name,
+                endpoints: (node_name_u, node_name_v),
+                extra_data,
+                explicit_q_node: is_q_line,
+            } => {
+                trace!(
+                    "Parsing {}-line with name {edge_name}",
+                    if is_q_line { "Q" } else { "E" }
+                );
+
+                let node_index_u = resolve_node(3, node_name_u)?;
+                let node_index_v = resolve_node(4, node_name_v)?;
+
+                trace!(
+                    "Edge {edge_name} has indices {:?}",
+                    self.graph
+                        .edges_between(node_index_u, node_index_v)
+                        .collect::<Vec<_>>(),
+                );
+
+                let mut edges_between = self.graph.edges_between(node_index_u, node_index_v);
+                let Some(first) = edges_between.next() else {
+                    return Err(ReadError::NoEdgeBetweenNodes(
+                        node_name_u.to_string(),
+                        node_name_v.to_string(),
+                    ));
+                };
+                let second = edges_between.next();
+                let is_multiedge = second.is_some();
+
+                if let Some(spqr_node_index) = self
+                    .name_to_spqr_node_index
+                    .get(spqr_node_or_block_or_component_name)
+                    .copied()
+                {
+                    for edge_index in iter::once(first).chain(second).chain(edges_between) {
+                        let result = self
+                            .builder
+                            .add_edge_to_spqr_node(edge_index, spqr_node_index);
+                        if !is_multiedge {
+                            result?;
                         }
-                    } else if let Some(block_index) = name_to_block_index
-                        .get(spqr_node_or_block_or_component_name)
-                        .copied()
-                    {
-                        for edge_index in iter::once(first).chain(second).chain(edges_between) {
-                            let result = builder.add_edge_to_block(edge_index, block_index);
-                            if !is_multiedge {
-                                result?;
-                            }
+                    }
+                } else if is_q_line {
+                    return Err(ReadError::UnknownSPQRNodeName(
+                        spqr_node_or_block_or_component_name.to_string(),
+                    ));
+                } else if let Some(block_index) = self
+                    .name_to_block_index
+                    .get(spqr_node_or_block_or_component_name)
+                    .copied()
+                {
+                    for edge_index in iter::once(first).chain(second).chain(edges_between) {
+                        let result = self.builder.add_edge_to_block(edge_index, block_index);
+                        if !is_multiedge {
+                            result?;
                         }
-                    } else if let Some(component_index) = name_to_component_index
-                        .get(spqr_node_or_block_or_component_name)
-                        .copied()
-                    {
-                        for edge_index in iter::once(first).chain(second).chain(edges_between) {
-                            let result = builder.add_edge_to_component(edge_index, component_index);
-                            if !is_multiedge {
-                                result?;
-                            }
+                    }
+                } else if let Some(component_index) = self
+                    .name_to_component_index
+                    .get(spqr_node_or_block_or_component_name)
+                    .copied()
+                {
+                    for edge_index in iter::once(first).chain(second).chain(edges_between) {
+                        let result = self
+                            .builder
+                            .add_edge_to_component(edge_index, component_index);
+                        if !is_multiedge {
+                            result?;
                         }
-                    } else {
-                        return Err(ReadError::UnknownSPQRNodeOrBlockOrComponentName(
-                            spqr_node_or_block_or_component_name.to_string(),
-                        ));
                     }
+                } else {
+                    return Err(ReadError::UnknownSPQRNodeOrBlockOrComponentName(
+                        spqr_node_or_block_or_component_name.to_string(),
+                    ));
                 }
-                other => {
-                    return Err(ReadError::InvalidLineType(other.to_string()));
+
+                let edge_index = if is_multiedge {
+                    self.resolve_multiedge(edge_name, node_index_u, node_index_v)
+                } else {
+                    Some(first)
+                };
+                if !extra_data.is_empty() {
+                    let edge_index = edge_index.ok_or_else(|| {
+                        ReadError::MoreELinesThanEdges(
+                            node_name_u.to_string(),
+                            node_name_v.to_string(),
+                        )
+                    })?;
+                    self.builder.add_extra_data_to_edge(
+                        edge_index,
+                        self.extra_data_pool.intern(extra_data),
+                    );
                 }
             }
+            Event::Header { .. } => {
+                return Err(ReadError::InvalidLineType("H".to_string()));
+            }
+            Event::Other { columns } if self.strictness == Strictness::Warn => {
+                warn!("Skipping line of unknown type {:?}", columns[0]);
+            }
+            Event::Other { columns } => {
+                return Err(ReadError::InvalidLineType(columns[0].to_string()));
+            }
         }
 
-        Ok(builder.build())
+        Ok(())
     }
 
+    /// Resolves an E-line between the endpoints of a multiedge to one of its edges.
+    ///
+    /// Names of the form written by [`write_plain_spqr`](SPQRDecomposition::write_plain_spqr), i.e. a letter followed by the edge index,
+    /// are resolved to that edge.
+    /// Other names are resolved to the first edge that no other E-line was resolved to,
+    /// so that E-lines with different names are resolved to different edges.
+    /// Returns `None` if all edges between the endpoints were resolved to already.
+    fn resolve_multiedge(
+        &mut self,
+        edge_name: &str,
+        node_index_u: Graph::NodeIndex,
+        node_index_v: Graph::NodeIndex,
+    ) -> Option<Graph::EdgeIndex> {
+        let named_index = edge_name
+            .get(1..)
+            .and_then(|index| index.parse::<usize>().ok());
+        let is_unresolved =
+            |edge_index: &Graph::EdgeIndex| !self.resolved_multiedges.contains(edge_index);
+        let edge_index = named_index
+            .and_then(|named_index| {
+                self.graph
+                    .edges_between(node_index_u, node_index_v)
+                    .find(|edge_index| {
+                        (*edge_index).into() == named_index && is_unresolved(edge_index)
+                    })
+            })
+            .or_else(|| {
+                self.graph
+                    .edges_between(node_index_u, node_index_v)
+                    .find(is_unresolved)
+            })?;
+        self.resolved_multiedges.insert(edge_index);
+        Some(edge_index)
+    }
+
+    /// Applies the C-lines and returns the builder.
+    fn into_builder(self) -> Result<SPQRDecompositionBuilder<'graph, Graph>, ReadError> {
+        let Self {
+            mut builder,
+            name_to_block_index,
+            cut_nodes,
+            ..
+        } = self;
+        for (cut_node_index, block_names) in cut_nodes {
+            let block_indices = block_names
+                .into_iter()
+                .map(|block_name| {
+                    name_to_block_index
+                        .get(&block_name)
+                        .copied()
+                        .ok_or(ReadError::UnknownBlockName(block_name))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            builder.try_add_cut_node(cut_node_index, block_indices)?;
+        }
+        Ok(builder)
+    }
+
+    fn finish(self) -> Result<SPQRDecomposition<'graph, Graph>, ReadError> {
+        let strictness = self.strictness;
+        self.into_builder()?
+            .try_build_with_strictness(strictness)
+            .map_err(|error| ReadError::InconsistentDecomposition(error.to_string()))
+    }
+
+    fn finish_with_profile(
+        self,
+        profile: &mut Profile,
+    ) -> Result<SPQRDecomposition<'graph, Graph>, ReadError> {
+        self.into_builder()?
+            .try_build_with_profile(profile)
+            .map_err(|error| ReadError::InconsistentDecomposition(error.to_string()))
+    }
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
     /// Write an SPQR decomposition in plain SPQR file format.
-    pub fn write_plain_spqr(&self, mut writer: impl Write) -> std::io::Result<()> {
-        writeln!(
-            writer,
-            "H v0.4 https://github.com/sebschmi/SPQR-tree-file-format"
-        )?;
+    ///
+    /// Entities are written in the order of their indices, so equal decompositions of graphs with equal node names
+    /// are written as identical bytes, independently of the index type and the platform.
+    ///
+    /// Only the extra data of nodes and edges is written, in N- and E-lines.
+    /// The format has no place for the extra data of components, blocks, cut nodes and SPQR nodes,
+    /// so it is dropped with a warning. Use the binary formats to keep it.
+    pub fn write_plain_spqr(&self, writer: impl Write) -> std::io::Result<()> {
+        self.write_plain_spqr_impl(writer, false)
+    }
+
+    /// Write an SPQR decomposition in plain SPQR file format, declaring the edges of SPQR nodes as explicit Q-nodes.
+    ///
+    /// Q-lines have the same syntax as E-lines, but start with `Q` and must refer to an S-, P- or R-node.
+    /// They were introduced in [`FormatVersion::V0_5`], so the file declares that version instead of [`FormatVersion::V0_4`].
+    /// See also [`ExplicitSPQRTreeNode`](crate::decomposition::q_nodes::ExplicitSPQRTreeNode).
+    pub fn write_plain_spqr_with_explicit_q_nodes(
+        &self,
+        writer: impl Write,
+    ) -> std::io::Result<()> {
+        self.write_plain_spqr_impl(writer, true)
+    }
+
+    fn write_plain_spqr_impl(
+        &self,
+        writer: impl Write,
+        explicit_q_nodes: bool,
+    ) -> std::io::Result<()> {
+        let version = if explicit_q_nodes {
+            FormatVersion::V0_5
+        } else {
+            FormatVersion::V0_4
+        };
+        if self
+            .components
+            .iter_values()
+            .any(|component| !component.extra_data().is_empty())
+            || self
+                .blocks
+                .iter_values()
+                .any(|block| !block.extra_data().is_empty())
+            || self
+                .cut_nodes
+                .iter_values()
+                .any(|cut_node| !cut_node.extra_data().is_empty())
+            || self
+                .spqr_nodes
+                .iter_values()
+                .any(|spqr_node| !spqr_node.extra_data().is_empty())
+        {
+            warn!(
+                "The extra data of components, blocks, cut nodes and SPQR nodes is not written in the plain SPQR file format"
+            );
+        }
+
+        let mut writer = LineWriter::new(writer);
+        writer
+            .start_line("H")
+            .column(version.name())
+            .column("https://github.com/sebschmi/SPQR-tree-file-format")
+            .end_line()?;
 
         // Write node extra data.
         for node_index in self.iter_nodes() {
             let extra_data = self.node_extra_data(node_index);
             if !extra_data.is_empty() {
-                let node_name = self.graph().node_name(node_index);
-                writeln!(writer, "N {node_name} {extra_data}")?;
+                let node_name = self.graph_ref().node_name(node_index);
+                writer
+                    .start_line("N")
+                    .column(&node_name)
+                    .column(extra_data)
+                    .end_line()?;
             }
         }
 
         // Write components.
         for (component_index, component) in self.iter_components() {
-            write!(writer, "G G{component_index}")?;
+            writer
+                .start_line("G")
+                .index_column("G", component_index.into_usize());
 
             for node_index in component.iter_nodes() {
-                let node_name = self.graph().node_name(node_index);
-                write!(writer, " {node_name}")?;
+                writer.column(&self.graph_ref().node_name(node_index));
             }
 
-            writeln!(writer)?;
+            writer.end_line()?;
 
             // Write contained edges.
             for edge_index in component.iter_edges() {
-                let (u, v) = self.graph().edge_endpoints(edge_index);
-                let node_name_u = self.graph().node_name(u);
-                let node_name_v = self.graph().node_name(v);
-                let edge_name = format!("E{edge_index}");
-                writeln!(
-                    writer,
-                    "E {edge_name} G{component_index} {node_name_u} {node_name_v}",
-                )?;
+                writer
+                    .start_line("E")
+                    .index_column("E", edge_index.into())
+                    .index_column("G", component_index.into_usize());
+                self.write_edge_columns(&mut writer, edge_index)?;
             }
 
             // Write cut nodes in component.
             for cut_node_index in component.iter_cut_nodes() {
                 let cut_node = self.cut_node(cut_node_index);
-                let node_name = self.graph().node_name(cut_node.node());
-                write!(writer, "C {node_name}")?;
+                writer
+                    .start_line("C")
+                    .column(&self.graph_ref().node_name(cut_node.node()));
 
                 for block_index in cut_node.iter_adjacent_blocks() {
-                    write!(writer, " B{block_index}")?;
+                    writer.index_column("B", block_index.into_usize());
                 }
-                writeln!(writer)?;
+                writer.end_line()?;
             }
 
             // Write blocks in component.
             for (block_index, block) in self.iter_blocks_in_component(component_index) {
-                write!(writer, "B B{block_index} G{component_index}")?;
+                writer
+                    .start_line("B")
+                    .index_column("B", block_index.into_usize())
+                    .index_column("G", component_index.into_usize());
 
                 for node_index in block.iter_nodes() {
-                    let node_name = self.graph().node_name(node_index);
-                    write!(writer, " {node_name}")?;
+                    writer.column(&self.graph_ref().node_name(node_index));
                 }
-                writeln!(writer)?;
+                writer.end_line()?;
 
                 // Write contained edges.
                 for edge_index in block.iter_edges() {
-                    let (u, v) = self.graph().edge_endpoints(edge_index);
-                    let node_name_u = self.graph().node_name(u);
-                    let node_name_v = self.graph().node_name(v);
-                    let edge_name = format!("E{edge_index}");
-                    writeln!(
-                        writer,
-                        "E {edge_name} B{block_index} {node_name_u} {node_name_v}",
-                    )?;
+                    writer
+                        .start_line("E")
+                        .index_column("E", edge_index.into())
+                        .index_column("B", block_index.into_usize());
+                    self.write_edge_columns(&mut writer, edge_index)?;
                 }
 
                 // Write contained SPQR nodes.
                 for (spqr_node_index, spqr_node) in self.iter_spqr_nodes_in_block(block_index) {
-                    let spqr_node_name = match spqr_node.spqr_node_type() {
-                        SPQRNodeType::SNode => format!("S{spqr_node_index}"),
-                        SPQRNodeType::PNode => format!("P{spqr_node_index}"),
-                        SPQRNodeType::RNode => format!("R{spqr_node_index}"),
-                    };
-                    match spqr_node.spqr_node_type() {
-                        SPQRNodeType::SNode => write!(writer, "S {spqr_node_name} B{block_index}")?,
-                        SPQRNodeType::PNode => write!(writer, "P {spqr_node_name} B{block_index}")?,
-                        SPQRNodeType::RNode => write!(writer, "R {spqr_node_name} B{block_index}")?,
-                    }
+                    let spqr_node_type = spqr_node.spqr_node_type().letter();
+                    writer
+                        .start_line(spqr_node_type)
+                        .index_column(spqr_node_type, spqr_node_index.into_usize())
+                        .index_column("B", block_index.into_usize());
 
                     for node_index in spqr_node.iter_nodes() {
-                        let node_name = self.graph().node_name(node_index);
-                        write!(writer, " {node_name}")?;
+                        writer.column(&self.graph_ref().node_name(node_index));
                     }
-                    writeln!(writer)?;
+                    writer.end_line()?;
 
                     // Write edges (Q-nodes).
+                    let line_type = if explicit_q_nodes { "Q" } else { "E" };
                     for edge_index in spqr_node.iter_edges() {
-                        let (u, v) = self.graph().edge_endpoints(edge_index);
-                        let node_name_u = self.graph().node_name(u);
-                        let node_name_v = self.graph().node_name(v);
-                        let edge_name = format!("E{edge_index}");
-
-                        write!(
-                            writer,
-                            "E {edge_name} {spqr_node_name} {node_name_u} {node_name_v}",
-                        )?;
-
-                        let extra_data = self.edge_extra_data(edge_index);
-                        if !extra_data.is_empty() {
-                            write!(writer, " {extra_data}")?;
-                        }
-                        writeln!(writer)?;
+                        writer
+                            .start_line(line_type)
+                            .index_column(line_type, edge_index.into())
+                            .index_column(spqr_node_type, spqr_node_index.into_usize());
+                        self.write_edge_columns(&mut writer, edge_index)?;
                     }
                 }
 
                 // Write contained SPQR edges.
                 for (spqr_edge_index, spqr_edge) in self.iter_spqr_edges_in_block(block_index) {
                     let (spqr_node_index_u, spqr_node_index_v) = spqr_edge.endpoints();
-                    let spqr_node_name_u = self.spqr_node_name(spqr_node_index_u);
-                    let spqr_node_name_v = self.spqr_node_name(spqr_node_index_v);
-                    let node_name_u = self.graph().node_name(spqr_edge.virtual_edge().0);
-                    let node_name_v = self.graph().node_name(spqr_edge.virtual_edge().1);
-
-                    writeln!(
-                        writer,
-                        "V V{spqr_edge_index} {spqr_node_name_u} {spqr_node_name_v} {node_name_u} {node_name_v}",
-                    )?;
+                    let (u, v) = spqr_edge.virtual_edge();
+
+                    writer
+                        .start_line("V")
+                        .index_column("V", spqr_edge_index.into_usize())
+                        .index_column(
+                            self.spqr_node(spqr_node_index_u).spqr_node_type().letter(),
+                            spqr_node_index_u.into_usize(),
+                        )
+                        .index_column(
+                            self.spqr_node(spqr_node_index_v).spqr_node_type().letter(),
+                            spqr_node_index_v.into_usize(),
+                        )
+                        .column(&self.graph_ref().node_name(u))
+                        .column(&self.graph_ref().node_name(v))
+                        .end_line()?;
                 }
             }
         }
 
-        Ok(())
+        writer.finish()
+    }
+
+    /// Writes the endpoints and the extra data of an edge and terminates the line.
+    fn write_edge_columns(
+        &self,
+        writer: &mut LineWriter<impl Write>,
+        edge_index: Graph::EdgeIndex,
+    ) -> std::io::Result<()> {
+        let (u, v) = self.graph_ref().edge_endpoints(edge_index);
+        writer
+            .column(&self.graph_ref().node_name(u))
+            .column(&self.graph_ref().node_name(v));
+
+        let extra_data = self.edge_extra_data(edge_index);
+        if !extra_data.is_empty() {
+            writer.column(extra_data);
+        }
+        writer.end_line()
     }
 }
@@ -1,5 +1,10 @@
+use std::fmt::Display;
+
 use crate::{
-    decomposition::builder::AddEdgeError, io::plain_spqr_file::line_reader::LineReaderError,
+    decomposition::builder::{AddEdgeError, AddError},
+    io::plain_spqr_file::{
+        line_handler::LineHandlerError, line_reader::LineReaderError, version::FormatVersion,
+    },
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -13,13 +18,13 @@ pub enum ReadError {
     #[error("Add edge error: {0}")]
     AddEdge(#[from] AddEdgeError),
 
-    #[error("invalid line type: {0:?}. Expected one of G, N, B, C, S, P, R, V, E")]
+    #[error("invalid line type: {0:?}. Expected one of G, N, B, C, S, P, R, V, E, Q")]
     InvalidLineType(String),
 
     #[error("the file does not start with a header line")]
     MissingHeader,
 
-    #[error("the file fromat version is unsupported. Supported is version v0.4")]
+    #[error("the file format version is unsupported. Supported are: {supported}", supported = supported_versions())]
     UnsupportedVersion,
 
     #[error("the header does not contain a URL pointing to the specification")]
@@ -96,4 +101,40 @@ pub enum ReadError {
 
     #[error("an E-line is declared between {0:?} and {1:?}, but no such edge exists in the graph")]
     NoEdgeBetweenNodes(String, String),
+
+    #[error(
+        "more E-lines are declared between {0:?} and {1:?} than edges exist between them in the graph"
+    )]
+    MoreELinesThanEdges(String, String),
+
+    #[error("line handler error: {0}")]
+    LineHandler(LineHandlerError),
+
+    #[error("the decomposition described by the file is inconsistent: {0}")]
+    InconsistentDecomposition(String),
+}
+
+impl ReadError {
+    /// Returns `true` if the error describes inconsistent contents of the file,
+    /// as opposed to a failure to read the file or an error returned by a line handler.
+    pub fn is_inconsistency(&self) -> bool {
+        !matches!(self, Self::Io(_) | Self::Line(_) | Self::LineHandler(_))
+    }
+}
+
+impl<NodeIndex: Display, EdgeIndex: Display, IndexType: Display>
+    From<AddError<NodeIndex, EdgeIndex, IndexType>> for ReadError
+{
+    fn from(error: AddError<NodeIndex, EdgeIndex, IndexType>) -> Self {
+        Self::InconsistentDecomposition(error.to_string())
+    }
+}
+
+fn supported_versions() -> String {
+    FormatVersion::SUPPORTED
+        .iter()
+        .copied()
+        .map(FormatVersion::name)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
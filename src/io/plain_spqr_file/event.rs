@@ -0,0 +1,159 @@
+//! The events emitted by [`parse_events`](super::parse_events).
+
+use crate::{
+    decomposition::SPQRNodeType,
+    io::plain_spqr_file::{error::ReadError, line_reader::Columns, version::FormatVersion},
+};
+
+/// A line of a file in the plain SPQR file format, with its columns borrowed from the line.
+///
+/// Names are not resolved, so an event may refer to entities that are declared later or not at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<'line> {
+    /// The header line, which is always the first event.
+    Header { version: FormatVersion },
+
+    /// An N-line, attaching extra data to a node.
+    NodeExtraData {
+        node: &'line str,
+        extra_data: &'line str,
+    },
+
+    /// A G-line, declaring a connected component.
+    Component {
+        name: &'line str,
+        nodes: Vec<&'line str>,
+    },
+
+    /// A B-line, declaring a block.
+    Block {
+        name: &'line str,
+        component: &'line str,
+        nodes: Vec<&'line str>,
+    },
+
+    /// A C-line, declaring a cut node.
+    CutNode {
+        node: &'line str,
+        blocks: Vec<&'line str>,
+    },
+
+    /// An S-, P- or R-line, declaring an SPQR node.
+    SPQRNode {
+        spqr_node_type: SPQRNodeType,
+        name: &'line str,
+        block: &'line str,
+        nodes: Vec<&'line str>,
+    },
+
+    /// A V-line, declaring an SPQR edge.
+    SPQREdge {
+        name: &'line str,
+        spqr_nodes: (&'line str, &'line str),
+        virtual_edge: (&'line str, &'line str),
+    },
+
+    /// An E- or Q-line, assigning an edge to an SPQR node, block or component.
+    Edge {
+        name: &'line str,
+        /// The SPQR node, block or component containing the edge.
+        parent: &'line str,
+        endpoints: (&'line str, &'line str),
+        extra_data: &'line str,
+        /// True for Q-lines, whose parent must be an SPQR node.
+        explicit_q_node: bool,
+    },
+
+    /// A line of a type not defined by the version of the file, given as all its columns.
+    Other { columns: Vec<&'line str> },
+}
+
+impl<'line> Event<'line> {
+    /// Parses a line following the header, according to the given version.
+    pub(super) fn from_columns(
+        version: FormatVersion,
+        line: &Columns<'line>,
+    ) -> Result<Self, ReadError> {
+        match version {
+            FormatVersion::V0_4 => Self::from_columns_v0_4(line),
+            FormatVersion::V0_5 => Self::from_columns_v0_5(line),
+        }
+    }
+
+    fn from_columns_v0_5(line: &Columns<'line>) -> Result<Self, ReadError> {
+        if &line[0] == "Q" {
+            Self::edge_from_columns(line, true)
+        } else {
+            Self::from_columns_v0_4(line)
+        }
+    }
+
+    fn from_columns_v0_4(line: &Columns<'line>) -> Result<Self, ReadError> {
+        let names_from = |column| line.iter().skip(column).collect::<Vec<_>>();
+        Ok(match &line[0] {
+            "N" => Self::NodeExtraData {
+                node: line.column(1).ok_or(ReadError::MissingNodeNameInNLine)?,
+                extra_data: line.rest(2),
+            },
+            "G" => Self::Component {
+                name: line
+                    .column(1)
+                    .ok_or(ReadError::MissingComponentNameInGLine)?,
+                nodes: names_from(2),
+            },
+            "B" => Self::Block {
+                name: line.column(1).ok_or(ReadError::MissingBlockNameInBLine)?,
+                component: line
+                    .column(2)
+                    .ok_or(ReadError::MissingComponentNameInBLine)?,
+                nodes: names_from(3),
+            },
+            "C" => Self::CutNode {
+                node: line.column(1).ok_or(ReadError::MissingNodeNameInCLine)?,
+                blocks: names_from(2),
+            },
+            "S" | "P" | "R" => Self::SPQRNode {
+                spqr_node_type: line[0].parse().unwrap(),
+                name: line
+                    .column(1)
+                    .ok_or(ReadError::MissingSPQRNodeNameInSPRLine)?,
+                block: line.column(2).ok_or(ReadError::MissingBlockNameInSPRLine)?,
+                nodes: names_from(3),
+            },
+            "V" => Self::SPQREdge {
+                name: line
+                    .column(1)
+                    .ok_or(ReadError::MissingSPQREdgeNameInVLine)?,
+                spqr_nodes: (
+                    line.column(2)
+                        .ok_or(ReadError::MissingSPQRNodeNameInVLine)?,
+                    line.column(3)
+                        .ok_or(ReadError::MissingSPQRNodeNameInVLine)?,
+                ),
+                virtual_edge: (
+                    line.column(4).ok_or(ReadError::MissingNodeNameInVLine)?,
+                    line.column(5).ok_or(ReadError::MissingNodeNameInVLine)?,
+                ),
+            },
+            "E" => Self::edge_from_columns(line, false)?,
+            _ => Self::Other {
+                columns: names_from(0),
+            },
+        })
+    }
+
+    fn edge_from_columns(line: &Columns<'line>, explicit_q_node: bool) -> Result<Self, ReadError> {
+        Ok(Self::Edge {
+            name: line.column(1).ok_or(ReadError::MissingEdgeNameInELine)?,
+            parent: line
+                .column(2)
+                .ok_or(ReadError::MissingSPQRNodeOrBlockOrComponentNameInELine)?,
+            endpoints: (
+                line.column(3).ok_or(ReadError::MissingNodeNameInELine)?,
+                line.column(4).ok_or(ReadError::MissingNodeNameInELine)?,
+            ),
+            extra_data: line.rest(5),
+            explicit_q_node,
+        })
+    }
+}
@@ -0,0 +1,86 @@
+//! Support for lines of types that are not part of the plain SPQR file format, e.g. tool-specific `X`-lines.
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    decomposition::indices::{BlockIndex, ComponentIndex, SPQREdgeIndex, SPQRNodeIndex},
+    graph::StaticGraph,
+};
+
+/// The error type that a [`LineHandler`] may return.
+pub type LineHandlerError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Handles the lines whose type is unknown to the plain reader, see [`SPQRDecomposition::read_plain_spqr_with_line_handler`](crate::decomposition::SPQRDecomposition::read_plain_spqr_with_line_handler).
+///
+/// This allows applications to store their own records next to a decomposition without forking the parser.
+/// The records can refer to the entities of the decomposition by their indices, which are the same in the finished decomposition.
+pub trait LineHandler<Graph: StaticGraph> {
+    /// Handles a line, given as its columns starting with the line type.
+    ///
+    /// Entities declared on earlier lines can be looked up by their names in `names`.
+    /// Returns `false` if the line was not handled, in which case the reader treats it as a line of unknown type.
+    fn handle_line(
+        &mut self,
+        columns: &[&str],
+        names: &DeclaredNames<'_, Graph>,
+    ) -> Result<bool, LineHandlerError>;
+}
+
+impl<Graph: StaticGraph> LineHandler<Graph> for () {
+    fn handle_line(
+        &mut self,
+        _columns: &[&str],
+        _names: &DeclaredNames<'_, Graph>,
+    ) -> Result<bool, LineHandlerError> {
+        Ok(false)
+    }
+}
+
+impl<Graph: StaticGraph, Function> LineHandler<Graph> for Function
+where
+    Function: FnMut(&[&str], &DeclaredNames<'_, Graph>) -> Result<bool, LineHandlerError>,
+{
+    fn handle_line(
+        &mut self,
+        columns: &[&str],
+        names: &DeclaredNames<'_, Graph>,
+    ) -> Result<bool, LineHandlerError> {
+        self(columns, names)
+    }
+}
+
+/// The names of the nodes of the graph and of the entities declared so far while reading a file.
+pub struct DeclaredNames<'reader, Graph: StaticGraph> {
+    pub(super) nodes: &'reader FxHashMap<String, Graph::NodeIndex>,
+    pub(super) components: &'reader FxHashMap<String, ComponentIndex<Graph::IndexType>>,
+    pub(super) blocks: &'reader FxHashMap<String, BlockIndex<Graph::IndexType>>,
+    pub(super) spqr_nodes: &'reader FxHashMap<String, SPQRNodeIndex<Graph::IndexType>>,
+    pub(super) spqr_edges: &'reader FxHashMap<String, SPQREdgeIndex<Graph::IndexType>>,
+}
+
+impl<Graph: StaticGraph> DeclaredNames<'_, Graph> {
+    /// Returns the node of the graph with the given name.
+    pub fn node(&self, name: &str) -> Option<Graph::NodeIndex> {
+        self.nodes.get(name).copied()
+    }
+
+    /// Returns the component declared with the given name, e.g. `G0`.
+    pub fn component(&self, name: &str) -> Option<ComponentIndex<Graph::IndexType>> {
+        self.components.get(name).copied()
+    }
+
+    /// Returns the block declared with the given name, e.g. `B0`.
+    pub fn block(&self, name: &str) -> Option<BlockIndex<Graph::IndexType>> {
+        self.blocks.get(name).copied()
+    }
+
+    /// Returns the S-, P- or R-node declared with the given name, e.g. `S0`.
+    pub fn spqr_node(&self, name: &str) -> Option<SPQRNodeIndex<Graph::IndexType>> {
+        self.spqr_nodes.get(name).copied()
+    }
+
+    /// Returns the SPQR edge declared with the given name, e.g. `V0`.
+    pub fn spqr_edge(&self, name: &str) -> Option<SPQREdgeIndex<Graph::IndexType>> {
+        self.spqr_edges.get(name).copied()
+    }
+}
@@ -14,6 +14,10 @@ pub struct LineReader<Reader> {
     reader: Reader,
     buffer: Vec<u8>,
     columns: Vec<usize>,
+    /// The one-based number of the line that was read last, or that is being read.
+    line_number: usize,
+    /// The byte offset of the start of the line that was read last, or that is being read.
+    line_offset: u64,
 }
 
 pub struct Columns<'a> {
@@ -32,12 +36,30 @@ impl<Reader: BufRead> LineReader<Reader> {
             reader,
             buffer: Vec::new(),
             columns: Vec::new(),
+            line_number: 0,
+            line_offset: 0,
         }
     }
 
+    /// Returns the one-based number of the line that was read last.
+    ///
+    /// After an error or after reaching the end of the file, this is the line that was being read.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Returns the byte offset of the start of the line that was read last.
+    ///
+    /// After an error or after reaching the end of the file, this is the line that was being read.
+    pub fn line_offset(&self) -> u64 {
+        self.line_offset
+    }
+
     /// Advances to the next non-empty line, returning `true` if a line was read and `false` if the end of the file was reached.
     pub fn next(&mut self) -> Result<Option<Columns<'_>>, LineReaderError> {
         loop {
+            self.line_offset += self.buffer.len() as u64;
+            self.line_number += 1;
             self.buffer.clear();
             // UTF-8 encoded strings contain the byte 0A only for newline chars.
             // Therefore, we can read a complete line simply like this.
@@ -48,31 +70,49 @@ impl<Reader: BufRead> LineReader<Reader> {
                 return Ok(None);
             }
 
-            self.columns.clear();
-            let mut has_non_whitespace = false;
-            for (index, c) in string.char_indices() {
-                if c == ' ' || c == '\n' {
-                    self.columns.push(index);
-                } else if c == '#' {
-                    // Skip the rest of the line after a comment character.
-                    self.columns.push(index);
-                    break;
-                } else {
-                    has_non_whitespace = true;
-                }
-            }
+            let has_non_whitespace = split_columns(string, &mut self.columns);
 
             if has_non_whitespace {
-                return Ok(Some(Columns {
-                    buffer: &self.buffer,
-                    columns: &self.columns,
-                }));
+                return Ok(Some(Columns::new(&self.buffer, &self.columns)));
             }
         }
     }
 }
 
+/// Splits a line into columns, storing the end offset of each column in `columns`.
+///
+/// Returns `true` if the line contains any non-whitespace characters before a comment.
+pub fn split_columns(line: &str, columns: &mut Vec<usize>) -> bool {
+    columns.clear();
+    let mut has_non_whitespace = false;
+    let mut terminated = false;
+    for (index, c) in line.char_indices() {
+        if c == ' ' || c == '\n' {
+            columns.push(index);
+            terminated = c == '\n';
+        } else if c == '#' {
+            // Skip the rest of the line after a comment character.
+            columns.push(index);
+            terminated = true;
+            break;
+        } else {
+            has_non_whitespace = true;
+        }
+    }
+
+    if !terminated && line.len() > columns.last().map(|end| end + 1).unwrap_or(0) {
+        // The last line of a file may not be terminated by a newline.
+        columns.push(line.len());
+    }
+
+    has_non_whitespace
+}
+
 impl<'a> Columns<'a> {
+    pub fn new(buffer: &'a [u8], columns: &'a [usize]) -> Self {
+        Self { buffer, columns }
+    }
+
     /// Returns the column at the given index, or `None` if the index is out of bounds.
     pub fn column(&self, index: usize) -> Option<&'a str> {
         if index >= self.columns.len() {
@@ -91,6 +131,24 @@ impl<'a> Columns<'a> {
         }
     }
 
+    /// Returns the columns starting at the given index as they appear in the line, i.e. separated by spaces.
+    ///
+    /// Returns an empty string if the index is out of bounds.
+    pub fn rest(&self, index: usize) -> &'a str {
+        if index >= self.columns.len() {
+            return "";
+        }
+
+        let start = if index == 0 {
+            0
+        } else {
+            self.columns[index - 1] + 1
+        };
+        let end = self.columns[self.columns.len() - 1];
+        // Safety: The `columns` vector is constructed from valid UTF-8 strings, so the indices are guaranteed to be valid UTF-8 boundaries.
+        unsafe { str::from_utf8_unchecked(&self.buffer[start..end]) }
+    }
+
     pub fn len(&self) -> usize {
         self.columns.len()
     }
@@ -0,0 +1,69 @@
+use std::io::Write;
+
+/// The amount of bytes that are collected before they are passed on to the underlying writer.
+const FLUSH_THRESHOLD: usize = 1 << 16;
+
+/// A buffered writer for space-separated lines, formatting integers without intermediate allocations.
+pub struct LineWriter<Writer> {
+    writer: Writer,
+    buffer: Vec<u8>,
+}
+
+impl<Writer: Write> LineWriter<Writer> {
+    pub fn new(writer: Writer) -> Self {
+        Self {
+            writer,
+            buffer: Vec::with_capacity(FLUSH_THRESHOLD + FLUSH_THRESHOLD / 4),
+        }
+    }
+
+    /// Starts a new line with the given line type as its first column.
+    pub fn start_line(&mut self, line_type: &str) -> &mut Self {
+        self.buffer.extend_from_slice(line_type.as_bytes());
+        self
+    }
+
+    /// Appends a column.
+    pub fn column(&mut self, column: &str) -> &mut Self {
+        self.buffer.push(b' ');
+        self.buffer.extend_from_slice(column.as_bytes());
+        self
+    }
+
+    /// Appends a column consisting of the given prefix followed by the decimal representation of the index.
+    pub fn index_column(&mut self, prefix: &str, index: usize) -> &mut Self {
+        self.buffer.push(b' ');
+        self.buffer.extend_from_slice(prefix.as_bytes());
+        self.decimal(index);
+        self
+    }
+
+    /// Terminates the current line, passing the buffered lines on to the underlying writer if the buffer is full.
+    pub fn end_line(&mut self) -> std::io::Result<()> {
+        self.buffer.push(b'\n');
+        if self.buffer.len() >= FLUSH_THRESHOLD {
+            self.writer.write_all(&self.buffer)?;
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+
+    /// Passes all remaining buffered lines on to the underlying writer.
+    pub fn finish(mut self) -> std::io::Result<()> {
+        self.writer.write_all(&self.buffer)
+    }
+
+    fn decimal(&mut self, mut value: usize) {
+        let mut digits = [0u8; 20];
+        let mut start = digits.len();
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        self.buffer.extend_from_slice(&digits[start..]);
+    }
+}
@@ -0,0 +1,123 @@
+use std::{io::Read, ops::Range};
+
+use log::debug;
+
+use crate::{
+    decomposition::{SPQRDecomposition, builder::SPQRDecompositionBuilderCapacities},
+    graph::StaticGraph,
+    io::plain_spqr_file::{
+        LineApplier, check_header,
+        error::ReadError,
+        event::Event,
+        line_reader::{Columns, split_columns},
+        node_name_map,
+    },
+    parallel::ParIter,
+    strictness::Strictness,
+};
+
+/// A non-empty line that was split into columns, with its node names already resolved.
+struct PreparedLine<'input, NodeIndex> {
+    line: &'input str,
+    columns: Vec<usize>,
+    /// The resolved node for each column that contains a node name, and `None` for all other columns.
+    nodes: Vec<Option<NodeIndex>>,
+}
+
+impl<'graph, Graph: StaticGraph + Sync> SPQRDecomposition<'graph, Graph>
+where
+    Graph::NodeIndex: Send + Sync,
+{
+    /// Read an SPQR decomposition in the plain SPQR file format using multiple threads.
+    ///
+    /// The file is read into memory completely.
+    /// Then, its lines are split into columns and their node names are resolved in parallel.
+    /// Finally, the lines are applied to the decomposition in their original order.
+    pub fn read_plain_spqr_parallel(
+        graph: &'graph Graph,
+        mut reader: impl Read,
+    ) -> Result<Self, ReadError> {
+        debug!("Reading SPQR decomposition in plain SPQR format using multiple threads...");
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        let lines = input.split_inclusive('\n').collect::<Vec<_>>();
+        let name_to_node_index = node_name_map(graph);
+
+        let prepared_lines = ParIter::new(lines.len(), |index| lines[index]).map(|line| {
+            let mut columns = Vec::new();
+            if !split_columns(line, &mut columns) {
+                return None;
+            }
+
+            let node_columns = node_columns(&Columns::new(line.as_bytes(), &columns));
+            let nodes = (0..columns.len())
+                .map(|column| {
+                    if node_columns.contains(&column) {
+                        let node_name = &Columns::new(line.as_bytes(), &columns)[column];
+                        name_to_node_index.get(node_name).copied()
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            Some(PreparedLine {
+                line,
+                columns,
+                nodes,
+            })
+        });
+        let mut prepared_lines = prepared_lines.into_iter().flatten();
+
+        let header = prepared_lines.next();
+        let version = check_header(header.as_ref().map(PreparedLine::columns))?;
+        let prepared_lines = prepared_lines.collect::<Vec<_>>();
+
+        let mut capacities = SPQRDecompositionBuilderCapacities::default();
+        for prepared_line in &prepared_lines {
+            match &prepared_line.columns()[0] {
+                "G" => capacities.components += 1,
+                "B" => capacities.blocks += 1,
+                "C" => capacities.cut_nodes += 1,
+                "S" | "P" | "R" => capacities.spqr_nodes += 1,
+                "V" => capacities.spqr_edges += 1,
+                _ => {}
+            }
+        }
+
+        let mut line_applier = LineApplier::new(graph, capacities, Strictness::Error);
+        for prepared_line in &prepared_lines {
+            line_applier.apply(
+                Event::from_columns(version, &prepared_line.columns())?,
+                |column, node_name| {
+                    prepared_line
+                        .nodes
+                        .get(column)
+                        .copied()
+                        .flatten()
+                        .ok_or_else(|| ReadError::UnknownNodeName(node_name.to_string()))
+                },
+            )?;
+        }
+
+        line_applier.finish()
+    }
+}
+
+impl<'input, NodeIndex> PreparedLine<'input, NodeIndex> {
+    fn columns(&self) -> Columns<'_> {
+        Columns::new(self.line.as_bytes(), &self.columns)
+    }
+}
+
+/// Returns the range of columns that contain node names in the given line.
+fn node_columns(line: &Columns<'_>) -> Range<usize> {
+    match &line[0] {
+        "G" => 2..line.len(),
+        "N" | "C" => 1..2,
+        "B" | "S" | "P" | "R" => 3..line.len(),
+        "V" => 4..6,
+        "E" | "Q" => 3..5,
+        _ => 0..0,
+    }
+}
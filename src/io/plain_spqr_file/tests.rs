@@ -9,7 +9,19 @@ use bidirected_adjacency_array::{
 };
 use regex::Regex;
 
-use crate::decomposition::SPQRDecomposition;
+use crate::{
+    decomposition::{
+        SPQRDecomposition,
+        tests::{ProgressEvent, RecordingProgress},
+    },
+    graph::StaticGraph,
+    io::plain_spqr_file::{
+        error::ReadError, event::Event, line_handler::DeclaredNames, parse_events, scan_capacities,
+        scan_statistics, version::FormatVersion,
+    },
+    profile::{Phase, Profile},
+    strictness::Strictness,
+};
 
 fn enable_logging() {
     let _ignore = simplelog::TermLogger::init(
@@ -254,3 +266,686 @@ fn test_ultrabubble_dataset_chr_22_g182870() {
 
     assert_eq!(spqr_decomposition_file_sorted, buffer_sorted);
 }
+
+#[test]
+fn test_edge_extra_data() {
+    enable_logging();
+
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr")
+        .unwrap()
+        .replace("E E0 S0 j k", "E E0 S0 j k bubble 42")
+        .replace("E E13 S4 a c", "E E13 S4 a c x");
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, &mut spqr_decomposition_file.as_bytes())
+            .unwrap();
+
+    let mut buffer = Vec::new();
+    spqr_decomposition.write_plain_spqr(&mut buffer).unwrap();
+    let buffer = String::from_utf8(buffer).unwrap();
+    let read_spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, &mut buffer.as_bytes()).unwrap();
+
+    let mut extra_data = read_spqr_decomposition
+        .iter_nodes()
+        .flat_map(|node_index| graph.iter_incident_edges(node_index))
+        .map(|edge_index| read_spqr_decomposition.edge_extra_data(edge_index))
+        .filter(|extra_data| !extra_data.is_empty())
+        .collect::<Vec<_>>();
+    extra_data.sort_unstable();
+    extra_data.dedup();
+    assert_eq!(extra_data, vec!["bubble 42", "x"]);
+}
+
+#[test]
+fn test_multiedge_extra_data() {
+    enable_logging();
+
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/multiedge.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/multiedge.spqr").unwrap();
+    let edge_extra_data = |spqr_decomposition: &SPQRDecomposition<'_, _>| {
+        graph
+            .edge_indices()
+            .map(|edge_index| spqr_decomposition.edge_extra_data(edge_index).to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // Parallel edges are resolved by their names, independently of the order of their E-lines.
+    let reordered = spqr_decomposition_file.replace(
+        "E E0 P0 2 1\nE E1 P0 1 2\n",
+        "E E1 P0 1 2 second\nE E0 P0 2 1 first\n",
+    );
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, reordered.as_bytes()).unwrap();
+    assert_eq!(
+        edge_extra_data(&spqr_decomposition),
+        ["first", "second", "", ""]
+    );
+
+    let mut buffer = Vec::new();
+    spqr_decomposition.write_plain_spqr(&mut buffer).unwrap();
+    let buffer = String::from_utf8(buffer).unwrap();
+    assert!(
+        buffer
+            .lines()
+            .any(|line| line.starts_with("E E0 ") && line.ends_with(" first"))
+    );
+    assert!(
+        buffer
+            .lines()
+            .any(|line| line.starts_with("E E1 ") && line.ends_with(" second"))
+    );
+    let read_spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, buffer.as_bytes()).unwrap();
+    assert_eq!(spqr_decomposition, read_spqr_decomposition);
+    assert_eq!(
+        edge_extra_data(&read_spqr_decomposition),
+        ["first", "second", "", ""]
+    );
+
+    // Equal extra data is kept for all parallel edges.
+    let identical = spqr_decomposition_file.replace(
+        "E E0 P0 2 1\nE E1 P0 1 2\n",
+        "E E0 P0 2 1 same\nE E1 P0 1 2 same\n",
+    );
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, identical.as_bytes()).unwrap();
+    assert_eq!(
+        edge_extra_data(&spqr_decomposition),
+        ["same", "same", "", ""]
+    );
+
+    // Other names are resolved to different edges.
+    let renamed = spqr_decomposition_file
+        .replace("E E0 P0 2 1\nE E1 P0 1 2\n", "E a P0 2 1 x\nE b P0 1 2 y\n");
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, renamed.as_bytes()).unwrap();
+    let mut renamed_extra_data = edge_extra_data(&spqr_decomposition);
+    renamed_extra_data[..2].sort_unstable();
+    assert_eq!(renamed_extra_data, ["x", "y", "", ""]);
+
+    let too_many = spqr_decomposition_file.replace("E E1 P0 1 2\n", "E E1 P0 1 2\nE E4 P0 1 2 z\n");
+    assert!(matches!(
+        SPQRDecomposition::read_plain_spqr(&graph, too_many.as_bytes()),
+        Err(ReadError::MoreELinesThanEdges(u, v)) if u == "1" && v == "2"
+    ));
+}
+
+#[test]
+fn test_scan_capacities() {
+    enable_logging();
+
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/random5_10_False_0.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/random5_10_False_0.spqr").unwrap();
+    let capacities = scan_capacities(spqr_decomposition_file.as_bytes()).unwrap();
+    let spqr_decomposition = SPQRDecomposition::read_plain_spqr_with_capacities(
+        &graph,
+        spqr_decomposition_file.as_bytes(),
+        capacities,
+    )
+    .unwrap();
+
+    assert_eq!(capacities.components, spqr_decomposition.component_count());
+    assert_eq!(capacities.blocks, spqr_decomposition.block_count());
+    assert_eq!(capacities.cut_nodes, spqr_decomposition.cut_node_count());
+    assert_eq!(capacities.spqr_nodes, spqr_decomposition.spqr_node_count());
+    assert_eq!(capacities.spqr_edges, spqr_decomposition.spqr_edge_count());
+}
+
+#[test]
+fn test_scan_statistics() {
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random1_1_False_0",
+        "random3_5_False_0",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+            BufReader::new(File::open(format!("test_files/{name}.gfa")).unwrap()),
+        )
+        .unwrap();
+        let spqr_decomposition_file =
+            fs::read_to_string(format!("test_files/{name}.spqr")).unwrap();
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+        assert_eq!(
+            scan_statistics(spqr_decomposition_file.as_bytes()).unwrap(),
+            spqr_decomposition.statistics(),
+            "{name}",
+        );
+    }
+}
+
+#[test]
+fn test_explicit_q_nodes() {
+    enable_logging();
+
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/random5_10_False_0.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/random5_10_False_0.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+    let mut buffer = Vec::new();
+    spqr_decomposition
+        .write_plain_spqr_with_explicit_q_nodes(&mut buffer)
+        .unwrap();
+    let buffer = String::from_utf8(buffer).unwrap();
+    assert!(buffer.starts_with("H v0.5 "));
+    assert!(buffer.lines().any(|line| line.starts_with("Q ")));
+    let read_spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, buffer.as_bytes()).unwrap();
+    assert_eq!(spqr_decomposition, read_spqr_decomposition);
+
+    let tiny1_graph =
+        BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+            BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+        )
+        .unwrap();
+    let tiny1_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let q_line_in_block = tiny1_file
+        .replacen("v0.4", "v0.5", 1)
+        .replace("E E0 S0 j k", "Q E0 B0 j k");
+    assert!(matches!(
+        SPQRDecomposition::read_plain_spqr(&tiny1_graph, q_line_in_block.as_bytes()),
+        Err(ReadError::UnknownSPQRNodeName(name)) if name == "B0"
+    ));
+
+    // Q-lines are not part of v0.4.
+    let q_line_in_v0_4 = tiny1_file.replace("E E0 S0 j k", "Q E0 S0 j k");
+    assert!(matches!(
+        SPQRDecomposition::read_plain_spqr(&tiny1_graph, q_line_in_v0_4.as_bytes()),
+        Err(ReadError::InvalidLineType(line_type)) if line_type == "Q"
+    ));
+    let q_line_in_v0_5 = q_line_in_v0_4.replacen("v0.4", "v0.5", 1);
+    SPQRDecomposition::read_plain_spqr(&tiny1_graph, q_line_in_v0_5.as_bytes()).unwrap();
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_read_plain_spqr_parallel() {
+    enable_logging();
+
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random1_1_False_0",
+        "random3_5_False_0",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+            BufReader::new(File::open(format!("test_files/{name}.gfa")).unwrap()),
+        )
+        .unwrap();
+        let spqr_decomposition_file =
+            fs::read_to_string(format!("test_files/{name}.spqr")).unwrap();
+        let sequential =
+            SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+        let parallel =
+            SPQRDecomposition::read_plain_spqr_parallel(&graph, spqr_decomposition_file.as_bytes())
+                .unwrap();
+
+        assert_eq!(sequential.canonical_form(), parallel.canonical_form());
+
+        let mut sequential_buffer = Vec::new();
+        sequential.write_plain_spqr(&mut sequential_buffer).unwrap();
+        let mut parallel_buffer = Vec::new();
+        parallel.write_plain_spqr(&mut parallel_buffer).unwrap();
+        assert_eq!(sequential_buffer, parallel_buffer);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_read_plain_spqr_parallel_unknown_node() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = "H v0.4 https://example.com\nG G0 unknown_node\n";
+    let result =
+        SPQRDecomposition::read_plain_spqr_parallel(&graph, spqr_decomposition_file.as_bytes());
+    assert!(matches!(
+        result,
+        Err(crate::io::plain_spqr_file::error::ReadError::UnknownNodeName(name)) if name == "unknown_node"
+    ));
+}
+
+#[test]
+fn test_read_plain_spqr_with_profile() {
+    enable_logging();
+
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/random5_10_False_0.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/random5_10_False_0.spqr").unwrap();
+    let mut profile = Profile::new();
+    let profiled = SPQRDecomposition::read_plain_spqr_with_profile(
+        &graph,
+        spqr_decomposition_file.as_bytes(),
+        &mut profile,
+    )
+    .unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    assert_eq!(profiled, spqr_decomposition);
+
+    let phases = profile
+        .phases()
+        .iter()
+        .map(|phase_profile| phase_profile.phase)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        phases,
+        [
+            Phase::Parse,
+            Phase::Validation,
+            Phase::CutNodeIdentification,
+            Phase::Build
+        ]
+    );
+    assert_eq!(
+        profile.total_duration(),
+        phases
+            .iter()
+            .map(|phase| profile.duration(*phase))
+            .sum::<std::time::Duration>()
+    );
+    assert!(
+        profile
+            .to_string()
+            .ends_with(&format!("total: {:?}", profile.total_duration()))
+    );
+}
+
+#[test]
+fn test_read_plain_spqr_presized() {
+    enable_logging();
+
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/Ultrabubble_dataset_chr_22_G182870.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file =
+        fs::read_to_string("test_files/Ultrabubble_dataset_chr_22_G182870.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let presized = SPQRDecomposition::read_plain_spqr_presized(
+        &graph,
+        BufReader::new(File::open("test_files/Ultrabubble_dataset_chr_22_G182870.spqr").unwrap()),
+    )
+    .unwrap();
+
+    assert_eq!(spqr_decomposition, presized);
+}
+
+#[test]
+fn test_shared_extra_data() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let mut spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    for node_name in ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k"] {
+        spqr_decomposition_file.push_str(&format!("N {node_name} annotated\n"));
+    }
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+    assert!(
+        spqr_decomposition
+            .iter_nodes()
+            .all(|node_index| spqr_decomposition.node_extra_data(node_index) == "annotated")
+    );
+    // All eleven nodes share a single allocation of the string and its reference counts.
+    assert_eq!(
+        spqr_decomposition.memory_usage().extra_data,
+        2 * size_of::<usize>() + "annotated".len(),
+    );
+}
+
+#[test]
+fn test_progress() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let mut progress = RecordingProgress::default();
+    let spqr_decomposition = SPQRDecomposition::read_plain_spqr_with_progress(
+        &graph,
+        spqr_decomposition_file.as_bytes(),
+        &mut progress,
+    )
+    .unwrap();
+
+    assert_eq!(
+        spqr_decomposition,
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap()
+    );
+    assert_eq!(
+        progress.events,
+        [
+            ProgressEvent::Start(Phase::Parse, None),
+            ProgressEvent::Advance(spqr_decomposition_file.lines().count()),
+            ProgressEvent::Finish(Phase::Parse),
+            ProgressEvent::Start(Phase::Validation, None),
+            ProgressEvent::Finish(Phase::Validation),
+            ProgressEvent::Start(Phase::CutNodeIdentification, None),
+            ProgressEvent::Finish(Phase::CutNodeIdentification),
+            ProgressEvent::Start(Phase::Build, None),
+            ProgressEvent::Finish(Phase::Build),
+        ]
+    );
+}
+
+#[test]
+fn test_read_from_path() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr_from_path(&graph, "test_files/tiny1.spqr").unwrap();
+    assert_eq!(
+        spqr_decomposition,
+        SPQRDecomposition::read_plain_spqr(
+            &graph,
+            BufReader::new(File::open("test_files/tiny1.spqr").unwrap())
+        )
+        .unwrap()
+    );
+
+    let error = SPQRDecomposition::read_plain_spqr_from_path(&graph, "test_files/missing.spqr")
+        .unwrap_err();
+    assert!(matches!(error.source, ReadError::Io(_)));
+    assert_eq!((error.line, error.offset), (None, None));
+
+    let header = fs::read_to_string("test_files/tiny1.spqr")
+        .unwrap()
+        .lines()
+        .next()
+        .unwrap()
+        .to_string();
+    let path = std::env::temp_dir().join(format!("spqr-tree-path-{}.spqr", std::process::id()));
+    fs::write(&path, format!("{header}\n\nG G0 a b\nN z\n")).unwrap();
+    let error = SPQRDecomposition::read_plain_spqr_from_path(&graph, &path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(&error.source, ReadError::UnknownNodeName(name) if name == "z"));
+    assert_eq!(error.line, Some(4));
+    assert_eq!(error.offset, Some(header.len() as u64 + 11));
+    assert!(
+        error
+            .to_string()
+            .starts_with(&format!("{}:4 (byte ", path.display()))
+    );
+}
+
+#[test]
+fn test_read_with_strictness() {
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let expected =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let extended = format!("{spqr_decomposition_file}X tool-specific record\n");
+    let read = |strictness| {
+        SPQRDecomposition::read_plain_spqr_with_strictness(
+            &graph,
+            extended.as_bytes(),
+            Default::default(),
+            strictness,
+        )
+    };
+
+    assert!(matches!(
+        read(Strictness::Error),
+        Err(ReadError::InvalidLineType(line_type)) if line_type == "X"
+    ));
+    assert_eq!(read(Strictness::Warn).unwrap(), expected);
+    assert!(std::panic::catch_unwind(|| read(Strictness::Panic)).is_err());
+
+    // Errors that are not inconsistencies are returned even with `Strictness::Panic`.
+    let invalid_utf8 = [spqr_decomposition_file.as_bytes(), b"\xff\n"].concat();
+    assert!(matches!(
+        SPQRDecomposition::read_plain_spqr_with_strictness(
+            &graph,
+            invalid_utf8.as_slice(),
+            Default::default(),
+            Strictness::Panic,
+        ),
+        Err(ReadError::Line(_))
+    ));
+
+    // Attaching the edges of a triangle to its block instead of an S-node fails the sanity checks of the builder,
+    // but the decomposition can still be built.
+    let inconsistent = spqr_decomposition_file
+        .replace("S S4 B4 a c b\n", "")
+        .replace(" S4 ", " B4 ");
+    let read = |strictness| {
+        SPQRDecomposition::read_plain_spqr_with_strictness(
+            &graph,
+            inconsistent.as_bytes(),
+            Default::default(),
+            strictness,
+        )
+    };
+    assert!(matches!(
+        read(Strictness::Error),
+        Err(ReadError::InconsistentDecomposition(_))
+    ));
+    let spqr_decomposition = read(Strictness::Warn).unwrap();
+    assert_eq!(spqr_decomposition.iter_spqr_nodes().count(), 4);
+    assert_eq!(
+        spqr_decomposition
+            .iter_blocks()
+            .map(|(_, block)| block.edges.len())
+            .sum::<usize>(),
+        3
+    );
+}
+
+#[test]
+fn test_versions() {
+    assert_eq!(FormatVersion::from_name("v0.4"), Some(FormatVersion::V0_4));
+    assert_eq!(FormatVersion::from_name("v0.5"), Some(FormatVersion::V0_5));
+    assert_eq!(FormatVersion::from_name("v0.6"), None);
+    assert!(!FormatVersion::V0_4.line_types().contains(&"Q"));
+    assert!(FormatVersion::V0_5.line_types().contains(&"Q"));
+    assert!(FormatVersion::SUPPORTED.contains(&FormatVersion::LATEST));
+
+    let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+    let mut written = Vec::new();
+    spqr_decomposition.write_plain_spqr(&mut written).unwrap();
+    assert!(written.starts_with(format!("H {} ", FormatVersion::V0_4).as_bytes()));
+
+    let error = SPQRDecomposition::read_plain_spqr(
+        &graph,
+        spqr_decomposition_file
+            .replacen("v0.4", "v0.1", 1)
+            .as_bytes(),
+    )
+    .unwrap_err();
+    assert!(matches!(&error, ReadError::UnsupportedVersion));
+    assert!(error.to_string().ends_with("Supported are: v0.4, v0.5"));
+}
+
+#[test]
+fn test_line_handler() {
+    type TestGraph = BidirectedAdjacencyArray<u8, PlainGfaNodeData, PlainGfaEdgeData>;
+    let graph =
+        TestGraph::read_gfa1(BufReader::new(File::open("test_files/tiny1.gfa").unwrap())).unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let extended = format!("{spqr_decomposition_file}X B3 0.5\nX S0 1.5\n");
+
+    let mut scores = Vec::new();
+    let spqr_decomposition = SPQRDecomposition::read_plain_spqr_with_line_handler(
+        &graph,
+        extended.as_bytes(),
+        &mut |columns: &[&str], names: &DeclaredNames<'_, TestGraph>| {
+            if columns[0] != "X" {
+                return Ok(false);
+            }
+            let score: f64 = columns[2].parse()?;
+            if let Some(block_index) = names.block(columns[1]) {
+                scores.push((Some(block_index), None, score));
+            } else {
+                let spqr_node_index = names.spqr_node(columns[1]).ok_or("unknown name")?;
+                scores.push((None, Some(spqr_node_index), score));
+            }
+            Ok(true)
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        spqr_decomposition,
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap()
+    );
+    assert_eq!(
+        scores,
+        [(Some(3.into()), None, 0.5), (None, Some(0.into()), 1.5)]
+    );
+
+    // Lines that the handler declines are rejected, and its errors are passed on.
+    for (line, is_handler_error) in [("Y B0\n", false), ("X B0 high\n", true), ("X Z0 1\n", true)] {
+        let error = SPQRDecomposition::read_plain_spqr_with_line_handler(
+            &graph,
+            format!("{spqr_decomposition_file}{line}").as_bytes(),
+            &mut |columns: &[&str], names: &DeclaredNames<'_, TestGraph>| {
+                if columns[0] != "X" {
+                    return Ok(false);
+                }
+                let _: f64 = columns[2].parse()?;
+                names.block(columns[1]).ok_or("unknown name")?;
+                Ok(true)
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            matches!(error, ReadError::LineHandler(_)),
+            is_handler_error,
+            "{line}"
+        );
+    }
+}
+
+#[test]
+fn test_parse_events() {
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+
+    // Rewriting the events as lines reproduces the file.
+    let mut lines = Vec::new();
+    let mut edge_count = 0;
+    parse_events::<ReadError>(spqr_decomposition_file.as_bytes(), |event| {
+        let line = match event {
+            Event::Header { version } => {
+                format!("H {version} https://github.com/sebschmi/SPQR-tree-file-format")
+            }
+            Event::Component { name, nodes } => format!("G {name} {}", nodes.join(" ")),
+            Event::Block {
+                name,
+                component,
+                nodes,
+            } => format!("B {name} {component} {}", nodes.join(" ")),
+            Event::CutNode { node, blocks } => format!("C {node} {}", blocks.join(" ")),
+            Event::SPQRNode {
+                spqr_node_type,
+                name,
+                block,
+                nodes,
+            } => format!(
+                "{} {name} {block} {}",
+                spqr_node_type.letter(),
+                nodes.join(" ")
+            ),
+            Event::SPQREdge {
+                name,
+                spqr_nodes: (a, b),
+                virtual_edge: (u, v),
+            } => format!("V {name} {a} {b} {u} {v}"),
+            Event::Edge {
+                name,
+                parent,
+                endpoints: (u, v),
+                explicit_q_node,
+                ..
+            } => {
+                edge_count += 1;
+                format!(
+                    "{} {name} {parent} {u} {v}",
+                    if explicit_q_node { "Q" } else { "E" }
+                )
+            }
+            event => panic!("unexpected event {event:?}"),
+        };
+        lines.push(line);
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(lines.join("\n") + "\n", spqr_decomposition_file);
+    assert_eq!(edge_count, 15);
+
+    // Unknown lines are passed on, and lines with missing columns are rejected.
+    let mut others = Vec::new();
+    parse_events::<ReadError>(
+        format!("{spqr_decomposition_file}X B0 0.5\nN a some data\n").as_bytes(),
+        |event| {
+            match event {
+                Event::Other { columns } => others.push(columns.join(" ")),
+                Event::NodeExtraData { node, extra_data } => {
+                    others.push(format!("{node}: {extra_data}"))
+                }
+                _ => {}
+            }
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert_eq!(others, ["X B0 0.5", "a: some data"]);
+    assert!(matches!(
+        parse_events::<ReadError>(
+            format!("{spqr_decomposition_file}E E99 S0 j\n").as_bytes(),
+            |_| Ok(())
+        ),
+        Err(ReadError::MissingNodeNameInELine)
+    ));
+
+    // Errors of the handler stop the parsing.
+    let mut event_count = 0;
+    assert!(matches!(
+        parse_events(spqr_decomposition_file.as_bytes(), |_| {
+            event_count += 1;
+            if event_count == 3 {
+                Err(ReadError::MissingBlockNameInBLine)
+            } else {
+                Ok(())
+            }
+        }),
+        Err(ReadError::MissingBlockNameInBLine)
+    ));
+    assert_eq!(event_count, 3);
+}
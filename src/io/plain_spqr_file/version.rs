@@ -0,0 +1,54 @@
+//! The versions of the plain SPQR file format.
+
+use std::fmt::Display;
+
+/// A version of the plain SPQR file format, as declared in the header line of a file.
+///
+/// A version determines which [line types](Self::line_types) are recognized, and lines of other types are rejected or passed to a line handler.
+/// The recognized lines are applied the same way for all versions, so files of older versions remain readable as the format evolves.
+/// Files are written in the oldest version that supports all line types they contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum FormatVersion {
+    V0_4,
+    /// Adds Q-lines, which declare edges of SPQR nodes as explicit Q-nodes.
+    V0_5,
+}
+
+impl FormatVersion {
+    /// All versions that can be read, from oldest to newest.
+    pub const SUPPORTED: &[Self] = &[Self::V0_4, Self::V0_5];
+
+    /// The newest version.
+    pub const LATEST: Self = Self::V0_5;
+
+    /// Returns the version with the given name as it appears in the header line, e.g. `v0.4`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::SUPPORTED
+            .iter()
+            .copied()
+            .find(|version| version.name() == name)
+    }
+
+    /// Returns the line types defined by this version.
+    pub fn line_types(self) -> &'static [&'static str] {
+        match self {
+            Self::V0_4 => &["G", "N", "B", "C", "S", "P", "R", "V", "E"],
+            Self::V0_5 => &["G", "N", "B", "C", "S", "P", "R", "V", "E", "Q"],
+        }
+    }
+
+    /// Returns the name of this version as it appears in the header line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::V0_4 => "v0.4",
+            Self::V0_5 => "v0.5",
+        }
+    }
+}
+
+impl Display for FormatVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
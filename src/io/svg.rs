@@ -0,0 +1,313 @@
+//! Methods for rendering the block-cut trees and SPQR trees of an SPQR decomposition as [SVG](https://www.w3.org/TR/SVG11/).
+//!
+//! The trees are drawn in layers with the coordinates of [`layout_spqr_tree`](SPQRDecomposition::layout_spqr_tree),
+//! so no external layout engine is needed.
+
+use std::{borrow::Borrow, f64::consts::TAU, io::Write};
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    decomposition::{
+        SPQRDecomposition, SPQRNodeType, SkeletonEdge,
+        indices::{BlockIndex, CutNodeIndex, SPQRNodeIndex},
+        layout::{Point, escape_xml, layered_tree_layout},
+    },
+    graph::StaticGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The amount of SVG units per layout unit in the block-cut tree.
+const BLOCK_CUT_TREE_SCALE: f64 = 60.0;
+
+/// The amount of SVG units per layout unit in an SPQR tree, which leaves room for the skeleton thumbnails.
+const SPQR_TREE_SCALE: f64 = 130.0;
+
+/// The side length of the square around the skeleton thumbnail of an SPQR node.
+const THUMBNAIL_SIZE: f64 = 100.0;
+
+/// The distance between the curves of parallel edges in a skeleton thumbnail, measured at their middle.
+const PARALLEL_EDGE_SPACING: f64 = 12.0;
+
+/// The distance between the drawing and the border of the image.
+const MARGIN: f64 = 70.0;
+
+/// A node of the block-cut tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum BlockCutTreeNode<IndexType> {
+    Block(BlockIndex<IndexType>),
+    CutNode(CutNodeIndex<IndexType>),
+}
+
+impl<'graph, Graph: StaticGraph, GraphStorage: Borrow<Graph>>
+    SPQRDecomposition<'graph, Graph, GraphStorage>
+{
+    /// Write the block-cut trees of all components as SVG.
+    ///
+    /// Blocks are drawn as rectangles labelled with their names as in the plain SPQR file format,
+    /// and cut nodes as circles labelled with the names of their nodes.
+    /// The block-cut tree of each component is rooted at its first block, and the trees are placed next to each other from left to right.
+    /// Components without blocks consist of a single node, which is drawn like a cut node.
+    pub fn write_block_cut_tree_svg(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let mut shapes = Vec::new();
+        let mut lines = Vec::new();
+        let mut component_offset = 0.0;
+        for (_, component) in self.iter_components() {
+            let Some(&root) = component.blocks.first() else {
+                shapes.push((
+                    Point {
+                        x: component_offset,
+                        y: 0.0,
+                    },
+                    false,
+                    self.graph_ref().node_name(component.nodes[0]),
+                ));
+                component_offset += 1.0;
+                continue;
+            };
+
+            let mut children = FxHashMap::<_, Vec<_>>::default();
+            let mut stack = vec![(BlockCutTreeNode::Block(root), None)];
+            while let Some((node, parent)) = stack.pop() {
+                let node_children: Vec<_> = match node {
+                    BlockCutTreeNode::Block(block_index) => self.blocks[block_index]
+                        .cut_nodes
+                        .iter()
+                        .map(|&cut_node_index| BlockCutTreeNode::CutNode(cut_node_index))
+                        .collect(),
+                    BlockCutTreeNode::CutNode(cut_node_index) => self.cut_nodes[cut_node_index]
+                        .adjacent_blocks
+                        .iter()
+                        .map(|&block_index| BlockCutTreeNode::Block(block_index))
+                        .collect(),
+                };
+                let node_children = node_children
+                    .into_iter()
+                    .filter(|&child| Some(child) != parent)
+                    .collect::<Vec<_>>();
+                stack.extend(node_children.iter().map(|&child| (child, Some(node))));
+                children.insert(node, node_children);
+            }
+
+            let positions = layered_tree_layout(BlockCutTreeNode::Block(root), &children);
+            let position = |node| {
+                let position: Point = positions[&node];
+                Point {
+                    x: position.x + component_offset,
+                    y: position.y,
+                }
+            };
+            for (&node, node_children) in &children {
+                for &child in node_children {
+                    lines.push((position(node), position(child)));
+                }
+                shapes.push(match node {
+                    BlockCutTreeNode::Block(block_index) => {
+                        (position(node), true, format!("B{block_index}").into())
+                    }
+                    BlockCutTreeNode::CutNode(cut_node_index) => (
+                        position(node),
+                        false,
+                        self.graph_ref()
+                            .node_name(self.cut_nodes[cut_node_index].node),
+                    ),
+                });
+            }
+            component_offset += positions
+                .values()
+                .map(|position| position.x)
+                .fold(0.0, f64::max)
+                + 1.0;
+        }
+
+        let height = shapes
+            .iter()
+            .map(|(position, _, _)| position.y)
+            .fold(0.0, f64::max);
+        let scale = |coordinate: f64| coordinate * BLOCK_CUT_TREE_SCALE + MARGIN;
+        writeln!(
+            writer,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">"#,
+            scale(component_offset - 1.0) + MARGIN,
+            scale(height) + MARGIN,
+        )?;
+        for (from, to) in lines {
+            writeln!(
+                writer,
+                r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="black"/>"#,
+                scale(from.x),
+                scale(from.y),
+                scale(to.x),
+                scale(to.y),
+            )?;
+        }
+        for (position, is_block, label) in shapes {
+            let (x, y) = (scale(position.x), scale(position.y));
+            if is_block {
+                writeln!(
+                    writer,
+                    r#"  <rect x="{}" y="{}" width="40" height="24" fill="white" stroke="black"/>"#,
+                    x - 20.0,
+                    y - 12.0,
+                )?;
+            } else {
+                writeln!(
+                    writer,
+                    r#"  <circle cx="{x}" cy="{y}" r="12" fill="white" stroke="black"/>"#,
+                )?;
+            }
+            writeln!(
+                writer,
+                r#"  <text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="central" font-size="10">{}</text>"#,
+                escape_xml(&label),
+            )?;
+        }
+        writeln!(writer, "</svg>")
+    }
+
+    /// Write the SPQR tree of the given block as SVG, with a thumbnail of the skeleton of each SPQR node.
+    ///
+    /// Each SPQR node is drawn as a square labelled with its [name](Self::spqr_node_name), containing its skeleton.
+    /// The nodes of the skeleton are placed on a circle, in the order of the cycle for S-nodes,
+    /// and its real edges are drawn solid and its virtual edges dashed.
+    /// A block without SPQR tree is drawn as an empty image.
+    pub fn write_spqr_tree_svg(
+        &self,
+        block_index: BlockIndex<Graph::IndexType>,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        let layout = self.layout_spqr_tree(block_index);
+        let (_, max) = layout.bounding_box().unwrap_or_default();
+        let scale = |coordinate: f64| coordinate * SPQR_TREE_SCALE + MARGIN;
+        writeln!(
+            writer,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">"#,
+            scale(max.x) + MARGIN,
+            scale(max.y) + MARGIN,
+        )?;
+
+        for (spqr_edge_index, spqr_edge) in self.iter_spqr_edges_in_block(block_index) {
+            let (from, to) = spqr_edge.endpoints();
+            let (from, to) = (layout.position(from).unwrap(), layout.position(to).unwrap());
+            writeln!(
+                writer,
+                r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="black"><title>V{spqr_edge_index}</title></line>"#,
+                scale(from.x),
+                scale(from.y),
+                scale(to.x),
+                scale(to.y),
+            )?;
+        }
+
+        for (spqr_node_index, _) in self.iter_spqr_nodes_in_block(block_index) {
+            let position = layout.position(spqr_node_index).unwrap();
+            let center = Point {
+                x: scale(position.x),
+                y: scale(position.y),
+            };
+            writeln!(
+                writer,
+                r#"  <rect x="{}" y="{}" width="{THUMBNAIL_SIZE}" height="{THUMBNAIL_SIZE}" fill="white" stroke="black"/>"#,
+                center.x - THUMBNAIL_SIZE / 2.0,
+                center.y - THUMBNAIL_SIZE / 2.0,
+            )?;
+            writeln!(
+                writer,
+                r#"  <text x="{}" y="{}" font-size="12">{}</text>"#,
+                center.x - THUMBNAIL_SIZE / 2.0 + 3.0,
+                center.y - THUMBNAIL_SIZE / 2.0 + 13.0,
+                escape_xml(&self.spqr_node_name(spqr_node_index)),
+            )?;
+            self.write_skeleton_thumbnail_svg(spqr_node_index, center, &mut writer)?;
+        }
+
+        writeln!(writer, "</svg>")
+    }
+
+    /// Write the skeleton of the given SPQR node centered at the given point.
+    fn write_skeleton_thumbnail_svg(
+        &self,
+        spqr_node_index: SPQRNodeIndex<Graph::IndexType>,
+        center: Point,
+        writer: &mut impl Write,
+    ) -> std::io::Result<()> {
+        let spqr_node = &self.spqr_nodes[spqr_node_index];
+        let order = if spqr_node.spqr_node_type == SPQRNodeType::SNode {
+            self.s_node_cycle(spqr_node_index)
+                .into_iter()
+                .map(|(_, node_index)| node_index)
+                .collect()
+        } else {
+            spqr_node.nodes.to_vec()
+        };
+        let radius = THUMBNAIL_SIZE * 0.3;
+        let positions: FxHashMap<_, _> = order
+            .iter()
+            .enumerate()
+            .map(|(position, &node_index)| {
+                let angle = TAU * position as f64 / order.len() as f64 - TAU / 4.0;
+                (
+                    node_index,
+                    Point {
+                        x: center.x + radius * angle.cos(),
+                        y: center.y + 5.0 + radius * angle.sin(),
+                    },
+                )
+            })
+            .collect();
+
+        // Parallel edges are drawn as curves bending away from each other.
+        let mut edges = FxHashMap::<_, Vec<_>>::default();
+        for (skeleton_edge, (u, v)) in self.iter_skeleton_edges(spqr_node_index) {
+            let key = if u <= v { (u, v) } else { (v, u) };
+            edges.entry(key).or_default().push(skeleton_edge);
+        }
+        for ((u, v), skeleton_edges) in edges {
+            let (from, to) = (positions[&u], positions[&v]);
+            for (index, skeleton_edge) in skeleton_edges.iter().enumerate() {
+                let dash = match skeleton_edge {
+                    SkeletonEdge::Real(_) => "",
+                    SkeletonEdge::Virtual(_) => r#" stroke-dasharray="4 3""#,
+                };
+                if u == v {
+                    writeln!(
+                        writer,
+                        r#"  <circle cx="{}" cy="{}" r="{}" fill="none" stroke="black"{dash}/>"#,
+                        from.x,
+                        from.y - 6.0 - 3.0 * index as f64,
+                        6.0 + 3.0 * index as f64,
+                    )?;
+                    continue;
+                }
+                let bend = (index as f64 - (skeleton_edges.len() - 1) as f64 / 2.0)
+                    * PARALLEL_EDGE_SPACING;
+                let (dx, dy) = (to.x - from.x, to.y - from.y);
+                let length = dx.hypot(dy);
+                // The control point lies on the perpendicular through the middle, at twice the distance of the curve.
+                let control = Point {
+                    x: (from.x + to.x) / 2.0 - dy / length * 2.0 * bend,
+                    y: (from.y + to.y) / 2.0 + dx / length * 2.0 * bend,
+                };
+                writeln!(
+                    writer,
+                    r#"  <path d="M {} {} Q {} {} {} {}" fill="none" stroke="black"{dash}/>"#,
+                    from.x, from.y, control.x, control.y, to.x, to.y,
+                )?;
+            }
+        }
+
+        for node_index in order {
+            let position = positions[&node_index];
+            writeln!(
+                writer,
+                r#"  <circle cx="{}" cy="{}" r="3" fill="black"><title>{}</title></circle>"#,
+                position.x,
+                position.y,
+                escape_xml(&self.graph_ref().node_name(node_index)),
+            )?;
+        }
+        Ok(())
+    }
+}
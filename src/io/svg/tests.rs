@@ -0,0 +1,101 @@
+use std::{
+    fs::{self, File},
+    io::BufReader,
+};
+
+use bidirected_adjacency_array::{
+    graph::BidirectedAdjacencyArray,
+    io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
+};
+
+use crate::decomposition::SPQRDecomposition;
+
+#[test]
+fn test_svg_rendering() {
+    for name in [
+        "tiny1",
+        "multiedge",
+        "random5_10_False_0",
+        "Ultrabubble_dataset_chr_22_G182870",
+    ] {
+        let graph = BidirectedAdjacencyArray::<u8, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+            BufReader::new(File::open(format!("test_files/{name}.gfa")).unwrap()),
+        )
+        .unwrap();
+        let spqr_decomposition_file =
+            fs::read_to_string(format!("test_files/{name}.spqr")).unwrap();
+        let spqr_decomposition =
+            SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+        let mut svg = Vec::new();
+        spqr_decomposition
+            .write_block_cut_tree_svg(&mut svg)
+            .unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(
+            svg.starts_with("<svg") && svg.ends_with("</svg>\n"),
+            "{name}"
+        );
+        assert_eq!(
+            svg.matches("<rect").count(),
+            spqr_decomposition.block_count(),
+            "{name}"
+        );
+        let blockless_components = spqr_decomposition
+            .iter_components()
+            .filter(|(_, component)| component.block_count() == 0)
+            .count();
+        assert_eq!(
+            svg.matches("<circle").count(),
+            spqr_decomposition.cut_node_count() + blockless_components,
+            "{name}"
+        );
+        assert_eq!(
+            svg.matches("<line").count(),
+            spqr_decomposition
+                .iter_cut_nodes()
+                .map(|(_, cut_node)| cut_node.iter_adjacent_blocks().count())
+                .sum::<usize>(),
+            "{name}"
+        );
+
+        for (block_index, _) in spqr_decomposition.iter_blocks() {
+            let mut svg = Vec::new();
+            spqr_decomposition
+                .write_spqr_tree_svg(block_index, &mut svg)
+                .unwrap();
+            let svg = String::from_utf8(svg).unwrap();
+            assert!(
+                svg.starts_with("<svg") && svg.ends_with("</svg>\n"),
+                "{name}"
+            );
+            let spqr_nodes = spqr_decomposition
+                .iter_spqr_nodes_in_block(block_index)
+                .collect::<Vec<_>>();
+            assert_eq!(svg.matches("<rect").count(), spqr_nodes.len(), "{name}");
+            assert_eq!(
+                svg.matches("<line").count(),
+                spqr_decomposition
+                    .iter_spqr_edges_in_block(block_index)
+                    .count(),
+                "{name}"
+            );
+            assert_eq!(
+                svg.matches(r#"fill="none""#).count(),
+                spqr_nodes
+                    .iter()
+                    .map(|(_, spqr_node)| spqr_node.skeleton_edge_count())
+                    .sum::<usize>(),
+                "{name}"
+            );
+            assert_eq!(
+                svg.matches(r#"fill="black""#).count(),
+                spqr_nodes
+                    .iter()
+                    .map(|(_, spqr_node)| spqr_node.node_count())
+                    .sum::<usize>(),
+                "{name}"
+            );
+        }
+    }
+}
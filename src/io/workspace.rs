@@ -0,0 +1,119 @@
+//! A workflow for graphs that do not fit into RAM, based on memory-mapped files.
+//!
+//! The graph is stored in the [compact graph format](crate::graph::implementations::compact),
+//! and its decomposition in the [binary format](crate::io::binary).
+//! Both files are typically created once, e.g. from a GFA file and a `.spqr` file:
+//!
+//! ```no_run
+//! # #[cfg(all(feature = "bidirected-adjacency-array", feature = "plain-io"))]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::{fs::File, io::BufReader};
+//!
+//! use bidirected_adjacency_array::{
+//!     graph::BidirectedAdjacencyArray,
+//!     io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
+//! };
+//! use spqr_tree::{
+//!     decomposition::SPQRDecomposition, graph::implementations::compact::write_compact_graph,
+//! };
+//!
+//! let graph = BidirectedAdjacencyArray::<u32, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+//!     BufReader::new(File::open("graph.gfa")?),
+//! )?;
+//! let decomposition =
+//!     SPQRDecomposition::read_plain_spqr(&graph, BufReader::new(File::open("graph.spqr")?))?;
+//! write_compact_graph(&graph, File::create("graph.compact")?)?;
+//! decomposition.write_binary(File::create("graph.spqr.bin")?)?;
+//! # Ok(())
+//! # }
+//! # #[cfg(not(all(feature = "bidirected-adjacency-array", feature = "plain-io")))]
+//! # fn main() {}
+//! ```
+//!
+//! Later, possibly on a machine with less memory, the files are opened as memory maps.
+//! Any byte source that implements `AsRef<[u8]>` can be used, e.g. a `memmap2::Mmap`:
+//!
+//! ```ignore
+//! use std::fs::File;
+//!
+//! use memmap2::Mmap;
+//! use spqr_tree::io::workspace::MappedWorkspace;
+//!
+//! // Safety: the files must not be modified while they are mapped.
+//! let graph_bytes = unsafe { Mmap::map(&File::open("graph.compact")?)? };
+//! let decomposition_bytes = unsafe { Mmap::map(&File::open("graph.spqr.bin")?)? };
+//! let workspace = MappedWorkspace::<_, u32>::open(graph_bytes, decomposition_bytes)?;
+//! let node_index = workspace.node_index_by_name("42").unwrap();
+//! println!("{} is a cut node: {}", node_index, workspace.is_cut_node(node_index));
+//! ```
+//!
+//! Only the graph is queried from its mapped bytes.
+//! The decomposition is fully decoded into memory when the workspace is opened,
+//! so the workflow saves the memory of the graph, but not that of the decomposition.
+
+use std::ops::Deref;
+
+use crate::{
+    decomposition::{
+        OwnedSPQRDecomposition, SPQRDecomposition,
+        indices::{DefaultIndexType, GraphIndexInteger},
+    },
+    graph::implementations::compact::CompactGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A decomposition of a memory-mapped graph, read from a memory-mapped decomposition file.
+///
+/// The graph is queried directly from its bytes, so only the parts of it that are accessed are loaded into memory.
+/// The decomposition is decoded from its bytes when the workspace is opened, without copying its sections first,
+/// so its bytes can be unmapped afterwards.
+/// The workspace dereferences to the decomposition, which offers the normal query API.
+pub struct MappedWorkspace<GraphBytes: AsRef<[u8]> + 'static, IndexType = DefaultIndexType>
+where
+    IndexType: GraphIndexInteger + 'static,
+{
+    decomposition: OwnedSPQRDecomposition<CompactGraph<GraphBytes, IndexType>>,
+}
+
+impl<GraphBytes: AsRef<[u8]> + 'static, IndexType> MappedWorkspace<GraphBytes, IndexType>
+where
+    IndexType: GraphIndexInteger + 'static,
+{
+    /// Opens a workspace from the bytes of a compact graph file and a binary decomposition file.
+    ///
+    /// The decomposition file must have been written for a graph with the same node and edge indices and the same index type as the compact graph,
+    /// e.g. for the graph the compact graph file was written from.
+    pub fn open(
+        graph_bytes: GraphBytes,
+        decomposition_bytes: impl AsRef<[u8]>,
+    ) -> std::io::Result<Self> {
+        let graph = CompactGraph::new(graph_bytes)?;
+        let decomposition = OwnedSPQRDecomposition::new_owned(graph, |graph| {
+            SPQRDecomposition::read_binary_from_slice(graph, decomposition_bytes.as_ref())
+        })?;
+        Ok(Self { decomposition })
+    }
+
+    /// Returns the decomposition of the workspace.
+    pub fn decomposition(&self) -> &OwnedSPQRDecomposition<CompactGraph<GraphBytes, IndexType>> {
+        &self.decomposition
+    }
+
+    /// Returns the decomposition of the workspace, which owns the graph.
+    pub fn into_decomposition(self) -> OwnedSPQRDecomposition<CompactGraph<GraphBytes, IndexType>> {
+        self.decomposition
+    }
+}
+
+impl<GraphBytes: AsRef<[u8]> + 'static, IndexType> Deref for MappedWorkspace<GraphBytes, IndexType>
+where
+    IndexType: GraphIndexInteger + 'static,
+{
+    type Target = OwnedSPQRDecomposition<CompactGraph<GraphBytes, IndexType>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.decomposition
+    }
+}
@@ -0,0 +1,50 @@
+use std::{
+    fs::{self, File},
+    io::BufReader,
+};
+
+use bidirected_adjacency_array::{
+    graph::BidirectedAdjacencyArray,
+    io::gfa1::{PlainGfaEdgeData, PlainGfaNodeData},
+};
+
+use crate::{
+    decomposition::SPQRDecomposition,
+    graph::{StaticGraph, implementations::compact::write_compact_graph},
+    io::workspace::MappedWorkspace,
+};
+
+#[test]
+fn test_mapped_workspace() {
+    let graph = BidirectedAdjacencyArray::<u32, PlainGfaNodeData, PlainGfaEdgeData>::read_gfa1(
+        BufReader::new(File::open("test_files/tiny1.gfa").unwrap()),
+    )
+    .unwrap();
+    let spqr_decomposition_file = fs::read_to_string("test_files/tiny1.spqr").unwrap();
+    let spqr_decomposition =
+        SPQRDecomposition::read_plain_spqr(&graph, spqr_decomposition_file.as_bytes()).unwrap();
+
+    let mut graph_bytes = Vec::new();
+    write_compact_graph(&graph, &mut graph_bytes).unwrap();
+    let mut decomposition_bytes = Vec::new();
+    spqr_decomposition
+        .write_binary(&mut decomposition_bytes)
+        .unwrap();
+
+    let workspace = MappedWorkspace::<_, u32>::open(graph_bytes, &decomposition_bytes).unwrap();
+    assert_eq!(workspace.graph_ref().node_count(), graph.node_count());
+    assert_eq!(workspace.statistics(), spqr_decomposition.statistics());
+    for node_index in graph.node_indices() {
+        let name = graph.node_name(node_index);
+        let mapped_node_index = workspace.node_index_by_name(&name).unwrap();
+        assert_eq!(mapped_node_index.into_usize(), node_index.into_usize());
+        assert_eq!(
+            workspace.is_cut_node(mapped_node_index),
+            spqr_decomposition.is_cut_node(node_index)
+        );
+    }
+
+    assert!(
+        MappedWorkspace::<_, u32>::open(decomposition_bytes.clone(), &decomposition_bytes).is_err()
+    );
+}
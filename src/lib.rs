@@ -1,3 +1,13 @@
 pub mod decomposition;
+pub mod error;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
 pub mod graph;
 pub mod io;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod profile;
+pub mod progress;
+pub mod strictness;
+
+pub use error::Error;
@@ -0,0 +1,64 @@
+//! Minimal data parallelism based on the scoped threads of the standard library.
+//!
+//! This module is only available with the `parallel` feature.
+
+use std::{num::NonZero, panic, thread};
+
+/// A parallel iterator over a fixed amount of items that can be accessed by their position.
+///
+/// The items are split into one contiguous chunk per available core, and each chunk is processed by its own scoped thread.
+/// Unlike the adapters of [`Iterator`], the methods of this type are eager.
+pub struct ParIter<Get> {
+    len: usize,
+    get: Get,
+}
+
+impl<Item, Get: Fn(usize) -> Item + Sync> ParIter<Get> {
+    pub(crate) fn new(len: usize, get: Get) -> Self {
+        Self { len, get }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Applies `f` to all items in parallel and returns the results in the order of the items.
+    pub fn map<Result: Send>(self, f: impl Fn(Item) -> Result + Sync) -> Vec<Result> {
+        let thread_count = thread::available_parallelism()
+            .map_or(1, NonZero::get)
+            .min(self.len)
+            .max(1);
+        let chunk_size = self.len.div_ceil(thread_count);
+        let get = &self.get;
+        let f = &f;
+
+        thread::scope(|scope| {
+            let handles = (0..thread_count)
+                .map(|thread_index| {
+                    let start = (thread_index * chunk_size).min(self.len);
+                    let end = (start + chunk_size).min(self.len);
+                    scope.spawn(move || (start..end).map(|index| f(get(index))).collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>();
+
+            let mut results = Vec::with_capacity(self.len);
+            for handle in handles {
+                results.extend(
+                    handle
+                        .join()
+                        .unwrap_or_else(|payload| panic::resume_unwind(payload)),
+                );
+            }
+            results
+        })
+    }
+
+    /// Applies `f` to all items in parallel.
+    pub fn for_each(self, f: impl Fn(Item) + Sync) {
+        self.map(f);
+    }
+}
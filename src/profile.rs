@@ -0,0 +1,153 @@
+//! Opt-in timing and allocation instrumentation of reading and building decompositions.
+//!
+//! Pass a [`Profile`] to [`SPQRDecompositionBuilder::try_build_with_profile`](crate::decomposition::builder::SPQRDecompositionBuilder::try_build_with_profile)
+//! or [`SPQRDecomposition::read_plain_spqr_with_profile`](crate::decomposition::SPQRDecomposition::read_plain_spqr_with_profile)
+//! to find out where time goes on a specific graph.
+//!
+//! Allocations are only counted if [`CountingAllocator`] is installed as the global allocator of the binary:
+//!
+//! ```
+//! #[global_allocator]
+//! static ALLOCATOR: spqr_tree::profile::CountingAllocator = spqr_tree::profile::CountingAllocator;
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::{self, Display, Formatter},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+static ALLOCATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator that counts allocations before forwarding them to the [`System`] allocator.
+pub struct CountingAllocator;
+
+/// The phases recorded in a [`Profile`] or reported to a [`ProgressSink`](crate::progress::ProgressSink).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Parsing a file and adding its entities to the builder.
+    Parse,
+    /// Running the sanity checks of the builder.
+    Validation,
+    /// Identifying cut nodes that were not declared explicitly.
+    CutNodeIdentification,
+    /// Assembling the final decomposition from the builder.
+    Build,
+}
+
+/// The measurements of a single phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseProfile {
+    pub phase: Phase,
+    pub duration: Duration,
+    /// The amount of allocations made during the phase, or zero if [`CountingAllocator`] is not installed.
+    pub allocations: usize,
+}
+
+/// Collects per-phase timings and allocation counts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    phases: Vec<PhaseProfile>,
+}
+
+/// Returns the amount of allocations made through [`CountingAllocator`] so far.
+pub fn allocation_count() -> usize {
+    ALLOCATION_COUNT.load(Ordering::Relaxed)
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+impl Profile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `f` and records its duration and allocations as the given phase.
+    pub fn record<Result>(&mut self, phase: Phase, f: impl FnOnce() -> Result) -> Result {
+        let allocations = allocation_count();
+        let start = Instant::now();
+        let result = f();
+        self.phases.push(PhaseProfile {
+            phase,
+            duration: start.elapsed(),
+            allocations: allocation_count().saturating_sub(allocations),
+        });
+        result
+    }
+
+    /// Returns the recorded phases in the order they were recorded.
+    pub fn phases(&self) -> &[PhaseProfile] {
+        &self.phases
+    }
+
+    /// Returns the total duration of all recordings of the given phase.
+    pub fn duration(&self, phase: Phase) -> Duration {
+        self.phases
+            .iter()
+            .filter(|phase_profile| phase_profile.phase == phase)
+            .map(|phase_profile| phase_profile.duration)
+            .sum()
+    }
+
+    /// Returns the total amount of allocations of all recordings of the given phase.
+    pub fn allocations(&self, phase: Phase) -> usize {
+        self.phases
+            .iter()
+            .filter(|phase_profile| phase_profile.phase == phase)
+            .map(|phase_profile| phase_profile.allocations)
+            .sum()
+    }
+
+    /// Returns the total duration of all recorded phases.
+    pub fn total_duration(&self) -> Duration {
+        self.phases
+            .iter()
+            .map(|phase_profile| phase_profile.duration)
+            .sum()
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Validation => write!(f, "validation"),
+            Self::CutNodeIdentification => write!(f, "cut node identification"),
+            Self::Build => write!(f, "build"),
+        }
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for PhaseProfile {
+            phase,
+            duration,
+            allocations,
+        } in &self.phases
+        {
+            writeln!(f, "{phase}: {duration:?} ({allocations} allocations)")?;
+        }
+        write!(f, "total: {:?}", self.total_duration())
+    }
+}
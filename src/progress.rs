@@ -0,0 +1,70 @@
+//! Opt-in progress reporting of reading and building decompositions.
+//!
+//! Pass a [`ProgressSink`] to [`SPQRDecomposition::read_plain_spqr_with_progress`](crate::decomposition::SPQRDecomposition::read_plain_spqr_with_progress),
+//! [`SPQRDecomposition::read_binary_with_progress`](crate::decomposition::SPQRDecomposition::read_binary_with_progress)
+//! or [`SPQRDecompositionBuilder::try_build_with_progress`](crate::decomposition::builder::SPQRDecompositionBuilder::try_build_with_progress)
+//! to receive the phases of the operation as they start and finish, and the amount of items processed within each phase.
+//! The items are the lines of a plain file, and the sections of a binary file.
+//! Phases without a meaningful amount of items only report their start and finish.
+//!
+//! The sink is called from the thread running the operation, and at most once per [`PROGRESS_INTERVAL`] items,
+//! so it may update a progress bar directly.
+
+use log::info;
+
+use crate::profile::Phase;
+
+/// The amount of items between two calls to [`ProgressSink::advance`] while parsing a plain file.
+pub const PROGRESS_INTERVAL: usize = 1 << 16;
+
+/// Receives the progress of long operations, see the [module documentation](self).
+///
+/// All methods do nothing by default, and `()` is a sink that ignores all progress.
+pub trait ProgressSink {
+    /// Called when the given phase starts, with the total amount of items if it is known.
+    fn start_phase(&mut self, phase: Phase, total: Option<usize>) {
+        let _ = (phase, total);
+    }
+
+    /// Called with the amount of items processed so far in the current phase.
+    fn advance(&mut self, processed: usize) {
+        let _ = processed;
+    }
+
+    /// Called when the given phase finishes.
+    fn finish_phase(&mut self, phase: Phase) {
+        let _ = phase;
+    }
+}
+
+impl ProgressSink for () {}
+
+/// A sink that logs the progress at the info level.
+#[derive(Debug, Clone, Default)]
+pub struct LogProgress {
+    total: Option<usize>,
+}
+
+impl LogProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ProgressSink for LogProgress {
+    fn start_phase(&mut self, phase: Phase, total: Option<usize>) {
+        self.total = total;
+        info!("Starting {phase}...");
+    }
+
+    fn advance(&mut self, processed: usize) {
+        match self.total {
+            Some(total) => info!("Processed {processed} of {total} items"),
+            None => info!("Processed {processed} items"),
+        }
+    }
+
+    fn finish_phase(&mut self, phase: Phase) {
+        info!("Finished {phase}");
+    }
+}
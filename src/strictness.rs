@@ -0,0 +1,22 @@
+//! A setting for how builders and readers react to inconsistent input.
+//!
+//! It applies to the sanity checks of [`try_build_with_strictness`](crate::decomposition::builder::SPQRDecompositionBuilder::try_build_with_strictness)
+//! and to the inconsistencies found by [`read_plain_spqr_with_strictness`](crate::decomposition::SPQRDecomposition::read_plain_spqr_with_strictness).
+//! I/O errors are always returned, the panicking `add_*` methods of the builder keep panicking,
+//! and the assertions guarding internal invariants of the builder are not affected.
+
+/// How to react to inconsistencies found in the input of a builder or reader.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Strictness {
+    /// Panic on inconsistencies.
+    Panic,
+
+    /// Return inconsistencies as errors.
+    #[default]
+    Error,
+
+    /// Log inconsistencies as warnings and continue, unless they make it impossible to produce a result.
+    ///
+    /// The result may then fail [`validate`](crate::decomposition::SPQRDecomposition::validate).
+    Warn,
+}